@@ -51,8 +51,9 @@ impl SyslogDeserializerConfig {
                     .optional_field("appname", Kind::bytes(), None)
                     .optional_field("msgid", Kind::bytes(), None)
                     .optional_field("procid", Kind::integer().or_bytes(), None)
-                    // "structured data" is placed at the root. It will always be a map of strings
-                    .unknown_fields(Kind::object(Collection::from_unknown(Kind::bytes())))
+                    // "structured data" is placed at the root. It will always be a map of strings,
+                    // or arrays of strings for repeated parameters.
+                    .unknown_fields(structured_data_kind())
             }
             LogNamespace::Vector => {
                 schema::Definition::new_with_default_metadata(
@@ -68,13 +69,19 @@ impl SyslogDeserializerConfig {
                 .optional_field("appname", Kind::bytes(), None)
                 .optional_field("msgid", Kind::bytes(), None)
                 .optional_field("procid", Kind::integer().or_bytes(), None)
-                // "structured data" is placed at the root. It will always be a map strings
-                .unknown_fields(Kind::object(Collection::from_unknown(Kind::bytes())))
+                // "structured data" is placed at the root. It will always be a map strings,
+                // or arrays of strings for repeated parameters.
+                .unknown_fields(structured_data_kind())
             }
         }
     }
 }
 
+fn structured_data_kind() -> Kind {
+    let param = Kind::bytes().or_array(Collection::from_unknown(Kind::bytes()));
+    Kind::object(Collection::from_unknown(param))
+}
+
 /// Deserializer that builds an `Event` from a byte frame containing a syslog
 /// message.
 #[derive(Debug, Clone)]
@@ -87,7 +94,7 @@ impl Deserializer for SyslogDeserializer {
         log_namespace: LogNamespace,
     ) -> vector_core::Result<SmallVec<[Event; 1]>> {
         let line = std::str::from_utf8(&bytes)?;
-        let line = line.trim();
+        let line = line.trim_start_matches(BOM).trim();
         let parsed = syslog_loose::parse_message_with_year_exact(line, resolve_year)?;
 
         let mut log = LogEvent::from(Value::Object(BTreeMap::new()));
@@ -97,6 +104,10 @@ impl Deserializer for SyslogDeserializer {
     }
 }
 
+/// The byte order mark prefixing messages encoded as UTF-8, as described in
+/// RFC 5424 section 6.4.
+const BOM: char = '\u{feff}';
+
 /// Function used to resolve the year for syslog messages that don't include the
 /// year.
 ///
@@ -118,12 +129,13 @@ fn insert_fields_from_syslog(
     parsed: Message<&str>,
     log_namespace: LogNamespace,
 ) {
+    let msg = parsed.msg.strip_prefix(BOM).unwrap_or(parsed.msg);
     match log_namespace {
         LogNamespace::Legacy => {
-            log.insert(event_path!(log_schema().message_key()), parsed.msg);
+            log.insert(event_path!(log_schema().message_key()), msg);
         }
         LogNamespace::Vector => {
-            log.insert(event_path!("message"), parsed.msg);
+            log.insert(event_path!("message"), msg);
         }
     }
 
@@ -164,12 +176,28 @@ fn insert_fields_from_syslog(
         log.insert(event_path!("procid"), value);
     }
 
+    // Elements sharing an ID are merged, and parameters repeated within an
+    // element are collected into an array, as both are allowed by RFC 5424.
+    let mut structured_data: BTreeMap<String, BTreeMap<String, Value>> = BTreeMap::new();
     for element in parsed.structured_data.into_iter() {
-        let mut sdata: BTreeMap<String, Value> = BTreeMap::new();
+        let sdata = structured_data.entry(element.id.to_string()).or_default();
         for (name, value) in element.params() {
-            sdata.insert(name.to_string(), value.into());
+            let name = name.to_string();
+            let value: Value = value.into();
+            match sdata.get_mut(&name) {
+                Some(Value::Array(values)) => values.push(value),
+                Some(existing) => {
+                    let first = std::mem::replace(existing, Value::Null);
+                    *existing = Value::Array(vec![first, value]);
+                }
+                None => {
+                    sdata.insert(name, value);
+                }
+            }
         }
-        log.insert(event_path!(element.id), sdata);
+    }
+    for (id, sdata) in structured_data {
+        log.insert(event_path!(id.as_str()), sdata);
     }
 }
 
@@ -224,4 +252,32 @@ mod tests {
         assert_eq!(events[0].as_log()["message"], "MSG".into());
         assert!(events[0].as_log()["timestamp"].is_timestamp());
     }
+
+    #[test]
+    fn deserialize_syslog_strips_bom() {
+        let input = Bytes::from(
+            "<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - \u{feff}MSG",
+        );
+        let deserializer = SyslogDeserializer;
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        assert_eq!(events[0].as_log()["message"], "MSG".into());
+    }
+
+    #[test]
+    fn deserialize_syslog_repeated_structured_data() {
+        let input = Bytes::from(
+            r#"<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 [origin ip="192.0.2.1" ip="192.0.2.2"][meta sequenceId="1"][origin software="test"] MSG"#,
+        );
+        let deserializer = SyslogDeserializer;
+
+        let events = deserializer.parse(input, LogNamespace::Vector).unwrap();
+        let log = events[0].as_log();
+        assert_eq!(
+            log["origin.ip"],
+            Value::from(vec![Value::from("192.0.2.1"), Value::from("192.0.2.2")])
+        );
+        assert_eq!(log["origin.software"], "test".into());
+        assert_eq!(log["meta.sequenceId"], "1".into());
+    }
 }
@@ -22,6 +22,7 @@ pub use newline_delimited::{
 };
 pub use octet_counting::{
     OctetCountingDecoder, OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+    OctetCountingFraming,
 };
 use tokio_util::codec::LinesCodecError;
 
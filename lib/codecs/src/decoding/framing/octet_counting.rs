@@ -41,12 +41,28 @@ pub struct OctetCountingDecoderOptions {
     max_length: Option<usize>,
 }
 
+/// Framing of a stream of messages, as specified in
+/// https://tools.ietf.org/html/rfc6587#section-3.4.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OctetCountingFraming {
+    /// Each message is prefixed with its length in bytes.
+    OctetCounting,
+
+    /// Messages are terminated by a newline.
+    NonTransparent,
+}
+
 /// Codec using the `Octet Counting` format as specified in
 /// https://tools.ietf.org/html/rfc6587#section-3.4.1.
+///
+/// By default, frames starting with a non-zero digit are octet counted and
+/// other frames are newline delimited.
 #[derive(Clone, Debug)]
 pub struct OctetCountingDecoder {
     other: LinesCodec,
     octet_decoding: Option<State>,
+    per_stream: bool,
+    framing: Option<OctetCountingFraming>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -62,6 +78,8 @@ impl OctetCountingDecoder {
         Self {
             other: LinesCodec::new(),
             octet_decoding: None,
+            per_stream: false,
+            framing: None,
         }
     }
 
@@ -70,9 +88,27 @@ impl OctetCountingDecoder {
         Self {
             other: LinesCodec::new_with_max_length(max_length),
             octet_decoding: None,
+            per_stream: false,
+            framing: None,
         }
     }
 
+    /// Detects the framing from the first frame and keeps it for the rest of
+    /// the stream, instead of detecting it for every frame.
+    ///
+    /// This allows newline delimited messages to start with a digit.
+    pub const fn detect_per_stream(mut self) -> Self {
+        self.per_stream = true;
+        self
+    }
+
+    /// Uses the given framing for the whole stream.
+    pub const fn with_framing(mut self, framing: OctetCountingFraming) -> Self {
+        self.per_stream = true;
+        self.framing = Some(framing);
+        self
+    }
+
     /// Decode a frame.
     fn octet_decode(
         &mut self,
@@ -250,6 +286,60 @@ impl OctetCountingDecoder {
         self.octet_decoding
             .map(|state| self.octet_decode(state, src))
     }
+
+    /// Decode a frame using the framing of the stream, detecting it from the
+    /// first frame if it isn't known yet.
+    fn stream_decode(
+        &mut self,
+        src: &mut BytesMut,
+        eof: bool,
+    ) -> Result<Option<Bytes>, LinesCodecError> {
+        let framing = match (self.framing, src.first()) {
+            (Some(framing), _) => framing,
+            (None, None) => return Ok(None),
+            (None, Some(first_byte)) => {
+                let framing = if (b'1'..=b'9').contains(first_byte) {
+                    OctetCountingFraming::OctetCounting
+                } else {
+                    OctetCountingFraming::NonTransparent
+                };
+                trace!(message = "Detected stream framing.", ?framing);
+                self.framing = Some(framing);
+                framing
+            }
+        };
+
+        match framing {
+            OctetCountingFraming::OctetCounting => {
+                let state = match self.octet_decoding {
+                    Some(state) => state,
+                    None => {
+                        // Some senders terminate octet counted frames with a
+                        // newline anyway, skip it before reading the length.
+                        let terminators = src
+                            .iter()
+                            .take_while(|&&b| b == b'\n' || b == b'\r')
+                            .count();
+                        src.advance(terminators);
+                        if src.is_empty() {
+                            return Ok(None);
+                        }
+                        self.octet_decoding = Some(State::NotDiscarding);
+                        State::NotDiscarding
+                    }
+                };
+                self.octet_decode(state, src)
+            }
+            OctetCountingFraming::NonTransparent if eof => self
+                .other
+                .decode_eof(src)
+                .map(|line| line.map(|line| line.into())),
+            OctetCountingFraming::NonTransparent => self
+                .other
+                .decode(src)
+                .map(|line| line.map(|line| line.into())),
+        }
+    }
 }
 
 impl Default for OctetCountingDecoder {
@@ -263,7 +353,9 @@ impl tokio_util::codec::Decoder for OctetCountingDecoder {
     type Error = BoxedFramingError;
 
     fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        if let Some(ret) = self.checked_decode(src) {
+        if self.per_stream {
+            self.stream_decode(src, false)
+        } else if let Some(ret) = self.checked_decode(src) {
             ret
         } else {
             // Octet counting isn't used so fallback to newline codec.
@@ -275,7 +367,9 @@ impl tokio_util::codec::Decoder for OctetCountingDecoder {
     }
 
     fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        if let Some(ret) = self.checked_decode(buf) {
+        if self.per_stream {
+            self.stream_decode(buf, true)
+        } else if let Some(ret) = self.checked_decode(buf) {
             ret
         } else {
             // Octet counting isn't used so fallback to newline codec.
@@ -415,4 +509,43 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(b"32 something valid"[..], buffer);
     }
+
+    #[test]
+    fn per_stream_non_transparent_allows_leading_digits() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(32).detect_per_stream();
+        let mut buffer = BytesMut::with_capacity(32);
+
+        buffer.put(&b"<13>first\n2 digits first\n"[..]);
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(Ok(Some("<13>first".into())), result.map_err(|_| false));
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(Ok(Some("2 digits first".into())), result.map_err(|_| false));
+    }
+
+    #[test]
+    fn per_stream_octet_counting_skips_trailing_newlines() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(32).detect_per_stream();
+        let mut buffer = BytesMut::with_capacity(32);
+
+        buffer.put(&b"5 hello\n5 world\r\n"[..]);
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(Ok(Some("hello".into())), result.map_err(|_| false));
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(Ok(Some("world".into())), result.map_err(|_| false));
+        let result = decoder.decode(&mut buffer);
+        assert_eq!(Ok(None), result.map_err(|_| false));
+        assert_eq!(b""[..], buffer);
+    }
+
+    #[test]
+    fn fixed_octet_counting_rejects_unframed_messages() {
+        let mut decoder = OctetCountingDecoder::new_with_max_length(32)
+            .with_framing(OctetCountingFraming::OctetCounting);
+        let mut buffer = BytesMut::with_capacity(32);
+
+        buffer.put(&b"<13>no length\n4 next"[..]);
+        let result = decoder.decode(&mut buffer);
+        assert!(result.is_err());
+        assert_eq!(b"length\n4 next"[..], buffer);
+    }
 }
@@ -19,7 +19,7 @@ pub use framing::{
     CharacterDelimitedDecoderConfig, CharacterDelimitedDecoderOptions, FramingError,
     LengthDelimitedDecoder, LengthDelimitedDecoderConfig, NewlineDelimitedDecoder,
     NewlineDelimitedDecoderConfig, NewlineDelimitedDecoderOptions, OctetCountingDecoder,
-    OctetCountingDecoderConfig, OctetCountingDecoderOptions,
+    OctetCountingDecoderConfig, OctetCountingDecoderOptions, OctetCountingFraming,
 };
 use smallvec::SmallVec;
 use std::fmt::Debug;
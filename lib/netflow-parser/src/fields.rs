@@ -0,0 +1,257 @@
+//! Information elements shared by NetFlow v9 and IPFIX.
+//!
+//! NetFlow v9 field types 1 to 127 were adopted as-is by IPFIX, so a single table covers both. Names
+//! follow the [IANA IPFIX registry](https://www.iana.org/assignments/ipfix/ipfix.xhtml), converted to
+//! snake case.
+
+use std::{
+    net::{Ipv4Addr, Ipv6Addr},
+    time::Duration,
+};
+
+use crate::flow::FieldValue;
+
+/// How the value of an information element is encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    Unsigned,
+    Ipv4,
+    Ipv6,
+    Mac,
+    String,
+    Bytes,
+    /// A `dateTimeSeconds` time, in seconds since the Unix epoch.
+    Seconds,
+    /// A `dateTimeMilliseconds` time, in milliseconds since the Unix epoch.
+    Milliseconds,
+}
+
+/// Returns the name and type of a known information element.
+pub fn lookup(id: u16) -> Option<(&'static str, FieldType)> {
+    use FieldType::*;
+
+    let field = match id {
+        1 => ("octet_delta_count", Unsigned),
+        2 => ("packet_delta_count", Unsigned),
+        3 => ("delta_flow_count", Unsigned),
+        4 => ("protocol_identifier", Unsigned),
+        5 => ("ip_class_of_service", Unsigned),
+        6 => ("tcp_control_bits", Unsigned),
+        7 => ("source_transport_port", Unsigned),
+        8 => ("source_ipv4_address", Ipv4),
+        9 => ("source_ipv4_prefix_length", Unsigned),
+        10 => ("ingress_interface", Unsigned),
+        11 => ("destination_transport_port", Unsigned),
+        12 => ("destination_ipv4_address", Ipv4),
+        13 => ("destination_ipv4_prefix_length", Unsigned),
+        14 => ("egress_interface", Unsigned),
+        15 => ("ip_next_hop_ipv4_address", Ipv4),
+        16 => ("bgp_source_as_number", Unsigned),
+        17 => ("bgp_destination_as_number", Unsigned),
+        18 => ("bgp_next_hop_ipv4_address", Ipv4),
+        19 => ("post_mcast_packet_delta_count", Unsigned),
+        20 => ("post_mcast_octet_delta_count", Unsigned),
+        21 => ("flow_end_sys_up_time", Unsigned),
+        22 => ("flow_start_sys_up_time", Unsigned),
+        23 => ("post_octet_delta_count", Unsigned),
+        24 => ("post_packet_delta_count", Unsigned),
+        25 => ("minimum_ip_total_length", Unsigned),
+        26 => ("maximum_ip_total_length", Unsigned),
+        27 => ("source_ipv6_address", Ipv6),
+        28 => ("destination_ipv6_address", Ipv6),
+        29 => ("source_ipv6_prefix_length", Unsigned),
+        30 => ("destination_ipv6_prefix_length", Unsigned),
+        31 => ("flow_label_ipv6", Unsigned),
+        32 => ("icmp_type_code_ipv4", Unsigned),
+        33 => ("igmp_type", Unsigned),
+        34 => ("sampling_interval", Unsigned),
+        35 => ("sampling_algorithm", Unsigned),
+        36 => ("flow_active_timeout", Unsigned),
+        37 => ("flow_idle_timeout", Unsigned),
+        38 => ("engine_type", Unsigned),
+        39 => ("engine_id", Unsigned),
+        40 => ("exported_octet_total_count", Unsigned),
+        41 => ("exported_message_total_count", Unsigned),
+        42 => ("exported_flow_record_total_count", Unsigned),
+        44 => ("source_ipv4_prefix", Ipv4),
+        45 => ("destination_ipv4_prefix", Ipv4),
+        46 => ("mpls_top_label_type", Unsigned),
+        47 => ("mpls_top_label_ipv4_address", Ipv4),
+        48 => ("sampler_id", Unsigned),
+        49 => ("sampler_mode", Unsigned),
+        50 => ("sampler_random_interval", Unsigned),
+        52 => ("minimum_ttl", Unsigned),
+        53 => ("maximum_ttl", Unsigned),
+        54 => ("fragment_identification", Unsigned),
+        55 => ("post_ip_class_of_service", Unsigned),
+        56 => ("source_mac_address", Mac),
+        57 => ("post_destination_mac_address", Mac),
+        58 => ("vlan_id", Unsigned),
+        59 => ("post_vlan_id", Unsigned),
+        60 => ("ip_version", Unsigned),
+        61 => ("flow_direction", Unsigned),
+        62 => ("ip_next_hop_ipv6_address", Ipv6),
+        63 => ("bgp_next_hop_ipv6_address", Ipv6),
+        64 => ("ipv6_extension_headers", Unsigned),
+        80 => ("destination_mac_address", Mac),
+        81 => ("post_source_mac_address", Mac),
+        82 => ("interface_name", String),
+        83 => ("interface_description", String),
+        85 => ("octet_total_count", Unsigned),
+        86 => ("packet_total_count", Unsigned),
+        88 => ("fragment_offset", Unsigned),
+        89 => ("forwarding_status", Unsigned),
+        90 => ("mpls_vpn_route_distinguisher", Bytes),
+        94 => ("application_description", String),
+        95 => ("application_id", Bytes),
+        96 => ("application_name", String),
+        98 => ("post_ip_diff_serv_code_point", Unsigned),
+        128 => ("bgp_next_adjacent_as_number", Unsigned),
+        129 => ("bgp_prev_adjacent_as_number", Unsigned),
+        130 => ("exporter_ipv4_address", Ipv4),
+        131 => ("exporter_ipv6_address", Ipv6),
+        132 => ("dropped_octet_delta_count", Unsigned),
+        133 => ("dropped_packet_delta_count", Unsigned),
+        136 => ("flow_end_reason", Unsigned),
+        137 => ("common_properties_id", Unsigned),
+        138 => ("observation_point_id", Unsigned),
+        139 => ("icmp_type_code_ipv6", Unsigned),
+        148 => ("flow_id", Unsigned),
+        149 => ("observation_domain_id", Unsigned),
+        150 => ("flow_start_seconds", Seconds),
+        151 => ("flow_end_seconds", Seconds),
+        152 => ("flow_start_milliseconds", Milliseconds),
+        153 => ("flow_end_milliseconds", Milliseconds),
+        160 => ("system_init_time_milliseconds", Milliseconds),
+        161 => ("flow_duration_milliseconds", Unsigned),
+        176 => ("icmp_type_ipv4", Unsigned),
+        177 => ("icmp_code_ipv4", Unsigned),
+        178 => ("icmp_type_ipv6", Unsigned),
+        179 => ("icmp_code_ipv6", Unsigned),
+        180 => ("udp_source_port", Unsigned),
+        181 => ("udp_destination_port", Unsigned),
+        182 => ("tcp_source_port", Unsigned),
+        183 => ("tcp_destination_port", Unsigned),
+        192 => ("ip_ttl", Unsigned),
+        195 => ("ip_diff_serv_code_point", Unsigned),
+        225 => ("post_nat_source_ipv4_address", Ipv4),
+        226 => ("post_nat_destination_ipv4_address", Ipv4),
+        227 => ("post_napt_source_transport_port", Unsigned),
+        228 => ("post_napt_destination_transport_port", Unsigned),
+        230 => ("nat_event", Unsigned),
+        233 => ("firewall_event", Unsigned),
+        234 => ("ingress_vrf_id", Unsigned),
+        235 => ("egress_vrf_id", Unsigned),
+        281 => ("post_nat_source_ipv6_address", Ipv6),
+        282 => ("post_nat_destination_ipv6_address", Ipv6),
+        _ => return None,
+    };
+    Some(field)
+}
+
+/// Returns the name of an information element, falling back to its numeric identifier when unknown.
+pub(crate) fn field_name(id: u16, enterprise: Option<u32>) -> (String, FieldType) {
+    match (enterprise, lookup(id)) {
+        (None, Some((name, field_type))) => (name.to_owned(), field_type),
+        (None, None) => (format!("field_{}", id), FieldType::Bytes),
+        (Some(enterprise), _) => (
+            format!("enterprise_{}_field_{}", enterprise, id),
+            FieldType::Bytes,
+        ),
+    }
+}
+
+/// Decodes a value according to its type.
+///
+/// Values whose length does not match their type are kept as raw bytes rather than rejected, since
+/// exporters are allowed to use reduced-size encodings.
+pub(crate) fn decode_value(field_type: FieldType, bytes: &[u8]) -> FieldValue {
+    let unsigned = || {
+        bytes
+            .iter()
+            .fold(0u64, |value, byte| (value << 8) | u64::from(*byte))
+    };
+
+    match field_type {
+        FieldType::Unsigned if !bytes.is_empty() && bytes.len() <= 8 => {
+            FieldValue::Unsigned(unsigned())
+        }
+        FieldType::Seconds if !bytes.is_empty() && bytes.len() <= 8 => {
+            FieldValue::Timestamp(Duration::from_secs(unsigned()))
+        }
+        FieldType::Milliseconds if !bytes.is_empty() && bytes.len() <= 8 => {
+            FieldValue::Timestamp(Duration::from_millis(unsigned()))
+        }
+        FieldType::Ipv4 if bytes.len() == 4 => {
+            FieldValue::Ipv4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]))
+        }
+        FieldType::Ipv6 if bytes.len() == 16 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(bytes);
+            FieldValue::Ipv6(Ipv6Addr::from(octets))
+        }
+        FieldType::Mac if bytes.len() == 6 => {
+            let mut mac = [0; 6];
+            mac.copy_from_slice(bytes);
+            FieldValue::Mac(mac)
+        }
+        FieldType::String => FieldValue::String(
+            String::from_utf8_lossy(bytes)
+                .trim_end_matches('\0')
+                .to_owned(),
+        ),
+        _ => FieldValue::Bytes(bytes.to_vec()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_reduced_size_unsigned() {
+        assert_eq!(
+            decode_value(FieldType::Unsigned, &[1, 0]),
+            FieldValue::Unsigned(256)
+        );
+        assert_eq!(
+            decode_value(FieldType::Unsigned, &[0, 0, 0, 0, 0, 0, 0, 42]),
+            FieldValue::Unsigned(42)
+        );
+    }
+
+    #[test]
+    fn decodes_timestamps() {
+        assert_eq!(
+            decode_value(FieldType::Seconds, &1_600_000_000u32.to_be_bytes()),
+            FieldValue::Timestamp(Duration::from_secs(1_600_000_000))
+        );
+        assert_eq!(
+            decode_value(FieldType::Milliseconds, &1_600_000_000_123u64.to_be_bytes()),
+            FieldValue::Timestamp(Duration::from_millis(1_600_000_000_123))
+        );
+    }
+
+    #[test]
+    fn keeps_mismatched_lengths_as_bytes() {
+        assert_eq!(
+            decode_value(FieldType::Ipv4, &[10, 0, 0]),
+            FieldValue::Bytes(vec![10, 0, 0])
+        );
+    }
+
+    #[test]
+    fn trims_padded_strings() {
+        assert_eq!(
+            decode_value(FieldType::String, b"eth0\0\0\0\0"),
+            FieldValue::String("eth0".to_owned())
+        );
+    }
+
+    #[test]
+    fn names_unknown_fields() {
+        assert_eq!(field_name(8, None).0, "source_ipv4_address");
+        assert_eq!(field_name(999, None).0, "field_999");
+        assert_eq!(field_name(1, Some(9)).0, "enterprise_9_field_1");
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+    time::Duration,
+};
+
+/// The protocol a flow packet was encoded with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlowProtocol {
+    NetflowV5,
+    NetflowV9,
+    Ipfix,
+    SflowV5,
+}
+
+impl FlowProtocol {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            FlowProtocol::NetflowV5 => "netflow_v5",
+            FlowProtocol::NetflowV9 => "netflow_v9",
+            FlowProtocol::Ipfix => "ipfix",
+            FlowProtocol::SflowV5 => "sflow_v5",
+        }
+    }
+}
+
+/// The kind of information a record carries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecordKind {
+    /// A flow record, describing traffic seen by the exporter.
+    Flow,
+    /// An options record, describing the exporter itself (sampling configuration, interface names, ...).
+    Options,
+    /// An sFlow counters sample.
+    Counters,
+}
+
+impl RecordKind {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            RecordKind::Flow => "flow",
+            RecordKind::Options => "options",
+            RecordKind::Counters => "counters",
+        }
+    }
+}
+
+/// A decoded field value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FieldValue {
+    Unsigned(u64),
+    Ipv4(Ipv4Addr),
+    Ipv6(Ipv6Addr),
+    Mac([u8; 6]),
+    String(String),
+    Bytes(Vec<u8>),
+    /// A time, as the duration since the Unix epoch.
+    Timestamp(Duration),
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Unsigned(value) => write!(f, "{}", value),
+            FieldValue::Ipv4(address) => write!(f, "{}", address),
+            FieldValue::Ipv6(address) => write!(f, "{}", address),
+            FieldValue::Mac(mac) => write!(
+                f,
+                "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+                mac[0], mac[1], mac[2], mac[3], mac[4], mac[5]
+            ),
+            FieldValue::String(value) => write!(f, "{}", value),
+            FieldValue::Bytes(bytes) => {
+                for byte in bytes {
+                    write!(f, "{:02x}", byte)?;
+                }
+                Ok(())
+            }
+            FieldValue::Timestamp(since_epoch) => write!(
+                f,
+                "{}.{:03}",
+                since_epoch.as_secs(),
+                since_epoch.subsec_millis()
+            ),
+        }
+    }
+}
+
+/// A single record decoded from a flow packet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowRecord {
+    pub kind: RecordKind,
+    pub fields: BTreeMap<String, FieldValue>,
+}
+
+impl FlowRecord {
+    pub(crate) const fn new(kind: RecordKind) -> Self {
+        Self {
+            kind,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, name: impl Into<String>, value: FieldValue) {
+        self.fields.insert(name.into(), value);
+    }
+}
+
+/// A decoded flow packet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlowPacket {
+    pub protocol: FlowProtocol,
+    /// Fields from the packet header, shared by all records in the packet.
+    pub header: BTreeMap<String, FieldValue>,
+    /// The time the packet was exported, in seconds since the Unix epoch, if the protocol carries it.
+    pub export_time: Option<u32>,
+    pub records: Vec<FlowRecord>,
+    /// The IDs of data sets that could not be decoded because their template has not been received yet.
+    pub missing_templates: Vec<u16>,
+}
+
+impl FlowPacket {
+    pub(crate) const fn new(protocol: FlowProtocol) -> Self {
+        Self {
+            protocol,
+            header: BTreeMap::new(),
+            export_time: None,
+            records: Vec::new(),
+            missing_templates: Vec::new(),
+        }
+    }
+
+    pub(crate) fn insert_header(&mut self, name: &str, value: FieldValue) {
+        self.header.insert(name.to_owned(), value);
+    }
+}
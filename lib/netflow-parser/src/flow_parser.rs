@@ -0,0 +1,126 @@
+use std::net::SocketAddr;
+
+use thiserror::Error;
+
+use crate::{flow::FlowPacket, ipfix, netflow_v5, netflow_v9, sflow, template::TemplateCache};
+
+/// Error type for flow packet parsing
+#[derive(Error, Debug)]
+pub enum FlowParserError {
+    #[error("Packet is truncated while reading {}", context)]
+    Truncated { context: &'static str },
+    #[error("Invalid packet: {}", cause)]
+    Invalid { cause: String },
+    #[error("Unsupported protocol version {}", version)]
+    UnsupportedVersion { version: u32 },
+    #[error("Template limit of {} reached", max_templates)]
+    TooManyTemplates { max_templates: usize },
+}
+
+/// Result alias for parsing
+pub type FlowParserResult<T> = Result<T, FlowParserError>;
+
+/// A parser for NetFlow v5, NetFlow v9, IPFIX and sFlow v5 packets.
+///
+/// NetFlow v9 and IPFIX data can only be decoded using templates sent earlier by the same exporter, so
+/// a single parser should be used for all packets received on a socket.
+#[derive(Debug)]
+pub struct FlowParser {
+    templates: TemplateCache,
+}
+
+impl FlowParser {
+    /// Creates a parser keeping at most `max_templates` templates, across all exporters.
+    pub fn new(max_templates: usize) -> Self {
+        Self {
+            templates: TemplateCache::new(max_templates),
+        }
+    }
+
+    /// Parses a packet received from `exporter`, detecting its protocol from its version number.
+    pub fn parse(&mut self, exporter: SocketAddr, data: &[u8]) -> FlowParserResult<FlowPacket> {
+        if data.len() < 4 {
+            return Err(FlowParserError::Truncated { context: "version" });
+        }
+
+        match u16::from_be_bytes([data[0], data[1]]) {
+            5 => netflow_v5::parse(data),
+            9 => netflow_v9::parse(&mut self.templates, exporter, data),
+            10 => ipfix::parse(&mut self.templates, exporter, data),
+            // sFlow encodes its version on 32 bits, so its first two bytes are zero.
+            0 => match u32::from_be_bytes([data[0], data[1], data[2], data[3]]) {
+                5 => sflow::parse(data),
+                version => Err(FlowParserError::UnsupportedVersion { version }),
+            },
+            version => Err(FlowParserError::UnsupportedVersion {
+                version: version.into(),
+            }),
+        }
+    }
+
+    /// The number of templates currently known, across all exporters.
+    pub fn template_count(&self) -> usize {
+        self.templates.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flow::FlowProtocol;
+
+    fn exporter() -> SocketAddr {
+        "192.0.2.1:2055".parse().unwrap()
+    }
+
+    #[test]
+    fn detects_protocols() {
+        let mut parser = FlowParser::new(10);
+
+        let mut netflow_v5 = vec![0, 5, 0, 0];
+        netflow_v5.extend_from_slice(&[0; 20]);
+        assert_eq!(
+            parser.parse(exporter(), &netflow_v5).unwrap().protocol,
+            FlowProtocol::NetflowV5
+        );
+
+        let mut netflow_v9 = vec![0, 9, 0, 0];
+        netflow_v9.extend_from_slice(&[0; 16]);
+        assert_eq!(
+            parser.parse(exporter(), &netflow_v9).unwrap().protocol,
+            FlowProtocol::NetflowV9
+        );
+
+        let mut ipfix = vec![0, 10, 0, 16];
+        ipfix.extend_from_slice(&[0; 12]);
+        assert_eq!(
+            parser.parse(exporter(), &ipfix).unwrap().protocol,
+            FlowProtocol::Ipfix
+        );
+
+        let mut sflow = vec![0, 0, 0, 5, 0, 0, 0, 1];
+        sflow.extend_from_slice(&[0; 20]);
+        assert_eq!(
+            parser.parse(exporter(), &sflow).unwrap().protocol,
+            FlowProtocol::SflowV5
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_versions() {
+        let mut parser = FlowParser::new(10);
+
+        assert!(matches!(
+            parser.parse(exporter(), &[0, 1, 0, 0]),
+            Err(FlowParserError::UnsupportedVersion { version: 1 })
+        ));
+        assert!(matches!(
+            parser.parse(exporter(), &[0, 0, 0, 4]),
+            Err(FlowParserError::UnsupportedVersion { version: 4 })
+        ));
+        assert!(matches!(
+            parser.parse(exporter(), &[0, 5]),
+            Err(FlowParserError::Truncated { .. })
+        ));
+    }
+}
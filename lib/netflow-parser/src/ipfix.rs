@@ -0,0 +1,258 @@
+use std::net::SocketAddr;
+
+use crate::{
+    flow::{FieldValue, FlowPacket, FlowProtocol, RecordKind},
+    flow_parser::{FlowParserError, FlowParserResult},
+    reader::Reader,
+    template::{decode_data_set, Template, TemplateCache, TemplateField},
+};
+
+const HEADER_LENGTH: usize = 16;
+const TEMPLATE_SET_ID: u16 = 2;
+const OPTIONS_TEMPLATE_SET_ID: u16 = 3;
+const MIN_DATA_SET_ID: u16 = 256;
+const ENTERPRISE_BIT: u16 = 0x8000;
+
+/// Parses an IPFIX message, updating the template cache with any templates it carries.
+pub(crate) fn parse(
+    cache: &mut TemplateCache,
+    exporter: SocketAddr,
+    data: &[u8],
+) -> FlowParserResult<FlowPacket> {
+    let mut reader = Reader::new(data, "IPFIX header");
+    let mut packet = FlowPacket::new(FlowProtocol::Ipfix);
+
+    let _version = reader.u16()?;
+    let length = usize::from(reader.u16()?);
+    let export_time = reader.u32()?;
+    let sequence = reader.u32()?;
+    let domain = reader.u32()?;
+
+    if length < HEADER_LENGTH {
+        return Err(FlowParserError::Invalid {
+            cause: format!("IPFIX message length {} is too small", length),
+        });
+    }
+    // Anything past the length announced in the header is not part of the message.
+    let mut reader = reader.sub_reader(length - HEADER_LENGTH, "IPFIX message")?;
+
+    packet.export_time = Some(export_time);
+    packet.insert_header("sequence_number", FieldValue::Unsigned(sequence.into()));
+    packet.insert_header("observation_domain_id", FieldValue::Unsigned(domain.into()));
+
+    while !reader.is_empty() {
+        let set_id = reader.u16()?;
+        let length = usize::from(reader.u16()?);
+        if length < 4 {
+            return Err(FlowParserError::Invalid {
+                cause: format!("IPFIX set length {} is too small", length),
+            });
+        }
+        let set = reader.sub_reader(length - 4, "IPFIX set")?;
+
+        match set_id {
+            TEMPLATE_SET_ID => parse_templates(cache, exporter, domain, set, false)?,
+            OPTIONS_TEMPLATE_SET_ID => parse_templates(cache, exporter, domain, set, true)?,
+            id if id >= MIN_DATA_SET_ID => decode_data_set(
+                cache,
+                exporter,
+                domain,
+                id,
+                set,
+                &mut packet.records,
+                &mut packet.missing_templates,
+            )?,
+            // Set IDs 0, 1 and 4 to 255 are reserved.
+            _ => {}
+        }
+    }
+
+    Ok(packet)
+}
+
+fn parse_templates(
+    cache: &mut TemplateCache,
+    exporter: SocketAddr,
+    domain: u32,
+    mut reader: Reader<'_>,
+    options: bool,
+) -> FlowParserResult<()> {
+    // A template record is at least four bytes long, anything shorter is padding.
+    while reader.remaining() >= 4 {
+        let template_id = reader.u16()?;
+        let field_count = reader.u16()?;
+
+        if field_count == 0 {
+            // A template withdrawal. Withdrawing the template set or options template set ID
+            // withdraws every template of the observation domain.
+            if template_id == TEMPLATE_SET_ID || template_id == OPTIONS_TEMPLATE_SET_ID {
+                cache.remove_domain(exporter, domain);
+            } else {
+                cache.remove(exporter, domain, template_id);
+            }
+            continue;
+        }
+
+        if options {
+            // Scope fields are regular information elements in IPFIX, so the scope count is only
+            // needed to skip past it.
+            let _scope_field_count = reader.u16()?;
+        }
+
+        let mut fields = Vec::with_capacity(usize::from(field_count));
+        for _ in 0..field_count {
+            let id = reader.u16()?;
+            let length = reader.u16()?;
+            let field = if id & ENTERPRISE_BIT != 0 {
+                TemplateField {
+                    enterprise: Some(reader.u32()?),
+                    ..TemplateField::new(id & !ENTERPRISE_BIT, length)
+                }
+            } else {
+                TemplateField::new(id, length)
+            };
+            fields.push(field);
+        }
+
+        let kind = if options {
+            RecordKind::Options
+        } else {
+            RecordKind::Flow
+        };
+        cache.insert(exporter, domain, template_id, Template { kind, fields })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv6Addr;
+
+    use super::*;
+
+    fn exporter() -> SocketAddr {
+        "[2001:db8::1]:4739".parse().unwrap()
+    }
+
+    fn message(sets: &[Vec<u8>]) -> Vec<u8> {
+        let body = sets.concat();
+        let mut data = vec![];
+        data.extend_from_slice(&10u16.to_be_bytes());
+        data.extend_from_slice(&((HEADER_LENGTH + body.len()) as u16).to_be_bytes());
+        data.extend_from_slice(&1_600_000_000u32.to_be_bytes());
+        data.extend_from_slice(&5u32.to_be_bytes());
+        data.extend_from_slice(&7u32.to_be_bytes());
+        data.extend(body);
+        data
+    }
+
+    fn set(id: u16, content: &[u8]) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&id.to_be_bytes());
+        data.extend_from_slice(&((content.len() + 4) as u16).to_be_bytes());
+        data.extend_from_slice(content);
+        data
+    }
+
+    fn template_set() -> Vec<u8> {
+        let mut content = vec![];
+        content.extend_from_slice(&300u16.to_be_bytes());
+        content.extend_from_slice(&3u16.to_be_bytes());
+        content.extend_from_slice(&[0, 27, 0, 16]); // source_ipv6_address
+        content.extend_from_slice(&[0, 96, 0xff, 0xff]); // application_name, variable length
+        content.extend_from_slice(&[0x80, 1, 0, 2]); // enterprise specific
+        content.extend_from_slice(&29305u32.to_be_bytes());
+        set(TEMPLATE_SET_ID, &content)
+    }
+
+    fn data_set() -> Vec<u8> {
+        let mut content = vec![];
+        content.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+        content.push(3);
+        content.extend_from_slice(b"dns");
+        content.extend_from_slice(&[0xab, 0xcd]);
+        set(300, &content)
+    }
+
+    #[test]
+    fn parses_templates_and_data() {
+        let mut cache = TemplateCache::new(10);
+        let packet = parse(
+            &mut cache,
+            exporter(),
+            &message(&[template_set(), data_set()]),
+        )
+        .unwrap();
+
+        assert_eq!(packet.protocol, FlowProtocol::Ipfix);
+        assert_eq!(packet.export_time, Some(1_600_000_000));
+        assert_eq!(
+            packet.header["observation_domain_id"],
+            FieldValue::Unsigned(7)
+        );
+        assert_eq!(packet.records.len(), 1);
+
+        let record = &packet.records[0];
+        assert_eq!(
+            record.fields["source_ipv6_address"],
+            FieldValue::Ipv6(Ipv6Addr::LOCALHOST)
+        );
+        assert_eq!(
+            record.fields["application_name"],
+            FieldValue::String("dns".to_owned())
+        );
+        assert_eq!(
+            record.fields["enterprise_29305_field_1"],
+            FieldValue::Bytes(vec![0xab, 0xcd])
+        );
+    }
+
+    #[test]
+    fn withdraws_templates() {
+        let mut cache = TemplateCache::new(10);
+        parse(&mut cache, exporter(), &message(&[template_set()])).unwrap();
+
+        let mut withdrawal = vec![];
+        withdrawal.extend_from_slice(&300u16.to_be_bytes());
+        withdrawal.extend_from_slice(&0u16.to_be_bytes());
+        parse(
+            &mut cache,
+            exporter(),
+            &message(&[set(TEMPLATE_SET_ID, &withdrawal)]),
+        )
+        .unwrap();
+
+        let packet = parse(&mut cache, exporter(), &message(&[data_set()])).unwrap();
+        assert!(packet.records.is_empty());
+        assert_eq!(packet.missing_templates, vec![300]);
+    }
+
+    #[test]
+    fn parses_options_templates() {
+        let mut content = vec![];
+        content.extend_from_slice(&400u16.to_be_bytes());
+        content.extend_from_slice(&2u16.to_be_bytes());
+        content.extend_from_slice(&1u16.to_be_bytes()); // scope field count
+        content.extend_from_slice(&[0, 10, 0, 4]); // ingress_interface
+        content.extend_from_slice(&[0, 82, 0, 4]); // interface_name
+
+        let mut cache = TemplateCache::new(10);
+        let packet = parse(
+            &mut cache,
+            exporter(),
+            &message(&[
+                set(OPTIONS_TEMPLATE_SET_ID, &content),
+                set(400, &[0, 0, 0, 2, b'e', b't', b'h', b'1']),
+            ]),
+        )
+        .unwrap();
+
+        let record = &packet.records[0];
+        assert_eq!(record.kind, RecordKind::Options);
+        assert_eq!(record.fields["ingress_interface"], FieldValue::Unsigned(2));
+        assert_eq!(
+            record.fields["interface_name"],
+            FieldValue::String("eth1".to_owned())
+        );
+    }
+}
@@ -0,0 +1,18 @@
+#![deny(warnings)]
+#![warn(
+    missing_debug_implementations,
+    rust_2018_idioms,
+    unreachable_pub,
+    non_snake_case,
+    non_upper_case_globals
+)]
+
+pub mod fields;
+pub mod flow;
+pub mod flow_parser;
+mod ipfix;
+mod netflow_v5;
+mod netflow_v9;
+mod reader;
+mod sflow;
+mod template;
@@ -0,0 +1,174 @@
+use crate::{
+    flow::{FieldValue, FlowPacket, FlowProtocol, FlowRecord, RecordKind},
+    flow_parser::{FlowParserError, FlowParserResult},
+    reader::Reader,
+};
+
+const HEADER_LENGTH: usize = 24;
+const RECORD_LENGTH: usize = 48;
+
+/// Parses a NetFlow v5 packet, which has a fixed layout and needs no template state.
+pub(crate) fn parse(data: &[u8]) -> FlowParserResult<FlowPacket> {
+    let mut reader = Reader::new(data, "NetFlow v5 header");
+    let mut packet = FlowPacket::new(FlowProtocol::NetflowV5);
+
+    let _version = reader.u16()?;
+    let count = usize::from(reader.u16()?);
+    let sys_uptime = reader.u32()?;
+    let unix_secs = reader.u32()?;
+    let unix_nsecs = reader.u32()?;
+    let flow_sequence = reader.u32()?;
+    let engine_type = reader.u8()?;
+    let engine_id = reader.u8()?;
+    let sampling = reader.u16()?;
+
+    if data.len() < HEADER_LENGTH + count * RECORD_LENGTH {
+        return Err(FlowParserError::Truncated {
+            context: "NetFlow v5 records",
+        });
+    }
+
+    packet.export_time = Some(unix_secs);
+    packet.insert_header("sys_uptime", FieldValue::Unsigned(sys_uptime.into()));
+    packet.insert_header("unix_nanoseconds", FieldValue::Unsigned(unix_nsecs.into()));
+    packet.insert_header(
+        "sequence_number",
+        FieldValue::Unsigned(flow_sequence.into()),
+    );
+    packet.insert_header("engine_type", FieldValue::Unsigned(engine_type.into()));
+    packet.insert_header("engine_id", FieldValue::Unsigned(engine_id.into()));
+    // The two most significant bits hold the sampling mode, the rest the sampling interval.
+    packet.insert_header(
+        "sampling_mode",
+        FieldValue::Unsigned((sampling >> 14).into()),
+    );
+    packet.insert_header(
+        "sampling_interval",
+        FieldValue::Unsigned((sampling & 0x3fff).into()),
+    );
+
+    let mut reader = Reader::new(&data[HEADER_LENGTH..], "NetFlow v5 record");
+    for _ in 0..count {
+        packet.records.push(parse_record(&mut reader)?);
+    }
+
+    Ok(packet)
+}
+
+fn parse_record(reader: &mut Reader<'_>) -> FlowParserResult<FlowRecord> {
+    let mut record = FlowRecord::new(RecordKind::Flow);
+
+    record.insert("source_ipv4_address", FieldValue::Ipv4(reader.ipv4()?));
+    record.insert("destination_ipv4_address", FieldValue::Ipv4(reader.ipv4()?));
+    record.insert("ip_next_hop_ipv4_address", FieldValue::Ipv4(reader.ipv4()?));
+    record.insert("ingress_interface", unsigned(reader.u16()?));
+    record.insert("egress_interface", unsigned(reader.u16()?));
+    record.insert("packet_delta_count", unsigned(reader.u32()?));
+    record.insert("octet_delta_count", unsigned(reader.u32()?));
+    record.insert("flow_start_sys_up_time", unsigned(reader.u32()?));
+    record.insert("flow_end_sys_up_time", unsigned(reader.u32()?));
+    record.insert("source_transport_port", unsigned(reader.u16()?));
+    record.insert("destination_transport_port", unsigned(reader.u16()?));
+    reader.skip(1)?;
+    record.insert("tcp_control_bits", unsigned(reader.u8()?));
+    record.insert("protocol_identifier", unsigned(reader.u8()?));
+    record.insert("ip_class_of_service", unsigned(reader.u8()?));
+    record.insert("bgp_source_as_number", unsigned(reader.u16()?));
+    record.insert("bgp_destination_as_number", unsigned(reader.u16()?));
+    record.insert("source_ipv4_prefix_length", unsigned(reader.u8()?));
+    record.insert("destination_ipv4_prefix_length", unsigned(reader.u8()?));
+    reader.skip(2)?;
+
+    Ok(record)
+}
+
+fn unsigned(value: impl Into<u64>) -> FieldValue {
+    FieldValue::Unsigned(value.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn packet(count: u16) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&5u16.to_be_bytes());
+        data.extend_from_slice(&count.to_be_bytes());
+        data.extend_from_slice(&1000u32.to_be_bytes()); // sys_uptime
+        data.extend_from_slice(&1_600_000_000u32.to_be_bytes()); // unix_secs
+        data.extend_from_slice(&0u32.to_be_bytes()); // unix_nsecs
+        data.extend_from_slice(&7u32.to_be_bytes()); // flow_sequence
+        data.extend_from_slice(&[0, 1]); // engine_type, engine_id
+        data.extend_from_slice(&((1u16 << 14) | 100).to_be_bytes()); // sampling
+        for _ in 0..count {
+            data.extend_from_slice(&[10, 0, 0, 1]);
+            data.extend_from_slice(&[10, 0, 0, 2]);
+            data.extend_from_slice(&[0, 0, 0, 0]);
+            data.extend_from_slice(&1u16.to_be_bytes());
+            data.extend_from_slice(&2u16.to_be_bytes());
+            data.extend_from_slice(&3u32.to_be_bytes()); // packets
+            data.extend_from_slice(&180u32.to_be_bytes()); // octets
+            data.extend_from_slice(&900u32.to_be_bytes()); // first
+            data.extend_from_slice(&950u32.to_be_bytes()); // last
+            data.extend_from_slice(&12345u16.to_be_bytes());
+            data.extend_from_slice(&443u16.to_be_bytes());
+            data.extend_from_slice(&[0, 0x12, 6, 0]); // pad, flags, proto, tos
+            data.extend_from_slice(&64512u16.to_be_bytes());
+            data.extend_from_slice(&64513u16.to_be_bytes());
+            data.extend_from_slice(&[24, 16, 0, 0]); // masks, pad
+        }
+        data
+    }
+
+    #[test]
+    fn parses_records() {
+        let packet = parse(&packet(2)).unwrap();
+
+        assert_eq!(packet.export_time, Some(1_600_000_000));
+        assert_eq!(packet.header["sequence_number"], FieldValue::Unsigned(7));
+        assert_eq!(packet.header["sampling_mode"], FieldValue::Unsigned(1));
+        assert_eq!(
+            packet.header["sampling_interval"],
+            FieldValue::Unsigned(100)
+        );
+        assert_eq!(packet.records.len(), 2);
+
+        let record = &packet.records[0];
+        assert_eq!(
+            record.fields["source_ipv4_address"],
+            FieldValue::Ipv4(Ipv4Addr::new(10, 0, 0, 1))
+        );
+        assert_eq!(
+            record.fields["destination_transport_port"],
+            FieldValue::Unsigned(443)
+        );
+        assert_eq!(
+            record.fields["protocol_identifier"],
+            FieldValue::Unsigned(6)
+        );
+        assert_eq!(
+            record.fields["tcp_control_bits"],
+            FieldValue::Unsigned(0x12)
+        );
+        assert_eq!(
+            record.fields["octet_delta_count"],
+            FieldValue::Unsigned(180)
+        );
+        assert_eq!(
+            record.fields["destination_ipv4_prefix_length"],
+            FieldValue::Unsigned(16)
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_packets() {
+        let mut data = packet(2);
+        data.truncate(data.len() - 1);
+        assert!(matches!(
+            parse(&data),
+            Err(FlowParserError::Truncated { .. })
+        ));
+    }
+}
@@ -0,0 +1,258 @@
+use std::net::SocketAddr;
+
+use crate::{
+    flow::{FieldValue, FlowPacket, FlowProtocol, RecordKind},
+    flow_parser::{FlowParserError, FlowParserResult},
+    reader::Reader,
+    template::{decode_data_set, Template, TemplateCache, TemplateField},
+};
+
+const TEMPLATE_FLOWSET_ID: u16 = 0;
+const OPTIONS_TEMPLATE_FLOWSET_ID: u16 = 1;
+const MIN_DATA_FLOWSET_ID: u16 = 256;
+
+/// Parses a NetFlow v9 packet, updating the template cache with any templates it carries.
+pub(crate) fn parse(
+    cache: &mut TemplateCache,
+    exporter: SocketAddr,
+    data: &[u8],
+) -> FlowParserResult<FlowPacket> {
+    let mut reader = Reader::new(data, "NetFlow v9 header");
+    let mut packet = FlowPacket::new(FlowProtocol::NetflowV9);
+
+    let _version = reader.u16()?;
+    let _count = reader.u16()?;
+    let sys_uptime = reader.u32()?;
+    let unix_secs = reader.u32()?;
+    let sequence = reader.u32()?;
+    let source_id = reader.u32()?;
+
+    packet.export_time = Some(unix_secs);
+    packet.insert_header("sys_uptime", FieldValue::Unsigned(sys_uptime.into()));
+    packet.insert_header("sequence_number", FieldValue::Unsigned(sequence.into()));
+    packet.insert_header("source_id", FieldValue::Unsigned(source_id.into()));
+
+    while !reader.is_empty() {
+        let flowset_id = reader.u16()?;
+        let length = usize::from(reader.u16()?);
+        if length < 4 {
+            return Err(FlowParserError::Invalid {
+                cause: format!("NetFlow v9 flowset length {} is too small", length),
+            });
+        }
+        let flowset = reader.sub_reader(length - 4, "NetFlow v9 flowset")?;
+
+        match flowset_id {
+            TEMPLATE_FLOWSET_ID => parse_templates(cache, exporter, source_id, flowset)?,
+            OPTIONS_TEMPLATE_FLOWSET_ID => {
+                parse_options_templates(cache, exporter, source_id, flowset)?
+            }
+            id if id >= MIN_DATA_FLOWSET_ID => decode_data_set(
+                cache,
+                exporter,
+                source_id,
+                id,
+                flowset,
+                &mut packet.records,
+                &mut packet.missing_templates,
+            )?,
+            // Flowset IDs 2 to 255 are reserved.
+            _ => {}
+        }
+    }
+
+    Ok(packet)
+}
+
+fn parse_templates(
+    cache: &mut TemplateCache,
+    exporter: SocketAddr,
+    source_id: u32,
+    mut reader: Reader<'_>,
+) -> FlowParserResult<()> {
+    // A template record is at least four bytes long, anything shorter is padding.
+    while reader.remaining() >= 4 {
+        let template_id = reader.u16()?;
+        let field_count = reader.u16()?;
+
+        let mut fields = Vec::with_capacity(usize::from(field_count));
+        for _ in 0..field_count {
+            let id = reader.u16()?;
+            let length = reader.u16()?;
+            fields.push(TemplateField::new(id, length));
+        }
+
+        let template = Template {
+            kind: RecordKind::Flow,
+            fields,
+        };
+        cache.insert(exporter, source_id, template_id, template)?;
+    }
+    Ok(())
+}
+
+fn parse_options_templates(
+    cache: &mut TemplateCache,
+    exporter: SocketAddr,
+    source_id: u32,
+    mut reader: Reader<'_>,
+) -> FlowParserResult<()> {
+    while reader.remaining() >= 6 {
+        let template_id = reader.u16()?;
+        let scope_length = usize::from(reader.u16()?);
+        let option_length = usize::from(reader.u16()?);
+
+        let mut fields = Vec::with_capacity((scope_length + option_length) / 4);
+        for _ in 0..scope_length / 4 {
+            let id = reader.u16()?;
+            let length = reader.u16()?;
+            fields.push(TemplateField {
+                netflow_v9_scope: true,
+                ..TemplateField::new(id, length)
+            });
+        }
+        for _ in 0..option_length / 4 {
+            let id = reader.u16()?;
+            let length = reader.u16()?;
+            fields.push(TemplateField::new(id, length));
+        }
+
+        let template = Template {
+            kind: RecordKind::Options,
+            fields,
+        };
+        cache.insert(exporter, source_id, template_id, template)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn exporter() -> SocketAddr {
+        "192.0.2.1:2055".parse().unwrap()
+    }
+
+    fn header(source_id: u32) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&9u16.to_be_bytes());
+        data.extend_from_slice(&1u16.to_be_bytes());
+        data.extend_from_slice(&1000u32.to_be_bytes());
+        data.extend_from_slice(&1_600_000_000u32.to_be_bytes());
+        data.extend_from_slice(&42u32.to_be_bytes());
+        data.extend_from_slice(&source_id.to_be_bytes());
+        data
+    }
+
+    fn template_flowset() -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&TEMPLATE_FLOWSET_ID.to_be_bytes());
+        data.extend_from_slice(&16u16.to_be_bytes());
+        data.extend_from_slice(&256u16.to_be_bytes());
+        data.extend_from_slice(&2u16.to_be_bytes());
+        data.extend_from_slice(&[0, 8, 0, 4]); // source_ipv4_address
+        data.extend_from_slice(&[0, 1, 0, 4]); // octet_delta_count
+        data
+    }
+
+    fn data_flowset() -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&256u16.to_be_bytes());
+        data.extend_from_slice(&16u16.to_be_bytes());
+        data.extend_from_slice(&[10, 0, 0, 1, 0, 0, 1, 0]);
+        data.extend_from_slice(&[0, 0, 0, 0]); // padding
+        data
+    }
+
+    #[test]
+    fn parses_template_and_data_in_one_packet() {
+        let mut cache = TemplateCache::new(10);
+        let mut data = header(1);
+        data.extend(template_flowset());
+        data.extend(data_flowset());
+
+        let packet = parse(&mut cache, exporter(), &data).unwrap();
+
+        assert_eq!(packet.header["source_id"], FieldValue::Unsigned(1));
+        assert_eq!(packet.export_time, Some(1_600_000_000));
+        assert_eq!(packet.records.len(), 1);
+        assert_eq!(
+            packet.records[0].fields["source_ipv4_address"],
+            FieldValue::Ipv4(Ipv4Addr::new(10, 0, 0, 1))
+        );
+        assert_eq!(
+            packet.records[0].fields["octet_delta_count"],
+            FieldValue::Unsigned(256)
+        );
+    }
+
+    #[test]
+    fn uses_templates_from_previous_packets() {
+        let mut cache = TemplateCache::new(10);
+
+        let mut data = header(1);
+        data.extend(data_flowset());
+        let packet = parse(&mut cache, exporter(), &data).unwrap();
+        assert!(packet.records.is_empty());
+        assert_eq!(packet.missing_templates, vec![256]);
+
+        let mut data = header(1);
+        data.extend(template_flowset());
+        parse(&mut cache, exporter(), &data).unwrap();
+
+        let mut data = header(1);
+        data.extend(data_flowset());
+        let packet = parse(&mut cache, exporter(), &data).unwrap();
+        assert_eq!(packet.records.len(), 1);
+        assert!(packet.missing_templates.is_empty());
+
+        // Templates are not shared between source IDs.
+        let mut data = header(2);
+        data.extend(data_flowset());
+        let packet = parse(&mut cache, exporter(), &data).unwrap();
+        assert_eq!(packet.missing_templates, vec![256]);
+    }
+
+    #[test]
+    fn parses_options_templates() {
+        let mut cache = TemplateCache::new(10);
+        let mut data = header(1);
+        data.extend_from_slice(&OPTIONS_TEMPLATE_FLOWSET_ID.to_be_bytes());
+        data.extend_from_slice(&20u16.to_be_bytes());
+        data.extend_from_slice(&257u16.to_be_bytes());
+        data.extend_from_slice(&4u16.to_be_bytes()); // scope length
+        data.extend_from_slice(&4u16.to_be_bytes()); // option length
+        data.extend_from_slice(&[0, 2, 0, 2]); // scope_interface
+        data.extend_from_slice(&[0, 34, 0, 4]); // sampling_interval
+        data.extend_from_slice(&[0, 0]); // padding
+        data.extend_from_slice(&257u16.to_be_bytes());
+        data.extend_from_slice(&10u16.to_be_bytes());
+        data.extend_from_slice(&[0, 3, 0, 0, 0, 100]);
+
+        let packet = parse(&mut cache, exporter(), &data).unwrap();
+
+        assert_eq!(packet.records.len(), 1);
+        let record = &packet.records[0];
+        assert_eq!(record.kind, RecordKind::Options);
+        assert_eq!(record.fields["scope_interface"], FieldValue::Unsigned(3));
+        assert_eq!(
+            record.fields["sampling_interval"],
+            FieldValue::Unsigned(100)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_flowset_length() {
+        let mut cache = TemplateCache::new(10);
+        let mut data = header(1);
+        data.extend_from_slice(&[1, 0, 0, 2]);
+
+        assert!(matches!(
+            parse(&mut cache, exporter(), &data),
+            Err(FlowParserError::Invalid { .. })
+        ));
+    }
+}
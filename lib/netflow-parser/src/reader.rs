@@ -0,0 +1,103 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::flow_parser::{FlowParserError, FlowParserResult};
+
+/// A cursor over a big-endian encoded buffer.
+#[derive(Debug)]
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+    context: &'static str,
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) const fn new(data: &'a [u8], context: &'static str) -> Self {
+        Self { data, context }
+    }
+
+    pub(crate) const fn remaining(&self) -> usize {
+        self.data.len()
+    }
+
+    pub(crate) const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub(crate) fn bytes(&mut self, len: usize) -> FlowParserResult<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(FlowParserError::Truncated {
+                context: self.context,
+            });
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    /// Splits off the next `len` bytes into a reader of their own.
+    pub(crate) fn sub_reader(
+        &mut self,
+        len: usize,
+        context: &'static str,
+    ) -> FlowParserResult<Reader<'a>> {
+        self.bytes(len).map(|data| Reader::new(data, context))
+    }
+
+    pub(crate) fn skip(&mut self, len: usize) -> FlowParserResult<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    pub(crate) fn u8(&mut self) -> FlowParserResult<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    pub(crate) fn u16(&mut self) -> FlowParserResult<u16> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    pub(crate) fn u32(&mut self) -> FlowParserResult<u32> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    pub(crate) fn u64(&mut self) -> FlowParserResult<u64> {
+        self.array().map(u64::from_be_bytes)
+    }
+
+    pub(crate) fn ipv4(&mut self) -> FlowParserResult<Ipv4Addr> {
+        self.array::<4>().map(Ipv4Addr::from)
+    }
+
+    pub(crate) fn ipv6(&mut self) -> FlowParserResult<Ipv6Addr> {
+        self.array::<16>().map(Ipv6Addr::from)
+    }
+
+    pub(crate) fn array<const N: usize>(&mut self) -> FlowParserResult<[u8; N]> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.bytes(N)?);
+        Ok(array)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_big_endian_values() {
+        let mut reader = Reader::new(&[0, 1, 0, 0, 0, 2, 10, 0, 0, 1, 7], "test");
+        assert_eq!(reader.u16().unwrap(), 1);
+        assert_eq!(reader.u32().unwrap(), 2);
+        assert_eq!(reader.ipv4().unwrap(), Ipv4Addr::new(10, 0, 0, 1));
+        assert_eq!(reader.remaining(), 1);
+        assert_eq!(reader.u8().unwrap(), 7);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn reports_truncation() {
+        let mut reader = Reader::new(&[0, 1, 2], "test");
+        assert!(matches!(
+            reader.u32(),
+            Err(FlowParserError::Truncated { context: "test" })
+        ));
+    }
+}
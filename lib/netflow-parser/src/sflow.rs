@@ -0,0 +1,415 @@
+//! sFlow v5, as described in <https://sflow.org/sflow_version_5.txt>.
+//!
+//! Each flow sample is decoded into a single flow record, merging the fields of the flow records it
+//! contains. Counter samples only have their generic interface counters decoded.
+
+use crate::{
+    flow::{FieldValue, FlowPacket, FlowProtocol, FlowRecord, RecordKind},
+    flow_parser::{FlowParserError, FlowParserResult},
+    reader::Reader,
+};
+
+const FLOW_SAMPLE: u32 = 1;
+const COUNTER_SAMPLE: u32 = 2;
+const EXPANDED_FLOW_SAMPLE: u32 = 3;
+const EXPANDED_COUNTER_SAMPLE: u32 = 4;
+
+const SAMPLED_HEADER: u32 = 1;
+const SAMPLED_IPV4: u32 = 3;
+const SAMPLED_IPV6: u32 = 4;
+const EXTENDED_SWITCH: u32 = 1001;
+
+const GENERIC_INTERFACE_COUNTERS: u32 = 1;
+
+const HEADER_PROTOCOL_ETHERNET: u32 = 1;
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86dd;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+const IP_PROTOCOL_TCP: u8 = 6;
+const IP_PROTOCOL_UDP: u8 = 17;
+
+/// Parses an sFlow v5 datagram.
+pub(crate) fn parse(data: &[u8]) -> FlowParserResult<FlowPacket> {
+    let mut reader = Reader::new(data, "sFlow datagram header");
+    let mut packet = FlowPacket::new(FlowProtocol::SflowV5);
+
+    let _version = reader.u32()?;
+    let agent_address = match reader.u32()? {
+        1 => FieldValue::Ipv4(reader.ipv4()?),
+        2 => FieldValue::Ipv6(reader.ipv6()?),
+        address_type => {
+            return Err(FlowParserError::Invalid {
+                cause: format!("unknown sFlow agent address type {}", address_type),
+            })
+        }
+    };
+    let sub_agent_id = reader.u32()?;
+    let sequence = reader.u32()?;
+    let uptime = reader.u32()?;
+    let sample_count = reader.u32()?;
+
+    packet.insert_header("agent_address", agent_address);
+    packet.insert_header("sub_agent_id", FieldValue::Unsigned(sub_agent_id.into()));
+    packet.insert_header("sequence_number", FieldValue::Unsigned(sequence.into()));
+    packet.insert_header("sys_uptime", FieldValue::Unsigned(uptime.into()));
+
+    for _ in 0..sample_count {
+        let format = reader.u32()?;
+        let length = reader.u32()? as usize;
+        let sample = reader.sub_reader(length, "sFlow sample")?;
+
+        // Only the standard sample formats, with an enterprise of zero, are supported.
+        let record = match format {
+            FLOW_SAMPLE => parse_flow_sample(sample, false)?,
+            EXPANDED_FLOW_SAMPLE => parse_flow_sample(sample, true)?,
+            COUNTER_SAMPLE => parse_counter_sample(sample, false)?,
+            EXPANDED_COUNTER_SAMPLE => parse_counter_sample(sample, true)?,
+            _ => continue,
+        };
+        packet.records.push(record);
+    }
+
+    Ok(packet)
+}
+
+fn unsigned(value: impl Into<u64>) -> FieldValue {
+    FieldValue::Unsigned(value.into())
+}
+
+/// Reads the sample sequence number and data source, which start every sample.
+fn parse_sample_header(
+    reader: &mut Reader<'_>,
+    record: &mut FlowRecord,
+    expanded: bool,
+) -> FlowParserResult<()> {
+    record.insert("sample_sequence_number", unsigned(reader.u32()?));
+    let (source_id_type, source_id_index) = if expanded {
+        (reader.u32()?, reader.u32()?)
+    } else {
+        let source_id = reader.u32()?;
+        (source_id >> 24, source_id & 0x00ff_ffff)
+    };
+    record.insert("source_id_type", unsigned(source_id_type));
+    record.insert("source_id_index", unsigned(source_id_index));
+    Ok(())
+}
+
+fn parse_flow_sample(mut reader: Reader<'_>, expanded: bool) -> FlowParserResult<FlowRecord> {
+    let mut record = FlowRecord::new(RecordKind::Flow);
+    parse_sample_header(&mut reader, &mut record, expanded)?;
+
+    record.insert("sampling_rate", unsigned(reader.u32()?));
+    record.insert("sample_pool", unsigned(reader.u32()?));
+    record.insert("drops", unsigned(reader.u32()?));
+    if expanded {
+        // The interface format is only meaningful for special values, keep the index.
+        let _input_format = reader.u32()?;
+        record.insert("ingress_interface", unsigned(reader.u32()?));
+        let _output_format = reader.u32()?;
+        record.insert("egress_interface", unsigned(reader.u32()?));
+    } else {
+        // The two most significant bits hold the interface format.
+        record.insert("ingress_interface", unsigned(reader.u32()? & 0x3fff_ffff));
+        record.insert("egress_interface", unsigned(reader.u32()? & 0x3fff_ffff));
+    }
+
+    let record_count = reader.u32()?;
+    for _ in 0..record_count {
+        let format = reader.u32()?;
+        let length = reader.u32()? as usize;
+        let mut data = reader.sub_reader(length, "sFlow flow record")?;
+
+        match format {
+            SAMPLED_HEADER => parse_sampled_header(&mut data, &mut record)?,
+            SAMPLED_IPV4 => {
+                record.insert("frame_length", unsigned(data.u32()?));
+                record.insert("protocol_identifier", unsigned(data.u32()?));
+                record.insert("source_ipv4_address", FieldValue::Ipv4(data.ipv4()?));
+                record.insert("destination_ipv4_address", FieldValue::Ipv4(data.ipv4()?));
+                record.insert("source_transport_port", unsigned(data.u32()?));
+                record.insert("destination_transport_port", unsigned(data.u32()?));
+                record.insert("tcp_control_bits", unsigned(data.u32()?));
+                record.insert("ip_class_of_service", unsigned(data.u32()?));
+            }
+            SAMPLED_IPV6 => {
+                record.insert("frame_length", unsigned(data.u32()?));
+                record.insert("protocol_identifier", unsigned(data.u32()?));
+                record.insert("source_ipv6_address", FieldValue::Ipv6(data.ipv6()?));
+                record.insert("destination_ipv6_address", FieldValue::Ipv6(data.ipv6()?));
+                record.insert("source_transport_port", unsigned(data.u32()?));
+                record.insert("destination_transport_port", unsigned(data.u32()?));
+                record.insert("tcp_control_bits", unsigned(data.u32()?));
+                record.insert("ip_class_of_service", unsigned(data.u32()?));
+            }
+            EXTENDED_SWITCH => {
+                record.insert("vlan_id", unsigned(data.u32()?));
+                let _source_priority = data.u32()?;
+                record.insert("post_vlan_id", unsigned(data.u32()?));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(record)
+}
+
+/// Decodes the headers of a sampled Ethernet frame.
+///
+/// Sampled headers are truncated by the agent, so decoding stops silently once the data runs out.
+fn parse_sampled_header(reader: &mut Reader<'_>, record: &mut FlowRecord) -> FlowParserResult<()> {
+    let protocol = reader.u32()?;
+    record.insert("frame_length", unsigned(reader.u32()?));
+    let _stripped = reader.u32()?;
+    let header_length = reader.u32()? as usize;
+    let header = reader.bytes(header_length)?;
+
+    if protocol == HEADER_PROTOCOL_ETHERNET {
+        // Errors only mean the header was truncated, keep whatever was decoded until then.
+        let _ = parse_ethernet(Reader::new(header, "sampled header"), record);
+    }
+    Ok(())
+}
+
+fn parse_ethernet(mut reader: Reader<'_>, record: &mut FlowRecord) -> FlowParserResult<()> {
+    record.insert("destination_mac_address", FieldValue::Mac(reader.array()?));
+    record.insert("source_mac_address", FieldValue::Mac(reader.array()?));
+
+    let mut ethertype = reader.u16()?;
+    if ethertype == ETHERTYPE_VLAN {
+        record.insert("vlan_id", unsigned(reader.u16()? & 0x0fff));
+        ethertype = reader.u16()?;
+    }
+
+    let protocol = match ethertype {
+        ETHERTYPE_IPV4 => {
+            let version_ihl = reader.u8()?;
+            record.insert("ip_version", unsigned(4u8));
+            record.insert("ip_class_of_service", unsigned(reader.u8()?));
+            record.insert("ip_total_length", unsigned(reader.u16()?));
+            reader.skip(4)?;
+            record.insert("ip_ttl", unsigned(reader.u8()?));
+            let protocol = reader.u8()?;
+            record.insert("protocol_identifier", unsigned(protocol));
+            reader.skip(2)?;
+            record.insert("source_ipv4_address", FieldValue::Ipv4(reader.ipv4()?));
+            record.insert("destination_ipv4_address", FieldValue::Ipv4(reader.ipv4()?));
+            // Skip IPv4 options.
+            let header_length = usize::from(version_ihl & 0x0f) * 4;
+            reader.skip(header_length.saturating_sub(20))?;
+            protocol
+        }
+        ETHERTYPE_IPV6 => {
+            let first_word = reader.u32()?;
+            record.insert("ip_version", unsigned(6u8));
+            record.insert("ip_class_of_service", unsigned((first_word >> 20) & 0xff));
+            record.insert("flow_label_ipv6", unsigned(first_word & 0x000f_ffff));
+            record.insert("ip_total_length", unsigned(u32::from(reader.u16()?) + 40));
+            let protocol = reader.u8()?;
+            record.insert("protocol_identifier", unsigned(protocol));
+            record.insert("ip_ttl", unsigned(reader.u8()?));
+            record.insert("source_ipv6_address", FieldValue::Ipv6(reader.ipv6()?));
+            record.insert("destination_ipv6_address", FieldValue::Ipv6(reader.ipv6()?));
+            protocol
+        }
+        _ => return Ok(()),
+    };
+
+    if protocol == IP_PROTOCOL_TCP || protocol == IP_PROTOCOL_UDP {
+        record.insert("source_transport_port", unsigned(reader.u16()?));
+        record.insert("destination_transport_port", unsigned(reader.u16()?));
+    }
+    if protocol == IP_PROTOCOL_TCP {
+        reader.skip(9)?;
+        record.insert("tcp_control_bits", unsigned(reader.u8()?));
+    }
+    Ok(())
+}
+
+fn parse_counter_sample(mut reader: Reader<'_>, expanded: bool) -> FlowParserResult<FlowRecord> {
+    let mut record = FlowRecord::new(RecordKind::Counters);
+    parse_sample_header(&mut reader, &mut record, expanded)?;
+
+    let record_count = reader.u32()?;
+    for _ in 0..record_count {
+        let format = reader.u32()?;
+        let length = reader.u32()? as usize;
+        let mut data = reader.sub_reader(length, "sFlow counter record")?;
+
+        if format == GENERIC_INTERFACE_COUNTERS {
+            record.insert("if_index", unsigned(data.u32()?));
+            record.insert("if_type", unsigned(data.u32()?));
+            record.insert("if_speed", unsigned(data.u64()?));
+            record.insert("if_direction", unsigned(data.u32()?));
+            record.insert("if_status", unsigned(data.u32()?));
+            record.insert("if_in_octets", unsigned(data.u64()?));
+            record.insert("if_in_unicast_packets", unsigned(data.u32()?));
+            record.insert("if_in_multicast_packets", unsigned(data.u32()?));
+            record.insert("if_in_broadcast_packets", unsigned(data.u32()?));
+            record.insert("if_in_discards", unsigned(data.u32()?));
+            record.insert("if_in_errors", unsigned(data.u32()?));
+            record.insert("if_in_unknown_protocols", unsigned(data.u32()?));
+            record.insert("if_out_octets", unsigned(data.u64()?));
+            record.insert("if_out_unicast_packets", unsigned(data.u32()?));
+            record.insert("if_out_multicast_packets", unsigned(data.u32()?));
+            record.insert("if_out_broadcast_packets", unsigned(data.u32()?));
+            record.insert("if_out_discards", unsigned(data.u32()?));
+            record.insert("if_out_errors", unsigned(data.u32()?));
+            record.insert("if_promiscuous_mode", unsigned(data.u32()?));
+        }
+    }
+
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn datagram(samples: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&5u32.to_be_bytes());
+        data.extend_from_slice(&1u32.to_be_bytes());
+        data.extend_from_slice(&[192, 0, 2, 10]);
+        data.extend_from_slice(&0u32.to_be_bytes());
+        data.extend_from_slice(&9u32.to_be_bytes());
+        data.extend_from_slice(&5000u32.to_be_bytes());
+        data.extend_from_slice(&(samples.len() as u32).to_be_bytes());
+        for (format, sample) in samples {
+            data.extend_from_slice(&format.to_be_bytes());
+            data.extend_from_slice(&(sample.len() as u32).to_be_bytes());
+            data.extend_from_slice(sample);
+        }
+        data
+    }
+
+    fn ethernet_tcp_frame() -> Vec<u8> {
+        let mut frame = vec![];
+        frame.extend_from_slice(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55]);
+        frame.extend_from_slice(&[0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb]);
+        frame.extend_from_slice(&ETHERTYPE_IPV4.to_be_bytes());
+        frame.extend_from_slice(&[0x45, 0, 0, 60, 0, 0, 0, 0, 64, IP_PROTOCOL_TCP, 0, 0]);
+        frame.extend_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2]);
+        frame.extend_from_slice(&12345u16.to_be_bytes());
+        frame.extend_from_slice(&443u16.to_be_bytes());
+        frame.extend_from_slice(&[0; 9]);
+        frame.push(0x02); // SYN
+        frame
+    }
+
+    fn flow_sample() -> Vec<u8> {
+        let frame = ethernet_tcp_frame();
+        let mut header_record = vec![];
+        header_record.extend_from_slice(&HEADER_PROTOCOL_ETHERNET.to_be_bytes());
+        header_record.extend_from_slice(&1514u32.to_be_bytes());
+        header_record.extend_from_slice(&4u32.to_be_bytes());
+        header_record.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+        header_record.extend_from_slice(&frame);
+
+        let mut sample = vec![];
+        sample.extend_from_slice(&3u32.to_be_bytes()); // sequence number
+        sample.extend_from_slice(&7u32.to_be_bytes()); // source ID
+        sample.extend_from_slice(&1024u32.to_be_bytes()); // sampling rate
+        sample.extend_from_slice(&4096u32.to_be_bytes()); // sample pool
+        sample.extend_from_slice(&0u32.to_be_bytes()); // drops
+        sample.extend_from_slice(&7u32.to_be_bytes()); // input
+        sample.extend_from_slice(&8u32.to_be_bytes()); // output
+        sample.extend_from_slice(&1u32.to_be_bytes()); // record count
+        sample.extend_from_slice(&SAMPLED_HEADER.to_be_bytes());
+        sample.extend_from_slice(&(header_record.len() as u32).to_be_bytes());
+        sample.extend_from_slice(&header_record);
+        sample
+    }
+
+    #[test]
+    fn parses_flow_samples() {
+        let packet = parse(&datagram(&[(FLOW_SAMPLE, flow_sample())])).unwrap();
+
+        assert_eq!(packet.protocol, FlowProtocol::SflowV5);
+        assert_eq!(
+            packet.header["agent_address"],
+            FieldValue::Ipv4(Ipv4Addr::new(192, 0, 2, 10))
+        );
+        assert_eq!(packet.records.len(), 1);
+
+        let record = &packet.records[0];
+        assert_eq!(record.kind, RecordKind::Flow);
+        assert_eq!(record.fields["sampling_rate"], FieldValue::Unsigned(1024));
+        assert_eq!(record.fields["ingress_interface"], FieldValue::Unsigned(7));
+        assert_eq!(record.fields["frame_length"], FieldValue::Unsigned(1514));
+        assert_eq!(
+            record.fields["source_mac_address"],
+            FieldValue::Mac([0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb])
+        );
+        assert_eq!(
+            record.fields["destination_ipv4_address"],
+            FieldValue::Ipv4(Ipv4Addr::new(10, 0, 0, 2))
+        );
+        assert_eq!(
+            record.fields["destination_transport_port"],
+            FieldValue::Unsigned(443)
+        );
+        assert_eq!(record.fields["tcp_control_bits"], FieldValue::Unsigned(2));
+    }
+
+    #[test]
+    fn keeps_fields_of_truncated_sampled_headers() {
+        let mut frame = ethernet_tcp_frame();
+        frame.truncate(20);
+        let mut header_record = vec![];
+        header_record.extend_from_slice(&HEADER_PROTOCOL_ETHERNET.to_be_bytes());
+        header_record.extend_from_slice(&64u32.to_be_bytes());
+        header_record.extend_from_slice(&0u32.to_be_bytes());
+        header_record.extend_from_slice(&(frame.len() as u32).to_be_bytes());
+        header_record.extend_from_slice(&frame);
+
+        let mut record = FlowRecord::new(RecordKind::Flow);
+        parse_sampled_header(&mut Reader::new(&header_record, "test"), &mut record).unwrap();
+
+        assert!(record.fields.contains_key("source_mac_address"));
+        assert!(!record.fields.contains_key("source_ipv4_address"));
+    }
+
+    #[test]
+    fn parses_counter_samples() {
+        let mut counters = vec![];
+        counters.extend_from_slice(&3u32.to_be_bytes()); // if_index
+        counters.extend_from_slice(&6u32.to_be_bytes()); // if_type
+        counters.extend_from_slice(&1_000_000_000u64.to_be_bytes()); // if_speed
+        counters.extend_from_slice(&[0; 8]); // if_direction, if_status
+        counters.extend_from_slice(&123_456u64.to_be_bytes()); // if_in_octets
+        counters.extend_from_slice(&[0; 24]);
+        counters.extend_from_slice(&654_321u64.to_be_bytes()); // if_out_octets
+        counters.extend_from_slice(&[0; 24]);
+
+        let mut sample = vec![];
+        sample.extend_from_slice(&1u32.to_be_bytes());
+        sample.extend_from_slice(&3u32.to_be_bytes());
+        sample.extend_from_slice(&1u32.to_be_bytes());
+        sample.extend_from_slice(&GENERIC_INTERFACE_COUNTERS.to_be_bytes());
+        sample.extend_from_slice(&(counters.len() as u32).to_be_bytes());
+        sample.extend_from_slice(&counters);
+
+        let packet = parse(&datagram(&[(COUNTER_SAMPLE, sample)])).unwrap();
+
+        let record = &packet.records[0];
+        assert_eq!(record.kind, RecordKind::Counters);
+        assert_eq!(record.fields["if_index"], FieldValue::Unsigned(3));
+        assert_eq!(
+            record.fields["if_speed"],
+            FieldValue::Unsigned(1_000_000_000)
+        );
+        assert_eq!(record.fields["if_in_octets"], FieldValue::Unsigned(123_456));
+        assert_eq!(
+            record.fields["if_out_octets"],
+            FieldValue::Unsigned(654_321)
+        );
+    }
+
+    #[test]
+    fn skips_unknown_samples() {
+        let packet = parse(&datagram(&[(0x1234_5001, vec![0; 8])])).unwrap();
+        assert!(packet.records.is_empty());
+    }
+}
@@ -0,0 +1,265 @@
+use std::{collections::HashMap, net::SocketAddr};
+
+use crate::{
+    fields::{decode_value, field_name, FieldType},
+    flow::{FlowRecord, RecordKind},
+    flow_parser::{FlowParserError, FlowParserResult},
+    reader::Reader,
+};
+
+/// Field length marking a variable-length IPFIX field.
+const VARIABLE_LENGTH: u16 = 65535;
+
+/// A field specifier from a template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TemplateField {
+    pub(crate) id: u16,
+    pub(crate) enterprise: Option<u32>,
+    pub(crate) length: u16,
+    /// Whether this is a NetFlow v9 options scope field, whose type IDs live in their own namespace.
+    pub(crate) netflow_v9_scope: bool,
+}
+
+impl TemplateField {
+    pub(crate) const fn new(id: u16, length: u16) -> Self {
+        Self {
+            id,
+            enterprise: None,
+            length,
+            netflow_v9_scope: false,
+        }
+    }
+
+    fn name(&self) -> (String, FieldType) {
+        if self.netflow_v9_scope {
+            let name = match self.id {
+                1 => "scope_system",
+                2 => "scope_interface",
+                3 => "scope_line_card",
+                4 => "scope_cache",
+                5 => "scope_template",
+                _ => return (format!("scope_{}", self.id), FieldType::Unsigned),
+            };
+            (name.to_owned(), FieldType::Unsigned)
+        } else {
+            field_name(self.id, self.enterprise)
+        }
+    }
+}
+
+/// A template describing the layout of the records in a data set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Template {
+    pub(crate) kind: RecordKind,
+    pub(crate) fields: Vec<TemplateField>,
+}
+
+impl Template {
+    /// The smallest number of bytes a record built from this template can take.
+    fn min_record_length(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|field| match field.length {
+                VARIABLE_LENGTH => 1,
+                length => usize::from(length),
+            })
+            .sum()
+    }
+
+    fn decode_record(&self, reader: &mut Reader<'_>) -> FlowParserResult<FlowRecord> {
+        let mut record = FlowRecord::new(self.kind);
+        for field in &self.fields {
+            let length = match field.length {
+                VARIABLE_LENGTH => match reader.u8()? {
+                    255 => usize::from(reader.u16()?),
+                    length => usize::from(length),
+                },
+                length => usize::from(length),
+            };
+            let bytes = reader.bytes(length)?;
+            let (name, field_type) = field.name();
+            record.insert(name, decode_value(field_type, bytes));
+        }
+        Ok(record)
+    }
+
+    /// Decodes every record in a data set, ignoring any trailing padding.
+    pub(crate) fn decode_set(&self, mut reader: Reader<'_>) -> FlowParserResult<Vec<FlowRecord>> {
+        let min_length = self.min_record_length();
+        if min_length == 0 {
+            return Err(FlowParserError::Invalid {
+                cause: "template describes empty records".to_owned(),
+            });
+        }
+
+        let mut records = Vec::new();
+        while reader.remaining() >= min_length {
+            records.push(self.decode_record(&mut reader)?);
+        }
+        Ok(records)
+    }
+}
+
+/// Identifies a template: template IDs are only unique per exporter and observation domain.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct TemplateKey {
+    exporter: SocketAddr,
+    domain: u32,
+    template_id: u16,
+}
+
+/// Templates received from exporters, needed to decode NetFlow v9 and IPFIX data sets.
+#[derive(Debug)]
+pub(crate) struct TemplateCache {
+    templates: HashMap<TemplateKey, Template>,
+    max_templates: usize,
+}
+
+impl TemplateCache {
+    pub(crate) fn new(max_templates: usize) -> Self {
+        Self {
+            templates: HashMap::new(),
+            max_templates,
+        }
+    }
+
+    pub(crate) fn insert(
+        &mut self,
+        exporter: SocketAddr,
+        domain: u32,
+        template_id: u16,
+        template: Template,
+    ) -> FlowParserResult<()> {
+        let key = TemplateKey {
+            exporter,
+            domain,
+            template_id,
+        };
+        if !self.templates.contains_key(&key) && self.templates.len() >= self.max_templates {
+            return Err(FlowParserError::TooManyTemplates {
+                max_templates: self.max_templates,
+            });
+        }
+        self.templates.insert(key, template);
+        Ok(())
+    }
+
+    pub(crate) fn remove(&mut self, exporter: SocketAddr, domain: u32, template_id: u16) {
+        self.templates.remove(&TemplateKey {
+            exporter,
+            domain,
+            template_id,
+        });
+    }
+
+    /// Removes every template of an observation domain.
+    pub(crate) fn remove_domain(&mut self, exporter: SocketAddr, domain: u32) {
+        self.templates
+            .retain(|key, _| key.exporter != exporter || key.domain != domain);
+    }
+
+    pub(crate) fn get(
+        &self,
+        exporter: SocketAddr,
+        domain: u32,
+        template_id: u16,
+    ) -> Option<&Template> {
+        self.templates.get(&TemplateKey {
+            exporter,
+            domain,
+            template_id,
+        })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.templates.len()
+    }
+}
+
+/// Decodes the data sets of a packet, recording the IDs of sets whose template is unknown.
+pub(crate) fn decode_data_set(
+    cache: &TemplateCache,
+    exporter: SocketAddr,
+    domain: u32,
+    set_id: u16,
+    reader: Reader<'_>,
+    records: &mut Vec<FlowRecord>,
+    missing_templates: &mut Vec<u16>,
+) -> FlowParserResult<()> {
+    match cache.get(exporter, domain, set_id) {
+        Some(template) => records.extend(template.decode_set(reader)?),
+        None => missing_templates.push(set_id),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flow::FieldValue;
+
+    fn exporter() -> SocketAddr {
+        "192.0.2.1:2055".parse().unwrap()
+    }
+
+    fn template() -> Template {
+        Template {
+            kind: RecordKind::Flow,
+            fields: vec![TemplateField::new(8, 4), TemplateField::new(7, 2)],
+        }
+    }
+
+    #[test]
+    fn decodes_records_and_skips_padding() {
+        let data = [10, 0, 0, 1, 0, 80, 10, 0, 0, 2, 1, 187, 0, 0];
+        let records = template().decode_set(Reader::new(&data, "test")).unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[1].fields["source_transport_port"],
+            FieldValue::Unsigned(443)
+        );
+    }
+
+    #[test]
+    fn decodes_variable_length_fields() {
+        let template = Template {
+            kind: RecordKind::Options,
+            fields: vec![TemplateField::new(82, VARIABLE_LENGTH)],
+        };
+        let data = [4, b'e', b't', b'h', b'0'];
+        let records = template.decode_set(Reader::new(&data, "test")).unwrap();
+
+        assert_eq!(
+            records[0].fields["interface_name"],
+            FieldValue::String("eth0".to_owned())
+        );
+    }
+
+    #[test]
+    fn limits_the_number_of_templates() {
+        let mut cache = TemplateCache::new(1);
+        cache.insert(exporter(), 0, 256, template()).unwrap();
+        // Replacing an existing template is always allowed.
+        cache.insert(exporter(), 0, 256, template()).unwrap();
+        assert!(matches!(
+            cache.insert(exporter(), 0, 257, template()),
+            Err(FlowParserError::TooManyTemplates { max_templates: 1 })
+        ));
+    }
+
+    #[test]
+    fn keeps_templates_per_exporter_and_domain() {
+        let mut cache = TemplateCache::new(10);
+        cache.insert(exporter(), 1, 256, template()).unwrap();
+
+        assert!(cache.get(exporter(), 1, 256).is_some());
+        assert!(cache.get(exporter(), 2, 256).is_none());
+        assert!(cache
+            .get("192.0.2.2:2055".parse().unwrap(), 1, 256)
+            .is_none());
+
+        cache.remove_domain(exporter(), 1);
+        assert_eq!(cache.len(), 0);
+    }
+}
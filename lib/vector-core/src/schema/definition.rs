@@ -368,6 +368,15 @@ impl Definition {
         self
     }
 
+    /// Replace the kind of the event.
+    ///
+    /// Semantic meanings are kept, even if they point to fields the new kind doesn't define.
+    #[must_use]
+    pub fn with_event_kind(mut self, kind: Kind) -> Self {
+        self.event_kind = kind;
+        self
+    }
+
     /// Merge `other` definition into `self`.
     ///
     /// This just takes the union of both definitions.
@@ -224,6 +224,13 @@ impl TlsSettings {
         })
     }
 
+    /// Returns the identity as a DER encoded PKCS#12 archive, along with its password.
+    pub fn identity_pkcs12_der(&self) -> Option<(Vec<u8>, String)> {
+        self.identity
+            .as_ref()
+            .map(|identity| (identity.0.clone(), identity.1.clone()))
+    }
+
     pub fn authorities_pem(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
         self.authorities.iter().map(|authority| {
             authority
@@ -7,6 +7,11 @@ pub mod remove_secret;
 pub mod set_metadata_field;
 pub mod set_secret;
 pub mod set_semantic_meaning;
+pub mod state_delete;
+pub mod state_get;
+pub mod state_incr;
+pub mod state_set;
+pub mod state_store;
 
 use ::value::Value;
 use lookup::OwnedTargetPath;
@@ -37,6 +42,10 @@ pub fn vrl_functions() -> Vec<Box<dyn vrl::Function>> {
         Box::new(get_secret::GetSecret) as _,
         Box::new(remove_secret::RemoveSecret) as _,
         Box::new(set_secret::SetSecret) as _,
+        Box::new(state_delete::StateDelete) as _,
+        Box::new(state_get::StateGet) as _,
+        Box::new(state_incr::StateIncr) as _,
+        Box::new(state_set::StateSet) as _,
     ]
 }
 
@@ -0,0 +1,66 @@
+use ::value::Value;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+use crate::state_store::{self, StateStore};
+
+fn state_delete(store: &StateStore, key: Value) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+
+    Ok(store.remove(&key).unwrap_or(Value::Null))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct StateDelete;
+
+impl Function for StateDelete {
+    fn identifier(&self) -> &'static str {
+        "state_delete"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "key",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "delete a stored value",
+            source: r#"state_set!("last_host", "example.com"); state_delete("last_host")"#,
+            result: Ok(r#""example.com""#),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let store = state_store::from_context(ctx);
+
+        Ok(StateDeleteFn { key, store }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StateDeleteFn {
+    key: Box<dyn Expression>,
+    store: StateStore,
+}
+
+impl FunctionExpression for StateDeleteFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+
+        state_delete(&self.store, key)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::any().infallible()
+    }
+}
@@ -0,0 +1,73 @@
+use ::value::Value;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+use crate::state_store::{self, StateStore};
+
+fn state_get(store: &StateStore, key: Value) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+
+    Ok(store.get(&key).unwrap_or(Value::Null))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct StateGet;
+
+impl Function for StateGet {
+    fn identifier(&self) -> &'static str {
+        "state_get"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "key",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "get a stored value",
+                source: r#"state_set!("last_host", "example.com"); state_get("last_host")"#,
+                result: Ok(r#""example.com""#),
+            },
+            Example {
+                title: "get a missing value",
+                source: r#"state_get("missing")"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let store = state_store::from_context(ctx);
+
+        Ok(StateGetFn { key, store }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StateGetFn {
+    key: Box<dyn Expression>,
+    store: StateStore,
+}
+
+impl FunctionExpression for StateGetFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+
+        state_get(&self.store, key)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::any().infallible()
+    }
+}
@@ -0,0 +1,99 @@
+use ::value::Value;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+use crate::state_store::{self, StateStore};
+
+fn state_incr(store: &StateStore, key: Value, by: Value, ttl: Option<Value>) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+    let by = by.try_integer()?;
+    let ttl = state_store::ttl(ttl)?;
+
+    Ok(store.increment(&key, by, ttl)?.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct StateIncr;
+
+impl Function for StateIncr {
+    fn identifier(&self) -> &'static str {
+        "state_incr"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "by",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "ttl",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "count events",
+                source: r#"state_incr!("events"); state_incr!("events")"#,
+                result: Ok("2"),
+            },
+            Example {
+                title: "count within a window",
+                source: r#"state_incr!("requests", by: 10, ttl: 60)"#,
+                result: Ok("10"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let by = arguments.optional("by").unwrap_or(expr!(1));
+        let ttl = arguments.optional("ttl");
+        let store = state_store::from_context(ctx);
+
+        Ok(StateIncrFn {
+            key,
+            by,
+            ttl,
+            store,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StateIncrFn {
+    key: Box<dyn Expression>,
+    by: Box<dyn Expression>,
+    ttl: Option<Box<dyn Expression>>,
+    store: StateStore,
+}
+
+impl FunctionExpression for StateIncrFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let by = self.by.resolve(ctx)?;
+        let ttl = self.ttl.as_ref().map(|ttl| ttl.resolve(ctx)).transpose()?;
+
+        state_incr(&self.store, key, by, ttl)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::integer().fallible()
+    }
+}
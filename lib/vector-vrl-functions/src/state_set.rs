@@ -0,0 +1,100 @@
+use ::value::Value;
+use vrl::prelude::*;
+use vrl::state::TypeState;
+
+use crate::state_store::{self, StateStore};
+
+fn state_set(store: &StateStore, key: Value, value: Value, ttl: Option<Value>) -> Resolved {
+    let key = key.try_bytes_utf8_lossy()?;
+    let ttl = state_store::ttl(ttl)?;
+
+    store.set(&key, value, ttl)?;
+
+    Ok(Value::Null)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct StateSet;
+
+impl Function for StateSet {
+    fn identifier(&self) -> &'static str {
+        "state_set"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "value",
+                kind: kind::ANY,
+                required: true,
+            },
+            Parameter {
+                keyword: "ttl",
+                kind: kind::INTEGER | kind::FLOAT,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "store a value",
+                source: r#"state_set!("last_host", "example.com")"#,
+                result: Ok("null"),
+            },
+            Example {
+                title: "store a value for a limited time",
+                source: r#"state_set!("last_host", "example.com", ttl: 60)"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let key = arguments.required("key");
+        let value = arguments.required("value");
+        let ttl = arguments.optional("ttl");
+        let store = state_store::from_context(ctx);
+
+        Ok(StateSetFn {
+            key,
+            value,
+            ttl,
+            store,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StateSetFn {
+    key: Box<dyn Expression>,
+    value: Box<dyn Expression>,
+    ttl: Option<Box<dyn Expression>>,
+    store: StateStore,
+}
+
+impl FunctionExpression for StateSetFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let key = self.key.resolve(ctx)?;
+        let value = self.value.resolve(ctx)?;
+        let ttl = self.ttl.as_ref().map(|ttl| ttl.resolve(ctx)).transpose()?;
+
+        state_set(&self.store, key, value, ttl)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::null().fallible()
+    }
+}
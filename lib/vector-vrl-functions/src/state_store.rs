@@ -0,0 +1,479 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    mem,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant, SystemTime},
+};
+
+use ::value::Value;
+use vrl::prelude::*;
+
+/// The longest time-to-live of an entry, in seconds, which keeps expiration times representable.
+pub const MAX_TTL_SECS: u64 = 100 * 365 * 24 * 60 * 60;
+
+/// The limits applied to a [`StateStore`].
+#[derive(Clone, Copy, Debug)]
+pub struct StateLimits {
+    /// The maximum number of keys in the store.
+    pub max_keys: usize,
+
+    /// The maximum estimated size of the keys and values in the store, in bytes.
+    pub max_bytes: usize,
+}
+
+impl Default for StateLimits {
+    fn default() -> Self {
+        Self {
+            max_keys: 10_000,
+            max_bytes: 10 * 1024 * 1024,
+        }
+    }
+}
+
+/// A key/value store that outlives the events a VRL program runs on, used by the `state_*`
+/// functions.
+///
+/// Clones of the store share the same state, so all copies of a compiled program (and thus all
+/// concurrent instances of the transform running it) see the same values. When one of the limits
+/// is reached, expired entries are dropped first, followed by the least recently written ones.
+#[derive(Clone, Debug, Default)]
+pub struct StateStore {
+    inner: Arc<Mutex<Inner>>,
+}
+
+/// An entry of the store, as exchanged with persistent storage.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PersistedEntry {
+    pub key: String,
+    pub value: Value,
+    pub expires_at: Option<SystemTime>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    limits: StateLimits,
+    entries: HashMap<String, Entry>,
+
+    /// The keys of the store, ordered by the time they were last written.
+    writes: BTreeMap<u64, String>,
+    next_write: u64,
+    bytes: usize,
+}
+
+#[derive(Debug)]
+struct Entry {
+    value: Value,
+    expires_at: Option<Instant>,
+    write: u64,
+    size: usize,
+}
+
+impl Entry {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| expires_at <= now)
+    }
+}
+
+impl StateStore {
+    pub fn new(limits: StateLimits) -> Self {
+        let inner = Inner {
+            limits,
+            ..Default::default()
+        };
+
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    /// Returns the value stored for `key`, if it exists and hasn't expired.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        let mut inner = self.lock();
+        inner.purge_if_expired(key, Instant::now());
+        inner.entries.get(key).map(|entry| entry.value.clone())
+    }
+
+    /// Stores `value` for `key`, expiring it after `ttl`, if any.
+    pub fn set(&self, key: &str, value: Value, ttl: Option<Duration>) -> Result<(), String> {
+        let now = Instant::now();
+        let expires_at = ttl.map(|ttl| expiration(key, now, ttl)).transpose()?;
+
+        self.lock().insert(key, value, expires_at, now)
+    }
+
+    /// Adds `by` to the integer stored for `key`, and returns the result. A missing or expired key
+    /// counts as zero.
+    ///
+    /// The expiration of an existing key is only changed if `ttl` is set.
+    pub fn increment(&self, key: &str, by: i64, ttl: Option<Duration>) -> Result<i64, String> {
+        let now = Instant::now();
+        let mut inner = self.lock();
+        inner.purge_if_expired(key, now);
+
+        let (current, expires_at) = match inner.entries.get(key) {
+            Some(Entry {
+                value: Value::Integer(current),
+                expires_at,
+                ..
+            }) => (*current, *expires_at),
+            Some(entry) => {
+                return Err(format!(
+                    r#"state value for key "{}" is {}, not an integer"#,
+                    key,
+                    entry.value.kind_str()
+                ))
+            }
+            None => (0, None),
+        };
+
+        let value = current
+            .checked_add(by)
+            .ok_or_else(|| format!(r#"state value for key "{}" overflows"#, key))?;
+        let expires_at = match ttl {
+            Some(ttl) => Some(expiration(key, now, ttl)?),
+            None => expires_at,
+        };
+
+        inner.insert(key, value.into(), expires_at, now)?;
+        Ok(value)
+    }
+
+    /// Removes `key` from the store, returning its value if it existed and hadn't expired.
+    pub fn remove(&self, key: &str) -> Option<Value> {
+        let mut inner = self.lock();
+        inner.purge_if_expired(key, Instant::now());
+        inner.remove(key).map(|entry| entry.value)
+    }
+
+    /// Returns the number of keys in the store, including the ones that expired but weren't
+    /// dropped yet.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the entries of the store that haven't expired, oldest write first.
+    pub fn snapshot(&self) -> Vec<PersistedEntry> {
+        let now = Instant::now();
+        let system_now = SystemTime::now();
+        let inner = self.lock();
+
+        inner
+            .writes
+            .values()
+            .filter_map(|key| {
+                let entry = inner.entries.get(key)?;
+                if entry.is_expired(now) {
+                    return None;
+                }
+
+                Some(PersistedEntry {
+                    key: key.clone(),
+                    value: entry.value.clone(),
+                    expires_at: entry
+                        .expires_at
+                        .and_then(|expires_at| system_now.checked_add(expires_at - now)),
+                })
+            })
+            .collect()
+    }
+
+    /// Inserts previously persisted entries into the store. Entries that expired in the meantime
+    /// are skipped, as are entries that don't fit within the limits of the store.
+    pub fn restore(&self, entries: impl IntoIterator<Item = PersistedEntry>) {
+        let now = Instant::now();
+        let system_now = SystemTime::now();
+        let mut inner = self.lock();
+
+        for PersistedEntry {
+            key,
+            value,
+            expires_at,
+        } in entries
+        {
+            // Expiration times beyond what the store accepts are treated as no expiration.
+            let expires_at = match expires_at.map(|at| at.duration_since(system_now)) {
+                Some(Ok(remaining)) if remaining.as_secs() <= MAX_TTL_SECS => {
+                    now.checked_add(remaining)
+                }
+                Some(Ok(_)) | None => None,
+                Some(Err(_)) => continue,
+            };
+
+            let _ = inner.insert(&key, value, expires_at, now);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        // Losing all of the state because another thread panicked would be worse than carrying on
+        // with it, so a poisoned lock is recovered.
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Inner {
+    fn insert(
+        &mut self,
+        key: &str,
+        value: Value,
+        expires_at: Option<Instant>,
+        now: Instant,
+    ) -> Result<(), String> {
+        let size = key.len() + estimated_size(&value);
+        if size > self.limits.max_bytes {
+            return Err(format!(
+                r#"state value for key "{}" exceeds the maximum state size of {} bytes"#,
+                key, self.limits.max_bytes
+            ));
+        }
+
+        self.remove(key);
+        self.make_room(size, now);
+
+        let write = self.next_write;
+        self.next_write += 1;
+        self.writes.insert(write, key.to_owned());
+        self.bytes += size;
+        self.entries.insert(
+            key.to_owned(),
+            Entry {
+                value,
+                expires_at,
+                write,
+                size,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Entry> {
+        let entry = self.entries.remove(key)?;
+        self.writes.remove(&entry.write);
+        self.bytes -= entry.size;
+
+        Some(entry)
+    }
+
+    fn purge_if_expired(&mut self, key: &str, now: Instant) {
+        if self
+            .entries
+            .get(key)
+            .map_or(false, |entry| entry.is_expired(now))
+        {
+            self.remove(key);
+        }
+    }
+
+    /// Drops entries until an entry of `size` bytes fits within the limits.
+    fn make_room(&mut self, size: usize, now: Instant) {
+        let fits = |inner: &Self| {
+            inner.entries.len() < inner.limits.max_keys
+                && inner.bytes + size <= inner.limits.max_bytes
+        };
+
+        if fits(self) {
+            return;
+        }
+
+        let expired = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.is_expired(now))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in expired {
+            self.remove(&key);
+        }
+
+        while !fits(self) {
+            let key = match self.writes.values().next() {
+                Some(key) => key.clone(),
+                None => break,
+            };
+            self.remove(&key);
+        }
+    }
+}
+
+/// Returns the time an entry written at `now` expires.
+fn expiration(key: &str, now: Instant, ttl: Duration) -> Result<Instant, String> {
+    Some(ttl)
+        .filter(|ttl| ttl.as_secs() <= MAX_TTL_SECS)
+        .and_then(|ttl| now.checked_add(ttl))
+        .ok_or_else(|| {
+            format!(
+                r#"ttl of state key "{}" exceeds the maximum of {} seconds"#,
+                key, MAX_TTL_SECS
+            )
+        })
+}
+
+/// Estimates the memory used by a value.
+fn estimated_size(value: &Value) -> usize {
+    let nested = match value {
+        Value::Bytes(bytes) => bytes.len(),
+        Value::Regex(regex) => regex.as_str().len(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| key.len() + estimated_size(value))
+            .sum(),
+        Value::Array(array) => array.iter().map(estimated_size).sum(),
+        _ => 0,
+    };
+
+    mem::size_of::<Value>() + nested
+}
+
+/// Returns the store of the program being compiled, creating one if the caller of the compiler
+/// didn't provide it.
+pub(crate) fn from_context(ctx: &mut FunctionCompileContext) -> StateStore {
+    if let Some(store) = ctx.get_external_context::<StateStore>() {
+        return store.clone();
+    }
+
+    let store = StateStore::default();
+    ctx.set_external_context(store.clone());
+    store
+}
+
+/// Converts the optional `ttl` argument of a function, in seconds, into a duration.
+pub(crate) fn ttl(value: Option<Value>) -> Result<Option<Duration>, ExpressionError> {
+    let seconds = match value {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::Integer(seconds)) => seconds as f64,
+        Some(Value::Float(seconds)) => seconds.into_inner(),
+        Some(value) => {
+            return Err(format!("ttl must be a number, got {}", value.kind_str()).into());
+        }
+    };
+
+    if !seconds.is_finite() || seconds < 0.0 {
+        return Err(format!("ttl must be a positive number of seconds, got {}", seconds).into());
+    }
+    if seconds > MAX_TTL_SECS as f64 {
+        return Err(format!(
+            "ttl must be at most {} seconds, got {}",
+            MAX_TTL_SECS, seconds
+        )
+        .into());
+    }
+
+    Ok(Some(Duration::from_secs_f64(seconds)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(max_keys: usize, max_bytes: usize) -> StateLimits {
+        StateLimits {
+            max_keys,
+            max_bytes,
+        }
+    }
+
+    #[test]
+    fn set_get_remove() {
+        let store = StateStore::default();
+
+        store.set("foo", "bar".into(), None).unwrap();
+        assert_eq!(store.get("foo"), Some("bar".into()));
+        assert_eq!(store.remove("foo"), Some("bar".into()));
+        assert_eq!(store.get("foo"), None);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn expires_entries() {
+        let store = StateStore::default();
+
+        store
+            .set("foo", "bar".into(), Some(Duration::ZERO))
+            .unwrap();
+        assert_eq!(store.get("foo"), None);
+    }
+
+    #[test]
+    fn increments_integers() {
+        let store = StateStore::default();
+
+        assert_eq!(store.increment("count", 1, None), Ok(1));
+        assert_eq!(store.increment("count", 5, None), Ok(6));
+
+        store.set("name", "foo".into(), None).unwrap();
+        assert!(store.increment("name", 1, None).is_err());
+    }
+
+    #[test]
+    fn evicts_least_recently_written_keys() {
+        let store = StateStore::new(limits(2, usize::MAX));
+
+        store.set("a", 1.into(), None).unwrap();
+        store.set("b", 2.into(), None).unwrap();
+        store.set("a", 3.into(), None).unwrap();
+        store.set("c", 4.into(), None).unwrap();
+
+        assert_eq!(store.get("a"), Some(3.into()));
+        assert_eq!(store.get("b"), None);
+        assert_eq!(store.get("c"), Some(4.into()));
+    }
+
+    #[test]
+    fn enforces_size_limit() {
+        let max_bytes = 2 * (1 + mem::size_of::<Value>());
+        let store = StateStore::new(limits(usize::MAX, max_bytes));
+
+        store.set("a", 1.into(), None).unwrap();
+        store.set("b", 2.into(), None).unwrap();
+        store.set("c", 3.into(), None).unwrap();
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get("a"), None);
+
+        assert!(store.set("d", "x".repeat(max_bytes).into(), None).is_err());
+    }
+
+    #[test]
+    fn rejects_huge_ttls() {
+        assert!(ttl(Some(Value::from(1e30))).is_err());
+        assert!(ttl(Some(Value::from(i64::MAX))).is_err());
+        assert_eq!(
+            ttl(Some(Value::from(MAX_TTL_SECS as i64))).unwrap(),
+            Some(Duration::from_secs(MAX_TTL_SECS))
+        );
+
+        let store = StateStore::default();
+        assert!(store.set("a", 1.into(), Some(Duration::MAX)).is_err());
+        assert!(store.increment("b", 1, Some(Duration::MAX)).is_err());
+        assert!(store.is_empty());
+
+        // Persisted expiration times out of range don't expire.
+        store.restore([PersistedEntry {
+            key: "c".into(),
+            value: 1.into(),
+            expires_at: SystemTime::now().checked_add(Duration::from_secs(u32::MAX as u64 * 1000)),
+        }]);
+        assert_eq!(store.get("c"), Some(1.into()));
+    }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let store = StateStore::default();
+        store.set("a", 1.into(), None).unwrap();
+        store
+            .set("b", "foo".into(), Some(Duration::from_secs(60)))
+            .unwrap();
+
+        let restored = StateStore::default();
+        restored.restore(store.snapshot());
+
+        assert_eq!(restored.get("a"), Some(1.into()));
+        assert_eq!(restored.get("b"), Some("foo".into()));
+    }
+}
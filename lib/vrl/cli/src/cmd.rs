@@ -8,7 +8,7 @@ use std::{
 };
 
 use ::value::Value;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use lookup::{owned_value_path, OwnedTargetPath};
 use value::Secrets;
 use vector_common::TimeZone;
@@ -18,11 +18,18 @@ use vrl::{CompilationResult, CompileConfig};
 
 #[cfg(feature = "repl")]
 use super::repl;
-use super::Error;
+use super::{tap::Tap, unit_test, Error};
 
 #[derive(Parser, Debug)]
-#[clap(name = "VRL", about = "Vector Remap Language CLI")]
+#[clap(
+    name = "VRL",
+    about = "Vector Remap Language CLI",
+    args_conflicts_with_subcommands = true
+)]
 pub struct Opts {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
     /// The VRL program to execute. The program ".foo = true", for example, sets the event object's
     /// `foo` field to `true`.
     #[clap(name = "PROGRAM")]
@@ -52,16 +59,34 @@ pub struct Opts {
     // Should the CLI emit warnings
     #[clap(long = "print-warnings")]
     print_warnings: bool,
+
+    /// Load a sample of the events output by the components matching these patterns in a running
+    /// Vector instance, and use them as the REPL objects (comma-separated; accepts glob patterns).
+    #[clap(
+        long,
+        use_value_delimiter(true),
+        conflicts_with_all(&["PROGRAM", "program-file", "input-file"])
+    )]
+    tap: Vec<String>,
+
+    /// The maximum number of events to load from Vector.
+    #[clap(long, default_value = "100")]
+    tap_limit: usize,
+
+    /// The Vector GraphQL API server endpoint to load events from.
+    #[clap(short, long, default_value = "http://127.0.0.1:8686/graphql")]
+    url: String,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run the test cases of VRL programs.
+    Test(unit_test::Opts),
 }
 
 impl Opts {
     fn timezone(&self) -> Result<TimeZone, Error> {
-        if let Some(ref tz) = self.timezone {
-            TimeZone::parse(tz)
-                .ok_or_else(|| Error::Parse(format!("unable to parse timezone: {}", tz)))
-        } else {
-            Ok(TimeZone::default())
-        }
+        parse_timezone(self.timezone.as_deref())
     }
 
     fn read_program(&self) -> Result<String, Error> {
@@ -92,10 +117,21 @@ impl Opts {
     fn should_open_repl(&self) -> bool {
         self.program.is_none() && self.program_file.is_none()
     }
+
+    fn tap(&self) -> Tap {
+        Tap {
+            url: self.url.clone(),
+            limit: self.tap_limit,
+        }
+    }
 }
 
 #[must_use]
 pub fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    if let Some(Command::Test(opts)) = &opts.command {
+        return unit_test::cmd(opts);
+    }
+
     match run(opts) {
         Ok(_) => exitcode::OK,
         Err(err) => {
@@ -112,15 +148,18 @@ fn run(opts: &Opts) -> Result<(), Error> {
     let tz = opts.timezone()?;
     // Run the REPL if no program or program file is specified
     if opts.should_open_repl() {
-        // If an input file is provided, use that for the REPL objects, otherwise provide a
-        // generic default object.
-        let repl_objects = if opts.input_file.is_some() {
+        // If events are tapped or an input file is provided, use those for the REPL objects,
+        // otherwise provide a generic default object.
+        let tap = opts.tap();
+        let repl_objects = if !opts.tap.is_empty() {
+            tap.sample(opts.tap.clone(), tap.limit)?
+        } else if opts.input_file.is_some() {
             opts.read_into_objects()?
         } else {
             default_objects()
         };
 
-        repl(repl_objects, tz, opts.runtime)
+        repl(repl_objects, tz, opts.runtime, &tap)
     } else {
         let objects = opts.read_into_objects()?;
         let source = opts.read_program()?;
@@ -179,7 +218,12 @@ fn run(opts: &Opts) -> Result<(), Error> {
 
 #[cfg(feature = "repl")]
 #[allow(clippy::unnecessary_wraps)]
-fn repl(objects: Vec<Value>, timezone: TimeZone, vrl_runtime: VrlRuntime) -> Result<(), Error> {
+fn repl(
+    objects: Vec<Value>,
+    timezone: TimeZone,
+    vrl_runtime: VrlRuntime,
+    tap: &Tap,
+) -> Result<(), Error> {
     use core::TargetValue;
 
     let objects = objects
@@ -191,12 +235,17 @@ fn repl(objects: Vec<Value>, timezone: TimeZone, vrl_runtime: VrlRuntime) -> Res
         })
         .collect();
 
-    repl::run(objects, timezone, vrl_runtime).map_err(Into::into)
+    repl::run(objects, timezone, vrl_runtime, tap).map_err(Into::into)
 }
 
 #[cfg(not(feature = "repl"))]
 #[allow(clippy::needless_pass_by_value)]
-fn repl(_objects: Vec<Value>, _timezone: TimeZone, _vrl_runtime: VrlRuntime) -> Result<(), Error> {
+fn repl(
+    _objects: Vec<Value>,
+    _timezone: TimeZone,
+    _vrl_runtime: VrlRuntime,
+    _tap: &Tap,
+) -> Result<(), Error> {
     Err(Error::ReplFeature)
 }
 
@@ -214,7 +263,15 @@ fn execute(
     }
 }
 
-fn serde_to_vrl(value: serde_json::Value) -> Value {
+pub(crate) fn parse_timezone(tz: Option<&str>) -> Result<TimeZone, Error> {
+    match tz {
+        Some(tz) => TimeZone::parse(tz)
+            .ok_or_else(|| Error::Parse(format!("unable to parse timezone: {}", tz))),
+        None => Ok(TimeZone::default()),
+    }
+}
+
+pub(crate) fn serde_to_vrl(value: serde_json::Value) -> Value {
     use serde_json::Value as JsonValue;
 
     match value {
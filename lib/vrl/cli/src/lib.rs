@@ -15,6 +15,10 @@
 pub mod cmd;
 #[cfg(feature = "repl")]
 mod repl;
+mod tap;
+mod unit_test;
+
+use std::path::PathBuf;
 
 pub use cmd::{cmd, Opts};
 
@@ -33,9 +37,18 @@ pub enum Error {
     #[error("input error: {}", .0)]
     Json(#[from] serde_json::Error),
 
+    #[error("{}: {}", .0.display(), .1)]
+    TestFile(PathBuf, String),
+
     #[error("repl feature disabled, program input required")]
     ReplFeature,
 
+    #[error("unable to tap events: {}", .0)]
+    Tap(String),
+
+    #[error("tap feature disabled, events can't be loaded from Vector")]
+    TapFeature,
+
     #[cfg(feature = "repl")]
     #[error("error setting up readline: {}", .0)]
     Readline(#[from] rustyline::error::ReadlineError),
@@ -20,9 +20,12 @@ use vector_common::TimeZone;
 use vector_vrl_functions::vrl_functions;
 use vrl::state::TypeState;
 use vrl::{
-    diagnostic::Formatter, prelude::BTreeMap, state, CompileConfig, Runtime, Target, VrlRuntime,
+    diagnostic::Formatter, prelude::BTreeMap, state, CompileConfig, Program, Runtime, Target,
+    VrlRuntime,
 };
 
+use crate::{tap::Tap, unit_test::diff};
+
 // Create a list of all possible error values for potential docs lookup
 static ERRORS: Lazy<Vec<String>> = Lazy::new(|| {
     [
@@ -40,6 +43,10 @@ const ERRORS_URL_ROOT: &str = "https://errors.vrl.dev";
 const RESERVED_TERMS: &[&str] = &[
     "next",
     "prev",
+    "tap",
+    "run",
+    "program",
+    "reset",
     "exit",
     "quit",
     "help",
@@ -53,14 +60,21 @@ pub(crate) fn run(
     mut objects: Vec<TargetValue>,
     timezone: TimeZone,
     vrl_runtime: VrlRuntime,
+    tap: &Tap,
 ) -> Result<(), rustyline::error::ReadlineError> {
     let mut index = 0;
     let func_docs_regex = Regex::new(r"^help\sdocs\s(\w{1,})$").unwrap();
     let error_docs_regex = Regex::new(r"^help\serror\s(\w{1,})$").unwrap();
+    let tap_regex = Regex::new(r"^tap\s+(\S+)(?:\s+(\d+))?$").unwrap();
 
-    let mut state = TypeState::default();
+    // The events loaded when the REPL started, or by the last `tap` command, which the `run`
+    // command runs the program against.
+    let mut events = objects
+        .iter()
+        .map(|object| object.value.clone())
+        .collect::<Vec<_>>();
 
-    let mut rt = Runtime::new(state::Runtime::default());
+    let mut session = Session::new();
     let mut rl = Editor::<Repl>::new()?;
     rl.set_helper(Some(Repl::new()));
 
@@ -82,6 +96,19 @@ pub(crate) fn run(
             Ok(line) if error_docs_regex.is_match(line) => show_error_docs(line, &error_docs_regex),
             // Capture "help docs <func_name>"
             Ok(line) if func_docs_regex.is_match(line) => show_func_docs(line, &func_docs_regex),
+            // Capture "tap <patterns> [limit]"
+            Ok(line) if tap_regex.is_match(line) => {
+                if let Some(loaded) = load_events(line, &tap_regex, tap) {
+                    objects = loaded.iter().cloned().map(target_value).collect();
+                    events = loaded;
+                    index = 0;
+                }
+            }
+            Ok(line) if line == "run" => {
+                run_program(&session.program, &events, timezone, vrl_runtime)
+            }
+            Ok(line) if line == "program" => print_program(&session.program),
+            Ok(line) if line == "reset" => session = Session::new(),
             Ok(line) => {
                 rl.add_history_entry(line);
 
@@ -96,11 +123,7 @@ pub(crate) fn run(
 
                         // add new object
                         if index == objects.len() {
-                            objects.push(TargetValue {
-                                value: Value::Null,
-                                metadata: Value::Object(BTreeMap::new()),
-                                secrets: Secrets::new(),
-                            });
+                            objects.push(target_value(Value::Null));
                         }
 
                         "."
@@ -119,11 +142,11 @@ pub(crate) fn run(
                     _ => line,
                 };
 
-                let result = resolve(
+                let result = session.evaluate(
                     objects.get_mut(index).expect("object should exist"),
-                    &mut rt,
                     command,
-                    &mut state,
+                    // Only expressions are part of the program, not the REPL commands.
+                    command == line,
                     timezone,
                     vrl_runtime,
                 );
@@ -151,14 +174,44 @@ pub(crate) fn run(
     Ok(())
 }
 
-fn resolve(
-    target: &mut TargetValue,
-    runtime: &mut Runtime,
-    program: &str,
-    state: &mut TypeState,
-    timezone: TimeZone,
-    vrl_runtime: VrlRuntime,
-) -> Result<Value, String> {
+/// The expressions entered in the REPL, along with the state they compiled and ran with.
+struct Session {
+    /// The expressions that compiled since the REPL started, or since the last `reset` command.
+    program: Vec<String>,
+    state: TypeState,
+    runtime: Runtime,
+}
+
+impl Session {
+    fn new() -> Self {
+        Self {
+            program: Vec::new(),
+            state: TypeState::default(),
+            runtime: Runtime::new(state::Runtime::default()),
+        }
+    }
+
+    /// Compiles and runs `source` against `target`. If `record` is set and `source` compiles, it
+    /// is added to the program.
+    fn evaluate(
+        &mut self,
+        target: &mut TargetValue,
+        source: &str,
+        record: bool,
+        timezone: TimeZone,
+        vrl_runtime: VrlRuntime,
+    ) -> Result<Value, String> {
+        let program = compile(source, &self.state)?;
+        if record {
+            self.program.push(source.to_owned());
+        }
+
+        self.state = program.final_type_state();
+        execute(&mut self.runtime, &program, target, timezone, vrl_runtime)
+    }
+}
+
+fn compile(source: &str, state: &TypeState) -> Result<Program, String> {
     let mut functions = stdlib::all();
     functions.extend(vector_vrl_functions::vrl_functions());
 
@@ -166,20 +219,15 @@ fn resolve(
     // The CLI should be moved out of the "vrl" module, and then it can use the `vector-core::compile_vrl` function which includes this automatically
     config.set_read_only_path(OwnedTargetPath::metadata(owned_value_path!("vector")), true);
 
-    let program = match vrl::compile_with_state(program, &functions, state, config) {
-        Ok(result) => result.program,
-        Err(diagnostics) => {
-            return Err(Formatter::new(program, diagnostics).colored().to_string());
-        }
-    };
-
-    *state = program.final_type_state();
-    execute(runtime, &program, target, timezone, vrl_runtime)
+    match vrl::compile_with_state(source, &functions, state, config) {
+        Ok(result) => Ok(result.program),
+        Err(diagnostics) => Err(Formatter::new(source, diagnostics).colored().to_string()),
+    }
 }
 
 fn execute(
     runtime: &mut Runtime,
-    program: &vrl::Program,
+    program: &Program,
     object: &mut dyn Target,
     timezone: TimeZone,
     vrl_runtime: VrlRuntime,
@@ -191,6 +239,92 @@ fn execute(
     }
 }
 
+fn target_value(value: Value) -> TargetValue {
+    TargetValue {
+        value,
+        metadata: Value::Object(BTreeMap::new()),
+        secrets: Secrets::new(),
+    }
+}
+
+#[allow(clippy::print_stdout)]
+fn load_events(line: &str, pattern: &Regex, tap: &Tap) -> Option<Vec<Value>> {
+    // Unwrap is okay, as the pattern matched the line and the patterns group isn't optional
+    let matches = pattern.captures(line).unwrap();
+    let patterns = matches[1].split(',').map(ToOwned::to_owned).collect();
+    let limit = matches
+        .get(2)
+        .and_then(|limit| limit.as_str().parse().ok())
+        .unwrap_or(tap.limit);
+
+    match tap.sample(patterns, limit) {
+        Ok(events) => {
+            println!("loaded {} events\n", events.len());
+            Some(events)
+        }
+        Err(err) => {
+            println!("{}\n", err);
+            None
+        }
+    }
+}
+
+fn print_program(program: &[String]) {
+    #[allow(clippy::print_stdout)]
+    {
+        println!("{}\n", program.join("\n"));
+    }
+}
+
+/// Runs the program entered so far against each of the loaded events, showing how it changed the
+/// event, followed by the errors aggregated across events.
+#[allow(clippy::print_stdout)]
+fn run_program(program: &[String], events: &[Value], timezone: TimeZone, vrl_runtime: VrlRuntime) {
+    let source = program.join("\n");
+    let compiled = match compile(&source, &TypeState::default()) {
+        Ok(compiled) => compiled,
+        Err(err) => {
+            println!("{}\n", err);
+            return;
+        }
+    };
+
+    let mut errors: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (number, event) in (1..).zip(events) {
+        let mut target = target_value(event.clone());
+        let mut runtime = Runtime::new(state::Runtime::default());
+
+        match execute(&mut runtime, &compiled, &mut target, timezone, vrl_runtime) {
+            Ok(_) if target.value == *event => println!("event {}: unchanged\n", number),
+            Ok(_) => println!(
+                "event {}:\n{}\n",
+                number,
+                diff::lines(&pretty(event), &pretty(&target.value))
+            ),
+            Err(err) => {
+                println!("event {}: failed\n", number);
+                errors.entry(err).or_default().push(number);
+            }
+        }
+    }
+
+    let failed = errors.values().map(Vec::len).sum::<usize>();
+    println!("{} events, {} failed", events.len(), failed);
+    for (error, numbers) in errors {
+        let numbers = numbers
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("\nevents {}:\n{}", numbers, error);
+    }
+    println!();
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
 struct Repl {
     highlighter: MatchingBracketHighlighter,
     history_hinter: HistoryHinter,
@@ -288,21 +422,11 @@ impl Validator for Repl {
         &self,
         ctx: &mut validate::ValidationContext,
     ) -> rustyline::Result<ValidationResult> {
-        let timezone = TimeZone::default();
-        let mut state = TypeState::default();
-        let mut rt = Runtime::new(state::Runtime::default());
-        let mut target = TargetValue {
-            value: Value::Null,
-            metadata: Value::Object(BTreeMap::new()),
-            secrets: Secrets::new(),
-        };
-
-        let result = resolve(
-            &mut target,
-            &mut rt,
+        let result = Session::new().evaluate(
+            &mut target_value(Value::Null),
             ctx.input(),
-            &mut state,
-            timezone,
+            false,
+            TimeZone::default(),
             VrlRuntime::Ast,
         );
 
@@ -416,6 +540,10 @@ const HELP_TEXT: &str = indoc! {r#"
       help error <code>  Navigate to the docs for a specific error code
       next               Load the next object or create a new one
       prev               Load the previous object
+      tap <ids> [limit]  Load events output by the Vector components matching the (comma-separated) ids
+      run                Run the program against all of the loaded events, showing how each changed
+      program            Display the program, made of the expressions entered so far
+      reset              Clear the program, and its variables
       exit               Terminate the program
 "#};
 
@@ -450,6 +578,7 @@ const BANNER_TEXT: &str = indoc! {r#"
     >   help              Learn more about VRL
     >   next              Load the next object or create a new one
     >   prev              Load the previous object
+    >   run               Run the expressions entered so far against all objects
     >   exit              Terminate the program
     >
     > Any other value is resolved to a VRL expression.
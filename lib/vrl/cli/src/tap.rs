@@ -0,0 +1,137 @@
+//! Loads events from a running Vector instance, through the tap subscription of its API.
+use ::value::Value;
+
+use crate::Error;
+
+/// Where and how many events to tap.
+#[derive(Clone, Debug)]
+pub(crate) struct Tap {
+    /// The Vector GraphQL API server endpoint.
+    #[cfg_attr(not(feature = "tap"), allow(dead_code))]
+    pub(crate) url: String,
+
+    /// The maximum number of events to load.
+    pub(crate) limit: usize,
+}
+
+impl Tap {
+    /// Collects up to `limit` events output by the components matching `patterns`.
+    #[cfg(feature = "tap")]
+    pub(crate) fn sample(&self, patterns: Vec<String>, limit: usize) -> Result<Vec<Value>, Error> {
+        let url = url::Url::parse(&self.url)
+            .map_err(|err| Error::Tap(format!("invalid API URL {}: {}", self.url, err)))?;
+
+        // The CLI can run within the runtime of Vector itself, which can't be blocked on, so the
+        // events are collected on a thread of their own.
+        std::thread::spawn(move || {
+            tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|err| err.to_string())?
+                .block_on(collect(url, patterns, limit))
+        })
+        .join()
+        .unwrap_or_else(|_| Err("event collection panicked".to_owned()))
+        .map_err(Error::Tap)
+    }
+
+    #[cfg(not(feature = "tap"))]
+    #[allow(clippy::unused_self, clippy::needless_pass_by_value)]
+    pub(crate) fn sample(
+        &self,
+        _patterns: Vec<String>,
+        _limit: usize,
+    ) -> Result<Vec<Value>, Error> {
+        Err(Error::TapFeature)
+    }
+}
+
+#[cfg(feature = "tap")]
+async fn collect(
+    mut url: url::Url,
+    patterns: Vec<String>,
+    limit: usize,
+) -> Result<Vec<Value>, String> {
+    use std::time::Duration;
+
+    use tokio_stream::StreamExt;
+    use vector_api_client::{
+        connect_subscription_client,
+        gql::{
+            output_events_by_component_id_patterns_subscription::OutputEventsByComponentIdPatternsSubscriptionOutputEventsByComponentIdPatterns as TapEvent,
+            TapEncodingFormat, TapSubscriptionExt,
+        },
+        Client,
+    };
+
+    /// How often the tapped components are sampled, in milliseconds.
+    const INTERVAL: i64 = 500;
+
+    /// How long to wait for events, components with little traffic may not fill the limit.
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    // The health check tells users how to enable the API when it isn't reachable.
+    if Client::new_with_healthcheck(url.clone()).await.is_none() {
+        return Err(format!("unable to reach the Vector API at {}", url));
+    }
+
+    let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+    url.set_scheme(scheme)
+        .map_err(|()| format!("unable to build a WebSocket URL from {}", url))?;
+
+    let client = connect_subscription_client(url)
+        .await
+        .map_err(|err| format!("couldn't connect to Vector API via WebSockets: {}", err))?;
+    let mut stream = client.output_events_by_component_id_patterns_subscription(
+        patterns,
+        vec![],
+        TapEncodingFormat::Json,
+        i64::try_from(limit).unwrap_or(i64::MAX),
+        INTERVAL,
+    );
+
+    let timeout = tokio::time::sleep(TIMEOUT);
+    tokio::pin!(timeout);
+
+    let mut events = Vec::with_capacity(limit);
+    while events.len() < limit {
+        let message = tokio::select! {
+            message = stream.next() => message,
+            _ = &mut timeout => break,
+        };
+        let response = match message {
+            Some(Some(response)) => response,
+            _ => break,
+        };
+
+        for event in response
+            .data
+            .into_iter()
+            .flat_map(|data| data.output_events_by_component_id_patterns)
+        {
+            let string = match event {
+                TapEvent::Log(event) => event.string,
+                TapEvent::Metric(event) => event.string,
+                TapEvent::Trace(event) => event.string,
+                TapEvent::EventNotification(notification) => {
+                    #[allow(clippy::print_stderr)]
+                    {
+                        eprintln!("{}", notification.message);
+                    }
+                    continue;
+                }
+            };
+
+            let event = serde_json::from_str(&string)
+                .map_err(|err| format!("unable to decode tapped event: {}", err))?;
+            events.push(crate::cmd::serde_to_vrl(event));
+        }
+    }
+
+    if events.is_empty() {
+        return Err("no events were received from the matching components".to_owned());
+    }
+
+    events.truncate(limit);
+    Ok(events)
+}
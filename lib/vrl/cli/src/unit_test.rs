@@ -0,0 +1,416 @@
+//! Runs the test cases written next to VRL programs.
+//!
+//! The test cases of a program `foo.vrl` live in `foo.test.toml`, as a list of `[[tests]]` tables.
+//! Each case provides the event (and optionally the metadata) the program runs on, and the
+//! expected output event, result of the program, or error it fails with.
+mod coverage;
+pub(crate) mod diff;
+mod junit;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use ::value::{Secrets, Value};
+use clap::Parser;
+use colored::Colorize;
+use lookup::{owned_value_path, OwnedTargetPath};
+use serde::Deserialize;
+use vector_common::TimeZone;
+use vrl::{
+    diagnostic::{DiagnosticList, Formatter},
+    state::{self, TypeState},
+    CompileConfig, Compiler, Function, Program, Runtime, TargetValueRef,
+};
+
+use self::coverage::Coverage;
+use crate::{
+    cmd::{parse_timezone, serde_to_vrl},
+    Error,
+};
+
+/// The suffix replacing the `.vrl` extension of a program to get the file holding its tests.
+const TEST_FILE_SUFFIX: &str = ".test.toml";
+
+#[derive(Parser, Debug)]
+#[clap(rename_all = "kebab-case")]
+pub(crate) struct Opts {
+    /// VRL programs, or directories to search for VRL programs, to test. Each program `foo.vrl`
+    /// is tested with the cases defined in `foo.test.toml`. Defaults to the current directory.
+    #[clap(name = "PATH", parse(from_os_str))]
+    paths: Vec<PathBuf>,
+
+    /// Write the results of the tests to this file, in JUnit XML format.
+    #[clap(long, parse(from_os_str))]
+    junit: Option<PathBuf>,
+
+    /// Report which branches of the programs were executed by their tests.
+    #[clap(long)]
+    coverage: bool,
+
+    /// The timezone used to parse dates.
+    #[clap(short = 'z', long)]
+    timezone: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct TestFile {
+    #[serde(default)]
+    tests: Vec<TestCase>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct TestCase {
+    name: String,
+
+    /// The event the program runs on.
+    #[serde(default = "empty_object")]
+    input: serde_json::Value,
+
+    /// The metadata of the event.
+    #[serde(default = "empty_object")]
+    metadata: serde_json::Value,
+
+    /// The expected event once the program ran.
+    output: Option<serde_json::Value>,
+
+    /// The expected result of the last expression of the program.
+    result: Option<serde_json::Value>,
+
+    /// A part of the message of the error the program is expected to fail with.
+    error: Option<String>,
+}
+
+fn empty_object() -> serde_json::Value {
+    serde_json::Value::Object(serde_json::Map::new())
+}
+
+/// A program and the file holding its test cases.
+struct TestedProgram {
+    path: PathBuf,
+    tests_path: PathBuf,
+}
+
+/// The results of the test cases of a program.
+pub(crate) struct Suite {
+    pub(crate) name: String,
+    pub(crate) cases: Vec<CaseResult>,
+}
+
+pub(crate) struct CaseResult {
+    pub(crate) name: String,
+    pub(crate) duration: Duration,
+    pub(crate) errors: Vec<String>,
+}
+
+pub(crate) fn cmd(opts: &Opts) -> exitcode::ExitCode {
+    match run(opts) {
+        Ok(true) => exitcode::OK,
+        Ok(false) => exitcode::DATAERR,
+        Err(err) => {
+            #[allow(clippy::print_stderr)]
+            {
+                eprintln!("{}", err);
+            }
+            exitcode::SOFTWARE
+        }
+    }
+}
+
+/// Runs the tests, and returns whether all of them passed.
+fn run(opts: &Opts) -> Result<bool, Error> {
+    let timezone = parse_timezone(opts.timezone.as_deref())?;
+    let programs = discover(&opts.paths)?;
+
+    #[allow(clippy::print_stdout)]
+    {
+        println!("Running tests");
+    }
+
+    if programs.is_empty() {
+        #[allow(clippy::print_stdout)]
+        {
+            println!("{}", "No tests found.".yellow());
+        }
+        return Ok(true);
+    }
+
+    let mut suites = Vec::with_capacity(programs.len());
+    let mut reports = Vec::new();
+    for program in &programs {
+        let (suite, coverage) = run_program(program, &timezone, opts.coverage)?;
+
+        for case in &suite.cases {
+            let status = if case.errors.is_empty() {
+                "passed".green()
+            } else {
+                "failed".red()
+            };
+
+            #[allow(clippy::print_stdout)]
+            {
+                println!("test {}: {} ... {}", suite.name, case.name, status);
+            }
+        }
+
+        if let Some((coverage, source)) = coverage {
+            reports.push(coverage.report(&suite.name, &source));
+        }
+        suites.push(suite);
+    }
+
+    let failures = suites
+        .iter()
+        .flat_map(|suite| {
+            suite
+                .cases
+                .iter()
+                .filter(|case| !case.errors.is_empty())
+                .map(move |case| (suite, case))
+        })
+        .collect::<Vec<_>>();
+
+    if !failures.is_empty() {
+        #[allow(clippy::print_stdout)]
+        {
+            println!("\nfailures:");
+        }
+        for (suite, case) in &failures {
+            #[allow(clippy::print_stdout)]
+            {
+                println!("\ntest {}: {}:\n", suite.name, case.name);
+            }
+            for error in &case.errors {
+                #[allow(clippy::print_stdout)]
+                {
+                    println!("{}\n", error);
+                }
+            }
+        }
+    }
+
+    if opts.coverage {
+        #[allow(clippy::print_stdout)]
+        {
+            println!("\ncoverage:\n");
+            for report in &reports {
+                println!("{}", report);
+            }
+        }
+    }
+
+    if let Some(path) = &opts.junit {
+        fs::write(path, junit::render(&suites))?;
+    }
+
+    Ok(failures.is_empty())
+}
+
+/// Finds the programs to test in `paths`. Directories are searched recursively for programs with
+/// a test file next to them.
+fn discover(paths: &[PathBuf]) -> Result<Vec<TestedProgram>, Error> {
+    let mut programs = Vec::new();
+
+    if paths.is_empty() {
+        walk(Path::new("."), &mut programs)?;
+    }
+
+    for path in paths {
+        if path.is_dir() {
+            walk(path, &mut programs)?;
+            continue;
+        }
+
+        let program = match path
+            .to_str()
+            .and_then(|path| path.strip_suffix(TEST_FILE_SUFFIX))
+        {
+            Some(stem) => PathBuf::from(format!("{}.vrl", stem)),
+            None => path.clone(),
+        };
+
+        let tests_path = tests_path(&program);
+        if !tests_path.is_file() {
+            return Err(Error::TestFile(
+                program,
+                format!("no test file found at {}", tests_path.display()),
+            ));
+        }
+
+        programs.push(TestedProgram {
+            path: program,
+            tests_path,
+        });
+    }
+
+    Ok(programs)
+}
+
+fn walk(dir: &Path, programs: &mut Vec<TestedProgram>) -> Result<(), Error> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            walk(&path, programs)?;
+        } else if path.extension().map_or(false, |ext| ext == "vrl") {
+            let tests_path = tests_path(&path);
+            if tests_path.is_file() {
+                programs.push(TestedProgram { path, tests_path });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn tests_path(program: &Path) -> PathBuf {
+    let mut path = program.with_extension("").into_os_string();
+    path.push(TEST_FILE_SUFFIX);
+    path.into()
+}
+
+/// Runs the test cases of `program`. If coverage is enabled, the coverage of the program is
+/// returned along with its source.
+fn run_program(
+    program: &TestedProgram,
+    timezone: &TimeZone,
+    coverage: bool,
+) -> Result<(Suite, Option<(Coverage, String)>), Error> {
+    let source = fs::read_to_string(&program.path)?;
+    let TestFile { tests } = toml::from_str(&fs::read_to_string(&program.tests_path)?)
+        .map_err(|err| Error::TestFile(program.tests_path.clone(), err.to_string()))?;
+
+    let compiled = compile(&source, &program.path, coverage);
+    let cases = tests
+        .into_iter()
+        .map(|case| {
+            let start = Instant::now();
+            let errors = match &compiled {
+                Ok((compiled, _)) => run_case(compiled, &case, timezone),
+                Err(diagnostics) => vec![diagnostics.clone()],
+            };
+
+            CaseResult {
+                name: case.name,
+                duration: start.elapsed(),
+                errors,
+            }
+        })
+        .collect();
+
+    let suite = Suite {
+        name: program.path.display().to_string(),
+        cases,
+    };
+    let coverage = compiled
+        .ok()
+        .and_then(|(_, coverage)| coverage)
+        .map(|coverage| (coverage, source));
+
+    Ok((suite, coverage))
+}
+
+/// Compiles the program, instrumenting it to record the branches it executes if `coverage` is
+/// set. Compilation errors are returned as rendered diagnostics.
+fn compile(
+    source: &str,
+    path: &Path,
+    coverage: bool,
+) -> Result<(Program, Option<Coverage>), String> {
+    let render = |diagnostics: DiagnosticList| Formatter::new(source, diagnostics).to_string();
+
+    let mut ast = parser::parse(source)
+        .map_err(|err| render(DiagnosticList::from(vec![Box::new(err) as Box<_>])))?;
+
+    let mut functions: Vec<Box<dyn Function>> = stdlib::all();
+    functions.extend(vector_vrl_functions::vrl_functions());
+
+    let coverage = coverage.then(|| Coverage::instrument(&mut ast));
+    if let Some(coverage) = &coverage {
+        functions.push(Box::new(coverage.probe()));
+    }
+
+    let mut config = CompileConfig::default();
+    config.set_read_only_path(OwnedTargetPath::metadata(owned_value_path!("vector")), true);
+    match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => config.set_import_root(dir),
+        _ => config.set_import_root("."),
+    }
+
+    let program = Compiler::compile(&functions, ast, &TypeState::default(), config)
+        .map_err(render)?
+        .program;
+
+    Ok((program, coverage))
+}
+
+fn run_case(program: &Program, case: &TestCase, timezone: &TimeZone) -> Vec<String> {
+    let mut value = serde_to_vrl(case.input.clone());
+    let mut metadata = serde_to_vrl(case.metadata.clone());
+    let mut secrets = Secrets::new();
+    let mut target = TargetValueRef {
+        value: &mut value,
+        metadata: &mut metadata,
+        secrets: &mut secrets,
+    };
+
+    let mut runtime = Runtime::new(state::Runtime::default());
+    let result = runtime.resolve(&mut target, program, timezone);
+
+    let mut errors = Vec::new();
+    match (result, &case.error) {
+        (Ok(result), None) => {
+            if let Some(expected) = &case.result {
+                errors.extend(compare("result", expected, &result));
+            }
+            if let Some(expected) = &case.output {
+                errors.extend(compare("output", expected, &value));
+            }
+        }
+        (Ok(_), Some(expected)) => errors.push(format!(
+            r#"expected an error containing "{}", but the program succeeded"#,
+            expected
+        )),
+        (Err(err), Some(expected)) => {
+            let message = err.to_string();
+            if !message.contains(expected.as_str()) {
+                errors.push(format!(
+                    r#"expected an error containing "{}", got: {}"#,
+                    expected, message
+                ));
+            }
+        }
+        (Err(err), None) => errors.push(format!("unexpected error: {}", err)),
+    }
+
+    errors
+}
+
+/// Compares the JSON representation of a value with its expected value, returning a diff of the
+/// two if they differ.
+fn compare(what: &str, expected: &serde_json::Value, actual: &Value) -> Option<String> {
+    let actual = match serde_json::to_value(actual) {
+        Ok(actual) => actual,
+        Err(err) => return Some(format!("unable to represent {} as JSON: {}", what, err)),
+    };
+
+    if &actual == expected {
+        return None;
+    }
+
+    let expected = serde_json::to_string_pretty(expected).unwrap_or_default();
+    let actual = serde_json::to_string_pretty(&actual).unwrap_or_default();
+
+    Some(format!(
+        "unexpected {}:\n- expected\n+ actual\n\n{}",
+        what,
+        diff::lines(&expected, &actual)
+    ))
+}
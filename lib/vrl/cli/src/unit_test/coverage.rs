@@ -0,0 +1,322 @@
+//! Branch coverage of VRL programs.
+//!
+//! Programs are instrumented before they are compiled: a call to a probe function is inserted at
+//! the start of every branch of their `if` statements, and `if` statements without an `else`
+//! branch get one that only calls the probe. Running the program then records the branches that
+//! executed. Only the branches of the program itself are instrumented, not those of the files it
+//! imports.
+use std::{
+    collections::BTreeSet,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use ::value::Value;
+use parser::ast::{
+    Assignment, Block, Container, Expr, FunctionArgument, FunctionCall, Ident, IfStatement,
+    Literal, Node, Op, Predicate, Program, QueryTarget, RootExpr, Unary,
+};
+use parser::Span;
+use vrl::prelude::{
+    kind, ArgumentList, Compiled, Context, Example, Expression, Function, FunctionCompileContext,
+    FunctionExpression, Parameter, Resolved, TypeDef,
+};
+use vrl::state::TypeState;
+
+/// The name of the function recording the execution of a branch.
+const PROBE: &str = "__vrl_test_coverage";
+
+#[derive(Clone, Copy, Debug)]
+enum BranchKind {
+    If,
+    Else,
+
+    /// The `else` branch added to an `if` statement that doesn't have one.
+    ImplicitElse,
+}
+
+impl fmt::Display for BranchKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BranchKind::If => f.write_str("if branch"),
+            BranchKind::Else => f.write_str("else branch"),
+            BranchKind::ImplicitElse => f.write_str("implicit else branch"),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Branch {
+    kind: BranchKind,
+    span: Span,
+}
+
+/// The branches of an instrumented program, and the ones that executed so far.
+#[derive(Debug)]
+pub(crate) struct Coverage {
+    branches: Vec<Branch>,
+    hits: Hits,
+}
+
+type Hits = Arc<Mutex<BTreeSet<usize>>>;
+
+fn lock(hits: &Hits) -> MutexGuard<'_, BTreeSet<usize>> {
+    hits.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Coverage {
+    /// Instruments `program`, which must then be compiled with the function returned by
+    /// [`Coverage::probe`].
+    pub(crate) fn instrument(program: &mut Program) -> Self {
+        let mut instrumenter = Instrumenter::default();
+        for expr in &mut program.0 {
+            instrumenter.root_expr(expr.inner_mut());
+        }
+
+        Self {
+            branches: instrumenter.branches,
+            hits: Hits::default(),
+        }
+    }
+
+    pub(crate) fn probe(&self) -> impl Function {
+        Probe {
+            hits: Arc::clone(&self.hits),
+        }
+    }
+
+    /// Renders the branches executed by the program, pointing at the ones that weren't.
+    pub(crate) fn report(&self, name: &str, source: &str) -> String {
+        let hits = lock(&self.hits);
+        let total = self.branches.len();
+        let executed = (0..total).filter(|id| hits.contains(id)).count();
+        let percent = if total == 0 {
+            100
+        } else {
+            executed * 100 / total
+        };
+
+        let mut lines = vec![format!(
+            "{}: {}/{} branches executed ({}%)",
+            name, executed, total, percent
+        )];
+        for (id, branch) in self.branches.iter().enumerate() {
+            if !hits.contains(&id) {
+                let (line, column) = line_column(source, branch.span.start());
+                lines.push(format!("  missed {} at {}:{}", branch.kind, line, column));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Returns the line and column, both starting at one, of a byte offset in `source`.
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = source.get(..offset).unwrap_or(source);
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+#[derive(Default)]
+struct Instrumenter {
+    branches: Vec<Branch>,
+}
+
+impl Instrumenter {
+    fn root_expr(&mut self, expr: &mut RootExpr) {
+        match expr {
+            RootExpr::Expr(expr) => self.expr(expr),
+            RootExpr::FunctionDefinition(definition) => {
+                self.block(definition.inner_mut().block.inner_mut());
+            }
+            RootExpr::Import(_) | RootExpr::Error(_) => {}
+        }
+    }
+
+    fn expr(&mut self, node: &mut Node<Expr>) {
+        match node.inner_mut() {
+            Expr::Literal(_) | Expr::Variable(_) => {}
+            Expr::Container(container) => self.container(container.inner_mut()),
+            Expr::IfStatement(statement) => self.if_statement(statement),
+            Expr::Op(op) => {
+                let Op(lhs, _, rhs) = op.inner_mut();
+                self.expr(lhs);
+                self.expr(rhs);
+            }
+            Expr::Assignment(assignment) => match assignment.inner_mut() {
+                Assignment::Single { expr, .. } | Assignment::Infallible { expr, .. } => {
+                    self.expr(expr);
+                }
+            },
+            Expr::Query(query) => match query.inner_mut().target.inner_mut() {
+                QueryTarget::FunctionCall(call) => self.function_call(call),
+                QueryTarget::Container(container) => self.container(container),
+                QueryTarget::Internal(_) | QueryTarget::External(_) => {}
+            },
+            Expr::FunctionCall(call) => self.function_call(call.inner_mut()),
+            Expr::Unary(unary) => match unary.inner_mut() {
+                Unary::Not(not) => self.expr(not.inner_mut().expr_mut()),
+            },
+            Expr::Abort(abort) => {
+                if let Some(message) = &mut abort.inner_mut().message {
+                    self.expr(message);
+                }
+            }
+        }
+    }
+
+    fn block(&mut self, block: &mut Block) {
+        for expr in &mut block.0 {
+            self.expr(expr);
+        }
+    }
+
+    fn container(&mut self, container: &mut Container) {
+        match container {
+            Container::Group(group) => self.expr(&mut group.inner_mut().0),
+            Container::Block(block) => self.block(block.inner_mut()),
+            Container::Array(array) => {
+                for expr in array.inner_mut().iter_mut() {
+                    self.expr(expr);
+                }
+            }
+            Container::Object(object) => {
+                for expr in object.inner_mut().values_mut() {
+                    self.expr(expr);
+                }
+            }
+        }
+    }
+
+    fn function_call(&mut self, call: &mut FunctionCall) {
+        for argument in &mut call.arguments {
+            self.expr(&mut argument.inner_mut().expr);
+        }
+
+        if let Some(closure) = &mut call.closure {
+            self.block(closure.inner_mut().block.inner_mut());
+        }
+    }
+
+    fn if_statement(&mut self, node: &mut Node<IfStatement>) {
+        let span = node.span();
+        let statement = node.inner_mut();
+
+        match statement.predicate.inner_mut() {
+            Predicate::One(expr) => self.expr(expr),
+            Predicate::Many(exprs) => {
+                for expr in exprs {
+                    self.expr(expr);
+                }
+            }
+        }
+
+        self.block(statement.if_node.inner_mut());
+        let probe = self.probe(BranchKind::If, statement.if_node.span());
+        statement.if_node.inner_mut().0.insert(0, probe);
+
+        match &mut statement.else_node {
+            Some(else_node) => {
+                self.block(else_node.inner_mut());
+                let probe = self.probe(BranchKind::Else, else_node.span());
+                else_node.inner_mut().0.insert(0, probe);
+            }
+            None => {
+                // An `if` statement without an `else` branch evaluates to `null` when its
+                // predicate is false, which the added branch preserves.
+                let probe = self.probe(BranchKind::ImplicitElse, span);
+                let null = Node::new(span, Expr::Literal(Node::new(span, Literal::Null)));
+                statement.else_node = Some(Node::new(span, Block(vec![probe, null])));
+            }
+        }
+    }
+
+    /// Registers a new branch, and returns the probe call recording its execution.
+    fn probe(&mut self, kind: BranchKind, span: Span) -> Node<Expr> {
+        let id = self.branches.len();
+        self.branches.push(Branch { kind, span });
+
+        let id = i64::try_from(id).unwrap_or(i64::MAX);
+        let argument = FunctionArgument {
+            ident: None,
+            expr: Node::new(span, Expr::Literal(Node::new(span, Literal::Integer(id)))),
+        };
+        let call = FunctionCall {
+            ident: Node::new(span, Ident::new(PROBE)),
+            abort_on_error: false,
+            arguments: vec![Node::new(span, argument)],
+            closure: None,
+        };
+
+        Node::new(span, Expr::FunctionCall(Node::new(span, call)))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Probe {
+    hits: Hits,
+}
+
+impl Function for Probe {
+    fn identifier(&self) -> &'static str {
+        PROBE
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "id",
+            kind: kind::INTEGER,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[]
+    }
+
+    fn compile(
+        &self,
+        _state: &TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        // Probes are only ever called with the literal id of their branch, ids that aren't are
+        // recorded as unknown branches, which the report ignores.
+        let id = arguments
+            .required("id")
+            .as_value()
+            .as_ref()
+            .and_then(Value::as_integer)
+            .and_then(|id| usize::try_from(id).ok())
+            .unwrap_or(usize::MAX);
+
+        Ok(ProbeFn {
+            id,
+            hits: Arc::clone(&self.hits),
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Clone, Debug)]
+struct ProbeFn {
+    id: usize,
+    hits: Hits,
+}
+
+impl FunctionExpression for ProbeFn {
+    fn resolve(&self, _ctx: &mut Context) -> Resolved {
+        lock(&self.hits).insert(self.id);
+
+        Ok(Value::Null)
+    }
+
+    fn type_def(&self, _: &TypeState) -> TypeDef {
+        TypeDef::null().infallible()
+    }
+}
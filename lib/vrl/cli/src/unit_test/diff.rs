@@ -0,0 +1,61 @@
+/// Renders a line-based diff turning `expected` into `actual`. Lines only found in `expected` are
+/// prefixed with `- `, lines only found in `actual` with `+ `, and common lines with two spaces.
+pub(crate) fn lines(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+
+    // `common[i][j]` is the length of the longest common subsequence of `expected[i..]` and
+    // `actual[j..]`.
+    let mut common = vec![vec![0_usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            common[i][j] = if expected[i] == actual[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(expected.len().max(actual.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() || j < actual.len() {
+        if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+            diff.push(format!("  {}", expected[i]));
+            i += 1;
+            j += 1;
+        } else if j == actual.len() || (i < expected.len() && common[i + 1][j] >= common[i][j + 1])
+        {
+            diff.push(format!("- {}", expected[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+ {}", actual[j]));
+            j += 1;
+        }
+    }
+
+    diff.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_changed_lines() {
+        let expected = "{\n  \"a\": 1,\n  \"b\": 2\n}";
+        let actual = "{\n  \"a\": 1,\n  \"b\": 3\n}";
+
+        assert_eq!(
+            lines(expected, actual),
+            "  {\n    \"a\": 1,\n-   \"b\": 2\n+   \"b\": 3\n  }"
+        );
+    }
+
+    #[test]
+    fn renders_added_and_removed_lines() {
+        assert_eq!(lines("a\nb", "b\nc"), "- a\n  b\n+ c");
+        assert_eq!(lines("", "a"), "+ a");
+        assert_eq!(lines("a", ""), "- a");
+    }
+}
@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+use super::Suite;
+
+/// Renders the results of the tests in the JUnit XML format, with one test suite per program.
+pub(crate) fn render(suites: &[Suite]) -> String {
+    let count = |suite: &Suite| suite.cases.len();
+    let failures = |suite: &Suite| {
+        suite
+            .cases
+            .iter()
+            .filter(|case| !case.errors.is_empty())
+            .count()
+    };
+    let time = |suite: &Suite| {
+        suite
+            .cases
+            .iter()
+            .map(|case| case.duration)
+            .sum::<Duration>()
+    };
+
+    let mut xml = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_owned(),
+        format!(
+            r#"<testsuites name="vrl" tests="{}" failures="{}" time="{}">"#,
+            suites.iter().map(count).sum::<usize>(),
+            suites.iter().map(failures).sum::<usize>(),
+            seconds(suites.iter().map(time).sum()),
+        ),
+    ];
+
+    for suite in suites {
+        xml.push(format!(
+            r#"  <testsuite name="{}" tests="{}" failures="{}" time="{}">"#,
+            escape(&suite.name),
+            count(suite),
+            failures(suite),
+            seconds(time(suite)),
+        ));
+
+        for case in &suite.cases {
+            let attributes = format!(
+                r#"name="{}" classname="{}" time="{}""#,
+                escape(&case.name),
+                escape(&suite.name),
+                seconds(case.duration),
+            );
+
+            match case.errors.first() {
+                None => xml.push(format!("    <testcase {} />", attributes)),
+                Some(first) => {
+                    let message = first.lines().next().unwrap_or_default();
+                    xml.push(format!("    <testcase {}>", attributes));
+                    xml.push(format!(
+                        r#"      <failure message="{}">{}</failure>"#,
+                        escape(message),
+                        escape(&case.errors.join("\n\n")),
+                    ));
+                    xml.push("    </testcase>".to_owned());
+                }
+            }
+        }
+
+        xml.push("  </testsuite>".to_owned());
+    }
+
+    xml.push("</testsuites>".to_owned());
+    xml.push(String::new());
+    xml.join("\n")
+}
+
+fn seconds(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64())
+}
+
+/// Escapes text for use in XML attributes and elements, dropping the characters XML can't
+/// represent.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            ch if ch.is_control() => {}
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(
+            escape("<a href=\"x\">'&'</a>\x1b[0m\n"),
+            "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;[0m\n"
+        );
+    }
+}
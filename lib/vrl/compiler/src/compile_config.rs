@@ -1,11 +1,18 @@
 use anymap::AnyMap;
 use lookup::OwnedTargetPath;
-use std::collections::BTreeSet;
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
 
 pub struct CompileConfig {
     /// Custom context injected by the external environment
     custom: AnyMap,
     read_only_paths: BTreeSet<ReadOnlyPath>,
+    /// The directory relative `import` paths are resolved against.
+    import_root: Option<PathBuf>,
+    /// Whether costly patterns used by the program are reported as warnings.
+    performance_lints: bool,
 }
 
 impl CompileConfig {
@@ -55,6 +62,38 @@ impl CompileConfig {
         false
     }
 
+    /// Sets the directory relative `import` paths are resolved against. Without one, they are
+    /// resolved against the current working directory.
+    pub fn set_import_root(&mut self, path: impl Into<PathBuf>) {
+        self.import_root = Some(path.into());
+    }
+
+    #[must_use]
+    pub fn import_root(&self) -> Option<&Path> {
+        self.import_root.as_deref()
+    }
+
+    /// Resolves the path of an `import` against the import root.
+    #[must_use]
+    pub fn resolve_import(&self, path: &str) -> PathBuf {
+        match &self.import_root {
+            Some(root) => root.join(path),
+            None => PathBuf::from(path),
+        }
+    }
+
+    /// Reports the costly patterns used by the program, such as regexes compiled at runtime, as
+    /// compilation warnings. These are disabled by default, as many programs use these patterns
+    /// on purpose.
+    pub fn set_performance_lints(&mut self, enabled: bool) {
+        self.performance_lints = enabled;
+    }
+
+    #[must_use]
+    pub fn performance_lints(&self) -> bool {
+        self.performance_lints
+    }
+
     /// Adds a path that is considered read only. Assignments to any paths that match
     /// will fail at compile time.
     pub fn set_read_only_path(&mut self, path: OwnedTargetPath, recursive: bool) {
@@ -68,6 +107,8 @@ impl Default for CompileConfig {
         Self {
             custom: AnyMap::new(),
             read_only_paths: BTreeSet::new(),
+            import_root: None,
+            performance_lints: false,
         }
     }
 }
@@ -1,7 +1,13 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    rc::Rc,
+};
+
 use core::Value;
 use diagnostic::{DiagnosticList, DiagnosticMessage, Note, Severity, Span};
 use lookup::{OwnedTargetPath, OwnedValuePath, PathPrefix};
-use parser::ast::{self, Node, QueryTarget};
+use parser::ast::{self, Ident, Node, QueryTarget};
 
 use crate::function::ArgumentList;
 use crate::state::TypeState;
@@ -13,10 +19,14 @@ use crate::{
         Literal, Noop, Not, Object, Op, Predicate, Query, Target, Unary, Variable,
     },
     parser::ast::RootExpr,
+    performance,
     program::ProgramInfo,
     CompileConfig, DeprecationWarning, Function, Program, TypeDef,
 };
 
+#[cfg(feature = "expr-function_call")]
+use crate::expression::{user_function, UserFunctionCall};
+
 pub(crate) type Diagnostics = Vec<Box<dyn DiagnosticMessage>>;
 
 pub struct CompilationResult {
@@ -51,9 +61,45 @@ pub struct Compiler<'a> {
     /// nullifies the fallibility of that expression.
     fallible_expression_error: Option<Box<dyn DiagnosticMessage>>,
 
+    /// Functions defined in the program, or in one of its imports.
+    user_functions: HashMap<Ident, UserFunction>,
+
+    /// The user-defined functions whose body is currently being compiled.
+    ///
+    /// This is used to reject (mutually) recursive calls, which would
+    /// otherwise require compiling the function body indefinitely.
+    user_function_calls: Vec<Ident>,
+
+    /// The files that were imported so far.
+    imports: HashSet<PathBuf>,
+
     config: CompileConfig,
 }
 
+#[derive(Clone)]
+struct UserFunction {
+    definition: ast::FunctionDefinition,
+
+    /// The file the function was imported from, if any.
+    import: Option<Rc<ImportedSource>>,
+}
+
+/// The source of an imported file, used to report the location of errors
+/// in the functions it defines.
+struct ImportedSource {
+    path: PathBuf,
+    source: String,
+}
+
+impl ImportedSource {
+    fn location(&self, span: Span) -> String {
+        let end = span.start().min(self.source.len());
+        let line = self.source[..end].matches('\n').count() + 1;
+
+        format!("{}:{}", self.path.display(), line)
+    }
+}
+
 impl<'a> Compiler<'a> {
     pub fn compile(
         fns: &'a [Box<dyn Function>],
@@ -73,10 +119,23 @@ impl<'a> Compiler<'a> {
             external_assignments: vec![],
             skip_missing_query_target: vec![],
             fallible_expression_error: None,
+            user_functions: HashMap::new(),
+            user_function_calls: vec![],
+            imports: HashSet::new(),
             config,
         };
+        let analysis = performance::analyze(&ast);
         let expressions = compiler.compile_root_exprs(ast, &mut state);
 
+        if compiler.config.performance_lints() {
+            compiler.diagnostics.extend(
+                analysis
+                    .warnings
+                    .into_iter()
+                    .map(|warning| Box::new(warning) as _),
+            );
+        }
+
         let (errors, warnings): (Vec<_>, Vec<_>) =
             compiler.diagnostics.into_iter().partition(|diagnostic| {
                 matches!(diagnostic.severity(), Severity::Bug | Severity::Error)
@@ -96,6 +155,7 @@ impl<'a> Compiler<'a> {
                     target_assignments: compiler.external_assignments,
                 },
                 initial_state,
+                cost: analysis.cost,
             },
             warnings: warnings.into(),
             config: compiler.config,
@@ -126,6 +186,10 @@ impl<'a> Compiler<'a> {
         let span = node.span();
 
         let expr = match node.into_inner() {
+            #[cfg(feature = "expr-function_call")]
+            FunctionCall(node) if self.user_functions.contains_key(node.ident.inner()) => {
+                self.compile_user_function_call(node, state).map(Into::into)
+            }
             Literal(node) => self.compile_literal(node, state),
             Container(node) => self.compile_container(node, state).map(Into::into),
             IfStatement(node) => self.compile_if_statement(node, state).map(Into::into),
@@ -240,6 +304,8 @@ impl<'a> Compiler<'a> {
                         node_exprs.push(expr);
                     }
                 }
+                RootExpr::FunctionDefinition(node) => self.compile_function_definition(node),
+                RootExpr::Import(node) => self.compile_import(node),
                 RootExpr::Error(err) => self.handle_parser_error(err),
             }
         }
@@ -600,6 +666,11 @@ impl<'a> Compiler<'a> {
                 let container = self.compile_container(Node::new(span, container), state)?;
                 Target::Container(container)
             }
+            #[cfg(feature = "expr-function_call")]
+            FunctionCall(call) if self.user_functions.contains_key(call.ident.inner()) => {
+                let call = self.compile_user_function_call(Node::new(span, call), state)?;
+                Target::Container(Container::new(Group::new(call.into()).into()))
+            }
             FunctionCall(call) => {
                 let call = self.compile_function_call(Node::new(span, call), state)?;
                 Target::FunctionCall(call)
@@ -813,6 +884,325 @@ impl<'a> Compiler<'a> {
         Some(FunctionArgument::new(ident, node))
     }
 
+    #[cfg(feature = "expr-function_call")]
+    fn compile_function_definition(&mut self, node: Node<ast::FunctionDefinition>) {
+        if let Err(err) = self.define_function(node.into_inner(), None) {
+            self.diagnostics.push(Box::new(err));
+        }
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn compile_function_definition(&mut self, node: Node<ast::FunctionDefinition>) {
+        self.handle_missing_feature_error(node.span(), "expr-function_call");
+    }
+
+    /// Registers a user-defined function. Its body is compiled at each call
+    /// site, using the type definitions of the arguments passed to it.
+    #[cfg(feature = "expr-function_call")]
+    fn define_function(
+        &mut self,
+        definition: ast::FunctionDefinition,
+        import: Option<Rc<ImportedSource>>,
+    ) -> Result<(), user_function::Error> {
+        let ident_span = definition.ident.span();
+        let ident = definition.ident.inner().clone();
+
+        let builtin = self.fns.iter().any(|f| f.identifier() == ident.as_ref());
+        if builtin || self.user_functions.contains_key(&ident) {
+            return Err(user_function::Error::DuplicateFunction {
+                ident_span,
+                ident,
+                builtin,
+            });
+        }
+
+        let mut parameters = HashSet::new();
+        for parameter in &definition.parameters {
+            if !parameters.insert(parameter.inner()) {
+                return Err(user_function::Error::DuplicateParameter {
+                    parameter_span: parameter.span(),
+                    ident: parameter.inner().clone(),
+                });
+            }
+        }
+
+        self.user_functions
+            .insert(ident, UserFunction { definition, import });
+
+        Ok(())
+    }
+
+    #[cfg(feature = "expr-function_call")]
+    fn compile_import(&mut self, node: Node<ast::Import>) {
+        let (span, import) = node.take();
+
+        if let Err(err) = self.import_file(import.path.inner(), span) {
+            self.diagnostics.push(Box::new(err));
+        }
+    }
+
+    #[cfg(not(feature = "expr-function_call"))]
+    fn compile_import(&mut self, node: Node<ast::Import>) {
+        self.handle_missing_feature_error(node.span(), "expr-function_call");
+    }
+
+    /// Defines the functions of an imported file.
+    ///
+    /// Imports nested in the file are resolved against the same import root,
+    /// and files that were imported before are skipped, which also breaks
+    /// import cycles.
+    #[cfg(feature = "expr-function_call")]
+    fn import_file(&mut self, path: &str, import_span: Span) -> Result<(), user_function::Error> {
+        let path = self.config.resolve_import(path);
+        let invalid = |reason: String| user_function::Error::Import {
+            import_span,
+            path: path.clone(),
+            reason,
+        };
+
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if !self.imports.insert(canonical) {
+            return Ok(());
+        }
+
+        let source = std::fs::read_to_string(&path).map_err(|err| invalid(err.to_string()))?;
+        let source = Rc::new(ImportedSource {
+            path: path.clone(),
+            source,
+        });
+
+        let program = parser::parse(&source.source).map_err(|err| {
+            let location = err.labels().first().map_or_else(
+                || path.display().to_string(),
+                |label| source.location(label.span),
+            );
+
+            invalid(format!("{}: {}", location, err.message()))
+        })?;
+
+        for node in program {
+            let (span, root_expr) = node.take();
+            let location = source.location(span);
+
+            match root_expr {
+                RootExpr::FunctionDefinition(node) => self
+                    .define_function(node.into_inner(), Some(source.clone()))
+                    .map_err(|err| {
+                        let reason = err
+                            .labels()
+                            .pop()
+                            .map_or_else(|| err.message(), |label| label.message);
+
+                        invalid(format!("{}: {}", location, reason))
+                    })?,
+                RootExpr::Import(node) => self.import_file(node.path.inner(), import_span)?,
+                RootExpr::Expr(_) => {
+                    return Err(invalid(format!(
+                        "{}: imported files can only contain function definitions and imports",
+                        location
+                    )))
+                }
+                RootExpr::Error(err) => {
+                    return Err(invalid(format!("{}: {}", location, err.message())))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "expr-function_call")]
+    fn compile_user_function_call(
+        &mut self,
+        node: Node<ast::FunctionCall>,
+        state: &mut TypeState,
+    ) -> Option<UserFunctionCall> {
+        use user_function::Error::{
+            AbortInfallible, DuplicateArgument, Imported, MissingArgument, RecursiveCall,
+            UnexpectedClosure, UnknownKeyword, WrongNumberOfArgs,
+        };
+
+        let call_span = node.span();
+        let ast::FunctionCall {
+            ident,
+            abort_on_error,
+            arguments,
+            closure,
+        } = node.into_inner();
+        let (ident_span, ident) = ident.take();
+
+        let UserFunction { definition, import } = self.user_functions.get(&ident)?.clone();
+        let parameters: Vec<Ident> = definition
+            .parameters
+            .iter()
+            .map(|parameter| parameter.inner().clone())
+            .collect();
+
+        if let Some(closure) = closure {
+            self.diagnostics.push(Box::new(UnexpectedClosure {
+                call_span,
+                closure_span: closure.span(),
+            }));
+            return None;
+        }
+
+        if self.user_function_calls.contains(&ident) {
+            self.diagnostics
+                .push(Box::new(RecursiveCall { call_span, ident }));
+            return None;
+        }
+
+        if arguments.len() > parameters.len() {
+            let start = arguments.first().unwrap().span().start();
+            let end = arguments.last().unwrap().span().end();
+
+            self.diagnostics.push(Box::new(WrongNumberOfArgs {
+                arguments_span: Span::new(start, end),
+                max: parameters.len(),
+            }));
+            return None;
+        }
+
+        // Map keyword arguments to their parameter, and positional arguments
+        // to the first parameter that isn't assigned yet.
+        let mut slots: Vec<Option<Node<ast::Expr>>> = vec![None; parameters.len()];
+        for argument in arguments {
+            let argument_span = argument.span();
+            let ast::FunctionArgument {
+                ident: keyword,
+                expr,
+            } = argument.into_inner();
+
+            let index = match keyword {
+                Some(keyword) => {
+                    if let Some(index) = parameters.iter().position(|p| p == keyword.inner()) {
+                        index
+                    } else {
+                        self.diagnostics.push(Box::new(UnknownKeyword {
+                            keyword_span: keyword.span(),
+                            ident_span,
+                            parameters,
+                        }));
+                        return None;
+                    }
+                }
+                None => slots.iter().position(Option::is_none)?,
+            };
+
+            if slots[index].is_some() {
+                self.diagnostics.push(Box::new(DuplicateArgument {
+                    argument_span,
+                    parameter: parameters[index].clone(),
+                }));
+                return None;
+            }
+
+            slots[index] = Some(expr);
+        }
+
+        if let Some(position) = slots.iter().position(Option::is_none) {
+            self.diagnostics.push(Box::new(MissingArgument {
+                call_span,
+                parameter: parameters[position].clone(),
+                position,
+            }));
+            return None;
+        }
+
+        let original_state = state.clone();
+        let arguments = slots
+            .into_iter()
+            .flatten()
+            .map(|expr| self.compile_expr(expr, state))
+            .collect::<Option<Vec<_>>>()?;
+
+        if abort_on_error {
+            self.fallible = true;
+        }
+
+        let (mut body_state, arguments_fallible) =
+            UserFunctionCall::body_state(&parameters, &arguments, &mut original_state.clone());
+
+        // The body is compiled in isolation, so that any errors it contains
+        // can be attributed to the imported function, and so that it doesn't
+        // leak fallibility tracking into the caller.
+        self.user_function_calls.push(ident.clone());
+        let fallible_expression_error = self.fallible_expression_error.take();
+        let diagnostics = std::mem::take(&mut self.diagnostics);
+
+        let exprs = self.compile_exprs(definition.block.into_inner(), &mut body_state);
+
+        self.user_function_calls.pop();
+        self.fallible_expression_error = fallible_expression_error;
+        let body_diagnostics = std::mem::replace(&mut self.diagnostics, diagnostics);
+
+        // The body is compiled once per call, so the same diagnostic can be
+        // reported more than once.
+        for diagnostic in body_diagnostics {
+            let diagnostic: Box<dyn DiagnosticMessage> = match &import {
+                Some(source) => {
+                    let location = diagnostic
+                        .labels()
+                        .into_iter()
+                        .find(|label| label.primary)
+                        .map_or_else(
+                            || source.location(definition.ident.span()),
+                            |label| source.location(label.span),
+                        );
+
+                    Box::new(Imported {
+                        call_span,
+                        ident: ident.clone(),
+                        location,
+                        error: diagnostic,
+                    })
+                }
+                None => diagnostic,
+            };
+
+            let duplicate = self.diagnostics.iter().any(|existing| {
+                existing.code() == diagnostic.code()
+                    && existing.message() == diagnostic.message()
+                    && existing.labels() == diagnostic.labels()
+            });
+
+            if !duplicate {
+                self.diagnostics.push(diagnostic);
+            }
+        }
+
+        let mut exprs = exprs?;
+        if exprs.is_empty() {
+            exprs.push(Expr::Noop(Noop));
+        }
+        let body = Block::new_inline(exprs);
+
+        let (body_state, _) =
+            UserFunctionCall::body_state(&parameters, &arguments, &mut original_state.clone());
+        let fallible = arguments_fallible || body.type_info(&body_state).result.is_fallible();
+
+        if abort_on_error && !fallible {
+            self.diagnostics.push(Box::new(AbortInfallible {
+                ident_span,
+                abort_span: Span::new(ident_span.end(), ident_span.end() + 1),
+            }));
+            return None;
+        }
+
+        let call = UserFunctionCall::new(
+            ident,
+            call_span,
+            abort_on_error,
+            parameters,
+            arguments,
+            body,
+            import.is_some(),
+        );
+
+        *state = call.type_info(&original_state).state;
+        Some(call)
+    }
+
     #[cfg(not(feature = "expr-function_call"))]
     fn compile_function_call(
         &mut self,
@@ -38,6 +38,8 @@ pub(crate) mod literal;
 pub(crate) mod predicate;
 #[cfg(feature = "expr-query")]
 pub mod query;
+#[cfg(feature = "expr-function_call")]
+pub(crate) mod user_function;
 
 pub use core::{ExpressionError, Resolved};
 
@@ -71,6 +73,8 @@ pub use predicate::Predicate;
 pub use query::{Query, Target};
 #[cfg(feature = "expr-unary")]
 pub use unary::Unary;
+#[cfg(feature = "expr-function_call")]
+pub use user_function::UserFunctionCall;
 pub use variable::Variable;
 
 pub trait Expression: Send + Sync + fmt::Debug + DynClone {
@@ -139,6 +143,8 @@ pub enum Expr {
     Query(Query),
     #[cfg(feature = "expr-function_call")]
     FunctionCall(FunctionCall),
+    #[cfg(feature = "expr-function_call")]
+    UserFunctionCall(UserFunctionCall),
     Variable(Variable),
     Noop(Noop),
     #[cfg(feature = "expr-unary")]
@@ -152,7 +158,7 @@ impl Expr {
         use container::Variant::{Array, Block, Group, Object};
         use Expr::{
             Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -174,6 +180,8 @@ impl Expr {
             Query(..) => "query",
             #[cfg(feature = "expr-function_call")]
             FunctionCall(..) => "function call",
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(..) => "function call",
             Variable(..) => "variable call",
             Noop(..) => "noop",
             #[cfg(feature = "expr-unary")]
@@ -244,7 +252,7 @@ impl Expression for Expr {
     fn resolve(&self, ctx: &mut Context) -> Resolved {
         use Expr::{
             Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -261,6 +269,8 @@ impl Expression for Expr {
             Query(v) => v.resolve(ctx),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => v.resolve(ctx),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.resolve(ctx),
             Variable(v) => v.resolve(ctx),
             Noop(v) => v.resolve(ctx),
             #[cfg(feature = "expr-unary")]
@@ -273,7 +283,7 @@ impl Expression for Expr {
     fn as_value(&self) -> Option<Value> {
         use Expr::{
             Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -290,6 +300,8 @@ impl Expression for Expr {
             Query(v) => Expression::as_value(v),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => Expression::as_value(v),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => Expression::as_value(v),
             Variable(v) => Expression::as_value(v),
             Noop(v) => Expression::as_value(v),
             #[cfg(feature = "expr-unary")]
@@ -302,7 +314,7 @@ impl Expression for Expr {
     fn type_info(&self, state: &TypeState) -> TypeInfo {
         use Expr::{
             Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -319,6 +331,8 @@ impl Expression for Expr {
             Query(v) => v.type_info(state),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => v.type_info(state),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.type_info(state),
             Variable(v) => v.type_info(state),
             Noop(v) => v.type_info(state),
             #[cfg(feature = "expr-unary")]
@@ -333,7 +347,7 @@ impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use Expr::{
             Abort, Assignment, Container, FunctionCall, IfStatement, Literal, Noop, Op, Query,
-            Unary, Variable,
+            Unary, UserFunctionCall, Variable,
         };
 
         match self {
@@ -350,6 +364,8 @@ impl fmt::Display for Expr {
             Query(v) => v.fmt(f),
             #[cfg(feature = "expr-function_call")]
             FunctionCall(v) => v.fmt(f),
+            #[cfg(feature = "expr-function_call")]
+            UserFunctionCall(v) => v.fmt(f),
             Variable(v) => v.fmt(f),
             Noop(v) => v.fmt(f),
             #[cfg(feature = "expr-unary")]
@@ -410,6 +426,13 @@ impl From<FunctionCall> for Expr {
     }
 }
 
+#[cfg(feature = "expr-function_call")]
+impl From<UserFunctionCall> for Expr {
+    fn from(function_call: UserFunctionCall) -> Self {
+        Expr::UserFunctionCall(function_call)
+    }
+}
+
 impl From<Variable> for Expr {
     fn from(variable: Variable) -> Self {
         Expr::Variable(variable)
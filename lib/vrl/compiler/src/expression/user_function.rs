@@ -0,0 +1,429 @@
+use std::{collections::HashMap, fmt, path::PathBuf};
+
+use diagnostic::{DiagnosticMessage, Label, Note, Severity, Urls};
+
+use crate::state::{TypeInfo, TypeState};
+use crate::{
+    expression::{Block, Expr, ExpressionError},
+    parser::Ident,
+    state::LocalEnv,
+    type_def::Details,
+    Context, Expression, Resolved, Span,
+};
+
+/// A call to a function defined in the VRL program itself, or in one of its imports.
+///
+/// The body of the function is compiled at each call site, with its parameters bound to the
+/// types of the arguments passed in. At runtime, the body runs in a scope of its own, so it can't
+/// see or modify the variables of the caller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunctionCall {
+    ident: Ident,
+    span: Span,
+    abort_on_error: bool,
+    parameters: Vec<Ident>,
+    arguments: Vec<Expr>,
+    body: Block,
+
+    // Spans in the body of an imported function point into a different source, so they are
+    // dropped from runtime errors.
+    imported: bool,
+}
+
+impl UserFunctionCall {
+    pub(crate) fn new(
+        ident: Ident,
+        span: Span,
+        abort_on_error: bool,
+        parameters: Vec<Ident>,
+        arguments: Vec<Expr>,
+        body: Block,
+        imported: bool,
+    ) -> Self {
+        Self {
+            ident,
+            span,
+            abort_on_error,
+            parameters,
+            arguments,
+            body,
+            imported,
+        }
+    }
+
+    /// Applies the type information of the arguments to `state`, and returns the state the body
+    /// of the function starts with, together with whether any of the arguments is fallible.
+    pub(crate) fn body_state(
+        parameters: &[Ident],
+        arguments: &[Expr],
+        state: &mut TypeState,
+    ) -> (TypeState, bool) {
+        let mut local = LocalEnv::default();
+        let mut fallible = false;
+
+        for (parameter, argument) in parameters.iter().zip(arguments) {
+            let type_def = argument.apply_type_info(state);
+            fallible |= type_def.is_fallible();
+
+            let details = Details {
+                type_def: type_def.infallible(),
+                value: argument.as_value(),
+            };
+            local.insert_variable(parameter.clone(), details);
+        }
+
+        let body_state = TypeState {
+            local,
+            external: state.external.clone(),
+        };
+
+        (body_state, fallible)
+    }
+}
+
+impl Expression for UserFunctionCall {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let mut variables = HashMap::with_capacity(self.parameters.len());
+        for (parameter, argument) in self.parameters.iter().zip(&self.arguments) {
+            variables.insert(parameter.clone(), argument.resolve(ctx)?);
+        }
+
+        let caller_variables = ctx.state_mut().replace_variables(variables);
+        let result = self.body.resolve(ctx);
+        ctx.state_mut().replace_variables(caller_variables);
+
+        result.map_err(|err| match err {
+            #[cfg(feature = "expr-abort")]
+            ExpressionError::Abort { .. } => err,
+            ExpressionError::Error {
+                message,
+                mut labels,
+                notes,
+            } => {
+                if self.imported {
+                    labels.clear();
+                }
+                labels.push(Label::primary(message.clone(), self.span));
+
+                ExpressionError::Error {
+                    message: format!(
+                        r#"function call error for "{}" at ({}:{}): {}"#,
+                        self.ident,
+                        self.span.start(),
+                        self.span.end(),
+                        message
+                    ),
+                    labels,
+                    notes,
+                }
+            }
+        })
+    }
+
+    fn type_info(&self, state: &TypeState) -> TypeInfo {
+        let mut state = state.clone();
+
+        let (body_state, arguments_fallible) =
+            Self::body_state(&self.parameters, &self.arguments, &mut state);
+        let body = self.body.type_info(&body_state);
+
+        // Variables are scoped to the function body, only changes to the external state are
+        // visible to the caller.
+        state.external = body.state.external;
+
+        let mut result = body.result;
+        if arguments_fallible {
+            result = result.fallible();
+        }
+        if self.abort_on_error {
+            result = result.infallible();
+        }
+
+        TypeInfo::new(state, result)
+    }
+}
+
+impl fmt::Display for UserFunctionCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.ident.fmt(f)?;
+        if self.abort_on_error {
+            f.write_str("!")?;
+        }
+        f.write_str("(")?;
+
+        let mut iter = self.arguments.iter().peekable();
+        while let Some(argument) = iter.next() {
+            argument.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(")")
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Error {
+    #[error("duplicate function definition")]
+    DuplicateFunction {
+        ident_span: Span,
+        ident: Ident,
+        builtin: bool,
+    },
+
+    #[error("duplicate function parameter")]
+    DuplicateParameter { parameter_span: Span, ident: Ident },
+
+    #[error("recursive function call")]
+    RecursiveCall { call_span: Span, ident: Ident },
+
+    #[error("invalid import")]
+    Import {
+        import_span: Span,
+        path: PathBuf,
+        reason: String,
+    },
+
+    #[error("{error}")]
+    Imported {
+        call_span: Span,
+        ident: Ident,
+        location: String,
+        error: Box<dyn DiagnosticMessage>,
+    },
+
+    #[error("wrong number of function arguments")]
+    WrongNumberOfArgs { arguments_span: Span, max: usize },
+
+    #[error("unknown function argument keyword")]
+    UnknownKeyword {
+        keyword_span: Span,
+        ident_span: Span,
+        parameters: Vec<Ident>,
+    },
+
+    #[error("duplicate function argument")]
+    DuplicateArgument {
+        argument_span: Span,
+        parameter: Ident,
+    },
+
+    #[error("missing function argument")]
+    MissingArgument {
+        call_span: Span,
+        parameter: Ident,
+        position: usize,
+    },
+
+    #[error("unexpected closure")]
+    UnexpectedClosure { call_span: Span, closure_span: Span },
+
+    #[error("can't abort infallible function")]
+    AbortInfallible { ident_span: Span, abort_span: Span },
+}
+
+impl DiagnosticMessage for Error {
+    fn code(&self) -> usize {
+        use Error::{
+            AbortInfallible, DuplicateArgument, DuplicateFunction, DuplicateParameter, Import,
+            Imported, MissingArgument, RecursiveCall, UnexpectedClosure, UnknownKeyword,
+            WrongNumberOfArgs,
+        };
+
+        match self {
+            DuplicateFunction { .. } => 112,
+            DuplicateParameter { .. } => 113,
+            RecursiveCall { .. } => 114,
+            Import { .. } => 115,
+            DuplicateArgument { .. } => 116,
+            Imported { error, .. } => error.code(),
+            WrongNumberOfArgs { .. } => 106,
+            MissingArgument { .. } => 107,
+            UnknownKeyword { .. } => 108,
+            UnexpectedClosure { .. } => 109,
+            AbortInfallible { .. } => 620,
+        }
+    }
+
+    fn severity(&self) -> Severity {
+        match self {
+            Error::Imported { error, .. } => error.severity(),
+            _ => Severity::Error,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Error::Imported { ident, error, .. } => {
+                format!(r#"{} (in imported function "{}")"#, error.message(), ident)
+            }
+            _ => self.to_string(),
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        use Error::{
+            AbortInfallible, DuplicateArgument, DuplicateFunction, DuplicateParameter, Import,
+            Imported, MissingArgument, RecursiveCall, UnexpectedClosure, UnknownKeyword,
+            WrongNumberOfArgs,
+        };
+
+        match self {
+            DuplicateFunction {
+                ident_span,
+                ident,
+                builtin,
+            } => {
+                let context = if *builtin {
+                    format!(r#"a built-in function named "{}" already exists"#, ident)
+                } else {
+                    format!(r#"the function "{}" is already defined"#, ident)
+                };
+
+                vec![
+                    Label::primary("duplicate function definition", ident_span),
+                    Label::context(context, ident_span),
+                ]
+            }
+
+            DuplicateParameter {
+                parameter_span,
+                ident,
+            } => vec![Label::primary(
+                format!(r#"the parameter "{}" is already defined"#, ident),
+                parameter_span,
+            )],
+
+            RecursiveCall { call_span, ident } => vec![
+                Label::primary("recursive function call", call_span),
+                Label::context(
+                    format!(r#"the function "{}" can't call itself"#, ident),
+                    call_span,
+                ),
+            ],
+
+            Import {
+                import_span,
+                path,
+                reason,
+            } => vec![
+                Label::primary(format!("unable to import {:?}", path), import_span),
+                Label::context(reason, import_span),
+            ],
+
+            Imported {
+                call_span,
+                location,
+                error,
+                ..
+            } => error
+                .labels()
+                .into_iter()
+                .map(|label| {
+                    let message = format!("{}: {}", location, label.message);
+                    if label.primary {
+                        Label::primary(message, call_span)
+                    } else {
+                        Label::context(message, call_span)
+                    }
+                })
+                .collect(),
+
+            WrongNumberOfArgs {
+                arguments_span,
+                max,
+            } => {
+                let arg = if *max == 1 { "argument" } else { "arguments" };
+
+                vec![
+                    Label::primary("too many function arguments", arguments_span),
+                    Label::context(
+                        format!("this function takes a maximum of {} {}", max, arg),
+                        arguments_span,
+                    ),
+                ]
+            }
+
+            UnknownKeyword {
+                keyword_span,
+                ident_span,
+                parameters,
+            } => vec![
+                Label::primary("unknown keyword", keyword_span),
+                Label::context(
+                    format!(
+                        "this function accepts the following keywords: {}",
+                        parameters
+                            .iter()
+                            .map(|p| format!(r#""{}""#, p))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    ident_span,
+                ),
+            ],
+
+            DuplicateArgument {
+                argument_span,
+                parameter,
+            } => vec![Label::primary(
+                format!(r#"the argument "{}" is already given"#, parameter),
+                argument_span,
+            )],
+
+            MissingArgument {
+                call_span,
+                parameter,
+                position,
+            } => vec![Label::primary(
+                format!(
+                    r#"required argument missing: "{}" (position {})"#,
+                    parameter, position
+                ),
+                call_span,
+            )],
+
+            UnexpectedClosure {
+                call_span,
+                closure_span,
+            } => vec![
+                Label::primary("unexpected closure", closure_span),
+                Label::context("user-defined functions don't accept a closure", call_span),
+            ],
+
+            AbortInfallible {
+                ident_span,
+                abort_span,
+            } => vec![
+                Label::primary("this function can't fail", ident_span),
+                Label::context("remove this abort-instruction", abort_span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        use Error::{
+            AbortInfallible, DuplicateArgument, DuplicateFunction, DuplicateParameter, Import,
+            Imported, RecursiveCall, WrongNumberOfArgs,
+        };
+
+        match self {
+            WrongNumberOfArgs { .. } => vec![Note::SeeDocs(
+                "function arguments".to_owned(),
+                Urls::expression_docs_url("#arguments"),
+            )],
+            Imported { error, .. } => error.notes(),
+            DuplicateFunction { .. }
+            | DuplicateParameter { .. }
+            | RecursiveCall { .. }
+            | Import { .. }
+            | DuplicateArgument { .. } => vec![Note::SeeCodeDocs(self.code())],
+            AbortInfallible { .. } => vec![Note::SeeErrorDocs],
+            _ => vec![],
+        }
+    }
+}
@@ -107,6 +107,13 @@ impl FunctionCompileContext {
         self.config.get_custom_mut()
     }
 
+    /// Store an external context, replacing any existing context of the same type.
+    ///
+    /// The context is available to the functions compiled after this one.
+    pub fn set_external_context<T: 'static>(&mut self, data: T) {
+        self.config.set_custom(data);
+    }
+
     #[must_use]
     pub fn is_read_only_path(&self, path: &OwnedTargetPath) -> bool {
         self.config.is_read_only_path(path)
@@ -32,6 +32,7 @@ mod compile_config;
 mod compiler;
 mod context;
 mod deprecation_warning;
+mod performance;
 mod program;
 mod test_util;
 
@@ -57,6 +58,7 @@ pub(crate) use diagnostic::Span;
 pub use expression::{Expression, FunctionExpression};
 pub use function::{Function, Parameter};
 pub use paste::paste;
+pub use performance::{Cost, CostLevel};
 pub use program::{Program, ProgramInfo};
 pub use state::{TypeInfo, TypeState};
 pub use type_def::TypeDef;
@@ -0,0 +1,674 @@
+//! Static analysis of the performance of VRL programs.
+//!
+//! Before a program is compiled, its AST is walked to estimate the cost of running it on an
+//! event, and to find patterns known to be slow. These are reported as warnings when enabled
+//! through [`CompileConfig::set_performance_lints`](crate::CompileConfig::set_performance_lints).
+//!
+//! Costs are expressed in abstract units, one unit being roughly the cost of calling a simple
+//! function such as `upcase`. Only function calls are accounted for, as they dominate the run
+//! time of most programs, and closures are assumed to iterate over [`ASSUMED_ELEMENTS`] elements.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use diagnostic::{DiagnosticMessage, Label, Note, Severity, Span};
+use parser::ast::{
+    self, Assignment, AssignmentTarget, Block, Container, Expr, FunctionCall, IfStatement, Literal,
+    Node, Predicate, QueryTarget, RootExpr, Unary,
+};
+
+/// The cost of calling a function that isn't listed in [`FUNCTION_COSTS`].
+const DEFAULT_FUNCTION_COST: u64 = 1;
+
+/// The cost of the functions that are notably more expensive than a simple function call.
+const FUNCTION_COSTS: &[(&str, u64)] = &[
+    ("decrypt", 10),
+    ("encode_json", 5),
+    ("encrypt", 10),
+    ("find_enrichment_table_records", 20),
+    ("get_enrichment_table_record", 20),
+    ("ip_cidr_contains", 3),
+    ("match", 10),
+    ("match_any", 10),
+    ("match_datadog_query", 10),
+    ("parse_apache_log", 15),
+    ("parse_aws_alb_log", 15),
+    ("parse_aws_vpc_flow_log", 10),
+    ("parse_cef", 15),
+    ("parse_common_log", 15),
+    ("parse_csv", 5),
+    ("parse_glog", 15),
+    ("parse_grok", 50),
+    ("parse_json", 10),
+    ("parse_key_value", 10),
+    ("parse_klog", 15),
+    ("parse_linux_authorization", 15),
+    ("parse_logfmt", 10),
+    ("parse_nginx_log", 15),
+    ("parse_regex", 20),
+    ("parse_regex_all", 30),
+    ("parse_ruby_hash", 15),
+    ("parse_syslog", 15),
+    ("parse_timestamp", 5),
+    ("parse_url", 5),
+    ("parse_user_agent", 30),
+    ("parse_xml", 30),
+    ("replace", 5),
+    ("sha2", 5),
+    ("sha3", 5),
+    ("to_regex", 50),
+];
+
+/// The cost of each pattern tried by `parse_groks`.
+const GROK_PATTERN_COST: u64 = 20;
+
+/// The cost of each alias `parse_groks` patterns can refer to.
+const GROK_ALIAS_COST: u64 = 5;
+
+/// The number of patterns assumed for `parse_groks` calls whose patterns aren't a literal.
+const DEFAULT_GROK_PATTERNS: u64 = 5;
+
+/// `parse_groks` calls trying more patterns than this are reported.
+const MAX_GROK_PATTERNS: usize = 16;
+
+/// The number of elements closures are assumed to iterate over.
+const ASSUMED_ELEMENTS: u64 = 10;
+
+/// The functions iterating over a collection with a closure.
+const CLOSURE_FUNCTIONS: &[&str] = &["filter", "for_each", "map_keys", "map_values"];
+
+/// Closures costing at least this much per element are reported when the size of the collection
+/// they iterate over isn't bounded.
+const EXPENSIVE_CLOSURE_COST: u64 = 10;
+
+/// An estimate of the cost of running a program on an event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cost {
+    /// The cost of the calls to each function.
+    functions: BTreeMap<String, u64>,
+}
+
+impl Cost {
+    fn function(ident: &str, units: u64) -> Self {
+        Self {
+            functions: BTreeMap::from([(ident.to_owned(), units)]),
+        }
+    }
+
+    /// The estimated number of units needed to run the program.
+    #[must_use]
+    pub fn units(&self) -> u64 {
+        self.functions
+            .values()
+            .fold(0, |total, units| total.saturating_add(*units))
+    }
+
+    #[must_use]
+    pub fn level(&self) -> CostLevel {
+        match self.units() {
+            0..=49 => CostLevel::Low,
+            50..=199 => CostLevel::Moderate,
+            _ => CostLevel::High,
+        }
+    }
+
+    /// Returns the `count` functions contributing the most to the cost, most expensive first.
+    #[must_use]
+    pub fn top_functions(&self, count: usize) -> Vec<(&str, u64)> {
+        let mut functions = self
+            .functions
+            .iter()
+            .map(|(ident, units)| (ident.as_str(), *units))
+            .collect::<Vec<_>>();
+        functions.sort_by(|(_, a), (_, b)| b.cmp(a));
+        functions.truncate(count);
+
+        functions
+    }
+
+    fn add(&mut self, other: Cost) {
+        for (ident, units) in other.functions {
+            let total = self.functions.entry(ident).or_default();
+            *total = total.saturating_add(units);
+        }
+    }
+
+    fn scaled(mut self, factor: u64) -> Self {
+        for units in self.functions.values_mut() {
+            *units = units.saturating_mul(factor);
+        }
+        self
+    }
+}
+
+impl fmt::Display for Cost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} units ({})", self.units(), self.level())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CostLevel {
+    Low,
+    Moderate,
+    High,
+}
+
+impl fmt::Display for CostLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CostLevel::Low => f.write_str("low"),
+            CostLevel::Moderate => f.write_str("moderate"),
+            CostLevel::High => f.write_str("high"),
+        }
+    }
+}
+
+pub(crate) struct Analysis {
+    pub(crate) cost: Cost,
+    pub(crate) warnings: Vec<Warning>,
+}
+
+/// Estimates the cost of `program`, and finds the costly patterns it uses.
+///
+/// Functions imported from other files aren't known before compilation, calls to them are counted
+/// as simple function calls.
+pub(crate) fn analyze(program: &ast::Program) -> Analysis {
+    let mut analyzer = Analyzer::default();
+    let mut cost = Cost::default();
+
+    for node in &program.0 {
+        match node.inner() {
+            RootExpr::Expr(expr) => cost.add(analyzer.expr(expr)),
+            RootExpr::FunctionDefinition(definition) => {
+                analyzer.function_definition(definition.inner());
+            }
+            RootExpr::Import(_) | RootExpr::Error(_) => {}
+        }
+    }
+
+    Analysis {
+        cost,
+        warnings: analyzer.warnings,
+    }
+}
+
+#[derive(Default)]
+struct Analyzer {
+    warnings: Vec<Warning>,
+
+    /// The cost of the body of the functions defined in the program, added to each call.
+    user_functions: HashMap<String, Cost>,
+
+    /// The values parsed by `parse_json` that weren't assigned since, with the span of the call
+    /// that parsed them.
+    parsed_json: HashMap<String, Span>,
+}
+
+impl Analyzer {
+    fn function_definition(&mut self, definition: &ast::FunctionDefinition) {
+        // The body runs with its own variables, values parsed by the caller are unrelated.
+        let parsed_json = std::mem::take(&mut self.parsed_json);
+        let cost = self.block(definition.block.inner());
+        self.parsed_json = parsed_json;
+
+        self.user_functions
+            .insert(definition.ident.inner().to_string(), cost);
+    }
+
+    fn expr(&mut self, node: &Node<Expr>) -> Cost {
+        match node.inner() {
+            Expr::Literal(_) | Expr::Variable(_) => Cost::default(),
+            Expr::Container(container) => self.container(container.inner()),
+            Expr::IfStatement(statement) => self.if_statement(statement.inner()),
+            Expr::Op(op) => {
+                let ast::Op(lhs, _, rhs) = op.inner();
+                let mut cost = self.expr(lhs);
+                cost.add(self.expr(rhs));
+                cost
+            }
+            Expr::Assignment(assignment) => self.assignment(assignment.inner()),
+            Expr::Query(query) => {
+                let target = &query.inner().target;
+                match target.inner() {
+                    QueryTarget::FunctionCall(call) => self.function_call(call, target.span()),
+                    QueryTarget::Container(container) => self.container(container),
+                    QueryTarget::Internal(_) | QueryTarget::External(_) => Cost::default(),
+                }
+            }
+            Expr::FunctionCall(call) => self.function_call(call.inner(), call.span()),
+            Expr::Unary(unary) => match unary.inner() {
+                Unary::Not(not) => self.expr(not.inner().expr()),
+            },
+            Expr::Abort(abort) => match &abort.inner().message {
+                Some(message) => self.expr(message),
+                None => Cost::default(),
+            },
+        }
+    }
+
+    fn exprs<'a>(&mut self, nodes: impl IntoIterator<Item = &'a Node<Expr>>) -> Cost {
+        let mut cost = Cost::default();
+        for node in nodes {
+            cost.add(self.expr(node));
+        }
+        cost
+    }
+
+    fn block(&mut self, block: &Block) -> Cost {
+        self.exprs(&block.0)
+    }
+
+    fn container(&mut self, container: &Container) -> Cost {
+        match container {
+            Container::Group(group) => self.expr(&group.inner().0),
+            Container::Block(block) => self.block(block.inner()),
+            Container::Array(array) => self.exprs(array.inner().iter()),
+            Container::Object(object) => self.exprs(object.inner().values()),
+        }
+    }
+
+    fn if_statement(&mut self, statement: &IfStatement) -> Cost {
+        let mut cost = match statement.predicate.inner() {
+            Predicate::One(expr) => self.expr(expr),
+            Predicate::Many(exprs) => self.exprs(exprs),
+        };
+
+        let if_cost = self.conditionally(|analyzer| analyzer.block(statement.if_node.inner()));
+        let else_cost = match &statement.else_node {
+            Some(else_node) => self.conditionally(|analyzer| analyzer.block(else_node.inner())),
+            None => Cost::default(),
+        };
+
+        // Only one of the branches runs, the estimate assumes the most expensive one does.
+        cost.add(if if_cost.units() >= else_cost.units() {
+            if_cost
+        } else {
+            else_cost
+        });
+        cost
+    }
+
+    fn assignment(&mut self, assignment: &Assignment) -> Cost {
+        let (targets, expr) = match assignment {
+            Assignment::Single { target, expr, .. } => (vec![target], expr),
+            Assignment::Infallible { ok, err, expr, .. } => (vec![ok, err], expr),
+        };
+
+        let cost = self.expr(expr);
+        for target in targets {
+            if !matches!(target.inner(), AssignmentTarget::Noop) {
+                let target = target.to_string();
+                self.parsed_json.retain(|value, _| {
+                    !value.contains(target.as_str()) && !target.starts_with(value.as_str())
+                });
+            }
+        }
+
+        cost
+    }
+
+    fn function_call(&mut self, call: &FunctionCall, span: Span) -> Cost {
+        let ident = call.ident.inner().to_string();
+        let mut cost = self.exprs(call.arguments.iter().map(|argument| &argument.inner().expr));
+
+        if let Some(body) = self.user_functions.get(&ident) {
+            cost.add(body.clone());
+            return cost;
+        }
+
+        cost.add(Cost::function(&ident, function_cost(&ident, call)));
+        match ident.as_str() {
+            "to_regex" => self.check_to_regex(call, span),
+            "parse_groks" => self.check_parse_groks(call, span),
+            "parse_json" => self.check_parse_json(call, span),
+            _ => {}
+        }
+
+        if let Some(closure) = &call.closure {
+            let body = self.conditionally(|analyzer| analyzer.block(closure.inner().block.inner()));
+            if CLOSURE_FUNCTIONS.contains(&ident.as_str()) {
+                self.check_closure(call, span, &body);
+            }
+            cost.add(body.scaled(ASSUMED_ELEMENTS));
+        }
+
+        cost
+    }
+
+    /// Analyzes code that may not run, such as the branch of an `if` statement. Values parsed
+    /// there aren't considered parsed afterwards, while values assigned there no longer are.
+    fn conditionally(&mut self, analyze: impl FnOnce(&mut Self) -> Cost) -> Cost {
+        let parsed_json = self.parsed_json.clone();
+        let cost = analyze(self);
+        let still_parsed = std::mem::replace(&mut self.parsed_json, parsed_json);
+        self.parsed_json
+            .retain(|value, _| still_parsed.contains_key(value));
+
+        cost
+    }
+
+    fn check_to_regex(&mut self, call: &FunctionCall, span: Span) {
+        let pattern = match argument(call, "value", 0).map(Node::inner) {
+            Some(Expr::Literal(literal)) => match literal.inner() {
+                Literal::String(template) => template.as_literal_string().map(str::to_owned),
+                Literal::RawString(string) => Some(string.clone()),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let Some(pattern) = pattern {
+            self.warnings.push(Warning::RuntimeRegex { span, pattern });
+        }
+    }
+
+    fn check_parse_groks(&mut self, call: &FunctionCall, span: Span) {
+        if let Some(patterns) = argument(call, "patterns", 1).and_then(literal_len) {
+            if patterns > MAX_GROK_PATTERNS {
+                self.warnings
+                    .push(Warning::LargeGrokPatternSet { span, patterns });
+            }
+        }
+    }
+
+    fn check_parse_json(&mut self, call: &FunctionCall, span: Span) {
+        let value = match argument(call, "value", 0) {
+            Some(value) => value.to_string(),
+            None => return,
+        };
+
+        match self.parsed_json.get(&value) {
+            Some(first_span) => self.warnings.push(Warning::RepeatedParseJson {
+                span,
+                first_span: *first_span,
+                value,
+            }),
+            None => {
+                self.parsed_json.insert(value, span);
+            }
+        }
+    }
+
+    fn check_closure(&mut self, call: &FunctionCall, span: Span, body: &Cost) {
+        let value = match argument(call, "value", 0) {
+            Some(value) => value,
+            None => return,
+        };
+
+        // Literal collections have a known size, and `slice` bounds the size of the collection.
+        let bounded = match value.inner() {
+            Expr::FunctionCall(call) => call.inner().ident.inner().as_ref() == "slice",
+            _ => literal_len(value).is_some(),
+        };
+
+        let units = body.units();
+        if !bounded && units >= EXPENSIVE_CLOSURE_COST {
+            self.warnings.push(Warning::ExpensiveClosure {
+                span,
+                value_span: value.span(),
+                units,
+            });
+        }
+    }
+}
+
+/// Estimates the cost of a call to the built-in function `ident`.
+fn function_cost(ident: &str, call: &FunctionCall) -> u64 {
+    if ident == "parse_groks" {
+        let patterns = argument(call, "patterns", 1)
+            .and_then(literal_len)
+            .map_or(DEFAULT_GROK_PATTERNS, |patterns| patterns as u64);
+        let aliases = argument(call, "aliases", 2)
+            .and_then(literal_len)
+            .unwrap_or_default() as u64;
+
+        return patterns
+            .saturating_mul(GROK_PATTERN_COST)
+            .saturating_add(aliases.saturating_mul(GROK_ALIAS_COST));
+    }
+
+    FUNCTION_COSTS
+        .iter()
+        .find(|(function, _)| *function == ident)
+        .map_or(DEFAULT_FUNCTION_COST, |(_, cost)| *cost)
+}
+
+/// Returns the argument of `call` for the parameter `keyword`, found at `position` in the
+/// parameters of the function.
+fn argument<'a>(call: &'a FunctionCall, keyword: &str, position: usize) -> Option<&'a Node<Expr>> {
+    call.arguments
+        .iter()
+        .map(Node::inner)
+        .find(|argument| {
+            argument
+                .ident
+                .as_ref()
+                .map_or(false, |ident| ident.inner().as_ref() == keyword)
+        })
+        .or_else(|| {
+            call.arguments
+                .get(position)
+                .map(Node::inner)
+                .filter(|argument| argument.ident.is_none())
+        })
+        .map(|argument| &argument.expr)
+}
+
+/// Returns the number of elements of a literal array or object.
+fn literal_len(node: &Node<Expr>) -> Option<usize> {
+    match node.inner() {
+        Expr::Container(container) => match container.inner() {
+            Container::Array(array) => Some(array.inner().iter().count()),
+            Container::Object(object) => Some(object.inner().values().count()),
+            Container::Group(_) | Container::Block(_) => None,
+        },
+        _ => None,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum Warning {
+    #[error("regex compiled at runtime")]
+    RuntimeRegex { span: Span, pattern: String },
+
+    #[error("large grok pattern set")]
+    LargeGrokPatternSet { span: Span, patterns: usize },
+
+    #[error("value parsed as JSON more than once")]
+    RepeatedParseJson {
+        span: Span,
+        first_span: Span,
+        value: String,
+    },
+
+    #[error("expensive closure over a collection of unknown size")]
+    ExpensiveClosure {
+        span: Span,
+        value_span: Span,
+        units: u64,
+    },
+}
+
+impl DiagnosticMessage for Warning {
+    fn code(&self) -> usize {
+        match self {
+            Warning::RuntimeRegex { .. } => 901,
+            Warning::LargeGrokPatternSet { .. } => 902,
+            Warning::RepeatedParseJson { .. } => 903,
+            Warning::ExpensiveClosure { .. } => 904,
+        }
+    }
+
+    fn labels(&self) -> Vec<Label> {
+        match self {
+            Warning::RuntimeRegex { span, .. } => vec![Label::primary(
+                "this regex is compiled each time the program runs",
+                span,
+            )],
+            Warning::LargeGrokPatternSet { span, patterns } => vec![Label::primary(
+                format!("each event is matched against up to {} patterns", patterns),
+                span,
+            )],
+            Warning::RepeatedParseJson {
+                span,
+                first_span,
+                value,
+            } => vec![
+                Label::primary(format!("{} is parsed again here", value), span),
+                Label::context("it was first parsed here", first_span),
+            ],
+            Warning::ExpensiveClosure {
+                span,
+                value_span,
+                units,
+            } => vec![
+                Label::primary(
+                    format!("this closure costs about {} units per element", units),
+                    span,
+                ),
+                Label::context("the size of this collection isn't bounded", value_span),
+            ],
+        }
+    }
+
+    fn notes(&self) -> Vec<Note> {
+        let mut notes = match self {
+            Warning::RuntimeRegex { pattern, .. } => Note::solution(
+                "using a regex literal, which is compiled once",
+                vec![format!("r'{}'", pattern.replace('\'', "\\'"))],
+            ),
+            Warning::LargeGrokPatternSet { .. } => vec![Note::Hint(
+                "split the patterns between several calls, picked by a cheaper check of the event"
+                    .to_owned(),
+            )],
+            Warning::RepeatedParseJson { .. } => vec![Note::Hint(
+                "assign the parsed value to a variable, and reuse it".to_owned(),
+            )],
+            Warning::ExpensiveClosure { .. } => vec![Note::Hint(
+                "bound the collection with `slice`, or move the expensive calls out of the closure"
+                    .to_owned(),
+            )],
+        };
+        notes.push(Note::SeeCodeDocs(self.code()));
+
+        notes
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze_source(source: &str) -> Analysis {
+        analyze(&parser::parse(source).unwrap())
+    }
+
+    fn codes(source: &str) -> Vec<usize> {
+        analyze_source(source)
+            .warnings
+            .iter()
+            .map(DiagnosticMessage::code)
+            .collect()
+    }
+
+    #[test]
+    fn estimates_cost() {
+        let cost = analyze_source(
+            r#"
+            .message = upcase!(.message)
+            if exists(.data) {
+                .data = parse_json!(.data)
+            } else {
+                .data = parse_xml!(.raw)
+            }
+            .tags = map_values(array!(.tags)) -> |tag| { parse_regex!(tag, r'(?P<k>\w+)') }
+            "#,
+        )
+        .cost;
+
+        // `upcase`, `exists`, the `else` branch, `array`, `map_values`, and its closure over the
+        // assumed number of elements.
+        assert_eq!(cost.units(), 1 + 1 + 30 + 1 + 1 + 20 * ASSUMED_ELEMENTS);
+        assert_eq!(cost.level(), CostLevel::High);
+        assert_eq!(
+            cost.top_functions(2),
+            vec![("parse_regex", 200), ("parse_xml", 30)]
+        );
+    }
+
+    #[test]
+    fn adds_cost_of_user_functions() {
+        let cost = analyze_source(
+            r#"
+            fn parse(value) { parse_json!(value) }
+            parse(.a)
+            parse(.b)
+            "#,
+        )
+        .cost;
+
+        assert_eq!(cost.units(), 20);
+        assert_eq!(cost.to_string(), "20 units (low)");
+    }
+
+    #[test]
+    fn reports_runtime_regex() {
+        assert_eq!(codes(r#"to_regex!("^foo")"#), vec![901]);
+        assert_eq!(codes(r#"to_regex!(.pattern)"#), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn reports_large_grok_pattern_sets() {
+        let patterns = vec![r#""%{GREEDYDATA:message}""#; MAX_GROK_PATTERNS + 1].join(", ");
+        let source = format!("parse_groks!(.message, [{}])", patterns);
+
+        assert_eq!(codes(&source), vec![902]);
+        assert_eq!(
+            codes(r#"parse_groks!(.message, ["%{GREEDYDATA:message}"])"#),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn reports_repeated_parse_json() {
+        assert_eq!(
+            codes(".a = parse_json!(.message).a; .b = parse_json!(.message).b"),
+            vec![903]
+        );
+
+        // The value changes in between.
+        assert_eq!(
+            codes(".a = parse_json!(.message); .message = \"{}\"; .b = parse_json!(.message)"),
+            Vec::<usize>::new()
+        );
+
+        // Only one of the branches runs.
+        assert_eq!(
+            codes("if .a { parse_json!(.message) } else { parse_json!(.message) }"),
+            Vec::<usize>::new()
+        );
+    }
+
+    #[test]
+    fn reports_expensive_closures_over_unbounded_collections() {
+        assert_eq!(
+            codes("for_each(array!(.items)) -> |_i, v| { parse_json!(v) }"),
+            vec![904]
+        );
+        assert_eq!(
+            codes("for_each(slice!(.items, 0, 10)) -> |_i, v| { parse_json!(v) }"),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            codes("for_each(array!(.items)) -> |_i, v| { upcase!(v) }"),
+            Vec::<usize>::new()
+        );
+    }
+}
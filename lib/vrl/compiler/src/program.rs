@@ -3,7 +3,7 @@ use lookup::OwnedTargetPath;
 use crate::state::TypeState;
 use crate::{
     expression::{Block, Resolved},
-    Context, Expression,
+    Context, Cost, Expression,
 };
 
 #[derive(Debug, Clone)]
@@ -12,6 +12,7 @@ pub struct Program {
     pub(crate) initial_state: TypeState,
     pub(crate) expressions: Block,
     pub(crate) info: ProgramInfo,
+    pub(crate) cost: Cost,
 }
 
 impl Program {
@@ -34,6 +35,12 @@ impl Program {
         &self.info
     }
 
+    /// Get an estimate of the cost of running the program on an event.
+    #[must_use]
+    pub fn cost(&self) -> &Cost {
+        &self.cost
+    }
+
     /// Resolve the program to its final [`Value`].
     ///
     /// # Errors
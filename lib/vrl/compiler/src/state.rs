@@ -193,6 +193,16 @@ impl Runtime {
         self.variables.remove(ident);
     }
 
+    /// Replaces all variables, returning the replaced ones. This gives user-defined functions a
+    /// scope of their own.
+    #[cfg(feature = "expr-function_call")]
+    pub(crate) fn replace_variables(
+        &mut self,
+        variables: HashMap<Ident, Value>,
+    ) -> HashMap<Ident, Value> {
+        std::mem::replace(&mut self.variables, variables)
+    }
+
     pub(crate) fn swap_variable(&mut self, ident: Ident, value: Value) -> Option<Value> {
         match self.variables.entry(ident) {
             Entry::Occupied(mut v) => Some(std::mem::replace(v.get_mut(), value)),
@@ -69,6 +69,11 @@ impl<T> Node<T> {
         &self.node
     }
 
+    /// Get a mutable reference to the inner node type `T`.
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.node
+    }
+
     // Consume the node, taking out the [`Span`] and inner node type `T`.
     pub fn take(self) -> (Span, T) {
         (self.span, self.node)
@@ -187,6 +192,13 @@ impl IntoIterator for Program {
 pub enum RootExpr {
     Expr(Node<Expr>),
 
+    /// A user-defined function, callable from any expression after its
+    /// definition.
+    FunctionDefinition(Node<FunctionDefinition>),
+
+    /// An import of the function definitions in another VRL file.
+    Import(Node<Import>),
+
     /// A special expression that is returned if a given expression could not be
     /// parsed. This allows the parser to continue on to the next expression.
     Error(Error),
@@ -194,10 +206,12 @@ pub enum RootExpr {
 
 impl fmt::Debug for RootExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use RootExpr::{Error, Expr};
+        use RootExpr::{Error, Expr, FunctionDefinition, Import};
 
         let value = match self {
             Expr(v) => format!("{:?}", v),
+            FunctionDefinition(v) => format!("{:?}", v),
+            Import(v) => format!("{:?}", v),
             Error(v) => format!("{:?}", v),
         };
 
@@ -207,15 +221,82 @@ impl fmt::Debug for RootExpr {
 
 impl fmt::Display for RootExpr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use RootExpr::{Error, Expr};
+        use RootExpr::{Error, Expr, FunctionDefinition, Import};
 
         match self {
             Expr(v) => v.fmt(f),
+            FunctionDefinition(v) => v.fmt(f),
+            Import(v) => v.fmt(f),
             Error(v) => v.fmt(f),
         }
     }
 }
 
+// -----------------------------------------------------------------------------
+// function definition
+// -----------------------------------------------------------------------------
+
+/// A user-defined function.
+///
+/// The body of the function is type checked at each call site, using the type
+/// definitions of the arguments passed to it.
+#[derive(Clone, PartialEq)]
+pub struct FunctionDefinition {
+    pub ident: Node<Ident>,
+    pub parameters: Vec<Node<Ident>>,
+    pub block: Node<Block>,
+}
+
+impl fmt::Display for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "fn {}(", self.ident)?;
+
+        let mut iter = self.parameters.iter().peekable();
+        while let Some(parameter) = iter.next() {
+            parameter.fmt(f)?;
+
+            if iter.peek().is_some() {
+                f.write_str(", ")?;
+            }
+        }
+
+        f.write_str(") ")?;
+        self.block.fmt(f)
+    }
+}
+
+impl fmt::Debug for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "FunctionDefinition({:?}({:?}) {:?})",
+            self.ident, self.parameters, self.block
+        )
+    }
+}
+
+// -----------------------------------------------------------------------------
+// import
+// -----------------------------------------------------------------------------
+
+/// An import of another VRL file, resolved at compile-time.
+#[derive(Clone, PartialEq)]
+pub struct Import {
+    pub path: Node<String>,
+}
+
+impl fmt::Display for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "import {:?}", self.path.as_ref())
+    }
+}
+
+impl fmt::Debug for Import {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Import({:?})", self.path.as_ref())
+    }
+}
+
 // -----------------------------------------------------------------------------
 // expression
 // -----------------------------------------------------------------------------
@@ -526,6 +607,16 @@ impl fmt::Debug for Array {
     }
 }
 
+impl Array {
+    pub fn iter(&self) -> std::slice::Iter<'_, Node<Expr>> {
+        self.0.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, Node<Expr>> {
+        self.0.iter_mut()
+    }
+}
+
 impl IntoIterator for Array {
     type Item = Node<Expr>;
     type IntoIter = std::vec::IntoIter<Self::Item>;
@@ -568,6 +659,18 @@ impl fmt::Debug for Object {
     }
 }
 
+impl Object {
+    pub fn values(&self) -> std::collections::btree_map::Values<'_, Node<String>, Node<Expr>> {
+        self.0.values()
+    }
+
+    pub fn values_mut(
+        &mut self,
+    ) -> std::collections::btree_map::ValuesMut<'_, Node<String>, Node<Expr>> {
+        self.0.values_mut()
+    }
+}
+
 impl IntoIterator for Object {
     type Item = (Node<String>, Node<Expr>);
     type IntoIter = std::collections::btree_map::IntoIter<Node<String>, Node<Expr>>;
@@ -1164,6 +1267,15 @@ impl Not {
     pub fn take(self) -> (Node<()>, Box<Node<Expr>>) {
         (self.0, self.1)
     }
+
+    #[must_use]
+    pub fn expr(&self) -> &Node<Expr> {
+        &self.1
+    }
+
+    pub fn expr_mut(&mut self) -> &mut Node<Expr> {
+        &mut self.1
+    }
 }
 
 impl fmt::Display for Not {
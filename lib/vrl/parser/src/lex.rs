@@ -116,6 +116,8 @@ impl DiagnosticMessage for Error {
                         r#""null""#,
                         r#""true""#,
                         r#""if""#,
+                        r#""fn""#,
+                        r#""import""#,
                     ];
                     let is_any_ident = any_ident
                         .iter()
@@ -371,6 +373,8 @@ pub enum Token<S> {
     False,
     True,
     Abort,
+    Fn,
+    Import,
 
     // tokens
     Colon,
@@ -433,9 +437,9 @@ impl<S> Token<S> {
     pub(crate) fn map<R>(self, f: impl Fn(S) -> R) -> Token<R> {
         use self::Token::{
             Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
-            FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
+            FloatLiteral, Fn, FunctionCall, Identifier, If, Import, IntegerLiteral, InvalidToken,
+            LBrace, LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField,
+            Percent, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
             ReservedIdentifier, SemiColon, StringLiteral, TimestampLiteral, True, Underscore,
         };
 
@@ -465,6 +469,8 @@ impl<S> Token<S> {
             Null => Null,
             True => True,
             Abort => Abort,
+            Fn => Fn,
+            Import => Import,
 
             // tokens
             Colon => Colon,
@@ -502,9 +508,9 @@ where
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use self::Token::{
             Abort, Ampersand, Arrow, Bang, Colon, Comma, Dot, Else, Equals, Escape, False,
-            FloatLiteral, FunctionCall, Identifier, If, IntegerLiteral, InvalidToken, LBrace,
-            LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField, Percent,
-            Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
+            FloatLiteral, Fn, FunctionCall, Identifier, If, Import, IntegerLiteral, InvalidToken,
+            LBrace, LBracket, LParen, LQuery, MergeEquals, Newline, Null, Operator, PathField,
+            Percent, Question, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral,
             ReservedIdentifier, SemiColon, StringLiteral, TimestampLiteral, True, Underscore,
         };
 
@@ -528,6 +534,8 @@ where
             Null => "Null",
             True => "True",
             Abort => "Abort",
+            Fn => "Fn",
+            Import => "Import",
 
             // tokens
             Colon => "Colon",
@@ -1126,6 +1134,8 @@ impl<'input> Lexer<'input> {
 
         let token = if self.test_peek(|ch| ch == '(' || ch == '!') {
             Token::FunctionCall(ident)
+        } else if let Some(keyword) = contextual_keyword(ident, &self.input[end..]) {
+            keyword
         } else {
             Token::ident(ident)
         };
@@ -1257,6 +1267,26 @@ impl<'input> Lexer<'input> {
 // generic helpers
 // -----------------------------------------------------------------------------
 
+/// Returns the keyword token of `fn` and `import`, if they start a function definition or an
+/// import respectively.
+///
+/// These keywords were introduced after programs could already use them as identifiers, as in
+/// `import = .x`, so they are only recognized when followed by a function name and its opening
+/// parenthesis, or by a string literal.
+fn contextual_keyword<'input>(ident: &str, rest: &str) -> Option<Token<&'input str>> {
+    let rest = rest.trim_start_matches(|ch| ch == ' ' || ch == '\t');
+
+    match ident {
+        "fn" if rest.starts_with(is_ident_start)
+            && rest.trim_start_matches(is_ident_continue).starts_with('(') =>
+        {
+            Some(Token::Fn)
+        }
+        "import" if rest.starts_with('"') => Some(Token::Import),
+        _ => None,
+    }
+}
+
 fn is_ident_start(ch: char) -> bool {
     matches!(ch, '@' | '_' | 'a'..='z' | 'A'..='Z')
 }
@@ -1328,9 +1358,9 @@ mod test {
 
     use super::*;
     use crate::lex::Token::{
-        Arrow, Bang, Colon, Comma, Dot, Else, Equals, FloatLiteral, FunctionCall, Identifier, If,
-        IntegerLiteral, LBrace, LBracket, LParen, LQuery, Newline, Operator, PathField, Percent,
-        RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral, StringLiteral,
+        Arrow, Bang, Colon, Comma, Dot, Else, Equals, FloatLiteral, Fn, FunctionCall, Identifier,
+        If, Import, IntegerLiteral, LBrace, LBracket, LParen, LQuery, Newline, Operator, PathField,
+        Percent, RBrace, RBracket, RParen, RQuery, RawStringLiteral, RegexLiteral, StringLiteral,
         TimestampLiteral, True,
     };
 
@@ -2238,4 +2268,63 @@ mod test {
             ],
         );
     }
+
+    #[test]
+    fn function_definition() {
+        test(
+            data("fn add(a, b) { a + b }"),
+            vec![
+                ("~~                    ", Fn),
+                ("   ~~~                ", FunctionCall("add")),
+                ("      ~               ", LParen),
+                ("       ~              ", Identifier("a")),
+                ("        ~             ", Comma),
+                ("          ~           ", Identifier("b")),
+                ("           ~          ", RParen),
+                ("             ~        ", LBrace),
+                ("               ~      ", Identifier("a")),
+                ("                 ~    ", Operator("+")),
+                ("                   ~  ", Identifier("b")),
+                ("                     ~", RBrace),
+            ],
+        );
+    }
+
+    #[test]
+    fn import() {
+        test(
+            data(r#"import "lib.vrl""#),
+            vec![
+                (r#"~~~~~~           "#, Import),
+                (
+                    r#"       ~~~~~~~~~ "#,
+                    StringLiteral(StringLiteralToken("lib.vrl")),
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn contextual_keywords() {
+        test(
+            data("fn = 1"),
+            vec![
+                ("~~    ", Identifier("fn")),
+                ("   ~  ", Equals),
+                ("     ~", IntegerLiteral(1)),
+            ],
+        );
+
+        test(
+            data("import = .fn"),
+            vec![
+                ("~~~~~~      ", Identifier("import")),
+                ("       ~    ", Equals),
+                ("         ~  ", LQuery),
+                ("         ~  ", Dot),
+                ("          ~~", Identifier("fn")),
+                ("           ~", RQuery),
+            ],
+        );
+    }
 }
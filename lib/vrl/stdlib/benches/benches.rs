@@ -16,15 +16,22 @@ criterion_group!(
               r#bool,
               ceil,
               chunks,
+              community_id,
               compact,
               contains,
+              crc32,
               decode_base64,
+              decode_gzip,
               decode_percent,
+              decode_snappy,
+              decode_zlib,
+              decode_zstd,
               decrypt,
               // TODO: Cannot pass a Path to bench_function
               //del,
               downcase,
               encode_base64,
+              encode_cef,
               encode_key_value,
               encode_json,
               encode_logfmt,
@@ -43,15 +50,21 @@ criterion_group!(
               get,
               get_env_var,
               get_hostname,
+              hmac,
               includes,
               int,
               ip_aton,
               ip_cidr_contains,
+              ip_is_link_local,
+              ip_is_loopback,
+              ip_is_multicast,
+              ip_is_private,
               ip_ntoa,
               ip_ntop,
               ip_pton,
               ip_subnet,
               ip_to_ipv6,
+              ip_version,
               ipv6_to_ipv4,
               is_array,
               is_boolean,
@@ -79,11 +92,13 @@ criterion_group!(
               r#mod,
               // TODO: value is dynamic so we cannot assert equality
               //now,
+              murmur3,
               object,
               parse_apache_log,
               parse_aws_alb_log,
               parse_aws_cloudwatch_log_subscription_message,
               parse_aws_vpc_flow_log,
+              parse_cef,
               parse_common_log,
               parse_csv,
               parse_duration,
@@ -94,6 +109,7 @@ criterion_group!(
               parse_klog,
               parse_int,
               parse_json,
+              parse_leef,
               parse_nginx_log,
               parse_query_string,
               parse_regex,
@@ -105,12 +121,15 @@ criterion_group!(
               parse_url,
               parse_user_agent,
               parse_xml,
+              port_name,
+              protocol_name,
               push,
               redact,
               remove,
               replace,
               reverse_dns,
               round,
+              seahash,
               set,
               sha1,
               sha2,
@@ -142,6 +161,7 @@ criterion_group!(
               // TODO: value is dynamic so we cannot assert equality
               //uuidv4,
               upcase,
+              xxhash,
 );
 criterion_main!(benches);
 
@@ -226,6 +246,21 @@ bench_function! {
     }
 }
 
+bench_function! {
+    community_id => vrl_stdlib::CommunityId;
+
+    tcp {
+        args: func_args![
+            source_ip: "128.232.110.120",
+            destination_ip: "66.35.250.204",
+            source_port: 34855,
+            destination_port: 80,
+            protocol: 6,
+        ],
+        want: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+    }
+}
+
 bench_function! {
     compact => vrl_stdlib::Compact;
 
@@ -258,6 +293,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    crc32 => vrl_stdlib::Crc32;
+
+    default {
+        args: func_args![value: "foo"],
+        want: Ok(2_356_372_769_i64)
+    }
+}
+
 bench_function! {
     decode_base64 => vrl_stdlib::DecodeBase64;
 
@@ -267,6 +311,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    decode_gzip => vrl_stdlib::DecodeGzip;
+
+    literal {
+        args: func_args![value: Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x4b\xcb\xcf\x07\x00\x21\x65\x73\x8c\x03\x00\x00\x00")],
+        want: Ok("foo"),
+    }
+}
+
 bench_function! {
     decode_percent => vrl_stdlib::DecodePercent;
 
@@ -276,6 +329,33 @@ bench_function! {
     }
 }
 
+bench_function! {
+    decode_snappy => vrl_stdlib::DecodeSnappy;
+
+    literal {
+        args: func_args![value: Bytes::from_static(b"\x03\x08\x66\x6f\x6f")],
+        want: Ok("foo"),
+    }
+}
+
+bench_function! {
+    decode_zlib => vrl_stdlib::DecodeZlib;
+
+    literal {
+        args: func_args![value: Bytes::from_static(b"\x78\x9c\x4b\xcb\xcf\x07\x00\x02\x82\x01\x45")],
+        want: Ok("foo"),
+    }
+}
+
+bench_function! {
+    decode_zstd => vrl_stdlib::DecodeZstd;
+
+    literal {
+        args: func_args![value: Bytes::from_static(b"\x28\xb5\x2f\xfd\x20\x03\x19\x00\x00\x66\x6f\x6f")],
+        want: Ok("foo"),
+    }
+}
+
 bench_function! {
     downcase => vrl_stdlib::Downcase;
 
@@ -294,6 +374,27 @@ bench_function! {
     }
 }
 
+bench_function! {
+    encode_cef => vrl_stdlib::EncodeCef;
+
+    literal {
+        args: func_args![
+            value: value!({
+                "deviceVendor": "Security",
+                "deviceProduct": "threatmanager",
+                "deviceVersion": "1.0",
+                "deviceEventClassId": 100,
+                "name": "worm successfully stopped",
+                "severity": 10,
+                "src": "10.0.0.1",
+                "dst": "2.1.2.2",
+                "spt": 1232
+            })
+        ],
+        want: Ok("CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|dst=2.1.2.2 spt=1232 src=10.0.0.1"),
+    }
+}
+
 bench_function! {
     encode_key_value => vrl_stdlib::EncodeKeyValue;
 
@@ -532,6 +633,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    hmac => vrl_stdlib::Hmac;
+
+    default {
+        args: func_args![value: "foo", key: "secret"],
+        want: Ok("773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4")
+    }
+}
+
 bench_function! {
     includes => vrl_stdlib::Includes;
 
@@ -592,6 +702,42 @@ bench_function! {
     }
 }
 
+bench_function! {
+    ip_is_link_local => vrl_stdlib::IpIsLinkLocal;
+
+    literal {
+        args: func_args![value: "fe80::1"],
+        want: Ok(true),
+    }
+}
+
+bench_function! {
+    ip_is_loopback => vrl_stdlib::IpIsLoopback;
+
+    literal {
+        args: func_args![value: "127.0.0.1"],
+        want: Ok(true),
+    }
+}
+
+bench_function! {
+    ip_is_multicast => vrl_stdlib::IpIsMulticast;
+
+    literal {
+        args: func_args![value: "224.0.0.251"],
+        want: Ok(true),
+    }
+}
+
+bench_function! {
+    ip_is_private => vrl_stdlib::IpIsPrivate;
+
+    literal {
+        args: func_args![value: "192.168.10.32"],
+        want: Ok(true),
+    }
+}
+
 bench_function! {
     ip_ntoa => vrl_stdlib::IpNtoa;
 
@@ -667,6 +813,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    ip_version => vrl_stdlib::IpVersion;
+
+    literal {
+        args: func_args![value: "2001:db8::1"],
+        want: Ok(6),
+    }
+}
+
 bench_function! {
     ipv6_to_ipv4 => vrl_stdlib::Ipv6ToIpV4;
 
@@ -1203,6 +1358,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    murmur3 => vrl_stdlib::Murmur3;
+
+    default {
+        args: func_args![value: "foo", seed: 42],
+        want: Ok(2_972_666_014_i64)
+    }
+}
+
 bench_function! {
     object => vrl_stdlib::Object;
 
@@ -1331,6 +1495,29 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_cef => vrl_stdlib::ParseCef;
+
+    literal {
+        args: func_args![
+            value: "CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232 msg=Detected a threat. No action needed",
+        ],
+        want: Ok(value!({
+            "cefVersion": "0",
+            "deviceVendor": "Security",
+            "deviceProduct": "threatmanager",
+            "deviceVersion": "1.0",
+            "deviceEventClassId": "100",
+            "name": "worm successfully stopped",
+            "severity": "10",
+            "src": "10.0.0.1",
+            "dst": "2.1.2.2",
+            "spt": "1232",
+            "msg": "Detected a threat. No action needed"
+        })),
+    }
+}
+
 bench_function! {
     parse_apache_log => vrl_stdlib::ParseApacheLog;
 
@@ -1564,6 +1751,27 @@ bench_function! {
     }
 }
 
+bench_function! {
+    parse_leef => vrl_stdlib::ParseLeef;
+
+    literal {
+        args: func_args![
+            value: "LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tsev=5\tcat=anomaly",
+        ],
+        want: Ok(value!({
+            "leefVersion": "1.0",
+            "deviceVendor": "Microsoft",
+            "deviceProduct": "MSExchange",
+            "deviceVersion": "4.0 SP1",
+            "eventId": "15345",
+            "src": "192.0.2.0",
+            "dst": "172.50.123.1",
+            "sev": "5",
+            "cat": "anomaly"
+        })),
+    }
+}
+
 bench_function! {
     parse_klog  => vrl_stdlib::ParseKlog;
 
@@ -2002,6 +2210,24 @@ bench_function! {
     }
 }
 
+bench_function! {
+    port_name => vrl_stdlib::PortName;
+
+    literal {
+        args: func_args![value: 22],
+        want: Ok("ssh"),
+    }
+}
+
+bench_function! {
+    protocol_name => vrl_stdlib::ProtocolName;
+
+    literal {
+        args: func_args![value: 6],
+        want: Ok("TCP"),
+    }
+}
+
 bench_function! {
     push => vrl_stdlib::Push;
 
@@ -2095,6 +2321,15 @@ bench_function! {
     }
 }
 
+bench_function! {
+    seahash => vrl_stdlib::Seahash;
+
+    default {
+        args: func_args![value: "to be or not to be"],
+        want: Ok(1_988_685_042_348_123_509_i64)
+    }
+}
+
 bench_function! {
     sha1 => vrl_stdlib::Sha1;
 
@@ -2510,3 +2745,17 @@ bench_function! {
         want: Ok("FOO")
     }
 }
+
+bench_function! {
+    xxhash => vrl_stdlib::Xxhash;
+
+    xxh64 {
+        args: func_args![value: "foo"],
+        want: Ok(3_728_699_739_546_630_719_i64)
+    }
+
+    xxh3_64 {
+        args: func_args![value: "", variant: "XXH3-64"],
+        want: Ok(3_244_421_341_483_603_138_i64)
+    }
+}
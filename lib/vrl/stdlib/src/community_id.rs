@@ -0,0 +1,375 @@
+use std::net::IpAddr;
+
+use ::sha1::{Digest, Sha1};
+use ::value::Value;
+use vrl::prelude::*;
+
+const ICMP: u8 = 1;
+const TCP: u8 = 6;
+const UDP: u8 = 17;
+const ICMPV6: u8 = 58;
+const SCTP: u8 = 132;
+
+fn community_id(
+    source_ip: Value,
+    destination_ip: Value,
+    source_port: Option<Value>,
+    destination_port: Option<Value>,
+    protocol: Value,
+    seed: Option<Value>,
+) -> Resolved {
+    let source_ip = parse_ip(source_ip, "source_ip")?;
+    let destination_ip = parse_ip(destination_ip, "destination_ip")?;
+    if source_ip.is_ipv4() != destination_ip.is_ipv4() {
+        return Err("source_ip and destination_ip must be of the same IP version".into());
+    }
+
+    let protocol = protocol.try_integer()?;
+    let protocol = u8::try_from(protocol)
+        .map_err(|_| format!("invalid protocol {}: must be between 0 and 255", protocol))?;
+    let seed = seed
+        .map(|seed| to_u16(seed, "seed"))
+        .transpose()?
+        .unwrap_or(0);
+
+    // Ports are only part of the flow tuple for protocols that have them. For ICMP, the message
+    // type and code are given as the source and destination ports.
+    let mut ports = match (source_port, destination_port) {
+        (Some(source_port), Some(destination_port))
+            if matches!(protocol, ICMP | TCP | UDP | ICMPV6 | SCTP) =>
+        {
+            Some((
+                to_u16(source_port, "source_port")?,
+                to_u16(destination_port, "destination_port")?,
+            ))
+        }
+        _ => None,
+    };
+
+    let mut source = octets(source_ip);
+    let mut destination = octets(destination_ip);
+    let mut one_way = false;
+    if let Some((message_type, code)) = ports {
+        let counterpart = match protocol {
+            ICMP => icmp_counterpart(message_type),
+            ICMPV6 => icmpv6_counterpart(message_type),
+            _ => Some(code),
+        };
+        match counterpart {
+            Some(counterpart) => ports = Some((message_type, counterpart)),
+            None => one_way = true,
+        }
+    }
+
+    // Both directions of a flow hash to the same ID, so the endpoints are put in a canonical order.
+    let ordered = one_way
+        || match ports {
+            Some((source_port, destination_port)) => {
+                (&source, source_port) <= (&destination, destination_port)
+            }
+            None => source <= destination,
+        };
+    if !ordered {
+        std::mem::swap(&mut source, &mut destination);
+        ports = ports.map(|(source_port, destination_port)| (destination_port, source_port));
+    }
+
+    let mut hasher = Sha1::new();
+    hasher.update(seed.to_be_bytes());
+    hasher.update(source);
+    hasher.update(destination);
+    hasher.update([protocol, 0]);
+    if let Some((source_port, destination_port)) = ports {
+        hasher.update(source_port.to_be_bytes());
+        hasher.update(destination_port.to_be_bytes());
+    }
+
+    Ok(format!("1:{}", base64::encode(hasher.finalize())).into())
+}
+
+fn parse_ip(value: Value, name: &str) -> std::result::Result<IpAddr, ExpressionError> {
+    value
+        .try_bytes_utf8_lossy()?
+        .parse()
+        .map_err(|err| format!("unable to parse {}: {}", name, err).into())
+}
+
+fn to_u16(value: Value, name: &str) -> std::result::Result<u16, ExpressionError> {
+    let value = value.try_integer()?;
+    u16::try_from(value)
+        .map_err(|_| format!("invalid {} {}: must be between 0 and 65535", name, value).into())
+}
+
+fn octets(ip: IpAddr) -> Vec<u8> {
+    match ip {
+        IpAddr::V4(ip) => ip.octets().to_vec(),
+        IpAddr::V6(ip) => ip.octets().to_vec(),
+    }
+}
+
+/// Returns the type of the message answering ICMP messages of `message_type`, for the message
+/// types that are part of a request/response pair.
+fn icmp_counterpart(message_type: u16) -> Option<u16> {
+    match message_type {
+        // Echo, router, timestamp, information and address mask messages.
+        0 => Some(8),
+        8 => Some(0),
+        9 => Some(10),
+        10 => Some(9),
+        13 => Some(14),
+        14 => Some(13),
+        15 => Some(16),
+        16 => Some(15),
+        17 => Some(18),
+        18 => Some(17),
+        _ => None,
+    }
+}
+
+/// The ICMPv6 equivalent of `icmp_counterpart`.
+fn icmpv6_counterpart(message_type: u16) -> Option<u16> {
+    match message_type {
+        // Echo, multicast listener, router, neighbor, node information and home agent address
+        // discovery messages.
+        128 => Some(129),
+        129 => Some(128),
+        130 => Some(131),
+        131 => Some(130),
+        133 => Some(134),
+        134 => Some(133),
+        135 => Some(136),
+        136 => Some(135),
+        139 => Some(140),
+        140 => Some(139),
+        144 => Some(145),
+        145 => Some(144),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CommunityId;
+
+impl Function for CommunityId {
+    fn identifier(&self) -> &'static str {
+        "community_id"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "source_ip",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "destination_ip",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "source_port",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "destination_port",
+                kind: kind::INTEGER,
+                required: false,
+            },
+            Parameter {
+                keyword: "protocol",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "TCP",
+                source: r#"community_id!(source_ip: "128.232.110.120", destination_ip: "66.35.250.204", source_port: 34855, destination_port: 80, protocol: 6)"#,
+                result: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            },
+            Example {
+                title: "reverse direction",
+                source: r#"community_id!(source_ip: "66.35.250.204", destination_ip: "128.232.110.120", source_port: 80, destination_port: 34855, protocol: 6)"#,
+                result: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            },
+            Example {
+                title: "positional arguments",
+                source: r#"community_id!("128.232.110.120", "66.35.250.204", 34855, 80, 6)"#,
+                result: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let source_ip = arguments.required("source_ip");
+        let destination_ip = arguments.required("destination_ip");
+        let source_port = arguments.optional("source_port");
+        let destination_port = arguments.optional("destination_port");
+        let protocol = arguments.required("protocol");
+        let seed = arguments.optional("seed");
+
+        Ok(CommunityIdFn {
+            source_ip,
+            destination_ip,
+            source_port,
+            destination_port,
+            protocol,
+            seed,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct CommunityIdFn {
+    source_ip: Box<dyn Expression>,
+    destination_ip: Box<dyn Expression>,
+    source_port: Option<Box<dyn Expression>>,
+    destination_port: Option<Box<dyn Expression>>,
+    protocol: Box<dyn Expression>,
+    seed: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for CommunityIdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let source_ip = self.source_ip.resolve(ctx)?;
+        let destination_ip = self.destination_ip.resolve(ctx)?;
+        let source_port = self
+            .source_port
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        let destination_port = self
+            .destination_port
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+        let protocol = self.protocol.resolve(ctx)?;
+        let seed = self
+            .seed
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        community_id(
+            source_ip,
+            destination_ip,
+            source_port,
+            destination_port,
+            protocol,
+            seed,
+        )
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        community_id => CommunityId;
+
+        tcp {
+            args: func_args![source_ip: "128.232.110.120", destination_ip: "66.35.250.204", source_port: 34855, destination_port: 80, protocol: 6],
+            want: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        tcp_reversed {
+            args: func_args![source_ip: "66.35.250.204", destination_ip: "128.232.110.120", source_port: 80, destination_port: 34855, protocol: 6],
+            want: Ok("1:LQU9qZlK+B5F3KDmev6m5PMibrg="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        seed {
+            args: func_args![source_ip: "128.232.110.120", destination_ip: "66.35.250.204", source_port: 34855, destination_port: 80, protocol: 6, seed: 1],
+            want: Ok("1:3V71V58M3Ksw/yuFALMcW0LAHvc="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        udp {
+            args: func_args![source_ip: "1.2.3.4", destination_ip: "5.6.7.8", source_port: 1122, destination_port: 3344, protocol: 17],
+            want: Ok("1:0Mu9InQx6z4ZiCZM/7HXi2WMhOg="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_echo_request {
+            args: func_args![source_ip: "192.168.0.89", destination_ip: "192.168.0.1", source_port: 8, destination_port: 0, protocol: 1],
+            want: Ok("1:X0snYXpgwiv9TZtqg64sgzUn6Dk="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_echo_reply {
+            args: func_args![source_ip: "192.168.0.1", destination_ip: "192.168.0.89", source_port: 0, destination_port: 0, protocol: 1],
+            want: Ok("1:X0snYXpgwiv9TZtqg64sgzUn6Dk="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmp_one_way {
+            args: func_args![source_ip: "192.168.0.89", destination_ip: "192.168.0.1", source_port: 3, destination_port: 1, protocol: 1],
+            want: Ok("1:LXCfhMUFbYXe+CEMrQuZCbiLY3o="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        icmpv6 {
+            args: func_args![source_ip: "fe80::200:86ff:fe05:80da", destination_ip: "fe80::260:97ff:fe07:69ea", source_port: 135, destination_port: 0, protocol: 58],
+            want: Ok("1:dGHyGvjMfljg6Bppwm3bg0LO8TY="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        without_ports {
+            args: func_args![source_ip: "10.0.0.1", destination_ip: "10.0.0.2", protocol: 47],
+            want: Ok("1:+KlEHDT0vJgzs/eNmzHq0aSpRYw="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        ports_ignored {
+            args: func_args![source_ip: "10.0.0.1", destination_ip: "10.0.0.2", source_port: 1, destination_port: 2, protocol: 47],
+            want: Ok("1:+KlEHDT0vJgzs/eNmzHq0aSpRYw="),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_ip {
+            args: func_args![source_ip: "foo", destination_ip: "10.0.0.2", protocol: 6],
+            want: Err("unable to parse source_ip: invalid IP address syntax"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        mixed_ip_versions {
+            args: func_args![source_ip: "10.0.0.1", destination_ip: "::1", protocol: 6],
+            want: Err("source_ip and destination_ip must be of the same IP version"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_port {
+            args: func_args![source_ip: "10.0.0.1", destination_ip: "10.0.0.2", source_port: 65536, destination_port: 80, protocol: 6],
+            want: Err("invalid source_port 65536: must be between 0 and 65535"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_protocol {
+            args: func_args![source_ip: "10.0.0.1", destination_ip: "10.0.0.2", protocol: 256],
+            want: Err("invalid protocol 256: must be between 0 and 255"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,135 @@
+use ::value::Value;
+use flate2::read::MultiGzDecoder;
+use vrl::prelude::*;
+
+use crate::util::{max_decompressed_size, read_decompressed};
+
+fn decode_gzip(value: Value, max_size: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let max_size = max_decompressed_size(max_size)?;
+
+    read_decompressed(MultiGzDecoder::new(value.as_ref()), max_size, "gzip")
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeGzip;
+
+impl Function for DecodeGzip {
+    fn identifier(&self) -> &'static str {
+        "decode_gzip"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_size",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let max_size = arguments.optional("max_size");
+
+        Ok(DecodeGzipFn { value, max_size }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_gzip!(decode_base64!("H4sIAAAAAAACAw3LyQ3AIAwEwFZcAT05ZjkUgiUfkeg+mf8cTRr8gjxF4N5yrUMVohWVHhTR3Y0jF8fU7eX8gQ10GThG8D13Lx+GGe6BRgAAAA=="))"#,
+            result: Ok("you have successfully decoded me.congratulations.you are breathtaking."),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeGzipFn {
+    value: Box<dyn Expression>,
+    max_size: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for DecodeGzipFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let max_size = self
+            .max_size
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        decode_gzip(value, max_size)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Always fallible, the value might not be valid gzip data, or decompress to more than
+        // `max_size` bytes.
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn foo() -> Value {
+        Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x4b\xcb\xcf\x07\x00\x21\x65\x73\x8c\x03\x00\x00\x00").into()
+    }
+
+    fn foobar() -> Value {
+        // Two concatenated gzip members, as produced by appending to a gzip file.
+        Bytes::from_static(b"\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x4b\xcb\xcf\x07\x00\x21\x65\x73\x8c\x03\x00\x00\x00\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\x03\x4b\x4a\x2c\x02\x00\xaa\x8c\xff\x76\x03\x00\x00\x00").into()
+    }
+
+    test_function![
+        decode_gzip => DecodeGzip;
+
+        valid {
+            args: func_args![value: foo()],
+            want: Ok(value!("foo")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        multiple_members {
+            args: func_args![value: foobar()],
+            want: Ok(value!("foobar")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        within_max_size {
+            args: func_args![value: foo(), max_size: 3],
+            want: Ok(value!("foo")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        exceeds_max_size {
+            args: func_args![value: foo(), max_size: 2],
+            want: Err("decompressed value exceeds max_size of 2 bytes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative_max_size {
+            args: func_args![value: foo(), max_size: -1],
+            want: Err("invalid max_size -1: must not be negative"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "this is not gzip data"],
+            want: Err("unable to decode value with gzip decoder: invalid gzip header"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,133 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::max_decompressed_size;
+
+fn decode_snappy(value: Value, max_size: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let max_size = max_decompressed_size(max_size)?;
+
+    // The raw format starts with the decompressed length, so oversized values are rejected
+    // before anything is allocated for them.
+    let len = snap::raw::decompress_len(&value)
+        .map_err(|err| format!("unable to decode value with snappy decoder: {}", err))?;
+    if len > max_size {
+        return Err(format!("decompressed value exceeds max_size of {} bytes", max_size).into());
+    }
+
+    snap::raw::Decoder::new()
+        .decompress_vec(&value)
+        .map(|buf| Bytes::from(buf).into())
+        .map_err(|err| format!("unable to decode value with snappy decoder: {}", err).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeSnappy;
+
+impl Function for DecodeSnappy {
+    fn identifier(&self) -> &'static str {
+        "decode_snappy"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_size",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let max_size = arguments.optional("max_size");
+
+        Ok(DecodeSnappyFn { value, max_size }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_snappy!(decode_base64!("RvBFeW91IGhhdmUgc3VjY2Vzc2Z1bGx5IGRlY29kZWQgbWUuY29uZ3JhdHVsYXRpb25zLnlvdSBhcmUgYnJlYXRodGFraW5nLg=="))"#,
+            result: Ok("you have successfully decoded me.congratulations.you are breathtaking."),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeSnappyFn {
+    value: Box<dyn Expression>,
+    max_size: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for DecodeSnappyFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let max_size = self
+            .max_size
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        decode_snappy(value, max_size)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Always fallible, the value might not be valid snappy data, or decompress to more than
+        // `max_size` bytes.
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn foo() -> Value {
+        Bytes::from_static(b"\x03\x08\x66\x6f\x6f").into()
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(decode_snappy(value!("this is not snappy data"), None).is_err());
+    }
+
+    test_function![
+        decode_snappy => DecodeSnappy;
+
+        valid {
+            args: func_args![value: foo()],
+            want: Ok(value!("foo")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        within_max_size {
+            args: func_args![value: foo(), max_size: 3],
+            want: Ok(value!("foo")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        exceeds_max_size {
+            args: func_args![value: foo(), max_size: 2],
+            want: Err("decompressed value exceeds max_size of 2 bytes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative_max_size {
+            args: func_args![value: foo(), max_size: -1],
+            want: Err("invalid max_size -1: must not be negative"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,124 @@
+use ::value::Value;
+use flate2::read::ZlibDecoder;
+use vrl::prelude::*;
+
+use crate::util::{max_decompressed_size, read_decompressed};
+
+fn decode_zlib(value: Value, max_size: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let max_size = max_decompressed_size(max_size)?;
+
+    read_decompressed(ZlibDecoder::new(value.as_ref()), max_size, "zlib")
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeZlib;
+
+impl Function for DecodeZlib {
+    fn identifier(&self) -> &'static str {
+        "decode_zlib"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_size",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let max_size = arguments.optional("max_size");
+
+        Ok(DecodeZlibFn { value, max_size }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zlib!(decode_base64!("eJwNy8kNwCAMBMBWXAE9OWY5FIIlH5HoPpn/HE0a/II8ReDecq1DFaIVlR4U0d2NIxfH1O3l/IENdBk4RvA9dy8fv7wa8Q=="))"#,
+            result: Ok("you have successfully decoded me.congratulations.you are breathtaking."),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeZlibFn {
+    value: Box<dyn Expression>,
+    max_size: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for DecodeZlibFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let max_size = self
+            .max_size
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        decode_zlib(value, max_size)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Always fallible, the value might not be valid zlib data, or decompress to more than
+        // `max_size` bytes.
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn foo() -> Value {
+        Bytes::from_static(b"\x78\x9c\x4b\xcb\xcf\x07\x00\x02\x82\x01\x45").into()
+    }
+
+    test_function![
+        decode_zlib => DecodeZlib;
+
+        valid {
+            args: func_args![value: foo()],
+            want: Ok(value!("foo")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        within_max_size {
+            args: func_args![value: foo(), max_size: 3],
+            want: Ok(value!("foo")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        exceeds_max_size {
+            args: func_args![value: foo(), max_size: 2],
+            want: Err("decompressed value exceeds max_size of 2 bytes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative_max_size {
+            args: func_args![value: foo(), max_size: -1],
+            want: Err("invalid max_size -1: must not be negative"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "this is not zlib data"],
+            want: Err("unable to decode value with zlib decoder: corrupt deflate stream"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,126 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::{max_decompressed_size, read_decompressed};
+
+fn decode_zstd(value: Value, max_size: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let max_size = max_decompressed_size(max_size)?;
+
+    let decoder = zstd::stream::read::Decoder::new(value.as_ref())
+        .map_err(|err| format!("unable to decode value with zstd decoder: {}", err))?;
+
+    read_decompressed(decoder, max_size, "zstd")
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DecodeZstd;
+
+impl Function for DecodeZstd {
+    fn identifier(&self) -> &'static str {
+        "decode_zstd"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "max_size",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let max_size = arguments.optional("max_size");
+
+        Ok(DecodeZstdFn { value, max_size }.as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zstd!(decode_base64!("KLUv/SBGMQIAeW91IGhhdmUgc3VjY2Vzc2Z1bGx5IGRlY29kZWQgbWUuY29uZ3JhdHVsYXRpb25zLnlvdSBhcmUgYnJlYXRodGFraW5nLg=="))"#,
+            result: Ok("you have successfully decoded me.congratulations.you are breathtaking."),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct DecodeZstdFn {
+    value: Box<dyn Expression>,
+    max_size: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for DecodeZstdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let max_size = self
+            .max_size
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        decode_zstd(value, max_size)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Always fallible, the value might not be valid zstd data, or decompress to more than
+        // `max_size` bytes.
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn foo() -> Value {
+        Bytes::from_static(b"\x28\xb5\x2f\xfd\x20\x03\x19\x00\x00\x66\x6f\x6f").into()
+    }
+
+    test_function![
+        decode_zstd => DecodeZstd;
+
+        valid {
+            args: func_args![value: foo()],
+            want: Ok(value!("foo")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        within_max_size {
+            args: func_args![value: foo(), max_size: 3],
+            want: Ok(value!("foo")),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        exceeds_max_size {
+            args: func_args![value: foo(), max_size: 2],
+            want: Err("decompressed value exceeds max_size of 2 bytes"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        negative_max_size {
+            args: func_args![value: foo(), max_size: -1],
+            want: Err("invalid max_size -1: must not be negative"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "this is not zstd data"],
+            want: Err("unable to decode value with zstd decoder: Unknown frame descriptor"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+const HEADER_FIELDS: [&str; 6] = [
+    "deviceVendor",
+    "deviceProduct",
+    "deviceVersion",
+    "deviceEventClassId",
+    "name",
+    "severity",
+];
+
+fn encode_cef(value: Value) -> Resolved {
+    let mut object = value.try_object()?;
+
+    let version = match object.remove("cefVersion") {
+        Some(version) => to_string(&version, "cefVersion")?,
+        None => "0".to_owned(),
+    };
+    let mut encoded = format!("CEF:{}", escape_header(&version));
+    for field in HEADER_FIELDS {
+        let value = object
+            .remove(field)
+            .ok_or_else(|| format!("missing required field {:?}", field))?;
+        encoded.push('|');
+        encoded.push_str(&escape_header(&to_string(&value, field)?));
+    }
+    encoded.push('|');
+
+    let mut first = true;
+    for (key, value) in object {
+        if value.is_null() {
+            continue;
+        }
+        if key.is_empty() || !key.chars().all(is_key_char) {
+            return Err(format!("invalid extension key {:?}", key).into());
+        }
+        if !first {
+            encoded.push(' ');
+        }
+        first = false;
+        encoded.push_str(&key);
+        encoded.push('=');
+        encoded.push_str(&escape_extension(&to_string(&value, &key)?));
+    }
+
+    Ok(encoded.into())
+}
+
+fn to_string(value: &Value, field: &str) -> std::result::Result<String, String> {
+    match value {
+        Value::Bytes(bytes) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => Ok(value.to_string()),
+        // CEF timestamps, such as `rt`, are commonly given in milliseconds since the epoch.
+        Value::Timestamp(timestamp) => Ok(timestamp.timestamp_millis().to_string()),
+        _ => Err(format!(
+            "field {:?} has unsupported type {}",
+            field,
+            value.kind_str()
+        )),
+    }
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '[' | ']')
+}
+
+fn escape_header(value: &str) -> String {
+    value.replace('\\', r"\\").replace('|', r"\|")
+}
+
+fn escape_extension(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('=', r"\=")
+        .replace('\n', r"\n")
+        .replace('\r', r"\r")
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeCef;
+
+impl Function for EncodeCef {
+    fn identifier(&self) -> &'static str {
+        "encode_cef"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::OBJECT,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "encode to CEF",
+            source: r#"encode_cef!({"deviceVendor": "Security", "deviceProduct": "threatmanager", "deviceVersion": "1.0", "deviceEventClassId": 100, "name": "worm successfully stopped", "severity": 10, "src": "10.0.0.1", "spt": 1232})"#,
+            result: Ok(
+                "CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|spt=1232 src=10.0.0.1",
+            ),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(EncodeCefFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct EncodeCefFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for EncodeCefFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        encode_cef(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        // Fallible, required header fields might be missing and fields might not be scalars.
+        TypeDef::bytes().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use vector_common::btreemap;
+
+    use super::*;
+
+    fn header() -> BTreeMap<String, Value> {
+        btreemap! {
+            "deviceVendor" => "Security",
+            "deviceProduct" => "threatmanager",
+            "deviceVersion" => "1.0",
+            "deviceEventClassId" => 100,
+            "name" => "worm successfully stopped",
+            "severity" => 10,
+        }
+    }
+
+    fn with(fields: BTreeMap<String, Value>) -> Value {
+        let mut object = header();
+        object.extend(fields);
+        object.into()
+    }
+
+    test_function![
+        encode_cef => EncodeCef;
+
+        header_only {
+            args: func_args![value: header()],
+            want: Ok("CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        extension {
+            args: func_args![value: with(btreemap! {
+                "cefVersion" => 1,
+                "src" => "10.0.0.1",
+                "spt" => 1232,
+                "rt" => Utc.ymd(2022, 10, 19).and_hms(12, 0, 0),
+                "act" => Value::Null,
+            })],
+            want: Ok("CEF:1|Security|threatmanager|1.0|100|worm successfully stopped|10|rt=1666180800000 spt=1232 src=10.0.0.1"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        escaping {
+            args: func_args![value: with(btreemap! {
+                "deviceVendor" => r"security|corp\",
+                "msg" => "a=b\\c\nd",
+                "cs1" => "guest",
+                "cs1Label" => "Role",
+            })],
+            want: Ok(r"CEF:0|security\|corp\\|threatmanager|1.0|100|worm successfully stopped|10|cs1=guest cs1Label=Role msg=a\=b\\c\nd"),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        missing_header {
+            args: func_args![value: value!({deviceVendor: "Security"})],
+            want: Err(r#"missing required field "deviceProduct""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        invalid_key {
+            args: func_args![value: with(btreemap! { "source ip" => "10.0.0.1" })],
+            want: Err(r#"invalid extension key "source ip""#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+
+        unsupported_type {
+            args: func_args![value: with(btreemap! { "src" => vec!["10.0.0.1"] })],
+            want: Err(r#"field "src" has unsupported type array"#),
+            tdef: TypeDef::bytes().fallible(),
+        }
+    ];
+}
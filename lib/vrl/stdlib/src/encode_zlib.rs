@@ -0,0 +1,153 @@
+use std::io::Write;
+
+use ::value::Value;
+use flate2::{write::ZlibEncoder, Compression};
+use vrl::prelude::*;
+
+const DEFAULT_COMPRESSION_LEVEL: i64 = 6;
+const MAX_COMPRESSION_LEVEL: i64 = 9;
+
+fn encode_zlib(value: Value, compression_level: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let compression_level = match compression_level {
+        Some(level) => level.try_integer()?,
+        None => DEFAULT_COMPRESSION_LEVEL,
+    };
+    if !(0..=MAX_COMPRESSION_LEVEL).contains(&compression_level) {
+        return Err(format!(
+            "invalid compression level {}: must be between 0 and {} (inclusive)",
+            compression_level, MAX_COMPRESSION_LEVEL
+        )
+        .into());
+    }
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(compression_level as u32));
+    encoder
+        .write_all(&value)
+        .expect("writing to a vector never fails");
+    let buf = encoder.finish().expect("writing to a vector never fails");
+
+    Ok(Bytes::from(buf).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeZlib;
+
+impl Function for EncodeZlib {
+    fn identifier(&self) -> &'static str {
+        "encode_zlib"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compression_level",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let compression_level = arguments.optional("compression_level");
+
+        if let Some(literal) = compression_level
+            .as_ref()
+            .and_then(|level| level.as_value())
+        {
+            if let Some(level) = literal.as_integer() {
+                if !(0..=MAX_COMPRESSION_LEVEL).contains(&level) {
+                    return Err(vrl::function::Error::InvalidArgument {
+                        keyword: "compression_level",
+                        value: literal,
+                        error: r#""compression_level" must be between 0 and 9"#,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        Ok(EncodeZlibFn {
+            value,
+            compression_level,
+        }
+        .as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zlib!(encode_zlib("please encode me"))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeZlibFn {
+    value: Box<dyn Expression>,
+    compression_level: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for EncodeZlibFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let compression_level = self
+            .compression_level
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        encode_zlib(value, compression_level)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        let not_literal = self
+            .compression_level
+            .as_ref()
+            .map_or(false, |level| level.as_value().is_none());
+
+        TypeDef::bytes().with_fallibility(not_literal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use flate2::read::ZlibDecoder;
+    use std::io::Read;
+
+    use super::*;
+
+    fn decode(value: Value) -> String {
+        let mut buf = String::new();
+        ZlibDecoder::new(value.try_bytes().unwrap().as_ref())
+            .read_to_string(&mut buf)
+            .unwrap();
+        buf
+    }
+
+    #[test]
+    fn roundtrip() {
+        for level in [None, Some(value!(0)), Some(value!(1)), Some(value!(9))] {
+            let encoded = encode_zlib(value!("you have successfully decoded me"), level).unwrap();
+            assert_eq!(decode(encoded), "you have successfully decoded me");
+        }
+    }
+
+    #[test]
+    fn invalid_compression_level() {
+        assert!(encode_zlib(value!("foo"), Some(value!(10))).is_err());
+        assert!(encode_zlib(value!("foo"), Some(value!(-1))).is_err());
+    }
+}
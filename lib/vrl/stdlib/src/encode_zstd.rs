@@ -0,0 +1,142 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+const DEFAULT_COMPRESSION_LEVEL: i64 = 3;
+const MIN_COMPRESSION_LEVEL: i64 = 1;
+const MAX_COMPRESSION_LEVEL: i64 = 21;
+
+fn encode_zstd(value: Value, compression_level: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let compression_level = match compression_level {
+        Some(level) => level.try_integer()?,
+        None => DEFAULT_COMPRESSION_LEVEL,
+    };
+    if !(MIN_COMPRESSION_LEVEL..=MAX_COMPRESSION_LEVEL).contains(&compression_level) {
+        return Err(format!(
+            "invalid compression level {}: must be between {} and {} (inclusive)",
+            compression_level, MIN_COMPRESSION_LEVEL, MAX_COMPRESSION_LEVEL
+        )
+        .into());
+    }
+
+    let buf = zstd::stream::encode_all(value.as_ref(), compression_level as i32)
+        .expect("reading from a slice never fails");
+
+    Ok(Bytes::from(buf).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct EncodeZstd;
+
+impl Function for EncodeZstd {
+    fn identifier(&self) -> &'static str {
+        "encode_zstd"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "compression_level",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let compression_level = arguments.optional("compression_level");
+
+        if let Some(literal) = compression_level
+            .as_ref()
+            .and_then(|level| level.as_value())
+        {
+            if let Some(level) = literal.as_integer() {
+                if !(MIN_COMPRESSION_LEVEL..=MAX_COMPRESSION_LEVEL).contains(&level) {
+                    return Err(vrl::function::Error::InvalidArgument {
+                        keyword: "compression_level",
+                        value: literal,
+                        error: r#""compression_level" must be between 1 and 21"#,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        Ok(EncodeZstdFn {
+            value,
+            compression_level,
+        }
+        .as_expr())
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "demo string",
+            source: r#"decode_zstd!(encode_zstd("please encode me"))"#,
+            result: Ok("please encode me"),
+        }]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct EncodeZstdFn {
+    value: Box<dyn Expression>,
+    compression_level: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for EncodeZstdFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let compression_level = self
+            .compression_level
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        encode_zstd(value, compression_level)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        let not_literal = self
+            .compression_level
+            .as_ref()
+            .map_or(false, |level| level.as_value().is_none());
+
+        TypeDef::bytes().with_fallibility(not_literal)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(value: Value) -> String {
+        let buf = zstd::stream::decode_all(value.try_bytes().unwrap().as_ref()).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn roundtrip() {
+        for level in [None, Some(value!(1)), Some(value!(10)), Some(value!(21))] {
+            let encoded = encode_zstd(value!("you have successfully decoded me"), level).unwrap();
+            assert_eq!(decode(encoded), "you have successfully decoded me");
+        }
+    }
+
+    #[test]
+    fn invalid_compression_level() {
+        assert!(encode_zstd(value!("foo"), Some(value!(22))).is_err());
+        assert!(encode_zstd(value!("foo"), Some(value!(0))).is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use ::sha1::Sha1;
+use ::value::Value;
+use hmac::{
+    digest::{core_api::BlockSizeUser, Digest},
+    Mac, SimpleHmac,
+};
+use sha_2::{Sha224, Sha256, Sha384, Sha512};
+use vrl::prelude::*;
+
+fn hmac(value: Value, key: Value, algorithm: &Bytes) -> Resolved {
+    let value = value.try_bytes()?;
+    let key = key.try_bytes()?;
+    let hash = match algorithm.as_ref() {
+        b"SHA1" => encode::<Sha1>(&value, &key),
+        b"SHA-224" => encode::<Sha224>(&value, &key),
+        b"SHA-256" => encode::<Sha256>(&value, &key),
+        b"SHA-384" => encode::<Sha384>(&value, &key),
+        b"SHA-512" => encode::<Sha512>(&value, &key),
+        _ => unreachable!("enum invariant"),
+    };
+    Ok(hash.into())
+}
+
+fn algorithms() -> Vec<Value> {
+    vec![
+        value!("SHA1"),
+        value!("SHA-224"),
+        value!("SHA-256"),
+        value!("SHA-384"),
+        value!("SHA-512"),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Hmac;
+
+impl Function for Hmac {
+    fn identifier(&self) -> &'static str {
+        "hmac"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "key",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "algorithm",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default algorithm",
+                source: r#"hmac("foo", "secret")"#,
+                result: Ok("773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"),
+            },
+            Example {
+                title: "custom algorithm",
+                source: r#"hmac("foo", "secret", "SHA1")"#,
+                result: Ok("9baed91be7f58b57c824b60da7cb262b2ecafbd2"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let key = arguments.required("key");
+        let algorithm = arguments
+            .optional_enum("algorithm", &algorithms())?
+            .unwrap_or_else(|| value!("SHA-256"))
+            .try_bytes()
+            .expect("algorithm not bytes");
+
+        Ok(HmacFn {
+            value,
+            key,
+            algorithm,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HmacFn {
+    value: Box<dyn Expression>,
+    key: Box<dyn Expression>,
+    algorithm: Bytes,
+}
+
+impl FunctionExpression for HmacFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let key = self.key.resolve(ctx)?;
+
+        hmac(value, key, &self.algorithm)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().infallible()
+    }
+}
+
+#[inline]
+fn encode<D: Digest + BlockSizeUser>(value: &[u8], key: &[u8]) -> String {
+    let mut mac = SimpleHmac::<D>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(value);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        hmac => Hmac;
+
+        hmac {
+            args: func_args![value: "foo", key: "secret"],
+            want: Ok("773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_sha1 {
+            args: func_args![value: "foo", key: "secret", algorithm: "SHA1"],
+            want: Ok("9baed91be7f58b57c824b60da7cb262b2ecafbd2"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_sha224 {
+            args: func_args![value: "foo", key: "secret", algorithm: "SHA-224"],
+            want: Ok("21f62f59e04ee0d50b3546230207af9d2bf36ce2075eaa2dc50c0b37"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_sha384 {
+            args: func_args![value: "foo", key: "secret", algorithm: "SHA-384"],
+            want: Ok("0edb7068ecbf4de2c47b8819fd534333379f208f989c51018d03ee1155e4c0740a418ec220d4260eabcb2d090b16de6e"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+
+        hmac_sha512 {
+            args: func_args![value: "foo", key: "secret", algorithm: "SHA-512"],
+            want: Ok("82df7103de8d82de45e01c45fe642b5d13c6c2b47decafebc009431c665c6fa5f3d1af4e978ea1bde91426622073ebeac61a3461efd467e0971c788bc8ebdbbe"),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,124 @@
+use std::net::IpAddr;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::{parse_ip, unmap_ip};
+
+fn ip_is_link_local(value: Value) -> Resolved {
+    let is_link_local = match unmap_ip(parse_ip(value)?) {
+        IpAddr::V4(ip) => ip.is_link_local(),
+        // Unicast link-local addresses, fe80::/10.
+        IpAddr::V6(ip) => ip.segments()[0] & 0xffc0 == 0xfe80,
+    };
+
+    Ok(is_link_local.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpIsLinkLocal;
+
+impl Function for IpIsLinkLocal {
+    fn identifier(&self) -> &'static str {
+        "ip_is_link_local"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "IPv4 link-local address",
+                source: r#"ip_is_link_local!("169.254.10.1")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "IPv6 link-local address",
+                source: r#"ip_is_link_local!("fe80::1")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "other address",
+                source: r#"ip_is_link_local!("192.168.10.32")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IpIsLinkLocalFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpIsLinkLocalFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IpIsLinkLocalFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.value.resolve(ctx).and_then(ip_is_link_local)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        ip_is_link_local => IpIsLinkLocal;
+
+        ipv4 {
+            args: func_args![value: "169.254.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_other {
+            args: func_args![value: "169.253.0.1"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6 {
+            args: func_args![value: "fe80::200:86ff:fe05:80da"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6_range {
+            args: func_args![value: "febf::1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6_other {
+            args: func_args![value: "fec0::1"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "foo"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
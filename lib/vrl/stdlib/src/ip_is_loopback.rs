@@ -0,0 +1,129 @@
+use std::net::IpAddr;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::{parse_ip, unmap_ip};
+
+fn ip_is_loopback(value: Value) -> Resolved {
+    let is_loopback = match unmap_ip(parse_ip(value)?) {
+        IpAddr::V4(ip) => ip.is_loopback(),
+        IpAddr::V6(ip) => ip.is_loopback(),
+    };
+
+    Ok(is_loopback.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpIsLoopback;
+
+impl Function for IpIsLoopback {
+    fn identifier(&self) -> &'static str {
+        "ip_is_loopback"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "IPv4 loopback address",
+                source: r#"ip_is_loopback!("127.0.0.1")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "IPv6 loopback address",
+                source: r#"ip_is_loopback!("::1")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "other address",
+                source: r#"ip_is_loopback!("192.168.10.32")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IpIsLoopbackFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpIsLoopbackFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IpIsLoopbackFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.value.resolve(ctx).and_then(ip_is_loopback)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        ip_is_loopback => IpIsLoopback;
+
+        ipv4 {
+            args: func_args![value: "127.0.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_range {
+            args: func_args![value: "127.255.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_other {
+            args: func_args![value: "10.0.0.1"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6 {
+            args: func_args![value: "::1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6_other {
+            args: func_args![value: "::2"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_mapped {
+            args: func_args![value: "::ffff:127.0.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "foo"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
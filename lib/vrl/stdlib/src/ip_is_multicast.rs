@@ -0,0 +1,117 @@
+use std::net::IpAddr;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::{parse_ip, unmap_ip};
+
+fn ip_is_multicast(value: Value) -> Resolved {
+    let is_multicast = match unmap_ip(parse_ip(value)?) {
+        IpAddr::V4(ip) => ip.is_multicast(),
+        IpAddr::V6(ip) => ip.is_multicast(),
+    };
+
+    Ok(is_multicast.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpIsMulticast;
+
+impl Function for IpIsMulticast {
+    fn identifier(&self) -> &'static str {
+        "ip_is_multicast"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "IPv4 multicast address",
+                source: r#"ip_is_multicast!("224.0.0.251")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "IPv6 multicast address",
+                source: r#"ip_is_multicast!("ff02::fb")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "other address",
+                source: r#"ip_is_multicast!("192.168.10.32")"#,
+                result: Ok("false"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IpIsMulticastFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpIsMulticastFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IpIsMulticastFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.value.resolve(ctx).and_then(ip_is_multicast)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        ip_is_multicast => IpIsMulticast;
+
+        ipv4 {
+            args: func_args![value: "239.255.255.250"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_other {
+            args: func_args![value: "192.168.0.1"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6 {
+            args: func_args![value: "ff02::1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6_other {
+            args: func_args![value: "fe80::1"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "foo"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,136 @@
+use std::net::IpAddr;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::{parse_ip, unmap_ip};
+
+fn ip_is_private(value: Value) -> Resolved {
+    let is_private = match unmap_ip(parse_ip(value)?) {
+        IpAddr::V4(ip) => ip.is_private(),
+        // Unique local addresses, fc00::/7.
+        IpAddr::V6(ip) => ip.segments()[0] & 0xfe00 == 0xfc00,
+    };
+
+    Ok(is_private.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpIsPrivate;
+
+impl Function for IpIsPrivate {
+    fn identifier(&self) -> &'static str {
+        "ip_is_private"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "private IPv4 address",
+                source: r#"ip_is_private!("192.168.10.32")"#,
+                result: Ok("true"),
+            },
+            Example {
+                title: "public IPv4 address",
+                source: r#"ip_is_private!("8.8.8.8")"#,
+                result: Ok("false"),
+            },
+            Example {
+                title: "unique local IPv6 address",
+                source: r#"ip_is_private!("fd12:3456:789a:1::1")"#,
+                result: Ok("true"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IpIsPrivateFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpIsPrivateFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IpIsPrivateFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.value.resolve(ctx).and_then(ip_is_private)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::boolean().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        ip_is_private => IpIsPrivate;
+
+        ipv4_10 {
+            args: func_args![value: "10.0.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_172 {
+            args: func_args![value: "172.31.255.255"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_192 {
+            args: func_args![value: "192.168.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_public {
+            args: func_args![value: "172.32.0.1"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6_unique_local {
+            args: func_args![value: "fc00::1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv6_public {
+            args: func_args![value: "2001:4860:4860::8888"],
+            want: Ok(value!(false)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        ipv4_mapped {
+            args: func_args![value: "::ffff:10.0.0.1"],
+            want: Ok(value!(true)),
+            tdef: TypeDef::boolean().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "foo"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::boolean().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,106 @@
+use std::net::IpAddr;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::parse_ip;
+
+fn ip_version(value: Value) -> Resolved {
+    let version = match parse_ip(value)? {
+        IpAddr::V4(_) => 4,
+        IpAddr::V6(_) => 6,
+    };
+
+    Ok(version.into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IpVersion;
+
+impl Function for IpVersion {
+    fn identifier(&self) -> &'static str {
+        "ip_version"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "IPv4 address",
+                source: r#"ip_version!("192.168.10.32")"#,
+                result: Ok("4"),
+            },
+            Example {
+                title: "IPv6 address",
+                source: r#"ip_version!("2001:db8::1")"#,
+                result: Ok("6"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(IpVersionFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IpVersionFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for IpVersionFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.value.resolve(ctx).and_then(ip_version)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::integer().fallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        ip_version => IpVersion;
+
+        ipv4 {
+            args: func_args![value: "192.168.10.32"],
+            want: Ok(value!(4)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        ipv6 {
+            args: func_args![value: "2001:4f8:3:ba:2e0:81ff:fe22:d1f1"],
+            want: Ok(value!(6)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        ipv4_mapped {
+            args: func_args![value: "::ffff:192.168.10.32"],
+            want: Ok(value!(6)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        invalid {
+            args: func_args![value: "foo"],
+            want: Err("unable to parse IP address: invalid IP address syntax"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
@@ -42,14 +42,26 @@ mod boolean;
 mod ceil;
 #[cfg(feature = "chunks")]
 mod chunks;
+#[cfg(feature = "community_id")]
+mod community_id;
 #[cfg(feature = "compact")]
 mod compact;
 #[cfg(feature = "contains")]
 mod contains;
+#[cfg(feature = "crc32")]
+mod crc32;
 #[cfg(feature = "decode_base64")]
 mod decode_base64;
+#[cfg(feature = "decode_gzip")]
+mod decode_gzip;
 #[cfg(feature = "decode_percent")]
 mod decode_percent;
+#[cfg(feature = "decode_snappy")]
+mod decode_snappy;
+#[cfg(feature = "decode_zlib")]
+mod decode_zlib;
+#[cfg(feature = "decode_zstd")]
+mod decode_zstd;
 #[cfg(feature = "decrypt")]
 mod decrypt;
 #[cfg(feature = "del")]
@@ -58,6 +70,10 @@ mod del;
 mod downcase;
 #[cfg(feature = "encode_base64")]
 mod encode_base64;
+#[cfg(feature = "encode_cef")]
+mod encode_cef;
+#[cfg(feature = "encode_gzip")]
+mod encode_gzip;
 #[cfg(feature = "encode_json")]
 mod encode_json;
 #[cfg(feature = "encode_key_value")]
@@ -66,6 +82,10 @@ mod encode_key_value;
 mod encode_logfmt;
 #[cfg(feature = "encode_percent")]
 mod encode_percent;
+#[cfg(feature = "encode_zlib")]
+mod encode_zlib;
+#[cfg(feature = "encode_zstd")]
+mod encode_zstd;
 #[cfg(feature = "encrypt")]
 mod encrypt;
 #[cfg(feature = "ends_with")]
@@ -96,6 +116,8 @@ mod get;
 mod get_env_var;
 #[cfg(feature = "get_hostname")]
 mod get_hostname;
+#[cfg(feature = "hmac")]
+mod hmac;
 #[cfg(feature = "includes")]
 mod includes;
 #[cfg(feature = "integer")]
@@ -104,6 +126,14 @@ mod integer;
 mod ip_aton;
 #[cfg(feature = "ip_cidr_contains")]
 mod ip_cidr_contains;
+#[cfg(feature = "ip_is_link_local")]
+mod ip_is_link_local;
+#[cfg(feature = "ip_is_loopback")]
+mod ip_is_loopback;
+#[cfg(feature = "ip_is_multicast")]
+mod ip_is_multicast;
+#[cfg(feature = "ip_is_private")]
+mod ip_is_private;
 #[cfg(feature = "ip_ntoa")]
 mod ip_ntoa;
 #[cfg(feature = "ip_ntop")]
@@ -114,6 +144,8 @@ mod ip_pton;
 mod ip_subnet;
 #[cfg(feature = "ip_to_ipv6")]
 mod ip_to_ipv6;
+#[cfg(feature = "ip_version")]
+mod ip_version;
 #[cfg(feature = "ipv6_to_ipv4")]
 mod ipv6_to_ipv4;
 #[cfg(feature = "is_array")]
@@ -174,6 +206,8 @@ mod md5;
 mod merge;
 #[cfg(feature = "mod")]
 mod mod_func;
+#[cfg(feature = "murmur3")]
+mod murmur3;
 #[cfg(feature = "now")]
 mod now;
 #[cfg(feature = "object")]
@@ -188,6 +222,8 @@ mod parse_aws_alb_log;
 mod parse_aws_cloudwatch_log_subscription_message;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 mod parse_aws_vpc_flow_log;
+#[cfg(feature = "parse_cef")]
+mod parse_cef;
 #[cfg(feature = "parse_common_log")]
 mod parse_common_log;
 #[cfg(feature = "parse_csv")]
@@ -208,6 +244,8 @@ mod parse_json;
 mod parse_key_value;
 #[cfg(feature = "parse_klog")]
 mod parse_klog;
+#[cfg(feature = "parse_leef")]
+mod parse_leef;
 #[cfg(feature = "parse_linux_authorization")]
 mod parse_linux_authorization;
 #[cfg(feature = "parse_logfmt")]
@@ -234,6 +272,10 @@ mod parse_url;
 mod parse_user_agent;
 #[cfg(feature = "parse_xml")]
 mod parse_xml;
+#[cfg(feature = "port_name")]
+mod port_name;
+#[cfg(feature = "protocol_name")]
+mod protocol_name;
 #[cfg(feature = "push")]
 mod push;
 #[cfg(feature = "random_bytes")]
@@ -248,6 +290,8 @@ mod replace;
 mod reverse_dns;
 #[cfg(feature = "round")]
 mod round;
+#[cfg(feature = "seahash")]
+mod seahash;
 #[cfg(feature = "set")]
 mod set;
 #[cfg(feature = "sha1")]
@@ -310,6 +354,8 @@ mod unnest;
 mod upcase;
 #[cfg(feature = "uuid_v4")]
 mod uuid_v4;
+#[cfg(feature = "xxhash")]
+mod xxhash;
 
 // -----------------------------------------------------------------------------
 
@@ -325,14 +371,26 @@ pub use boolean::Boolean;
 pub use ceil::Ceil;
 #[cfg(feature = "chunks")]
 pub use chunks::Chunks;
+#[cfg(feature = "community_id")]
+pub use community_id::CommunityId;
 #[cfg(feature = "compact")]
 pub use compact::Compact;
 #[cfg(feature = "contains")]
 pub use contains::Contains;
+#[cfg(feature = "crc32")]
+pub use crc32::Crc32;
 #[cfg(feature = "decode_base64")]
 pub use decode_base64::DecodeBase64;
+#[cfg(feature = "decode_gzip")]
+pub use decode_gzip::DecodeGzip;
 #[cfg(feature = "decode_percent")]
 pub use decode_percent::DecodePercent;
+#[cfg(feature = "decode_snappy")]
+pub use decode_snappy::DecodeSnappy;
+#[cfg(feature = "decode_zlib")]
+pub use decode_zlib::DecodeZlib;
+#[cfg(feature = "decode_zstd")]
+pub use decode_zstd::DecodeZstd;
 #[cfg(feature = "decrypt")]
 pub use decrypt::Decrypt;
 #[cfg(feature = "del")]
@@ -341,6 +399,10 @@ pub use del::Del;
 pub use downcase::Downcase;
 #[cfg(feature = "encode_base64")]
 pub use encode_base64::EncodeBase64;
+#[cfg(feature = "encode_cef")]
+pub use encode_cef::EncodeCef;
+#[cfg(feature = "encode_gzip")]
+pub use encode_gzip::EncodeGzip;
 #[cfg(feature = "encode_json")]
 pub use encode_json::EncodeJson;
 #[cfg(feature = "encode_key_value")]
@@ -349,6 +411,10 @@ pub use encode_key_value::EncodeKeyValue;
 pub use encode_logfmt::EncodeLogfmt;
 #[cfg(feature = "encode_percent")]
 pub use encode_percent::EncodePercent;
+#[cfg(feature = "encode_zlib")]
+pub use encode_zlib::EncodeZlib;
+#[cfg(feature = "encode_zstd")]
+pub use encode_zstd::EncodeZstd;
 #[cfg(feature = "encrypt")]
 pub use encrypt::Encrypt;
 #[cfg(feature = "ends_with")]
@@ -387,6 +453,14 @@ pub use integer::Integer;
 pub use ip_aton::IpAton;
 #[cfg(feature = "ip_cidr_contains")]
 pub use ip_cidr_contains::IpCidrContains;
+#[cfg(feature = "ip_is_link_local")]
+pub use ip_is_link_local::IpIsLinkLocal;
+#[cfg(feature = "ip_is_loopback")]
+pub use ip_is_loopback::IpIsLoopback;
+#[cfg(feature = "ip_is_multicast")]
+pub use ip_is_multicast::IpIsMulticast;
+#[cfg(feature = "ip_is_private")]
+pub use ip_is_private::IpIsPrivate;
 #[cfg(feature = "ip_ntoa")]
 pub use ip_ntoa::IpNtoa;
 #[cfg(feature = "ip_ntop")]
@@ -397,6 +471,8 @@ pub use ip_pton::IpPton;
 pub use ip_subnet::IpSubnet;
 #[cfg(feature = "ip_to_ipv6")]
 pub use ip_to_ipv6::IpToIpv6;
+#[cfg(feature = "ip_version")]
+pub use ip_version::IpVersion;
 #[cfg(feature = "ipv6_to_ipv4")]
 pub use ipv6_to_ipv4::Ipv6ToIpV4;
 #[cfg(feature = "is_array")]
@@ -461,6 +537,8 @@ pub use parse_aws_alb_log::ParseAwsAlbLog;
 pub use parse_aws_cloudwatch_log_subscription_message::ParseAwsCloudWatchLogSubscriptionMessage;
 #[cfg(feature = "parse_aws_vpc_flow_log")]
 pub use parse_aws_vpc_flow_log::ParseAwsVpcFlowLog;
+#[cfg(feature = "parse_cef")]
+pub use parse_cef::ParseCef;
 #[cfg(feature = "parse_common_log")]
 pub use parse_common_log::ParseCommonLog;
 #[cfg(feature = "parse_csv")]
@@ -481,6 +559,8 @@ pub use parse_json::ParseJson;
 pub use parse_key_value::ParseKeyValue;
 #[cfg(feature = "parse_klog")]
 pub use parse_klog::ParseKlog;
+#[cfg(feature = "parse_leef")]
+pub use parse_leef::ParseLeef;
 #[cfg(feature = "parse_linux_authorization")]
 pub use parse_linux_authorization::ParseLinuxAuthorization;
 #[cfg(feature = "parse_logfmt")]
@@ -507,6 +587,10 @@ pub use parse_url::ParseUrl;
 pub use parse_user_agent::ParseUserAgent;
 #[cfg(feature = "parse_xml")]
 pub use parse_xml::ParseXml;
+#[cfg(feature = "port_name")]
+pub use port_name::PortName;
+#[cfg(feature = "protocol_name")]
+pub use protocol_name::ProtocolName;
 #[cfg(feature = "push")]
 pub use push::Push;
 #[cfg(feature = "match")]
@@ -583,11 +667,19 @@ pub use unnest::Unnest;
 pub use upcase::Upcase;
 #[cfg(feature = "uuid_v4")]
 pub use uuid_v4::UuidV4;
+#[cfg(feature = "xxhash")]
+pub use xxhash::Xxhash;
 
 #[cfg(feature = "array")]
 pub use crate::array::Array;
+#[cfg(feature = "hmac")]
+pub use crate::hmac::Hmac;
 #[cfg(feature = "md5")]
 pub use crate::md5::Md5;
+#[cfg(feature = "murmur3")]
+pub use crate::murmur3::Murmur3;
+#[cfg(feature = "seahash")]
+pub use crate::seahash::Seahash;
 #[cfg(feature = "sha1")]
 pub use crate::sha1::Sha1;
 
@@ -608,14 +700,26 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Ceil),
         #[cfg(feature = "chunks")]
         Box::new(Chunks),
+        #[cfg(feature = "community_id")]
+        Box::new(CommunityId),
         #[cfg(feature = "compact")]
         Box::new(Compact),
         #[cfg(feature = "contains")]
         Box::new(Contains),
+        #[cfg(feature = "crc32")]
+        Box::new(Crc32),
         #[cfg(feature = "decode_base64")]
         Box::new(DecodeBase64),
+        #[cfg(feature = "decode_gzip")]
+        Box::new(DecodeGzip),
         #[cfg(feature = "decode_percent")]
         Box::new(DecodePercent),
+        #[cfg(feature = "decode_snappy")]
+        Box::new(DecodeSnappy),
+        #[cfg(feature = "decode_zlib")]
+        Box::new(DecodeZlib),
+        #[cfg(feature = "decode_zstd")]
+        Box::new(DecodeZstd),
         #[cfg(feature = "decrypt")]
         Box::new(Decrypt),
         #[cfg(feature = "del")]
@@ -624,6 +728,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Downcase),
         #[cfg(feature = "encode_base64")]
         Box::new(EncodeBase64),
+        #[cfg(feature = "encode_cef")]
+        Box::new(EncodeCef),
+        #[cfg(feature = "encode_gzip")]
+        Box::new(EncodeGzip),
         #[cfg(feature = "encode_json")]
         Box::new(EncodeJson),
         #[cfg(feature = "encode_key_value")]
@@ -632,6 +740,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(EncodeLogfmt),
         #[cfg(feature = "encode_percent")]
         Box::new(EncodePercent),
+        #[cfg(feature = "encode_zlib")]
+        Box::new(EncodeZlib),
+        #[cfg(feature = "encode_zstd")]
+        Box::new(EncodeZstd),
         #[cfg(feature = "encrypt")]
         Box::new(Encrypt),
         #[cfg(feature = "ends_with")]
@@ -662,6 +774,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(GetEnvVar),
         #[cfg(feature = "get_hostname")]
         Box::new(GetHostname),
+        #[cfg(feature = "hmac")]
+        Box::new(Hmac),
         #[cfg(feature = "includes")]
         Box::new(Includes),
         #[cfg(feature = "integer")]
@@ -670,6 +784,14 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(IpAton),
         #[cfg(feature = "ip_cidr_contains")]
         Box::new(IpCidrContains),
+        #[cfg(feature = "ip_is_link_local")]
+        Box::new(IpIsLinkLocal),
+        #[cfg(feature = "ip_is_loopback")]
+        Box::new(IpIsLoopback),
+        #[cfg(feature = "ip_is_multicast")]
+        Box::new(IpIsMulticast),
+        #[cfg(feature = "ip_is_private")]
+        Box::new(IpIsPrivate),
         #[cfg(feature = "ip_ntoa")]
         Box::new(IpNtoa),
         #[cfg(feature = "ip_ntop")]
@@ -680,6 +802,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(IpSubnet),
         #[cfg(feature = "ip_to_ipv6")]
         Box::new(IpToIpv6),
+        #[cfg(feature = "ip_version")]
+        Box::new(IpVersion),
         #[cfg(feature = "ipv6_to_ipv4")]
         Box::new(Ipv6ToIpV4),
         #[cfg(feature = "is_array")]
@@ -734,6 +858,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Merge),
         #[cfg(feature = "mod")]
         Box::new(Mod),
+        #[cfg(feature = "murmur3")]
+        Box::new(Murmur3),
         #[cfg(feature = "now")]
         Box::new(Now),
         // We are not sure if this is the way we want to expose this functionality yet
@@ -750,6 +876,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseAwsCloudWatchLogSubscriptionMessage),
         #[cfg(feature = "parse_aws_vpc_flow_log")]
         Box::new(ParseAwsVpcFlowLog),
+        #[cfg(feature = "parse_cef")]
+        Box::new(ParseCef),
         #[cfg(feature = "parse_common_log")]
         Box::new(ParseCommonLog),
         #[cfg(feature = "parse_csv")]
@@ -770,6 +898,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseKeyValue),
         #[cfg(feature = "parse_klog")]
         Box::new(ParseKlog),
+        #[cfg(feature = "parse_leef")]
+        Box::new(ParseLeef),
         #[cfg(feature = "parse_linux_authorization")]
         Box::new(ParseLinuxAuthorization),
         #[cfg(feature = "parse_logfmt")]
@@ -796,6 +926,10 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ParseUserAgent),
         #[cfg(feature = "parse_xml")]
         Box::new(ParseXml),
+        #[cfg(feature = "port_name")]
+        Box::new(PortName),
+        #[cfg(feature = "protocol_name")]
+        Box::new(ProtocolName),
         #[cfg(feature = "push")]
         Box::new(Push),
         #[cfg(feature = "random_bytes")]
@@ -810,6 +944,8 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(ReverseDns),
         #[cfg(feature = "round")]
         Box::new(Round),
+        #[cfg(feature = "seahash")]
+        Box::new(Seahash),
         #[cfg(feature = "set")]
         Box::new(Set),
         #[cfg(feature = "sha1")]
@@ -872,5 +1008,7 @@ pub fn all() -> Vec<Box<dyn vrl::Function>> {
         Box::new(Upcase),
         #[cfg(feature = "uuid_v4")]
         Box::new(UuidV4),
+        #[cfg(feature = "xxhash")]
+        Box::new(Xxhash),
     ]
 }
@@ -0,0 +1,126 @@
+use std::io::Cursor;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+fn murmur3(value: Value, seed: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let seed = match seed {
+        Some(seed) => {
+            let seed = seed.try_integer()?;
+            u32::try_from(seed)
+                .map_err(|_| format!("seed {} isn't between 0 and 4294967295", seed))?
+        }
+        None => 0,
+    };
+    let hash =
+        murmur3::murmur3_32(&mut Cursor::new(value), seed).expect("reading from memory can't fail");
+    Ok(i64::from(hash).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Murmur3;
+
+impl Function for Murmur3 {
+    fn identifier(&self) -> &'static str {
+        "murmur3"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "murmur3",
+                source: r#"murmur3("foobar")"#,
+                result: Ok("2764362941"),
+            },
+            Example {
+                title: "with seed",
+                source: r#"murmur3!("foobar", seed: 42)"#,
+                result: Ok("1018276128"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let seed = arguments.optional("seed");
+
+        Ok(Murmur3Fn { value, seed }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Murmur3Fn {
+    value: Box<dyn Expression>,
+    seed: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for Murmur3Fn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let seed = self
+            .seed
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        murmur3(value, seed)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::integer().with_fallibility(self.seed.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        murmur3 => Murmur3;
+
+        murmur3 {
+            args: func_args![value: "The quick brown fox jumps over the lazy dog"],
+            want: Ok(value!(776_992_547)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        seed {
+            args: func_args![value: "foo", seed: 42],
+            want: Ok(value!(2_972_666_014_i64)),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        seed_out_of_range {
+            args: func_args![value: "foo", seed: 4_294_967_296_i64],
+            want: Err("seed 4294967296 isn't between 0 and 4294967295"),
+            tdef: TypeDef::integer().fallible(),
+        }
+
+        negative_seed {
+            args: func_args![value: "foo", seed: -1],
+            want: Err("seed -1 isn't between 0 and 4294967295"),
+            tdef: TypeDef::integer().fallible(),
+        }
+    ];
+}
@@ -0,0 +1,359 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::split_pipe_header;
+
+const HEADER_FIELDS: [&str; 7] = [
+    "cefVersion",
+    "deviceVendor",
+    "deviceProduct",
+    "deviceVersion",
+    "deviceEventClassId",
+    "name",
+    "severity",
+];
+
+fn parse_cef(value: Value, translate_custom_fields: bool) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let message = String::from_utf8_lossy(&bytes);
+
+    // Anything before the prefix, such as a syslog header, is skipped.
+    let start = message.find("CEF:").ok_or("missing CEF prefix")?;
+    let (header, extension) = split_pipe_header(&message[start + 4..], HEADER_FIELDS.len())
+        .ok_or("incomplete CEF header")?;
+
+    let mut extension = parse_extension(extension)?;
+    if translate_custom_fields {
+        translate(&mut extension);
+    }
+
+    let mut log = HEADER_FIELDS
+        .iter()
+        .zip(header)
+        .map(|(key, value)| ((*key).to_owned(), Value::from(value)))
+        .collect::<BTreeMap<_, _>>();
+    log.extend(
+        extension
+            .into_iter()
+            .map(|(key, value)| (key, Value::from(value))),
+    );
+
+    Ok(log.into())
+}
+
+/// Parses the space separated `key=value` pairs of the extension. Values can contain spaces, so a
+/// value only ends where the next key starts.
+fn parse_extension(input: &str) -> std::result::Result<BTreeMap<String, String>, String> {
+    let mut pairs = BTreeMap::new();
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+        let key_len =
+            key_len(rest).ok_or_else(|| format!("invalid CEF extension at {:?}", rest))?;
+        let key = &rest[..key_len];
+        rest = &rest[key_len + 1..];
+
+        let end = rest
+            .char_indices()
+            .find(|&(i, c)| c == ' ' && key_len_at(rest, i + 1).is_some())
+            .map_or(rest.len(), |(i, _)| i);
+        pairs.insert(key.to_owned(), unescape(rest[..end].trim_end()));
+        rest = rest[end..].trim_start();
+    }
+
+    Ok(pairs)
+}
+
+fn is_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '[' | ']')
+}
+
+/// Returns the length of the key `input` starts with, if it starts with `key=`.
+fn key_len(input: &str) -> Option<usize> {
+    let len = input.find(|c| !is_key_char(c))?;
+    (len > 0 && input[len..].starts_with('=')).then_some(len)
+}
+
+fn key_len_at(input: &str, at: usize) -> Option<usize> {
+    key_len(input[at..].trim_start_matches(' '))
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.peek() {
+            Some('\\' | '=') => unescaped.extend(chars.next()),
+            Some('n') => {
+                chars.next();
+                unescaped.push('\n');
+            }
+            Some('r') => {
+                chars.next();
+                unescaped.push('\r');
+            }
+            _ => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+/// Replaces custom fields, such as `cs1`, and their labels, such as `cs1Label`, with a field named
+/// after the label.
+fn translate(extension: &mut BTreeMap<String, String>) {
+    let labelled = extension
+        .keys()
+        .filter_map(|key| key.strip_suffix("Label"))
+        .filter(|field| extension.contains_key(*field))
+        .map(ToOwned::to_owned)
+        .collect::<Vec<_>>();
+
+    for field in labelled {
+        let label = extension
+            .remove(&format!("{}Label", field))
+            .expect("label exists");
+        let value = extension.remove(&field).expect("field exists");
+        extension.insert(label, value);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseCef;
+
+impl Function for ParseCef {
+    fn identifier(&self) -> &'static str {
+        "parse_cef"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "translate_custom_fields",
+                kind: kind::BOOLEAN,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "valid",
+                source: r#"parse_cef!("CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232")"#,
+                result: Ok(indoc! { r#"{
+                    "cefVersion": "0",
+                    "deviceEventClassId": "100",
+                    "deviceProduct": "threatmanager",
+                    "deviceVendor": "Security",
+                    "deviceVersion": "1.0",
+                    "dst": "2.1.2.2",
+                    "name": "worm successfully stopped",
+                    "severity": "10",
+                    "spt": "1232",
+                    "src": "10.0.0.1"
+                }"#}),
+            },
+            Example {
+                title: "translate custom fields",
+                source: r#"parse_cef!("CEF:0|Dev|firewall|2.1|9|Blocked|3|cs1=guest cs1Label=Role act=blocked", translate_custom_fields: true)"#,
+                result: Ok(indoc! { r#"{
+                    "Role": "guest",
+                    "act": "blocked",
+                    "cefVersion": "0",
+                    "deviceEventClassId": "9",
+                    "deviceProduct": "firewall",
+                    "deviceVendor": "Dev",
+                    "deviceVersion": "2.1",
+                    "name": "Blocked",
+                    "severity": "3"
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let translate_custom_fields = arguments
+            .optional("translate_custom_fields")
+            .unwrap_or_else(|| expr!(false));
+
+        Ok(ParseCefFn {
+            value,
+            translate_custom_fields,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseCefFn {
+    value: Box<dyn Expression>,
+    translate_custom_fields: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseCefFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let translate_custom_fields = self.translate_custom_fields.resolve(ctx)?.try_boolean()?;
+
+        parse_cef(value, translate_custom_fields)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> Collection<Field> {
+    Collection::from_parts(
+        HEADER_FIELDS
+            .iter()
+            .map(|field| ((*field).into(), Kind::bytes()))
+            .collect(),
+        Kind::bytes(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    test_function![
+        parse_cef => ParseCef;
+
+        valid {
+            args: func_args![value: "CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 spt=1232"],
+            want: Ok(btreemap! {
+                "cefVersion" => "0",
+                "deviceVendor" => "Security",
+                "deviceProduct" => "threatmanager",
+                "deviceVersion" => "1.0",
+                "deviceEventClassId" => "100",
+                "name" => "worm successfully stopped",
+                "severity" => "10",
+                "src" => "10.0.0.1",
+                "dst" => "2.1.2.2",
+                "spt" => "1232",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        syslog_prefix {
+            args: func_args![value: "Sep 29 08:26:10 host CEF:1|Security|threatmanager|1.0|100|detected|Low|"],
+            want: Ok(btreemap! {
+                "cefVersion" => "1",
+                "deviceVendor" => "Security",
+                "deviceProduct" => "threatmanager",
+                "deviceVersion" => "1.0",
+                "deviceEventClassId" => "100",
+                "name" => "detected",
+                "severity" => "Low",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        escaped_header {
+            args: func_args![value: r#"CEF:0|security\|corp|threat\\manager|1.0|100|detected a \| in message|10|"#],
+            want: Ok(btreemap! {
+                "cefVersion" => "0",
+                "deviceVendor" => "security|corp",
+                "deviceProduct" => r#"threat\manager"#,
+                "deviceVersion" => "1.0",
+                "deviceEventClassId" => "100",
+                "name" => "detected a | in message",
+                "severity" => "10",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        extension_values {
+            args: func_args![value: r#"CEF:0|Vendor|Product|1|42|Name|5|msg=Detected a threat. No action needed  filePath=C:\\Program Files\\app.exe query=a\=b|c request=line1\nline2"#],
+            want: Ok(btreemap! {
+                "cefVersion" => "0",
+                "deviceVendor" => "Vendor",
+                "deviceProduct" => "Product",
+                "deviceVersion" => "1",
+                "deviceEventClassId" => "42",
+                "name" => "Name",
+                "severity" => "5",
+                "msg" => "Detected a threat. No action needed",
+                "filePath" => r#"C:\Program Files\app.exe"#,
+                "query" => "a=b|c",
+                "request" => "line1\nline2",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        custom_fields {
+            args: func_args![value: "CEF:0|Vendor|Product|1|42|Name|5|cs1=guest cs1Label=Role cn1=3 cs2Label=Unused", translate_custom_fields: true],
+            want: Ok(btreemap! {
+                "cefVersion" => "0",
+                "deviceVendor" => "Vendor",
+                "deviceProduct" => "Product",
+                "deviceVersion" => "1",
+                "deviceEventClassId" => "42",
+                "name" => "Name",
+                "severity" => "5",
+                "Role" => "guest",
+                "cn1" => "3",
+                "cs2Label" => "Unused",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        custom_fields_untranslated {
+            args: func_args![value: "CEF:0|Vendor|Product|1|42|Name|5|cs1=guest cs1Label=Role"],
+            want: Ok(btreemap! {
+                "cefVersion" => "0",
+                "deviceVendor" => "Vendor",
+                "deviceProduct" => "Product",
+                "deviceVersion" => "1",
+                "deviceEventClassId" => "42",
+                "name" => "Name",
+                "severity" => "5",
+                "cs1" => "guest",
+                "cs1Label" => "Role",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_prefix {
+            args: func_args![value: "LEEF:1.0|Vendor|Product|1|42|"],
+            want: Err("missing CEF prefix"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        incomplete_header {
+            args: func_args![value: "CEF:0|Vendor|Product|1|42|Name"],
+            want: Err("incomplete CEF header"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_extension {
+            args: func_args![value: "CEF:0|Vendor|Product|1|42|Name|5|not an extension"],
+            want: Err(r#"invalid CEF extension at "not an extension""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
@@ -0,0 +1,306 @@
+use std::collections::BTreeMap;
+
+use ::value::Value;
+use vrl::prelude::*;
+
+use crate::util::split_pipe_header;
+
+const HEADER_FIELDS: [&str; 5] = [
+    "leefVersion",
+    "deviceVendor",
+    "deviceProduct",
+    "deviceVersion",
+    "eventId",
+];
+
+fn parse_leef(value: Value) -> Resolved {
+    let bytes = value.try_bytes()?;
+    let message = String::from_utf8_lossy(&bytes);
+
+    // Anything before the prefix, such as a syslog header, is skipped.
+    let start = message.find("LEEF:").ok_or("missing LEEF prefix")?;
+    let (header, mut attributes) = split_pipe_header(&message[start + 5..], HEADER_FIELDS.len())
+        .ok_or("incomplete LEEF header")?;
+
+    // LEEF 2.0 can declare the attribute delimiter in an extra header field, LEEF 1.0 always
+    // delimits attributes with tabs. The field may be left empty to keep tabs.
+    let mut delimiter = '\t';
+    if header[0].starts_with('2') {
+        if let Some((field, rest)) = attributes.split_once('|') {
+            if field.is_empty() {
+                attributes = rest;
+            } else if let Some(declared) = parse_delimiter(field) {
+                delimiter = declared;
+                attributes = rest;
+            }
+        }
+    }
+
+    let mut log = HEADER_FIELDS
+        .iter()
+        .zip(header)
+        .map(|(key, value)| ((*key).to_owned(), Value::from(value)))
+        .collect::<BTreeMap<_, _>>();
+
+    // The header fields, and the first value of repeated attributes, are never overwritten.
+    let mut last_key = None;
+    let mut skipping = false;
+    for attribute in attributes.split(delimiter) {
+        match attribute.split_once('=') {
+            Some((key, value)) if !key.is_empty() && !key.contains(' ') => {
+                skipping = log.contains_key(key);
+                if !skipping {
+                    log.insert(key.to_owned(), value.into());
+                    last_key = Some(key);
+                }
+            }
+            _ if attribute.is_empty() || skipping => {}
+            // The delimiter isn't escaped in values, so text that isn't an attribute belongs to
+            // the value of the previous one.
+            _ => match last_key.and_then(|key| log.get_mut(key)) {
+                Some(Value::Bytes(value)) => {
+                    let mut joined = value.to_vec();
+                    joined.extend(delimiter.to_string().as_bytes());
+                    joined.extend(attribute.as_bytes());
+                    *value = joined.into();
+                }
+                _ => return Err(format!("invalid LEEF attribute {:?}", attribute).into()),
+            },
+        }
+    }
+
+    Ok(log.into())
+}
+
+/// Parses a delimiter declared as a single character, or as a hex code such as `x09` or `0x5E`.
+fn parse_delimiter(field: &str) -> Option<char> {
+    let mut chars = field.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(c),
+        _ => {
+            let hex = field
+                .strip_prefix("0x")
+                .or_else(|| field.strip_prefix('x'))?;
+            u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ParseLeef;
+
+impl Function for ParseLeef {
+    fn identifier(&self) -> &'static str {
+        "parse_leef"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "LEEF 1.0",
+                source: r#"parse_leef!("LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tsev=5")"#,
+                result: Ok(indoc! { r#"{
+                    "deviceProduct": "MSExchange",
+                    "deviceVendor": "Microsoft",
+                    "deviceVersion": "4.0 SP1",
+                    "dst": "172.50.123.1",
+                    "eventId": "15345",
+                    "leefVersion": "1.0",
+                    "sev": "5",
+                    "src": "192.0.2.0"
+                }"#}),
+            },
+            Example {
+                title: "LEEF 2.0 with a custom delimiter",
+                source: r#"parse_leef!("LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5")"#,
+                result: Ok(indoc! { r#"{
+                    "deviceProduct": "StealthWatch",
+                    "deviceVendor": "Lancope",
+                    "deviceVersion": "1.0",
+                    "dst": "10.0.0.5",
+                    "eventId": "41",
+                    "leefVersion": "2.0",
+                    "sev": "5",
+                    "src": "10.0.1.8"
+                }"#}),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ParseLeefFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ParseLeefFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ParseLeefFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        parse_leef(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::object(inner_kind()).fallible()
+    }
+}
+
+fn inner_kind() -> Collection<Field> {
+    Collection::from_parts(
+        HEADER_FIELDS
+            .iter()
+            .map(|field| ((*field).into(), Kind::bytes()))
+            .collect(),
+        Kind::bytes(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_common::btreemap;
+
+    use super::*;
+
+    test_function![
+        parse_leef => ParseLeef;
+
+        leef_1 {
+            args: func_args![value: "LEEF:1.0|Microsoft|MSExchange|4.0 SP1|15345|src=192.0.2.0\tdst=172.50.123.1\tsev=5\tcat=anomaly\tmsg=there are spaces in this message"],
+            want: Ok(btreemap! {
+                "leefVersion" => "1.0",
+                "deviceVendor" => "Microsoft",
+                "deviceProduct" => "MSExchange",
+                "deviceVersion" => "4.0 SP1",
+                "eventId" => "15345",
+                "src" => "192.0.2.0",
+                "dst" => "172.50.123.1",
+                "sev" => "5",
+                "cat" => "anomaly",
+                "msg" => "there are spaces in this message",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leef_2_delimiter {
+            args: func_args![value: "<13>Jan 18 11:07:53 192.168.1.1 LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^url=https://example.com/?a=b"],
+            want: Ok(btreemap! {
+                "leefVersion" => "2.0",
+                "deviceVendor" => "Lancope",
+                "deviceProduct" => "StealthWatch",
+                "deviceVersion" => "1.0",
+                "eventId" => "41",
+                "src" => "10.0.1.8",
+                "dst" => "10.0.0.5",
+                "url" => "https://example.com/?a=b",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leef_2_hex_delimiter {
+            args: func_args![value: "LEEF:2.0|Vendor|Product|1.0|42|x7C|src=10.0.1.8|dst=10.0.0.5"],
+            want: Ok(btreemap! {
+                "leefVersion" => "2.0",
+                "deviceVendor" => "Vendor",
+                "deviceProduct" => "Product",
+                "deviceVersion" => "1.0",
+                "eventId" => "42",
+                "src" => "10.0.1.8",
+                "dst" => "10.0.0.5",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leef_2_default_delimiter {
+            args: func_args![value: "LEEF:2.0|Vendor|Product|1.0|42|src=10.0.1.8\tdst=10.0.0.5"],
+            want: Ok(btreemap! {
+                "leefVersion" => "2.0",
+                "deviceVendor" => "Vendor",
+                "deviceProduct" => "Product",
+                "deviceVersion" => "1.0",
+                "eventId" => "42",
+                "src" => "10.0.1.8",
+                "dst" => "10.0.0.5",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        delimiter_in_value {
+            args: func_args![value: "LEEF:2.0|Vendor|Product|1.0|42|^|msg=a^b^c^sev=1"],
+            want: Ok(btreemap! {
+                "leefVersion" => "2.0",
+                "deviceVendor" => "Vendor",
+                "deviceProduct" => "Product",
+                "deviceVersion" => "1.0",
+                "eventId" => "42",
+                "msg" => "a^b^c",
+                "sev" => "1",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        leef_2_empty_delimiter {
+            args: func_args![value: "LEEF:2.0|Vendor|Product|1.0|42||src=10.0.1.8\tdst=10.0.0.5"],
+            want: Ok(btreemap! {
+                "leefVersion" => "2.0",
+                "deviceVendor" => "Vendor",
+                "deviceProduct" => "Product",
+                "deviceVersion" => "1.0",
+                "eventId" => "42",
+                "src" => "10.0.1.8",
+                "dst" => "10.0.0.5",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        header_not_overwritten {
+            args: func_args![value: "LEEF:1.0|Vendor|Product|1.0|42|eventId=7\tdevTime=Jan 18 2023\tdevTime=Jan 19 2023\tsev=1"],
+            want: Ok(btreemap! {
+                "leefVersion" => "1.0",
+                "deviceVendor" => "Vendor",
+                "deviceProduct" => "Product",
+                "deviceVersion" => "1.0",
+                "eventId" => "42",
+                "devTime" => "Jan 18 2023",
+                "sev" => "1",
+            }),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        missing_prefix {
+            args: func_args![value: "CEF:0|Vendor|Product|1|42|Name|5|"],
+            want: Err("missing LEEF prefix"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        incomplete_header {
+            args: func_args![value: "LEEF:1.0|Vendor|Product"],
+            want: Err("incomplete LEEF header"),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+
+        invalid_attribute {
+            args: func_args![value: "LEEF:1.0|Vendor|Product|1.0|42|not an attribute"],
+            want: Err(r#"invalid LEEF attribute "not an attribute""#),
+            tdef: TypeDef::object(inner_kind()).fallible(),
+        }
+    ];
+}
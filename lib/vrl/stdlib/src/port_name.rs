@@ -0,0 +1,269 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+/// The names of the most common services of the [IANA service name and port number
+/// registry][iana] running over TCP, sorted by port.
+///
+/// [iana]: https://www.iana.org/assignments/service-names-port-numbers/service-names-port-numbers.xhtml
+const TCP_SERVICES: &[(u16, &str)] = &[
+    (1, "tcpmux"),
+    (7, "echo"),
+    (9, "discard"),
+    (13, "daytime"),
+    (17, "qotd"),
+    (19, "chargen"),
+    (20, "ftp-data"),
+    (21, "ftp"),
+    (22, "ssh"),
+    (23, "telnet"),
+    (25, "smtp"),
+    (37, "time"),
+    (49, "tacacs"),
+    (53, "domain"),
+    (70, "gopher"),
+    (79, "finger"),
+    (80, "http"),
+    (88, "kerberos"),
+    (110, "pop3"),
+    (111, "sunrpc"),
+    (119, "nntp"),
+    (135, "epmap"),
+    (139, "netbios-ssn"),
+    (143, "imap"),
+    (179, "bgp"),
+    (194, "irc"),
+    (389, "ldap"),
+    (443, "https"),
+    (445, "microsoft-ds"),
+    (464, "kpasswd"),
+    (465, "submissions"),
+    (512, "exec"),
+    (513, "login"),
+    (514, "shell"),
+    (515, "printer"),
+    (554, "rtsp"),
+    (587, "submission"),
+    (631, "ipp"),
+    (636, "ldaps"),
+    (873, "rsync"),
+    (993, "imaps"),
+    (995, "pop3s"),
+    (1433, "ms-sql-s"),
+    (1812, "radius"),
+    (1813, "radius-acct"),
+    (1883, "mqtt"),
+    (2049, "nfs"),
+    (2379, "etcd-client"),
+    (2380, "etcd-server"),
+    (3306, "mysql"),
+    (3389, "ms-wbt-server"),
+    (5060, "sip"),
+    (5061, "sips"),
+    (5432, "postgresql"),
+    (5671, "amqps"),
+    (5672, "amqp"),
+    (6379, "redis"),
+    (6514, "syslog-tls"),
+    (8080, "http-alt"),
+    (8883, "secure-mqtt"),
+    (11211, "memcache"),
+    (27017, "mongodb"),
+];
+
+/// The names of the most common services of the IANA registry running over UDP, sorted by port.
+const UDP_SERVICES: &[(u16, &str)] = &[
+    (7, "echo"),
+    (9, "discard"),
+    (13, "daytime"),
+    (19, "chargen"),
+    (37, "time"),
+    (49, "tacacs"),
+    (53, "domain"),
+    (67, "bootps"),
+    (68, "bootpc"),
+    (69, "tftp"),
+    (88, "kerberos"),
+    (111, "sunrpc"),
+    (123, "ntp"),
+    (137, "netbios-ns"),
+    (138, "netbios-dgm"),
+    (161, "snmp"),
+    (162, "snmptrap"),
+    (389, "ldap"),
+    (443, "https"),
+    (464, "kpasswd"),
+    (500, "isakmp"),
+    (514, "syslog"),
+    (520, "router"),
+    (546, "dhcpv6-client"),
+    (547, "dhcpv6-server"),
+    (1812, "radius"),
+    (1813, "radius-acct"),
+    (1900, "ssdp"),
+    (2049, "nfs"),
+    (3478, "stun"),
+    (4500, "ipsec-nat-t"),
+    (4739, "ipfix"),
+    (4789, "vxlan"),
+    (5060, "sip"),
+    (5353, "mdns"),
+    (6081, "geneve"),
+    (6343, "sflow"),
+];
+
+fn port_name(value: Value, protocol: &Bytes) -> Resolved {
+    let port = value.try_integer()?;
+    let services = match protocol.as_ref() {
+        b"tcp" => TCP_SERVICES,
+        b"udp" => UDP_SERVICES,
+        _ => unreachable!("enum invariant"),
+    };
+    let name = u16::try_from(port).ok().and_then(|port| {
+        services
+            .binary_search_by_key(&port, |(key, _)| *key)
+            .ok()
+            .map(|index| services[index].1)
+    });
+
+    Ok(name.map_or(Value::Null, Value::from))
+}
+
+fn protocols() -> Vec<Value> {
+    vec![value!("tcp"), value!("udp")]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PortName;
+
+impl Function for PortName {
+    fn identifier(&self) -> &'static str {
+        "port_name"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::INTEGER,
+                required: true,
+            },
+            Parameter {
+                keyword: "protocol",
+                kind: kind::BYTES,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "TCP",
+                source: r#"port_name(22)"#,
+                result: Ok("ssh"),
+            },
+            Example {
+                title: "UDP",
+                source: r#"port_name(514, "udp")"#,
+                result: Ok("syslog"),
+            },
+            Example {
+                title: "unknown",
+                source: r#"port_name(65000)"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let protocol = arguments
+            .optional_enum("protocol", &protocols())?
+            .unwrap_or_else(|| value!("tcp"))
+            .try_bytes()
+            .expect("protocol not bytes");
+
+        Ok(PortNameFn { value, protocol }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct PortNameFn {
+    value: Box<dyn Expression>,
+    protocol: Bytes,
+}
+
+impl FunctionExpression for PortNameFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+
+        port_name(value, &self.protocol)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().add_null().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn services_are_sorted() {
+        for services in [TCP_SERVICES, UDP_SERVICES] {
+            assert!(services.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        }
+    }
+
+    test_function![
+        port_name => PortName;
+
+        tcp_by_default {
+            args: func_args![value: 443],
+            want: Ok("https"),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        tcp {
+            args: func_args![value: 514, protocol: "tcp"],
+            want: Ok("shell"),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        udp {
+            args: func_args![value: 514, protocol: "udp"],
+            want: Ok("syslog"),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        udp_only {
+            args: func_args![value: 123],
+            want: Ok(value!(null)),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        unknown {
+            args: func_args![value: 65000, protocol: "udp"],
+            want: Ok(value!(null)),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        out_of_range {
+            args: func_args![value: 70000],
+            want: Ok(value!(null)),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        negative {
+            args: func_args![value: -1],
+            want: Ok(value!(null)),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+    ];
+}
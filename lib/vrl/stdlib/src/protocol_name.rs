@@ -0,0 +1,283 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+/// The keywords of the [IANA assigned internet protocol numbers][iana], indexed by number.
+/// Numbers that are assigned without a keyword, such as 61 for "any host internal protocol", are
+/// `None`, and numbers from 146 onwards are unassigned or reserved.
+///
+/// [iana]: https://www.iana.org/assignments/protocol-numbers/protocol-numbers.xhtml
+const PROTOCOLS: [Option<&str>; 146] = [
+    // 0
+    Some("HOPOPT"),
+    Some("ICMP"),
+    Some("IGMP"),
+    Some("GGP"),
+    Some("IPv4"),
+    Some("ST"),
+    Some("TCP"),
+    Some("CBT"),
+    Some("EGP"),
+    Some("IGP"),
+    // 10
+    Some("BBN-RCC-MON"),
+    Some("NVP-II"),
+    Some("PUP"),
+    Some("ARGUS"),
+    Some("EMCON"),
+    Some("XNET"),
+    Some("CHAOS"),
+    Some("UDP"),
+    Some("MUX"),
+    Some("DCN-MEAS"),
+    // 20
+    Some("HMP"),
+    Some("PRM"),
+    Some("XNS-IDP"),
+    Some("TRUNK-1"),
+    Some("TRUNK-2"),
+    Some("LEAF-1"),
+    Some("LEAF-2"),
+    Some("RDP"),
+    Some("IRTP"),
+    Some("ISO-TP4"),
+    // 30
+    Some("NETBLT"),
+    Some("MFE-NSP"),
+    Some("MERIT-INP"),
+    Some("DCCP"),
+    Some("3PC"),
+    Some("IDPR"),
+    Some("XTP"),
+    Some("DDP"),
+    Some("IDPR-CMTP"),
+    Some("TP++"),
+    // 40
+    Some("IL"),
+    Some("IPv6"),
+    Some("SDRP"),
+    Some("IPv6-Route"),
+    Some("IPv6-Frag"),
+    Some("IDRP"),
+    Some("RSVP"),
+    Some("GRE"),
+    Some("DSR"),
+    Some("BNA"),
+    // 50
+    Some("ESP"),
+    Some("AH"),
+    Some("I-NLSP"),
+    Some("SWIPE"),
+    Some("NARP"),
+    Some("Min-IPv4"),
+    Some("TLSP"),
+    Some("SKIP"),
+    Some("IPv6-ICMP"),
+    Some("IPv6-NoNxt"),
+    // 60
+    Some("IPv6-Opts"),
+    None,
+    Some("CFTP"),
+    None,
+    Some("SAT-EXPAK"),
+    Some("KRYPTOLAN"),
+    Some("RVD"),
+    Some("IPPC"),
+    None,
+    Some("SAT-MON"),
+    // 70
+    Some("VISA"),
+    Some("IPCV"),
+    Some("CPNX"),
+    Some("CPHB"),
+    Some("WSN"),
+    Some("PVP"),
+    Some("BR-SAT-MON"),
+    Some("SUN-ND"),
+    Some("WB-MON"),
+    Some("WB-EXPAK"),
+    // 80
+    Some("ISO-IP"),
+    Some("VMTP"),
+    Some("SECURE-VMTP"),
+    Some("VINES"),
+    Some("TTP"),
+    Some("NSFNET-IGP"),
+    Some("DGP"),
+    Some("TCF"),
+    Some("EIGRP"),
+    Some("OSPFIGP"),
+    // 90
+    Some("Sprite-RPC"),
+    Some("LARP"),
+    Some("MTP"),
+    Some("AX.25"),
+    Some("IPIP"),
+    Some("MICP"),
+    Some("SCC-SP"),
+    Some("ETHERIP"),
+    Some("ENCAP"),
+    None,
+    // 100
+    Some("GMTP"),
+    Some("IFMP"),
+    Some("PNNI"),
+    Some("PIM"),
+    Some("ARIS"),
+    Some("SCPS"),
+    Some("QNX"),
+    Some("A/N"),
+    Some("IPComp"),
+    Some("SNP"),
+    // 110
+    Some("Compaq-Peer"),
+    Some("IPX-in-IP"),
+    Some("VRRP"),
+    Some("PGM"),
+    None,
+    Some("L2TP"),
+    Some("DDX"),
+    Some("IATP"),
+    Some("STP"),
+    Some("SRP"),
+    // 120
+    Some("UTI"),
+    Some("SMP"),
+    Some("SM"),
+    Some("PTP"),
+    Some("ISIS over IPv4"),
+    Some("FIRE"),
+    Some("CRTP"),
+    Some("CRUDP"),
+    Some("SSCOPMCE"),
+    Some("IPLT"),
+    // 130
+    Some("SPS"),
+    Some("PIPE"),
+    Some("SCTP"),
+    Some("FC"),
+    Some("RSVP-E2E-IGNORE"),
+    Some("Mobility Header"),
+    Some("UDPLite"),
+    Some("MPLS-in-IP"),
+    Some("manet"),
+    Some("HIP"),
+    // 140
+    Some("Shim6"),
+    Some("WESP"),
+    Some("ROHC"),
+    Some("Ethernet"),
+    Some("AGGFRAG"),
+    Some("NSH"),
+];
+
+fn protocol_name(value: Value) -> Resolved {
+    let number = value.try_integer()?;
+    let name = usize::try_from(number)
+        .ok()
+        .and_then(|number| PROTOCOLS.get(number).copied().flatten());
+
+    Ok(name.map_or(Value::Null, Value::from))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ProtocolName;
+
+impl Function for ProtocolName {
+    fn identifier(&self) -> &'static str {
+        "protocol_name"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::INTEGER,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "TCP",
+                source: r#"protocol_name(6)"#,
+                result: Ok("TCP"),
+            },
+            Example {
+                title: "unassigned",
+                source: r#"protocol_name(200)"#,
+                result: Ok("null"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(ProtocolNameFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ProtocolNameFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for ProtocolNameFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        self.value.resolve(ctx).and_then(protocol_name)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::bytes().add_null().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        protocol_name => ProtocolName;
+
+        tcp {
+            args: func_args![value: 6],
+            want: Ok("TCP"),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        icmpv6 {
+            args: func_args![value: 58],
+            want: Ok("IPv6-ICMP"),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        last_assigned {
+            args: func_args![value: 145],
+            want: Ok("NSH"),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        without_keyword {
+            args: func_args![value: 61],
+            want: Ok(value!(null)),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        unassigned {
+            args: func_args![value: 146],
+            want: Ok(value!(null)),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+
+        negative {
+            args: func_args![value: -1],
+            want: Ok(value!(null)),
+            tdef: TypeDef::bytes().add_null().infallible(),
+        }
+    ];
+}
@@ -0,0 +1,75 @@
+use ::value::Value;
+use vrl::prelude::*;
+
+#[allow(clippy::cast_possible_wrap)] // VRL integers are signed, all 64 bits of the hash are kept.
+fn seahash(value: Value) -> Resolved {
+    let value = value.try_bytes()?;
+    Ok((seahash::hash(&value) as i64).into())
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Seahash;
+
+impl Function for Seahash {
+    fn identifier(&self) -> &'static str {
+        "seahash"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[Parameter {
+            keyword: "value",
+            kind: kind::BYTES,
+            required: true,
+        }]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example {
+            title: "seahash",
+            source: r#"seahash("to be or not to be")"#,
+            result: Ok("1988685042348123509"),
+        }]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+
+        Ok(SeahashFn { value }.as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SeahashFn {
+    value: Box<dyn Expression>,
+}
+
+impl FunctionExpression for SeahashFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        seahash(value)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        TypeDef::integer().infallible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        seahash => Seahash;
+
+        seahash {
+            args: func_args![value: "to be or not to be"],
+            want: Ok(value!(1_988_685_042_348_123_509_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+    ];
+}
@@ -122,3 +122,129 @@ impl std::str::FromStr for Base64Charset {
         }
     }
 }
+
+/// The size decompressed data is limited to when no `max_size` is given, so a small malicious
+/// payload can't expand to exhaust memory.
+#[cfg(any(
+    feature = "decode_gzip",
+    feature = "decode_snappy",
+    feature = "decode_zlib",
+    feature = "decode_zstd"
+))]
+pub(crate) const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 10 * 1024 * 1024;
+
+/// Resolves the optional `max_size` argument of the decompression functions.
+#[cfg(any(
+    feature = "decode_gzip",
+    feature = "decode_snappy",
+    feature = "decode_zlib",
+    feature = "decode_zstd"
+))]
+pub(crate) fn max_decompressed_size(
+    max_size: Option<::value::Value>,
+) -> Result<usize, vrl::prelude::ExpressionError> {
+    match max_size {
+        None => Ok(DEFAULT_MAX_DECOMPRESSED_SIZE),
+        Some(max_size) => {
+            let max_size = max_size.try_integer()?;
+            usize::try_from(max_size)
+                .map_err(|_| format!("invalid max_size {}: must not be negative", max_size).into())
+        }
+    }
+}
+
+/// Reads the decompressed data from `reader`, failing once more than `max_size` bytes have been
+/// produced instead of reading the rest.
+#[cfg(any(
+    feature = "decode_gzip",
+    feature = "decode_zlib",
+    feature = "decode_zstd"
+))]
+pub(crate) fn read_decompressed(
+    reader: impl std::io::Read,
+    max_size: usize,
+    format: &str,
+) -> vrl::prelude::Resolved {
+    use std::io::Read;
+
+    let mut buf = Vec::new();
+    reader
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut buf)
+        .map_err(|err| format!("unable to decode value with {} decoder: {}", format, err))?;
+
+    if buf.len() > max_size {
+        return Err(format!("decompressed value exceeds max_size of {} bytes", max_size).into());
+    }
+
+    Ok(vrl::prelude::Bytes::from(buf).into())
+}
+
+/// Splits the first `count` pipe delimited header fields of a CEF or LEEF message from the rest of
+/// it, unescaping `\|` and `\\` in the fields.
+#[cfg(any(feature = "parse_cef", feature = "parse_leef"))]
+pub(crate) fn split_pipe_header(input: &str, count: usize) -> Option<(Vec<String>, &str)> {
+    let mut fields = Vec::with_capacity(count);
+    let mut field = String::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.peek() {
+                Some(&(_, next @ ('|' | '\\'))) => {
+                    field.push(next);
+                    chars.next();
+                }
+                _ => field.push(c),
+            },
+            '|' => {
+                fields.push(std::mem::take(&mut field));
+                if fields.len() == count {
+                    return Some((fields, &input[i + 1..]));
+                }
+            }
+            _ => field.push(c),
+        }
+    }
+
+    None
+}
+
+#[cfg(any(
+    feature = "ip_is_link_local",
+    feature = "ip_is_loopback",
+    feature = "ip_is_multicast",
+    feature = "ip_is_private",
+    feature = "ip_version"
+))]
+pub(crate) fn parse_ip(
+    value: ::value::Value,
+) -> Result<std::net::IpAddr, vrl::prelude::ExpressionError> {
+    use vrl::prelude::VrlValueConvert;
+
+    value
+        .try_bytes_utf8_lossy()?
+        .parse()
+        .map_err(|err| format!("unable to parse IP address: {}", err).into())
+}
+
+/// Converts IPv4-mapped IPv6 addresses, such as `::ffff:10.0.0.1`, to the IPv4 address they map,
+/// so they are classified like it.
+#[cfg(any(
+    feature = "ip_is_link_local",
+    feature = "ip_is_loopback",
+    feature = "ip_is_multicast",
+    feature = "ip_is_private"
+))]
+pub(crate) fn unmap_ip(ip: std::net::IpAddr) -> std::net::IpAddr {
+    match ip {
+        std::net::IpAddr::V6(v6) => match v6.segments() {
+            [0, 0, 0, 0, 0, 0xffff, ..] => {
+                let [.., a, b, c, d] = v6.octets();
+                std::net::Ipv4Addr::new(a, b, c, d).into()
+            }
+            _ => ip,
+        },
+        std::net::IpAddr::V4(_) => ip,
+    }
+}
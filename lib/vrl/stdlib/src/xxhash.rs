@@ -0,0 +1,170 @@
+use std::hash::Hasher;
+
+use ::value::Value;
+use twox_hash::{xxh3, XxHash32, XxHash64};
+use vrl::prelude::*;
+
+#[allow(clippy::cast_possible_wrap)] // VRL integers are signed, all 64 bits of the hash are kept.
+fn xxhash(value: Value, variant: &Bytes, seed: Option<Value>) -> Resolved {
+    let value = value.try_bytes()?;
+    let seed = match seed {
+        Some(seed) => seed.try_integer()? as u64,
+        None => 0,
+    };
+    let hash: Value = match variant.as_ref() {
+        b"XXH32" => hash_with(XxHash32::with_seed(seed as u32), &value).into(),
+        b"XXH64" => (hash_with(XxHash64::with_seed(seed), &value) as i64).into(),
+        b"XXH3-64" => (xxh3::hash64_with_seed(&value, seed) as i64).into(),
+        // 128 bits don't fit in an integer, so the hash is returned as hex.
+        b"XXH3-128" => format!("{:032x}", xxh3::hash128_with_seed(&value, seed)).into(),
+        _ => unreachable!("enum invariant"),
+    };
+    Ok(hash)
+}
+
+fn hash_with(mut hasher: impl Hasher, value: &[u8]) -> u64 {
+    hasher.write(value);
+    hasher.finish()
+}
+
+fn variants() -> Vec<Value> {
+    vec![
+        value!("XXH32"),
+        value!("XXH64"),
+        value!("XXH3-64"),
+        value!("XXH3-128"),
+    ]
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Xxhash;
+
+impl Function for Xxhash {
+    fn identifier(&self) -> &'static str {
+        "xxhash"
+    }
+
+    fn parameters(&self) -> &'static [Parameter] {
+        &[
+            Parameter {
+                keyword: "value",
+                kind: kind::BYTES,
+                required: true,
+            },
+            Parameter {
+                keyword: "variant",
+                kind: kind::BYTES,
+                required: false,
+            },
+            Parameter {
+                keyword: "seed",
+                kind: kind::INTEGER,
+                required: false,
+            },
+        ]
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[
+            Example {
+                title: "default variant",
+                source: r#"xxhash("The quick brown fox jumps over the lazy dog")"#,
+                result: Ok("802816344064684476"),
+            },
+            Example {
+                title: "custom variant",
+                source: r#"xxhash("The quick brown fox jumps over the lazy dog", "XXH32")"#,
+                result: Ok("3898516702"),
+            },
+        ]
+    }
+
+    fn compile(
+        &self,
+        _state: &state::TypeState,
+        _ctx: &mut FunctionCompileContext,
+        arguments: ArgumentList,
+    ) -> Compiled {
+        let value = arguments.required("value");
+        let variant = arguments
+            .optional_enum("variant", &variants())?
+            .unwrap_or_else(|| value!("XXH64"))
+            .try_bytes()
+            .expect("variant not bytes");
+        let seed = arguments.optional("seed");
+
+        Ok(XxhashFn {
+            value,
+            variant,
+            seed,
+        }
+        .as_expr())
+    }
+}
+
+#[derive(Debug, Clone)]
+struct XxhashFn {
+    value: Box<dyn Expression>,
+    variant: Bytes,
+    seed: Option<Box<dyn Expression>>,
+}
+
+impl FunctionExpression for XxhashFn {
+    fn resolve(&self, ctx: &mut Context) -> Resolved {
+        let value = self.value.resolve(ctx)?;
+        let seed = self
+            .seed
+            .as_ref()
+            .map(|expr| expr.resolve(ctx))
+            .transpose()?;
+
+        xxhash(value, &self.variant, seed)
+    }
+
+    fn type_def(&self, _: &state::TypeState) -> TypeDef {
+        if self.variant.as_ref() == b"XXH3-128" {
+            TypeDef::bytes().infallible()
+        } else {
+            TypeDef::integer().infallible()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_function![
+        xxhash => Xxhash;
+
+        xxh64 {
+            args: func_args![value: "foo"],
+            want: Ok(value!(3_728_699_739_546_630_719_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh64_wraps {
+            args: func_args![value: "foobar"],
+            want: Ok(value!(-6_725_556_575_634_347_271_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh32 {
+            args: func_args![value: "foo", variant: "XXH32"],
+            want: Ok(value!(3_792_637_401_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh3_64 {
+            args: func_args![value: "", variant: "XXH3-64"],
+            want: Ok(value!(3_244_421_341_483_603_138_i64)),
+            tdef: TypeDef::integer().infallible(),
+        }
+
+        xxh3_128 {
+            args: func_args![value: "", variant: "XXH3-128"],
+            want: Ok(value!("99aa06d3014798d86001c324468d497f")),
+            tdef: TypeDef::bytes().infallible(),
+        }
+    ];
+}
@@ -194,6 +194,8 @@ impl Test {
             result_approx: false,
             skip,
             read_only_paths: vec![],
+            import_root: None,
+            performance_lints: false,
         }
     }
 }
@@ -190,6 +190,12 @@ fn main() {
             config.set_read_only_path(path.clone(), *recursive);
         }
 
+        if let Some(root) = &test.import_root {
+            config.set_import_root(root);
+        }
+
+        config.set_performance_lints(test.performance_lints);
+
         let compile_start = Instant::now();
         let result = vrl::compile_with_external(&test.source, &functions, &external_env, config);
         let compile_end = compile_start.elapsed();
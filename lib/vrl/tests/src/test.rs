@@ -1,4 +1,8 @@
-use std::{collections::BTreeMap, fs, path::Path};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
 use ::value::Value;
 use lookup::lookup_v2::parse_value_path;
@@ -18,6 +22,12 @@ pub struct Test {
 
     // paths set to read-only
     pub read_only_paths: Vec<(OwnedTargetPath, bool)>,
+
+    // directory `import` statements are resolved against
+    pub import_root: Option<PathBuf>,
+
+    // whether costly patterns are reported as warnings
+    pub performance_lints: bool,
 }
 
 enum CaptureMode {
@@ -44,6 +54,7 @@ impl Test {
         }
 
         let mut read_only_paths = vec![];
+        let mut performance_lints = false;
 
         let mut capture_mode = CaptureMode::None;
         for mut line in content.lines() {
@@ -90,6 +101,13 @@ impl Test {
                     read_only_paths
                         .push((OwnedTargetPath::metadata(parse_value_path(path_str)), true));
                     continue;
+                } else if line.starts_with("performance_lints:") {
+                    let enabled = line
+                        .strip_prefix("performance_lints:")
+                        .expect("performance_lints")
+                        .trim();
+                    performance_lints = enabled == "true";
+                    continue;
                 }
 
                 match capture_mode {
@@ -135,6 +153,8 @@ impl Test {
             result_approx,
             skip,
             read_only_paths,
+            import_root: path.parent().map(Path::to_path_buf),
+            performance_lints,
         }
     }
 
@@ -155,6 +175,8 @@ impl Test {
             result_approx: false,
             skip: false,
             read_only_paths: vec![],
+            import_root: None,
+            performance_lints: false,
         }
     }
 }
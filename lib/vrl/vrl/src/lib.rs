@@ -11,8 +11,9 @@ pub mod prelude;
 mod runtime;
 
 pub use compiler::{
-    function, state, value, CompilationResult, CompileConfig, Compiler, Context, Expression,
-    Function, Program, ProgramInfo, SecretTarget, Target, TargetValue, TargetValueRef, VrlRuntime,
+    function, state, value, CompilationResult, CompileConfig, Compiler, Context, Cost, CostLevel,
+    Expression, Function, Program, ProgramInfo, SecretTarget, Target, TargetValue, TargetValueRef,
+    VrlRuntime,
 };
 pub use diagnostic;
 pub use runtime::{Runtime, RuntimeResult, Terminate};
@@ -0,0 +1,128 @@
+use lapin::tcp::{OwnedIdentity, OwnedTLSConfig};
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+use crate::tls::{TlsConfig, TlsError, TlsSettings};
+
+#[derive(Debug, Snafu)]
+pub enum AmqpConfigError {
+    #[snafu(display("AMQP TLS Config Error: {}", source))]
+    TlsSettings { source: TlsError },
+    #[snafu(display("AMQP Connect Error: {}", source))]
+    Connect { source: lapin::Error },
+    #[snafu(display("AMQP Channel Error: {}", source))]
+    CreateChannel { source: lapin::Error },
+}
+
+/// Connection options for AMQP 0-9-1 brokers, such as RabbitMQ.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct AmqpConfig {
+    /// URI for the AMQP server.
+    ///
+    /// The URI has the format of `amqp://<user>:<password>@<host>:<port>/<vhost>?timeout=<seconds>`.
+    ///
+    /// The default vhost can be specified by using a value of `%2f`.
+    #[serde(default = "default_connection_string")]
+    #[derivative(Default(value = "default_connection_string()"))]
+    pub(crate) connection_string: String,
+
+    #[configurable(derived)]
+    pub(crate) tls: Option<TlsConfig>,
+}
+
+fn default_connection_string() -> String {
+    "amqp://127.0.0.1:5672/%2f".into()
+}
+
+impl AmqpConfig {
+    /// Opens a connection to the broker along with a single channel on it.
+    ///
+    /// The connection must be kept alive for as long as the channel is in use.
+    pub(crate) async fn connect(
+        &self,
+    ) -> Result<(lapin::Connection, lapin::Channel), AmqpConfigError> {
+        let properties = lapin::ConnectionProperties::default();
+        let connection = match &self.tls {
+            Some(tls) => {
+                let tls_config = Self::tls_config(tls)?;
+                lapin::Connection::connect_with_config(
+                    &self.connection_string,
+                    properties,
+                    tls_config,
+                )
+                .await
+            }
+            None => lapin::Connection::connect(&self.connection_string, properties).await,
+        }
+        .context(ConnectSnafu)?;
+
+        let channel = connection
+            .create_channel()
+            .await
+            .context(CreateChannelSnafu)?;
+
+        Ok((connection, channel))
+    }
+
+    fn tls_config(tls: &TlsConfig) -> Result<OwnedTLSConfig, AmqpConfigError> {
+        let settings = TlsSettings::from_options(&Some(tls.clone())).context(TlsSettingsSnafu)?;
+
+        // `lapin` expects the client identity as a PKCS#12 archive, which `TlsSettings` builds from
+        // either a PEM certificate and key pair, or an archive.
+        let identity = settings
+            .identity_pkcs12_der()
+            .map(|(der, password)| OwnedIdentity { der, password });
+
+        let cert_chain = settings
+            .authorities_pem()
+            .map(|authority| String::from_utf8_lossy(&authority).into_owned())
+            .collect::<String>();
+
+        Ok(OwnedTLSConfig {
+            identity,
+            cert_chain: if cert_chain.is_empty() {
+                None
+            } else {
+                Some(cert_chain)
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::{TEST_PEM_CA_PATH, TEST_PEM_CLIENT_CRT_PATH, TEST_PEM_CLIENT_KEY_PATH};
+
+    #[test]
+    fn default_config() {
+        let config: AmqpConfig = toml::from_str("").unwrap();
+        assert_eq!(config.connection_string, "amqp://127.0.0.1:5672/%2f");
+        assert!(config.tls.is_none());
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        toml::from_str::<AmqpConfig>(r#"exchange = "foo""#).unwrap_err();
+    }
+
+    #[test]
+    fn builds_tls_config_from_pem_files() {
+        let tls = TlsConfig {
+            ca_file: Some(TEST_PEM_CA_PATH.into()),
+            crt_file: Some(TEST_PEM_CLIENT_CRT_PATH.into()),
+            key_file: Some(TEST_PEM_CLIENT_KEY_PATH.into()),
+            ..TlsConfig::default()
+        };
+
+        let config = AmqpConfig::tls_config(&tls).unwrap();
+        assert!(config.identity.is_some());
+        assert!(config
+            .cert_chain
+            .unwrap()
+            .starts_with("-----BEGIN CERTIFICATE-----"));
+    }
+}
@@ -628,6 +628,7 @@ mod tests {
                     namespace: None,
                     tags: None,
                 })],
+                window: None,
             },
         );
         config.add_sink(
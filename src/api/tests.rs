@@ -184,6 +184,7 @@ async fn integration_test_source_metric() {
                 namespace: None,
                 tags: None,
             })],
+            window: None,
         },
     );
     config.add_sink(
@@ -173,8 +173,12 @@ pub(super) mod process {
             format: Format,
         ) -> Result<Option<(String, Table, Vec<String>)>, Vec<String>> {
             if let (Ok(name), Some(file)) = (component_name(path), open_file(path)) {
-                self.load(file, format)
-                    .map(|(value, warnings)| Some((name, value, warnings)))
+                self.load(file, format).map(|(mut value, warnings)| {
+                    if let Some(dir) = path.parent() {
+                        set_import_roots(dir, &mut value);
+                    }
+                    Some((name, value, warnings))
+                })
             } else {
                 Ok(None)
             }
@@ -290,6 +294,33 @@ where
     }
 }
 
+/// Resolves the VRL `import` statements of the `remap` transforms defined in a file, including the
+/// nested ones, relative to the directory of the file, unless they set `import_root` themselves.
+fn set_import_roots(dir: &Path, table: &mut Table) {
+    if table.get("type").and_then(Value::as_str) == Some("remap")
+        && !table.contains_key("import_root")
+    {
+        table.insert(
+            "import_root".to_owned(),
+            Value::String(dir.to_string_lossy().into_owned()),
+        );
+    }
+
+    for value in table.values_mut() {
+        match value {
+            Value::Table(table) => set_import_roots(dir, table),
+            Value::Array(values) => {
+                for value in values {
+                    if let Value::Table(table) = value {
+                        set_import_roots(dir, table);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 /// Merge two TOML `Value`s, returning a new `Value`.
 fn merge_values(value: toml::Value, other: toml::Value) -> Result<toml::Value, Vec<String>> {
     serde_toml_merge::merge(value, other).map_err(|e| vec![e.to_string()])
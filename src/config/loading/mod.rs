@@ -372,6 +372,25 @@ mod tests {
         assert_eq!(first.transforms().len(), 2);
     }
 
+    #[test]
+    fn load_sets_remap_import_root() {
+        let path = PathBuf::from(".")
+            .join("tests")
+            .join("namespacing")
+            .join("success");
+        let configs = vec![ConfigPath::Dir(path.clone())];
+        let (builder, _) = load_builder_from_paths(&configs).unwrap();
+
+        let apache_parser = builder
+            .transforms
+            .get(&ComponentKey::from("apache_parser"))
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&apache_parser.inner).unwrap()["import_root"],
+            path.join("transforms").to_string_lossy().into_owned()
+        );
+    }
+
     #[test]
     fn load_namespacing_ignore_invalid() {
         let path = PathBuf::from(".")
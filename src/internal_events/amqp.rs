@@ -0,0 +1,149 @@
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, UNINTENTIONAL},
+};
+
+#[derive(Debug)]
+pub struct AmqpBytesReceived {
+    pub byte_size: usize,
+    pub protocol: &'static str,
+}
+
+impl InternalEvent for AmqpBytesReceived {
+    fn emit(self) {
+        trace!(
+            message = "Bytes received.",
+            byte_size = %self.byte_size,
+            protocol = %self.protocol,
+        );
+        counter!(
+            "component_received_bytes_total",
+            self.byte_size as u64,
+            "protocol" => self.protocol,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpEventsReceived {
+    pub byte_size: usize,
+    pub count: usize,
+}
+
+impl InternalEvent for AmqpEventsReceived {
+    fn emit(self) {
+        trace!(
+            message = "Events received.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!("component_received_events_total", self.count as u64);
+        counter!(
+            "component_received_event_bytes_total",
+            self.byte_size as u64
+        );
+        // deprecated
+        counter!("events_in_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpReceiveError {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read message.",
+            error = %self.error,
+            error_code = "reading_message",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "reading_message",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpAckError {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to acknowledge delivery.",
+            error = %self.error,
+            error_code = "amqp_acknowledgement",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "amqp_acknowledgement",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpDeliveryError<'a> {
+    pub error: &'a lapin::Error,
+}
+
+impl InternalEvent for AmqpDeliveryError<'_> {
+    fn emit(self) {
+        let reason = "Failed to publish message.";
+        error!(
+            message = reason,
+            error = %self.error,
+            error_code = "amqp_publish",
+            error_type = error_type::REQUEST_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "amqp_publish",
+            "error_type" => error_type::REQUEST_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
+
+#[derive(Debug)]
+pub struct AmqpNackError;
+
+impl InternalEvent for AmqpNackError {
+    fn emit(self) {
+        let reason = "Received a negative acknowledgement from the broker.";
+        error!(
+            message = reason,
+            error_code = "amqp_nack",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "amqp_nack",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> { count: 1, reason });
+    }
+}
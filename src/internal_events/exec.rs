@@ -1,7 +1,6 @@
 use std::time::Duration;
 
 use metrics::{counter, histogram};
-use tokio::time::error::Elapsed;
 use vector_common::internal_event::{error_stage, error_type};
 use vector_core::internal_event::InternalEvent;
 
@@ -75,7 +74,6 @@ impl InternalEvent for ExecFailedError<'_> {
 pub struct ExecTimeoutError<'a> {
     pub command: &'a str,
     pub elapsed_seconds: u64,
-    pub error: Elapsed,
 }
 
 impl InternalEvent for ExecTimeoutError<'_> {
@@ -84,7 +82,6 @@ impl InternalEvent for ExecTimeoutError<'_> {
             message = "Timeout during exec.",
             command = %self.command,
             elapsed_seconds = %self.elapsed_seconds,
-            error = %self.error,
             error_type = error_type::TIMED_OUT,
             stage = error_stage::RECEIVING,
         );
@@ -0,0 +1,29 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub(crate) struct LogPatternCreated;
+
+impl InternalEvent for LogPatternCreated {
+    fn emit(self) {
+        counter!("log_patterns_created_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct LogPatternExpired;
+
+impl InternalEvent for LogPatternExpired {
+    fn emit(self) {
+        counter!("log_patterns_expired_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct LogPatternEvicted;
+
+impl InternalEvent for LogPatternEvicted {
+    fn emit(self) {
+        counter!("log_patterns_evicted_total", 1);
+    }
+}
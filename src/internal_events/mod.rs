@@ -2,6 +2,8 @@ pub mod prelude;
 
 mod adaptive_concurrency;
 mod aggregate;
+#[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
 mod apache_metrics;
 #[cfg(feature = "api")]
@@ -69,6 +71,8 @@ mod journald;
 mod kafka;
 #[cfg(feature = "sources-kubernetes_logs")]
 mod kubernetes_logs;
+#[cfg(feature = "transforms-log_patterns")]
+mod log_patterns;
 mod log_to_metric;
 mod logplex;
 #[cfg(feature = "sinks-loki")]
@@ -79,8 +83,10 @@ mod lua;
 mod metric_to_log;
 #[cfg(feature = "sources-mongodb_metrics")]
 mod mongodb_metrics;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 mod nats;
+#[cfg(feature = "sources-netflow")]
+mod netflow;
 #[cfg(feature = "sources-nginx_metrics")]
 mod nginx_metrics;
 mod open;
@@ -96,10 +102,14 @@ mod pulsar;
 mod redis;
 #[cfg(feature = "transforms-reduce")]
 mod reduce;
+#[cfg(feature = "transforms-relabel")]
+mod relabel;
 mod remap;
 mod sample;
 #[cfg(feature = "sinks-sematext")]
 mod sematext_metrics;
+#[cfg(feature = "sources-snmp_trap")]
+mod snmp_trap;
 mod socket;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 mod splunk_hec;
@@ -115,6 +125,8 @@ mod tcp;
 mod template;
 #[cfg(feature = "transforms-throttle")]
 mod throttle;
+#[cfg(feature = "transforms-trace_sample")]
+mod trace_sample;
 mod udp;
 mod unix;
 #[cfg(feature = "sinks-websocket")]
@@ -133,6 +145,8 @@ pub(crate) use mongodb_metrics::*;
 
 #[cfg(feature = "transforms-aggregate")]
 pub(crate) use self::aggregate::*;
+#[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+pub(crate) use self::amqp::*;
 #[cfg(feature = "sources-apache_metrics")]
 pub(crate) use self::apache_metrics::*;
 #[cfg(feature = "api")]
@@ -205,6 +219,8 @@ pub(crate) use self::journald::*;
 pub(crate) use self::kafka::*;
 #[cfg(feature = "sources-kubernetes_logs")]
 pub(crate) use self::kubernetes_logs::*;
+#[cfg(feature = "transforms-log_patterns")]
+pub(crate) use self::log_patterns::*;
 pub(crate) use self::log_to_metric::*;
 #[cfg(feature = "sources-heroku_logs")]
 pub(crate) use self::logplex::*;
@@ -214,8 +230,10 @@ pub(crate) use self::loki::*;
 pub(crate) use self::lua::*;
 #[cfg(feature = "transforms-metric_to_log")]
 pub(crate) use self::metric_to_log::*;
-#[cfg(feature = "sinks-nats")]
+#[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) use self::nats::*;
+#[cfg(feature = "sources-netflow")]
+pub(crate) use self::netflow::*;
 #[cfg(feature = "sources-nginx_metrics")]
 pub(crate) use self::nginx_metrics::*;
 pub(crate) use self::parser::*;
@@ -229,12 +247,16 @@ pub(crate) use self::pulsar::*;
 pub(crate) use self::redis::*;
 #[cfg(feature = "transforms-reduce")]
 pub(crate) use self::reduce::*;
+#[cfg(feature = "transforms-relabel")]
+pub(crate) use self::relabel::*;
 #[cfg(feature = "transforms-remap")]
 pub(crate) use self::remap::*;
 #[cfg(feature = "transforms-sample")]
 pub(crate) use self::sample::*;
 #[cfg(feature = "sinks-sematext")]
 pub(crate) use self::sematext_metrics::*;
+#[cfg(feature = "sources-snmp_trap")]
+pub(crate) use self::snmp_trap::*;
 #[cfg(any(feature = "sources-splunk_hec", feature = "sinks-splunk_hec"))]
 pub(crate) use self::splunk_hec::*;
 #[cfg(feature = "sinks-statsd")]
@@ -247,6 +269,8 @@ pub(crate) use self::syslog::*;
 pub(crate) use self::tag_cardinality_limit::*;
 #[cfg(feature = "transforms-throttle")]
 pub(crate) use self::throttle::*;
+#[cfg(feature = "transforms-trace_sample")]
+pub(crate) use self::trace_sample::*;
 #[cfg(all(
     any(
         feature = "sinks-socket",
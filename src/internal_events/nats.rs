@@ -38,3 +38,51 @@ impl InternalEvent for NatsEventSendError {
         counter!("send_errors_total", 1);
     }
 }
+
+#[derive(Debug)]
+pub struct NatsJetStreamReceiveError {
+    pub error: Error,
+}
+
+impl InternalEvent for NatsJetStreamReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read message from JetStream.",
+            error = %self.error,
+            error_type = error_type::READER_FAILED,
+            error_code = io_error_code(&self.error),
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::READER_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NatsJetStreamAckError {
+    pub error: Error,
+}
+
+impl InternalEvent for NatsJetStreamAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to acknowledge JetStream message.",
+            error = %self.error,
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            error_code = io_error_code(&self.error),
+            stage = error_stage::RECEIVING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "error_code" => io_error_code(&self.error),
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
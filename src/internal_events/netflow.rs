@@ -0,0 +1,71 @@
+use std::{io::Error, net::SocketAddr};
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::io_error_code;
+
+#[derive(Debug)]
+pub struct NetflowReceiveError {
+    pub error: Error,
+}
+
+impl InternalEvent for NetflowReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Error receiving flow packet.",
+            error = %self.error,
+            error_code = io_error_code(&self.error),
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => io_error_code(&self.error),
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowParseError<E> {
+    pub error: E,
+    pub exporter: SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for NetflowParseError<E> {
+    fn emit(self) {
+        error!(
+            message = "Error occurred while parsing flow packet.",
+            error = %self.error,
+            exporter = %self.exporter,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct NetflowMissingTemplates<'a> {
+    pub template_ids: &'a [u16],
+    pub exporter: SocketAddr,
+}
+
+impl<'a> InternalEvent for NetflowMissingTemplates<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Dropping flow records whose template has not been received yet.",
+            template_ids = ?self.template_ids,
+            exporter = %self.exporter,
+            internal_log_rate_secs = 30,
+        );
+    }
+}
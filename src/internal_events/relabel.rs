@@ -0,0 +1,18 @@
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, INTENTIONAL},
+};
+
+#[derive(Debug)]
+pub(crate) struct RelabelEventsDropped;
+
+impl InternalEvent for RelabelEventsDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Metric dropped by relabeling rules.",
+        });
+    }
+}
@@ -0,0 +1,80 @@
+use std::{io::Error, net::SocketAddr};
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+use super::prelude::io_error_code;
+
+#[derive(Debug)]
+pub struct SnmpTrapReceiveError {
+    pub error: Error,
+}
+
+impl InternalEvent for SnmpTrapReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Error receiving SNMP message.",
+            error = %self.error,
+            error_code = io_error_code(&self.error),
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::RECEIVING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => io_error_code(&self.error),
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SnmpTrapParseError<E> {
+    pub error: E,
+    pub peer: SocketAddr,
+}
+
+impl<E: std::fmt::Display> InternalEvent for SnmpTrapParseError<E> {
+    fn emit(self) {
+        error!(
+            message = "Rejected SNMP message.",
+            error = %self.error,
+            peer = %self.peer,
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct SnmpTrapInformResponseError {
+    pub error: Error,
+    pub peer: SocketAddr,
+}
+
+impl InternalEvent for SnmpTrapInformResponseError {
+    fn emit(self) {
+        error!(
+            message = "Failed to acknowledge inform.",
+            error = %self.error,
+            peer = %self.peer,
+            error_code = io_error_code(&self.error),
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_secs = 10,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => io_error_code(&self.error),
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use metrics::{counter, gauge};
+use vector_core::internal_event::InternalEvent;
+
+use crate::{
+    emit,
+    internal_events::{ComponentEventsDropped, INTENTIONAL},
+};
+
+#[derive(Debug)]
+pub(crate) struct TraceSampleDecision {
+    pub kept: bool,
+    pub policy: &'static str,
+}
+
+impl InternalEvent for TraceSampleDecision {
+    fn emit(self) {
+        counter!(
+            "sampled_traces_total", 1,
+            "decision" => if self.kept { "kept" } else { "dropped" },
+            "policy" => self.policy,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TraceSampleEventsDropped {
+    pub count: u64,
+}
+
+impl InternalEvent for TraceSampleEventsDropped {
+    fn emit(self) {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: self.count,
+            reason: "Trace not sampled.",
+        });
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TraceSampleBufferLimitReached;
+
+impl InternalEvent for TraceSampleBufferLimitReached {
+    fn emit(self) {
+        debug!(
+            message = "Trace buffer limit reached; deciding on the oldest trace early.",
+            internal_log_rate_secs = 10,
+        );
+        counter!("traces_decided_early_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TraceSampleBufferSize {
+    pub traces: usize,
+    pub bytes: usize,
+}
+
+impl InternalEvent for TraceSampleBufferSize {
+    fn emit(self) {
+        gauge!("buffered_traces", self.traces as f64);
+        gauge!("buffered_trace_bytes", self.bytes as f64);
+    }
+}
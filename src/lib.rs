@@ -40,6 +40,8 @@ pub mod generate_schema;
 #[macro_use]
 #[allow(unreachable_pub)]
 pub mod internal_events;
+#[cfg(any(feature = "sources-amqp", feature = "sinks-amqp"))]
+pub(crate) mod amqp;
 #[cfg(feature = "api")]
 #[allow(unreachable_pub)]
 pub mod api;
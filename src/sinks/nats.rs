@@ -1,7 +1,7 @@
 use std::convert::TryFrom;
 
 use async_trait::async_trait;
-use bytes::BytesMut;
+use bytes::{Bytes, BytesMut};
 use codecs::JsonSerializerConfig;
 use futures::{stream::BoxStream, FutureExt, StreamExt, TryFutureExt};
 use snafu::{ResultExt, Snafu};
@@ -75,6 +75,14 @@ pub struct NatsSinkConfig {
 
     #[configurable(derived)]
     auth: Option<NatsAuthConfig>,
+
+    /// Whether to publish messages to JetStream.
+    ///
+    /// When enabled, each message is published to the JetStream stream bound to the subject, and is only
+    /// considered delivered once the server has acknowledged that it was stored. The stream must already
+    /// exist.
+    #[serde(default)]
+    jetstream: bool,
 }
 
 fn default_name() -> String {
@@ -91,6 +99,7 @@ impl GenerateConfig for NatsSinkConfig {
             subject: "from.vector".into(),
             tls: None,
             url: "nats://127.0.0.1:4222".into(),
+            jetstream: false,
         })
         .unwrap()
     }
@@ -130,28 +139,71 @@ impl NatsSinkConfig {
 
         options.connect(&self.url).await.context(ConnectSnafu)
     }
+
+    /// Connects with the blocking client, which is the only one exposing JetStream.
+    async fn connect_blocking(&self) -> Result<nats::Connection, BuildError> {
+        let options: nats::Options = self.try_into().context(ConfigSnafu)?;
+        let url = self.url.clone();
+        tokio::task::spawn_blocking(move || options.connect(&url))
+            .await
+            .expect("NATS connect task panicked")
+            .context(ConnectSnafu)
+    }
+}
+
+impl std::convert::TryFrom<&NatsSinkConfig> for nats::Options {
+    type Error = NatsConfigError;
+
+    fn try_from(config: &NatsSinkConfig) -> Result<Self, Self::Error> {
+        from_tls_auth_config(&config.connection_name, &config.auth, &config.tls)
+    }
 }
 
 async fn healthcheck(config: NatsSinkConfig) -> crate::Result<()> {
     config.connect().map_ok(|_| ()).map_err(|e| e.into()).await
 }
 
+enum Publisher {
+    Core(nats::asynk::Connection),
+    JetStream(nats::jetstream::JetStream),
+}
+
+impl Publisher {
+    async fn publish(&self, subject: String, payload: Bytes) -> std::io::Result<()> {
+        match self {
+            Publisher::Core(connection) => connection.publish(&subject, payload).await,
+            Publisher::JetStream(jetstream) => {
+                // Publishing to JetStream blocks until the server acknowledges the message.
+                let jetstream = jetstream.clone();
+                tokio::task::spawn_blocking(move || jetstream.publish(&subject, payload))
+                    .await
+                    .expect("NATS publish task panicked")
+                    .map(|_ack| ())
+            }
+        }
+    }
+}
+
 pub struct NatsSink {
     transformer: Transformer,
     encoder: Encoder<()>,
-    connection: nats::asynk::Connection,
+    publisher: Publisher,
     subject: Template,
 }
 
 impl NatsSink {
     async fn new(config: NatsSinkConfig) -> Result<Self, BuildError> {
-        let connection = config.connect().await?;
+        let publisher = if config.jetstream {
+            Publisher::JetStream(nats::jetstream::new(config.connect_blocking().await?))
+        } else {
+            Publisher::Core(config.connect().await?)
+        };
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build().context(EncodingSnafu)?;
         let encoder = Encoder::<()>::new(serializer);
 
         Ok(NatsSink {
-            connection,
+            publisher,
             transformer,
             encoder,
             subject: Template::try_from(config.subject).context(SubjectTemplateSnafu)?,
@@ -191,7 +243,8 @@ impl StreamSink<Event> for NatsSink {
                 continue;
             }
 
-            match self.connection.publish(&subject, &bytes).await {
+            let byte_size = bytes.len();
+            match self.publisher.publish(subject, bytes.freeze()).await {
                 Err(error) => {
                     finalizers.update_status(EventStatus::Errored);
 
@@ -205,7 +258,7 @@ impl StreamSink<Event> for NatsSink {
                         count: 1,
                         output: None
                     });
-                    bytes_sent.emit(ByteSize(bytes.len()));
+                    bytes_sent.emit(ByteSize(byte_size));
                 }
             }
         }
@@ -296,6 +349,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: None,
             auth: None,
         };
@@ -322,6 +376,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -350,6 +405,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: None,
             auth: Some(NatsAuthConfig::UserPassword {
                 user_password: NatsAuthUserPassword {
@@ -381,6 +437,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -411,6 +468,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: None,
             auth: Some(NatsAuthConfig::Token {
                 token: NatsAuthToken {
@@ -441,6 +499,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -472,6 +531,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: None,
             auth: Some(NatsAuthConfig::Nkey {
                 nkey: NatsAuthNKey {
@@ -503,6 +563,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -535,6 +596,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: None,
             auth: None,
         };
@@ -561,6 +623,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -595,6 +658,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -627,6 +691,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -663,6 +728,7 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: false,
             tls: Some(TlsEnableableConfig {
                 enabled: Some(true),
                 options: TlsConfig {
@@ -684,4 +750,55 @@ mod integration_tests {
             r
         );
     }
+
+    #[tokio::test]
+    async fn nats_jetstream_publish() {
+        trace_init();
+
+        let subject = format!("test-{}", random_string(10));
+        let stream = format!("test-{}", random_string(10));
+        let url = std::env::var("NATS_JETSTREAM_ADDRESS")
+            .unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+        let js = {
+            let (url, stream, subject) = (url.clone(), stream.clone(), subject.clone());
+            tokio::task::spawn_blocking(move || {
+                let js = nats::jetstream::new(nats::connect(&url).unwrap());
+                js.add_stream(nats::jetstream::StreamConfig {
+                    name: stream,
+                    subjects: vec![subject],
+                    ..Default::default()
+                })
+                .unwrap();
+                js
+            })
+            .await
+            .unwrap()
+        };
+
+        let conf = NatsSinkConfig {
+            acknowledgements: Default::default(),
+            encoding: TextSerializerConfig::new().into(),
+            connection_name: "".to_owned(),
+            subject,
+            url,
+            jetstream: true,
+            tls: None,
+            auth: None,
+        };
+
+        let sink = NatsSink::new(conf).await.unwrap();
+        let sink = VectorSink::from_event_streamsink(sink);
+
+        let num_events = 100;
+        let (_input, events) = random_lines_with_stream(100, num_events, None);
+        run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+        // Every message was acknowledged by the server, so they must all be stored in the stream.
+        let info = tokio::task::spawn_blocking(move || js.stream_info(&stream))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.state.messages, num_events as u64);
+    }
 }
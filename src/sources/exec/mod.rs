@@ -1,7 +1,11 @@
 use std::{
+    collections::{HashMap, VecDeque},
     io::{Error, ErrorKind},
     path::PathBuf,
+    pin::Pin,
     process::ExitStatus,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
 };
 
 use bytes::Bytes;
@@ -10,11 +14,11 @@ use codecs::{
     decoding::{DeserializerConfig, FramingConfig},
     StreamDecodingError,
 };
-use futures::StreamExt;
+use futures::{future, StreamExt};
 use smallvec::SmallVec;
 use snafu::Snafu;
 use tokio::{
-    io::{AsyncRead, BufReader},
+    io::{AsyncRead, AsyncWriteExt, BufReader, ReadBuf},
     process::Command,
     sync::mpsc::{channel, Sender},
     time::{self, sleep, Duration, Instant},
@@ -28,7 +32,7 @@ use vector_core::ByteSizeOf;
 use crate::{
     codecs::{Decoder, DecodingConfig},
     config::{log_schema, Output, SourceConfig, SourceContext},
-    event::Event,
+    event::{Event, LogEvent},
     internal_events::{
         ExecCommandExecuted, ExecEventsReceived, ExecFailedError, ExecFailedToSignalChild,
         ExecFailedToSignalChildError, ExecTimeoutError, StreamClosedError,
@@ -62,6 +66,20 @@ pub struct ExecConfig {
     /// The directory in which to run the command.
     pub working_directory: Option<PathBuf>,
 
+    /// Custom environment variables to set or update when running the command.
+    ///
+    /// If a variable name already exists in the environment, its value is replaced.
+    pub environment: Option<HashMap<String, String>>,
+
+    /// Whether or not to clear the environment before setting custom environment variables.
+    #[serde(default = "crate::serde::default_false")]
+    pub clear_environment: bool,
+
+    /// The data written to the standard input of the command, which is closed afterwards.
+    ///
+    /// If not set, the standard input of the command is empty.
+    pub stdin: Option<String>,
+
     /// Whether or not the output from stderr should be included when generating events.
     #[serde(default = "default_include_stderr")]
     pub include_stderr: bool,
@@ -70,6 +88,13 @@ pub struct ExecConfig {
     #[serde(default = "default_maximum_buffer_size")]
     pub maximum_buffer_size_bytes: usize,
 
+    /// Whether or not to generate an event once each run of the command is over.
+    ///
+    /// The event holds the exit code of the command, the duration of the run, whether it timed
+    /// out, and the last 4 KiB of the output from stderr.
+    #[serde(default = "crate::serde::default_false")]
+    pub run_metadata: bool,
+
     #[configurable(derived)]
     framing: Option<FramingConfig>,
 
@@ -100,6 +125,11 @@ pub struct ScheduledConfig {
     /// If the command takes longer than `exec_interval_secs` to run, it will be killed.
     #[serde(default = "default_exec_interval_secs")]
     exec_interval_secs: u64,
+
+    /// The maximum time, in seconds, a scheduled command is allowed to run before it is killed.
+    ///
+    /// Must not be greater than `exec_interval_secs`, which is used if not set.
+    timeout_secs: Option<u64>,
 }
 
 /// Configuration options for streaming commands.
@@ -122,6 +152,10 @@ pub enum ExecConfigError {
     CommandEmpty,
     #[snafu(display("The maximum buffer size must be greater than zero"))]
     ZeroBuffer,
+    #[snafu(display("The timeout must be greater than zero"))]
+    ZeroTimeout,
+    #[snafu(display("The timeout must not be greater than the interval between runs"))]
+    TimeoutExceedsInterval,
 }
 
 impl Default for ExecConfig {
@@ -130,12 +164,17 @@ impl Default for ExecConfig {
             mode: Mode::Scheduled,
             scheduled: Some(ScheduledConfig {
                 exec_interval_secs: default_exec_interval_secs(),
+                timeout_secs: None,
             }),
             streaming: None,
             command: vec!["echo".to_owned(), "Hello World!".to_owned()],
             working_directory: None,
+            environment: None,
+            clear_environment: false,
+            stdin: None,
             include_stderr: default_include_stderr(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            run_metadata: false,
             framing: None,
             decoding: default_decoding(),
         }
@@ -172,6 +211,15 @@ const STDERR: &str = "stderr";
 const STREAM_KEY: &str = "stream";
 const PID_KEY: &str = "pid";
 const COMMAND_KEY: &str = "command";
+const EXIT_CODE_KEY: &str = "exit_code";
+const DURATION_KEY: &str = "duration_seconds";
+const TIMED_OUT_KEY: &str = "timed_out";
+const STDERR_KEY: &str = "stderr";
+
+/// The number of bytes of the end of the output from stderr kept for the run metadata.
+const STDERR_TAIL_BYTES: usize = 4096;
+/// How long to wait for the exit status of a command that closed its output, for the run metadata.
+const EXIT_STATUS_TIMEOUT: Duration = Duration::from_secs(1);
 
 impl_generate_config_from_default!(ExecConfig);
 
@@ -182,7 +230,19 @@ impl ExecConfig {
         } else if self.maximum_buffer_size_bytes == 0 {
             Err(ExecConfigError::ZeroBuffer)
         } else {
-            Ok(())
+            match &self.scheduled {
+                Some(ScheduledConfig {
+                    timeout_secs: Some(0),
+                    ..
+                }) => Err(ExecConfigError::ZeroTimeout),
+                Some(ScheduledConfig {
+                    exec_interval_secs,
+                    timeout_secs: Some(timeout_secs),
+                }) if timeout_secs > exec_interval_secs => {
+                    Err(ExecConfigError::TimeoutExceedsInterval)
+                }
+                _ => Ok(()),
+            }
         }
     }
 
@@ -197,6 +257,13 @@ impl ExecConfig {
         }
     }
 
+    fn timeout_secs_or_default(&self) -> u64 {
+        self.scheduled
+            .as_ref()
+            .and_then(|config| config.timeout_secs)
+            .unwrap_or_else(|| self.exec_interval_secs_or_default())
+    }
+
     const fn respawn_on_exit_or_default(&self) -> bool {
         match &self.streaming {
             None => default_respawn_on_exit(),
@@ -228,11 +295,13 @@ impl SourceConfig for ExecConfig {
         match &self.mode {
             Mode::Scheduled => {
                 let exec_interval_secs = self.exec_interval_secs_or_default();
+                let timeout_secs = self.timeout_secs_or_default();
 
                 Ok(Box::pin(run_scheduled(
                     self.clone(),
                     hostname,
                     exec_interval_secs,
+                    timeout_secs,
                     decoder,
                     cx.shutdown,
                     cx.out,
@@ -268,45 +337,34 @@ async fn run_scheduled(
     config: ExecConfig,
     hostname: Option<String>,
     exec_interval_secs: u64,
+    timeout_secs: u64,
     decoder: Decoder,
     shutdown: ShutdownSignal,
     out: SourceSender,
 ) -> Result<(), ()> {
     debug!("Starting scheduled exec runs.");
     let schedule = Duration::from_secs(exec_interval_secs);
+    let timeout = Duration::from_secs(timeout_secs);
 
     let mut interval = IntervalStream::new(time::interval(schedule)).take_until(shutdown.clone());
 
     while interval.next().await.is_some() {
-        // Wait for our task to finish, wrapping it in a timeout
-        let timeout = tokio::time::timeout(
-            schedule,
-            run_command(
-                config.clone(),
-                hostname.clone(),
-                decoder.clone(),
-                shutdown.clone(),
-                out.clone(),
-            ),
+        // Wait for our task to finish, which kills the command if it times out
+        let output = run_command(
+            config.clone(),
+            hostname.clone(),
+            Some(timeout),
+            decoder.clone(),
+            shutdown.clone(),
+            out.clone(),
         )
         .await;
 
-        match timeout {
-            Ok(output) => {
-                if let Err(command_error) = output {
-                    emit!(ExecFailedError {
-                        command: config.command_line().as_str(),
-                        error: command_error,
-                    });
-                }
-            }
-            Err(error) => {
-                emit!(ExecTimeoutError {
-                    command: config.command_line().as_str(),
-                    elapsed_seconds: schedule.as_secs(),
-                    error,
-                });
-            }
+        if let Err(command_error) = output {
+            emit!(ExecFailedError {
+                command: config.command_line().as_str(),
+                error: command_error,
+            });
         }
     }
 
@@ -331,6 +389,7 @@ async fn run_streaming(
             let output = run_command(
                 config.clone(),
                 hostname.clone(),
+                None,
                 decoder.clone(),
                 shutdown.clone(),
                 out.clone(),
@@ -351,7 +410,7 @@ async fn run_streaming(
             }
         }
     } else {
-        let output = run_command(config.clone(), hostname, decoder, shutdown, out).await;
+        let output = run_command(config.clone(), hostname, None, decoder, shutdown, out).await;
 
         if let Err(command_error) = output {
             emit!(ExecFailedError {
@@ -367,6 +426,7 @@ async fn run_streaming(
 async fn run_command(
     config: ExecConfig,
     hostname: Option<String>,
+    timeout: Option<Duration>,
     decoder: Decoder,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
@@ -380,19 +440,55 @@ async fn run_command(
 
     let mut child = command.spawn()?;
 
+    // Feed stdin in the background, so that commands reading it while writing their output
+    // don't block
+    if let Some(input) = config.stdin.clone() {
+        let mut stdin = child.stdin.take().ok_or_else(|| {
+            Error::new(ErrorKind::Other, "Unable to take stdin of spawned process")
+        })?;
+
+        tokio::spawn(async move {
+            // The command may exit, or close its stdin, without reading all of it.
+            if let Err(error) = stdin.write_all(input.as_bytes()).await {
+                debug!(message = "Unable to write to stdin of command.", %error);
+            }
+        });
+    }
+
     // Set up communication channels
     let (sender, mut receiver) = channel(1024);
 
+    // Keep the end of stderr for the run metadata
+    let stderr_tail = config.run_metadata.then(StderrTail::default);
+
     // Optionally include stderr
-    if config.include_stderr {
+    if config.include_stderr || stderr_tail.is_some() {
         let stderr = child.stderr.take().ok_or_else(|| {
             Error::new(ErrorKind::Other, "Unable to take stderr of spawned process")
         })?;
 
-        // Create stderr async reader
-        let stderr_reader = BufReader::new(stderr);
+        match &stderr_tail {
+            Some(tail) if config.include_stderr => {
+                let stderr_reader = BufReader::new(tail.reader(stderr));
+                spawn_reader_thread(stderr_reader, decoder.clone(), STDERR, sender.clone());
+            }
+            Some(tail) => {
+                // Only read stderr for the run metadata, holding a sender so that the run
+                // is only over once stderr is closed
+                let mut stderr_reader = tail.reader(stderr);
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    let _ = tokio::io::copy(&mut stderr_reader, &mut tokio::io::sink()).await;
+                    drop(sender);
+                });
+            }
+            None => {
+                // Create stderr async reader
+                let stderr_reader = BufReader::new(stderr);
 
-        spawn_reader_thread(stderr_reader, decoder.clone(), STDERR, sender.clone());
+                spawn_reader_thread(stderr_reader, decoder.clone(), STDERR, sender.clone());
+            }
+        }
     }
 
     let stdout = child
@@ -409,6 +505,15 @@ async fn run_command(
 
     let bytes_received = register!(BytesReceived::from(Protocol::NONE));
 
+    let deadline = async move {
+        match timeout {
+            Some(timeout) => sleep(timeout).await,
+            None => future::pending().await,
+        }
+    };
+    tokio::pin!(deadline);
+    let mut timed_out = false;
+
     'outer: loop {
         tokio::select! {
             _ = &mut shutdown => {
@@ -416,6 +521,19 @@ async fn run_command(
                         break 'outer; // couldn't signal, exit early
                 }
             }
+            _ = &mut deadline => {
+                emit!(ExecTimeoutError {
+                    command: config.command_line().as_str(),
+                    elapsed_seconds: timeout.unwrap_or_default().as_secs(),
+                });
+
+                timed_out = true;
+                if let Err(error) = child.kill().await {
+                    error!(message = "Unable to kill timed out command.", %error);
+                }
+                // Don't wait for the output, which may be held open by other processes
+                break 'outer;
+            }
             v = receiver.recv() => {
                 match v {
                     None => break 'outer,
@@ -444,6 +562,11 @@ async fn run_command(
 
     let elapsed = start.elapsed();
 
+    if stderr_tail.is_some() {
+        // The output is closed just before the command exits, so give it a moment to do so
+        let _ = time::timeout(EXIT_STATUS_TIMEOUT, child.wait()).await;
+    }
+
     let result = match child.try_wait() {
         Ok(Some(exit_status)) => {
             handle_exit_status(&config, exit_status.code(), elapsed);
@@ -461,11 +584,52 @@ async fn run_command(
         }
     };
 
+    if let Some(stderr_tail) = stderr_tail {
+        let exit_code = result
+            .as_ref()
+            .ok()
+            .and_then(Option::as_ref)
+            .and_then(ExitStatus::code);
+        let mut event = run_metadata_event(exit_code, elapsed, timed_out, &stderr_tail.contents());
+        handle_event(&config, &hostname, &None, pid, &mut event);
+
+        emit!(ExecEventsReceived {
+            count: 1,
+            command: config.command_line().as_str(),
+            byte_size: event.size_of(),
+        });
+        if let Err(error) = out.send_event(event).await {
+            emit!(StreamClosedError { count: 1, error });
+        }
+    }
+
     debug!("Finished command run.");
 
     result
 }
 
+fn run_metadata_event(
+    exit_code: Option<i32>,
+    exec_duration: Duration,
+    timed_out: bool,
+    stderr: &[u8],
+) -> Event {
+    let mut log = LogEvent::default();
+
+    // Commands killed by a signal have no exit code
+    if let Some(exit_code) = exit_code {
+        log.insert(event_path!(EXIT_CODE_KEY), exit_code as i64);
+    }
+    log.insert(event_path!(DURATION_KEY), exec_duration.as_secs_f64());
+    log.insert(event_path!(TIMED_OUT_KEY), timed_out);
+    log.insert(
+        event_path!(STDERR_KEY),
+        String::from_utf8_lossy(stderr).into_owned(),
+    );
+
+    log.into()
+}
+
 fn handle_exit_status(config: &ExecConfig, exit_status: Option<i32>, exec_duration: Duration) {
     emit!(ExecCommandExecuted {
         command: config.command_line().as_str(),
@@ -546,18 +710,32 @@ fn build_command(config: &ExecConfig) -> Command {
         command.current_dir(current_dir);
     }
 
+    // Clear the inherited environment if needed
+    if config.clear_environment {
+        command.env_clear();
+    }
+
+    // Set the custom environment variables if needed
+    if let Some(environment) = &config.environment {
+        command.envs(environment);
+    }
+
     // Pipe our stdout to the process
     command.stdout(std::process::Stdio::piped());
 
     // Pipe stderr to the process if needed
-    if config.include_stderr {
+    if config.include_stderr || config.run_metadata {
         command.stderr(std::process::Stdio::piped());
     } else {
         command.stderr(std::process::Stdio::null());
     }
 
-    // Stdin is not needed
-    command.stdin(std::process::Stdio::null());
+    // Pipe stdin from the process if needed
+    if config.stdin.is_some() {
+        command.stdin(std::process::Stdio::piped());
+    } else {
+        command.stdin(std::process::Stdio::null());
+    }
 
     command
 }
@@ -598,6 +776,49 @@ fn handle_event(
     }
 }
 
+/// The end of the output from stderr, kept for the run metadata.
+#[derive(Clone, Default)]
+struct StderrTail(Arc<Mutex<VecDeque<u8>>>);
+
+impl StderrTail {
+    /// Wraps a reader, keeping the end of what is read through it.
+    fn reader<R>(&self, inner: R) -> TailReader<R> {
+        TailReader {
+            inner,
+            tail: self.clone(),
+        }
+    }
+
+    fn contents(&self) -> Vec<u8> {
+        self.0.lock().expect("poisoned lock").drain(..).collect()
+    }
+}
+
+struct TailReader<R> {
+    inner: R,
+    tail: StderrTail,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for TailReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+
+        if let Poll::Ready(Ok(())) = result {
+            let mut tail = self.tail.0.lock().expect("poisoned lock");
+            tail.extend(&buf.filled()[filled..]);
+            let excess = tail.len().saturating_sub(STDERR_TAIL_BYTES);
+            tail.drain(..excess);
+        }
+
+        result
+    }
+}
+
 fn spawn_reader_thread<R: 'static + AsyncRead + Unpin + std::marker::Send>(
     reader: BufReader<R>,
     decoder: Decoder,
@@ -635,7 +856,7 @@ fn spawn_reader_thread<R: 'static + AsyncRead + Unpin + std::marker::Send>(
 
 #[cfg(test)]
 mod tests {
-    use std::io::Cursor;
+    use std::{ffi::OsStr, io::Cursor};
 
     #[cfg(unix)]
     use futures::task::Poll;
@@ -699,8 +920,12 @@ mod tests {
             }),
             command: vec!["./runner".to_owned(), "arg1".to_owned(), "arg2".to_owned()],
             working_directory: Some(PathBuf::from("/tmp")),
+            environment: None,
+            clear_environment: false,
+            stdin: None,
             include_stderr: default_include_stderr(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            run_metadata: false,
             framing: None,
             decoding: default_decoding(),
         };
@@ -719,6 +944,38 @@ mod tests {
         assert_eq!(expected_command_string, command_string);
     }
 
+    #[test]
+    fn test_build_command_environment() {
+        let mut config = standard_scheduled_test_config();
+        config.environment = Some(HashMap::from([("FOO".to_owned(), "foo".to_owned())]));
+        config.clear_environment = true;
+
+        let command = build_command(&config);
+
+        // The inherited variables aren't listed, only the ones that are set
+        let envs = command.as_std().get_envs().collect::<Vec<_>>();
+        assert_eq!(envs, vec![(OsStr::new("FOO"), Some(OsStr::new("foo")))]);
+    }
+
+    #[test]
+    fn test_validate_timeout() {
+        let mut config = standard_scheduled_test_config();
+        let scheduled = config.scheduled.as_mut().unwrap();
+        scheduled.exec_interval_secs = 10;
+
+        scheduled.timeout_secs = Some(10);
+        assert_eq!(config.validate(), Ok(()));
+
+        config.scheduled.as_mut().unwrap().timeout_secs = Some(0);
+        assert_eq!(config.validate(), Err(ExecConfigError::ZeroTimeout));
+
+        config.scheduled.as_mut().unwrap().timeout_secs = Some(11);
+        assert_eq!(
+            config.validate(),
+            Err(ExecConfigError::TimeoutExceedsInterval)
+        );
+    }
+
     #[tokio::test]
     async fn test_spawn_reader_thread() {
         trace_init();
@@ -846,6 +1103,115 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_run_command_metadata() {
+        let mut config = standard_scheduled_test_config();
+        config.command = vec![
+            String::from("/bin/sh"),
+            String::from("-c"),
+            String::from(
+                r#"read line; echo "$line $GREETING ${HOME:-cleared}"; echo oops >&2; exit 3"#,
+            ),
+        ];
+        config.environment = Some(HashMap::from([("GREETING".to_owned(), "hello".to_owned())]));
+        config.clear_environment = true;
+        config.stdin = Some("from stdin\n".to_owned());
+        config.include_stderr = false;
+        config.run_metadata = true;
+        let (tx, rx) = SourceSender::new_test();
+
+        let exit_status = tokio::time::timeout(
+            time::Duration::from_secs(5),
+            run_command(
+                config,
+                None,
+                None,
+                Default::default(),
+                ShutdownSignal::noop(),
+                tx,
+            ),
+        )
+        .await
+        .expect("command timed out")
+        .expect("command error");
+        assert_eq!(3_i32, exit_status.unwrap().code().unwrap());
+
+        let events = rx.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 2);
+
+        let log = events[0].as_log();
+        assert_eq!(
+            log[log_schema().message_key()],
+            "from stdin hello cleared".into()
+        );
+
+        let log = events[1].as_log();
+        assert_eq!(log[EXIT_CODE_KEY], 3_i64.into());
+        assert_eq!(log[TIMED_OUT_KEY], false.into());
+        assert_eq!(log[STDERR_KEY], "oops\n".into());
+        assert!(log.get(DURATION_KEY).is_some());
+        assert!(log.get(PID_KEY).is_some());
+        assert!(log.get(STREAM_KEY).is_none());
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_run_command_timeout() {
+        let mut config = standard_scheduled_test_config();
+        config.command = vec![
+            String::from("bash"),
+            String::from("-c"),
+            String::from("echo started >&2; exec sleep 10"),
+        ];
+        config.run_metadata = true;
+        let (tx, rx) = SourceSender::new_test();
+
+        let exit_status = tokio::time::timeout(
+            time::Duration::from_secs(5),
+            run_command(
+                config,
+                None,
+                Some(Duration::from_secs(1)),
+                Default::default(),
+                ShutdownSignal::noop(),
+                tx,
+            ),
+        )
+        .await
+        .expect("command wasn't killed")
+        .expect("command error");
+        assert_eq!(exit_status.unwrap().code(), None);
+
+        let events = rx.collect::<Vec<_>>().await;
+        let log = events.last().unwrap().as_log();
+        assert_eq!(log[TIMED_OUT_KEY], true.into());
+        assert_eq!(log[STDERR_KEY], "started\n".into());
+        assert!(log.get(EXIT_CODE_KEY).is_none());
+    }
+
+    #[test]
+    fn test_stderr_tail() {
+        let tail = StderrTail::default();
+        let mut reader = tail.reader(Cursor::new(vec![b'a'; STDERR_TAIL_BYTES + 10]));
+        let mut buf = [0; 64];
+        let mut read_buf = ReadBuf::new(&mut buf);
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            read_buf.clear();
+            match Pin::new(&mut reader).poll_read(&mut cx, &mut read_buf) {
+                Poll::Ready(Ok(())) if read_buf.filled().is_empty() => break,
+                Poll::Ready(Ok(())) => (),
+                other => panic!("unexpected read result: {:?}", other),
+            }
+        }
+
+        assert_eq!(tail.contents(), vec![b'a'; STDERR_TAIL_BYTES]);
+        assert!(tail.contents().is_empty());
+    }
+
     fn standard_scheduled_test_config() -> ExecConfig {
         Default::default()
     }
@@ -860,8 +1226,12 @@ mod tests {
             }),
             command: vec!["yes".to_owned()],
             working_directory: None,
+            environment: None,
+            clear_environment: false,
+            stdin: None,
             include_stderr: default_include_stderr(),
             maximum_buffer_size_bytes: default_maximum_buffer_size(),
+            run_metadata: false,
             framing: None,
             decoding: default_decoding(),
         }
@@ -28,6 +28,10 @@ mod disk;
 mod filesystem;
 mod memory;
 mod network;
+#[cfg(target_os = "linux")]
+mod process;
+#[cfg(target_os = "linux")]
+mod tcp;
 
 /// Collector types.
 #[configurable_component]
@@ -58,6 +62,27 @@ pub enum Collector {
 
     /// Network.
     Network,
+
+    /// Processes.
+    #[cfg(target_os = "linux")]
+    Process,
+
+    /// TCP connections.
+    ///
+    /// This collector isn't enabled by default.
+    #[cfg(target_os = "linux")]
+    Tcp,
+}
+
+impl Collector {
+    /// Whether the collector is used when no collectors are configured.
+    const fn is_default(self) -> bool {
+        #[cfg(target_os = "linux")]
+        if matches!(self, Self::Tcp) {
+            return false;
+        }
+        true
+    }
 }
 
 /// Filtering configuration.
@@ -83,7 +108,7 @@ pub struct HostMetricsConfig {
 
     /// The list of host metric collector services to use.
     ///
-    /// Defaults to all collectors.
+    /// Defaults to all collectors, except `tcp`.
     pub collectors: Option<Vec<Collector>>,
 
     /// Overrides the default namespace for the metrics emitted by the source.
@@ -109,6 +134,16 @@ pub struct HostMetricsConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub network: network::NetworkConfig,
+
+    #[cfg(target_os = "linux")]
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) process: process::ProcessConfig,
+
+    #[cfg(target_os = "linux")]
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(crate) tcp: tcp::TcpConfig,
 }
 
 const fn default_scrape_interval() -> f64 {
@@ -128,8 +163,9 @@ impl SourceConfig for HostMetricsConfig {
 
         let mut config = self.clone();
         config.namespace = config.namespace.filter(|namespace| !namespace.is_empty());
+        let generator = HostMetrics::try_new(config)?;
 
-        Ok(Box::pin(config.run(cx.out, cx.shutdown)))
+        Ok(Box::pin(generator.run(cx.out, cx.shutdown)))
     }
 
     fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
@@ -147,30 +183,9 @@ impl HostMetricsConfig {
         self.scrape_interval_secs = value;
     }
 
-    async fn run(self, mut out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
-        let duration = time::Duration::from_secs_f64(self.scrape_interval_secs);
-        let mut interval = IntervalStream::new(time::interval(duration)).take_until(shutdown);
-
-        let generator = HostMetrics::new(self);
-
-        let bytes_received = register!(BytesReceived::from(Protocol::NONE));
-
-        while interval.next().await.is_some() {
-            bytes_received.emit(ByteSize(0));
-            let metrics = generator.capture_metrics().await;
-            let count = metrics.len();
-            if let Err(error) = out.send_batch(metrics).await {
-                emit!(StreamClosedError { count, error });
-                return Err(());
-            }
-        }
-
-        Ok(())
-    }
-
     fn has_collector(&self, collector: Collector) -> bool {
         match &self.collectors {
-            None => true,
+            None => collector.is_default(),
             Some(collectors) => collectors.iter().any(|&c| c == collector),
         }
     }
@@ -180,6 +195,10 @@ pub struct HostMetrics {
     config: HostMetricsConfig,
     #[cfg(target_os = "linux")]
     root_cgroup: Option<cgroups::CGroupRoot>,
+    #[cfg(target_os = "linux")]
+    process_cmdline: Option<regex::Regex>,
+    #[cfg(target_os = "linux")]
+    process_state: std::sync::Mutex<process::ProcessState>,
 }
 
 impl HostMetrics {
@@ -188,13 +207,68 @@ impl HostMetrics {
         Self { config }
     }
 
+    /// Creates a generator of the metrics configured in `config`.
+    ///
+    /// An invalid `process.cmdline` pattern is logged, and processes aren't filtered by it. Use
+    /// [`HostMetrics::try_new`] to reject it instead.
     #[cfg(target_os = "linux")]
     pub fn new(config: HostMetricsConfig) -> Self {
+        let process_cmdline = config.process.cmdline_regex().unwrap_or_else(|error| {
+            error!(message = "Invalid `process.cmdline` pattern, ignoring it.", %error);
+            None
+        });
+        Self::with_process_cmdline(config, process_cmdline)
+    }
+
+    /// Creates a generator of the metrics configured in `config`, failing if the configuration is
+    /// invalid.
+    #[cfg(not(target_os = "linux"))]
+    fn try_new(config: HostMetricsConfig) -> crate::Result<Self> {
+        Ok(Self::new(config))
+    }
+
+    /// Creates a generator of the metrics configured in `config`, failing if the configuration is
+    /// invalid.
+    #[cfg(target_os = "linux")]
+    fn try_new(config: HostMetricsConfig) -> crate::Result<Self> {
+        let process_cmdline = config
+            .process
+            .cmdline_regex()
+            .map_err(|error| format!("Invalid `process.cmdline` pattern: {}", error))?;
+        Ok(Self::with_process_cmdline(config, process_cmdline))
+    }
+
+    #[cfg(target_os = "linux")]
+    fn with_process_cmdline(
+        config: HostMetricsConfig,
+        process_cmdline: Option<regex::Regex>,
+    ) -> Self {
         let root_cgroup = cgroups::CGroupRoot::new(&config.cgroups);
         Self {
             config,
             root_cgroup,
+            process_cmdline,
+            process_state: Default::default(),
+        }
+    }
+
+    async fn run(self, mut out: SourceSender, shutdown: ShutdownSignal) -> Result<(), ()> {
+        let duration = time::Duration::from_secs_f64(self.config.scrape_interval_secs);
+        let mut interval = IntervalStream::new(time::interval(duration)).take_until(shutdown);
+
+        let bytes_received = register!(BytesReceived::from(Protocol::NONE));
+
+        while interval.next().await.is_some() {
+            bytes_received.emit(ByteSize(0));
+            let metrics = self.capture_metrics().await;
+            let count = metrics.len();
+            if let Err(error) = out.send_batch(metrics).await {
+                emit!(StreamClosedError { count, error });
+                return Err(());
+            }
         }
+
+        Ok(())
     }
 
     pub fn buffer(&self) -> MetricsBuffer {
@@ -230,6 +304,14 @@ impl HostMetrics {
         if self.config.has_collector(Collector::Network) {
             self.network_metrics(&mut buffer).await;
         }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Process) {
+            self.process_metrics(&mut buffer).await;
+        }
+        #[cfg(target_os = "linux")]
+        if self.config.has_collector(Collector::Tcp) {
+            self.tcp_metrics(&mut buffer).await;
+        }
 
         let metrics = buffer.metrics;
         emit!(EventsReceived {
@@ -555,6 +637,8 @@ pub(self) mod tests {
             Collector::Host,
             Collector::Memory,
             Collector::Network,
+            #[cfg(target_os = "linux")]
+            Collector::Process,
         ] {
             let some_metrics = HostMetrics::new(HostMetricsConfig {
                 collectors: Some(vec![*collector]),
@@ -0,0 +1,772 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    num::ParseIntError,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+use tokio::fs;
+use vector_common::btreemap;
+use vector_config::configurable_component;
+
+use super::{filter_result_sync, FilterList, HostMetrics, MetricsBuffer};
+use crate::internal_events::HostMetricsScrapeDetailError;
+
+/// The number of clock ticks per second the kernel reports process CPU times in.
+///
+/// This is `USER_HZ`, which the kernel fixes to 100 on every supported architecture.
+const CLOCK_TICKS_PER_SECOND: f64 = 100.0;
+
+/// The name of the group processes are reported in once `max_groups` is reached.
+const OTHER_GROUP: &str = "other";
+
+const fn default_max_groups() -> usize {
+    50
+}
+
+/// Options for the “process” metrics collector.
+///
+/// This collector is only available on Linux systems.
+///
+/// Processes are grouped by name, and the metrics of the processes of a group are summed, so that
+/// the number of reported series doesn't depend on the number of running processes. The CPU time
+/// and I/O counters of a group also keep what the processes that exited used while running.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(default)]
+pub(crate) struct ProcessConfig {
+    /// Lists of process name patterns to include or exclude.
+    ///
+    /// The name of a process is the name of its executable, as found in `/proc/<pid>/comm`.
+    names: FilterList,
+
+    /// A regular expression the command line of processes must match to be reported.
+    ///
+    /// The command line is made of the arguments of the process, separated by spaces.
+    pub(super) cmdline: Option<String>,
+
+    /// Lists of cgroup name patterns to include or exclude.
+    ///
+    /// The cgroup of a process is its cgroup in the version 2 hierarchy, or in the first hierarchy
+    /// listed in `/proc/<pid>/cgroup` otherwise.
+    cgroups: FilterList,
+
+    /// The maximum number of process groups to report metrics for.
+    ///
+    /// Groups are ordered by name, and the processes of the groups over the limit are reported in
+    /// the `other` group.
+    #[derivative(Default(value = "default_max_groups()"))]
+    max_groups: usize,
+
+    /// Base procfs directory, for testing use only
+    #[serde(skip_serializing)]
+    base_dir: Option<PathBuf>,
+}
+
+impl ProcessConfig {
+    pub(super) fn cmdline_regex(&self) -> Result<Option<Regex>, regex::Error> {
+        self.cmdline.as_deref().map(Regex::new).transpose()
+    }
+}
+
+#[derive(Debug, Snafu)]
+enum ProcessError {
+    #[snafu(display("Could not read process data file {:?}.", filename))]
+    Reading {
+        filename: PathBuf,
+        source: io::Error,
+    },
+    #[snafu(display("Could not parse process data file {:?}.", filename))]
+    Parsing {
+        filename: PathBuf,
+        source: ParseIntError,
+    },
+}
+
+type ProcessResult<T> = Result<T, ProcessError>;
+
+fn add_option(sum: &mut Option<u64>, value: Option<u64>) {
+    if let Some(value) = value {
+        *sum = Some(sum.unwrap_or(0) + value);
+    }
+}
+
+/// The statistics of processes that only ever increase while they run.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ProcessCounters {
+    user_ticks: u64,
+    system_ticks: u64,
+    /// Only known for the processes Vector is allowed to inspect.
+    read_bytes: Option<u64>,
+    /// Only known for the processes Vector is allowed to inspect.
+    written_bytes: Option<u64>,
+}
+
+impl ProcessCounters {
+    fn add(&mut self, other: &Self) {
+        self.user_ticks += other.user_ticks;
+        self.system_ticks += other.system_ticks;
+        add_option(&mut self.read_bytes, other.read_bytes);
+        add_option(&mut self.written_bytes, other.written_bytes);
+    }
+
+    /// Returns the increase of the counters since `previous`.
+    fn since(&self, previous: &Self) -> Self {
+        let since_option = |value: Option<u64>, previous: Option<u64>| {
+            value.map(|value| value.saturating_sub(previous.unwrap_or(0)))
+        };
+
+        Self {
+            user_ticks: self.user_ticks.saturating_sub(previous.user_ticks),
+            system_ticks: self.system_ticks.saturating_sub(previous.system_ticks),
+            read_bytes: since_option(self.read_bytes, previous.read_bytes),
+            written_bytes: since_option(self.written_bytes, previous.written_bytes),
+        }
+    }
+}
+
+/// The summed statistics of a group of processes.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ProcessStats {
+    processes: u64,
+    resident_bytes: u64,
+    threads: u64,
+    /// Only known for the processes Vector is allowed to inspect.
+    open_fds: Option<u64>,
+    counters: ProcessCounters,
+}
+
+impl ProcessStats {
+    fn add(&mut self, other: &Self) {
+        self.processes += other.processes;
+        self.resident_bytes += other.resident_bytes;
+        self.threads += other.threads;
+        add_option(&mut self.open_fds, other.open_fds);
+        self.counters.add(&other.counters);
+    }
+}
+
+/// Identifies a process, even once its PID is reused, by its PID and its start time.
+type ProcessKey = (u64, u64);
+
+/// The counters of the processes seen by the previous scrape, and those accumulated by each group.
+///
+/// Summing the counters of the running processes of a group would make the group counters decrease
+/// whenever one of its processes exits. Instead, the increase of the counters of each process since
+/// the previous scrape is added to the counters of its group, which are kept for as long as the
+/// group has running processes.
+#[derive(Debug, Default)]
+pub(super) struct ProcessState {
+    processes: HashMap<ProcessKey, ProcessCounters>,
+    groups: HashMap<String, ProcessCounters>,
+}
+
+impl ProcessState {
+    /// Accumulates the counters of the running `processes` into their group, and returns the
+    /// accumulated counters of the groups.
+    fn update(
+        &mut self,
+        processes: impl IntoIterator<Item = (ProcessKey, String, ProcessCounters)>,
+    ) -> &HashMap<String, ProcessCounters> {
+        let mut previous = std::mem::take(&mut self.processes);
+        let mut previous_groups = std::mem::take(&mut self.groups);
+
+        for (key, group, counters) in processes {
+            // Processes started since the previous scrape count from zero.
+            let increase = match previous.remove(&key) {
+                Some(previous) => counters.since(&previous),
+                None => counters.clone(),
+            };
+
+            self.groups
+                .entry(group)
+                .or_insert_with_key(|group| previous_groups.remove(group).unwrap_or_default())
+                .add(&increase);
+            self.processes.insert(key, counters);
+        }
+
+        &self.groups
+    }
+}
+
+impl HostMetrics {
+    pub(super) async fn process_metrics(&self, output: &mut MetricsBuffer) {
+        output.name = "process";
+        let config = &self.config.process;
+        let root = config
+            .base_dir
+            .clone()
+            .unwrap_or_else(|| Path::new(&heim::os::linux::procfs_root()).into());
+
+        let mut entries = match fs::read_dir(&root).await {
+            Ok(entries) => entries,
+            Err(error) => {
+                emit!(HostMetricsScrapeDetailError {
+                    message: "Failed to list processes.",
+                    error,
+                });
+                return;
+            }
+        };
+
+        let mut processes = Vec::new();
+        let mut groups = BTreeMap::<String, ProcessStats>::new();
+        loop {
+            let entry = match entries.next_entry().await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(error) => {
+                    emit!(HostMetricsScrapeDetailError {
+                        message: "Failed to list processes.",
+                        error,
+                    });
+                    break;
+                }
+            };
+            // Only the numeric entries of procfs are processes.
+            let is_process = entry
+                .file_name()
+                .to_str()
+                .map_or(false, |name| name.bytes().all(|byte| byte.is_ascii_digit()));
+            if !is_process {
+                continue;
+            }
+
+            let pid = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<u64>().ok());
+            if let (Some(pid), Some(Some((name, start_ticks, stats)))) = (
+                pid,
+                filter_result_sync(
+                    self.load_process(&entry.path()).await,
+                    "Failed to load process data.",
+                ),
+            ) {
+                processes.push(((pid, start_ticks), name.clone(), stats.counters.clone()));
+                groups.entry(name).or_default().add(&stats);
+            }
+        }
+
+        let mut groups = bound_groups(groups, config.max_groups);
+        {
+            let mut state = self.process_state.lock().expect("poisoned lock");
+            let counters = state.update(processes.into_iter().map(|(key, name, counters)| {
+                let group = if groups.contains_key(&name) {
+                    name
+                } else {
+                    OTHER_GROUP.to_owned()
+                };
+                (key, group, counters)
+            }));
+            for (name, stats) in &mut groups {
+                if let Some(counters) = counters.get(name) {
+                    stats.counters = counters.clone();
+                }
+            }
+        }
+
+        for (name, stats) in groups {
+            let tags = btreemap! { "name" => name };
+            let with_mode = |mode: &str| {
+                let mut tags = tags.clone();
+                tags.insert("mode".into(), mode.into());
+                tags
+            };
+
+            output.gauge("process_count", stats.processes as f64, tags.clone());
+            output.counter(
+                "process_cpu_seconds_total",
+                stats.counters.user_ticks as f64 / CLOCK_TICKS_PER_SECOND,
+                with_mode("user"),
+            );
+            output.counter(
+                "process_cpu_seconds_total",
+                stats.counters.system_ticks as f64 / CLOCK_TICKS_PER_SECOND,
+                with_mode("system"),
+            );
+            output.gauge(
+                "process_resident_memory_bytes",
+                stats.resident_bytes as f64,
+                tags.clone(),
+            );
+            output.gauge("process_threads", stats.threads as f64, tags.clone());
+            if let Some(open_fds) = stats.open_fds {
+                output.gauge("process_open_fds", open_fds as f64, tags.clone());
+            }
+            if let Some(read_bytes) = stats.counters.read_bytes {
+                output.counter("process_read_bytes_total", read_bytes as f64, tags.clone());
+            }
+            if let Some(written_bytes) = stats.counters.written_bytes {
+                output.counter(
+                    "process_written_bytes_total",
+                    written_bytes as f64,
+                    tags.clone(),
+                );
+            }
+        }
+    }
+
+    /// Loads the name, start time and statistics of the process with the given procfs directory.
+    /// Returns `Ok(None)` if the process is filtered out, or exited while being loaded.
+    async fn load_process(&self, dir: &Path) -> ProcessResult<Option<(String, u64, ProcessStats)>> {
+        let config = &self.config.process;
+
+        let name = match read_file(dir, "comm").await? {
+            Some(comm) => comm.trim_end_matches('\n').to_owned(),
+            None => return Ok(None),
+        };
+        if !config.names.contains_str(Some(&name)) {
+            return Ok(None);
+        }
+
+        if let Some(regex) = &self.process_cmdline {
+            let cmdline = match read_file(dir, "cmdline").await? {
+                Some(cmdline) => parse_cmdline(&cmdline),
+                None => return Ok(None),
+            };
+            if !regex.is_match(&cmdline) {
+                return Ok(None);
+            }
+        }
+
+        let cgroup = read_file(dir, "cgroup").await?;
+        let cgroup = cgroup.as_deref().and_then(parse_cgroup);
+        if !config.cgroups.contains_path(cgroup.map(Path::new)) {
+            return Ok(None);
+        }
+
+        let (user_ticks, system_ticks, start_ticks) = match read_file(dir, "stat").await? {
+            Some(stat) => parse_stat(&stat).with_context(|_| parsing_context(dir, "stat"))?,
+            None => return Ok(None),
+        };
+        let (resident_bytes, threads) = match read_file(dir, "status").await? {
+            Some(status) => {
+                parse_status(&status).with_context(|_| parsing_context(dir, "status"))?
+            }
+            None => return Ok(None),
+        };
+
+        // Reading the I/O statistics and file descriptors of the processes of other users
+        // requires privileges Vector may not have, so these are skipped on any error.
+        let io = read_file(dir, "io").await.ok().flatten();
+        let (read_bytes, written_bytes) = match io.as_deref().map(parse_io) {
+            Some(Ok((read_bytes, written_bytes))) => (Some(read_bytes), Some(written_bytes)),
+            _ => (None, None),
+        };
+        let open_fds = count_entries(&dir.join("fd")).await.ok();
+
+        Ok(Some((
+            name,
+            start_ticks,
+            ProcessStats {
+                processes: 1,
+                resident_bytes,
+                threads,
+                open_fds,
+                counters: ProcessCounters {
+                    user_ticks,
+                    system_ticks,
+                    read_bytes,
+                    written_bytes,
+                },
+            },
+        )))
+    }
+}
+
+/// Reads a file of a process directory. Returns `Ok(None)` if it doesn't exist, as happens when
+/// the process exits.
+async fn read_file(dir: &Path, filename: &str) -> ProcessResult<Option<String>> {
+    let path = dir.join(filename);
+    match fs::read(&path).await {
+        Ok(contents) => Ok(Some(String::from_utf8_lossy(&contents).into_owned())),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(ProcessError::Reading {
+            filename: path,
+            source,
+        }),
+    }
+}
+
+async fn count_entries(dir: &Path) -> io::Result<u64> {
+    let mut entries = fs::read_dir(dir).await?;
+    let mut count = 0;
+    while entries.next_entry().await?.is_some() {
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn parsing_context(dir: &Path, filename: &str) -> ParsingSnafu<PathBuf> {
+    ParsingSnafu {
+        filename: dir.join(filename),
+    }
+}
+
+/// Keeps the first `max_groups` groups, and sums the others into the `other` group.
+fn bound_groups(
+    groups: BTreeMap<String, ProcessStats>,
+    max_groups: usize,
+) -> BTreeMap<String, ProcessStats> {
+    let mut result = BTreeMap::new();
+    let mut other = ProcessStats::default();
+    for (index, (name, stats)) in groups.into_iter().enumerate() {
+        if index < max_groups {
+            result.insert(name, stats);
+        } else {
+            other.add(&stats);
+        }
+    }
+    if other.processes > 0 {
+        result
+            .entry(OTHER_GROUP.to_owned())
+            .or_insert_with(ProcessStats::default)
+            .add(&other);
+    }
+    result
+}
+
+/// Joins the NUL-separated arguments of `/proc/<pid>/cmdline` with spaces.
+fn parse_cmdline(cmdline: &str) -> String {
+    cmdline
+        .split('\0')
+        .filter(|argument| !argument.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Finds the cgroup of the process in `/proc/<pid>/cgroup`, preferring the version 2 hierarchy.
+fn parse_cgroup(cgroup: &str) -> Option<&str> {
+    let mut lines = cgroup.lines();
+    let first = lines.next()?;
+    std::iter::once(first)
+        .chain(lines)
+        .find_map(|line| line.strip_prefix("0::"))
+        .or_else(|| first.splitn(3, ':').nth(2))
+}
+
+/// Parses the user and system CPU times, and the start time of the process since boot, in clock
+/// ticks, from `/proc/<pid>/stat`.
+fn parse_stat(stat: &str) -> Result<(u64, u64, u64), ParseIntError> {
+    // The name of the process may hold spaces and parentheses, so the fields are counted from the
+    // last closing parenthesis. `utime`, `stime` and `starttime` are the 14th, 15th and 22nd
+    // fields, and the state, the 3rd one, is the first after the name.
+    let fields = stat
+        .rfind(')')
+        .map_or("", |end| &stat[end + 1..])
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    let field = |index: usize| fields.get(index).copied().unwrap_or("").parse::<u64>();
+    Ok((field(11)?, field(12)?, field(19)?))
+}
+
+/// Parses the resident memory, in bytes, and the number of threads from `/proc/<pid>/status`.
+fn parse_status(status: &str) -> Result<(u64, u64), ParseIntError> {
+    let mut resident_bytes = 0;
+    let mut threads = 0;
+    for line in status.lines() {
+        if let Some(value) = line.strip_prefix("VmRSS:") {
+            // Kernel threads have no `VmRSS` line.
+            resident_bytes = value.trim().trim_end_matches("kB").trim().parse::<u64>()? * 1024;
+        } else if let Some(value) = line.strip_prefix("Threads:") {
+            threads = value.trim().parse()?;
+        }
+    }
+    Ok((resident_bytes, threads))
+}
+
+/// Parses the bytes read from and written to storage from `/proc/<pid>/io`.
+fn parse_io(io: &str) -> Result<(u64, u64), ParseIntError> {
+    let mut read_bytes = 0;
+    let mut written_bytes = 0;
+    for line in io.lines() {
+        if let Some(value) = line.strip_prefix("read_bytes:") {
+            read_bytes = value.trim().parse()?;
+        } else if let Some(value) = line.strip_prefix("write_bytes:") {
+            written_bytes = value.trim().parse()?;
+        }
+    }
+    Ok((read_bytes, written_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::{
+        super::{
+            tests::{count_name, count_tag},
+            HostMetrics, HostMetricsConfig, MetricsBuffer,
+        },
+        *,
+    };
+    use crate::event::metric::{Metric, MetricValue};
+
+    #[test]
+    fn parses_process_files() {
+        assert_eq!(
+            parse_stat(
+                "42 (tmux: server) S 1 42 42 0 -1 4194560 2253 0 0 0 150 27 0 0 20 0 1 0 3000 4096"
+            ),
+            Ok((150, 27, 3000))
+        );
+        assert!(parse_stat("42 (short) S 1").is_err());
+        assert_eq!(
+            parse_status("Name:\tnginx\nVmRSS:\t    2048 kB\nThreads:\t4\n"),
+            Ok((2048 * 1024, 4))
+        );
+        assert_eq!(parse_status("Name:\tkthreadd\nThreads:\t1\n"), Ok((0, 1)));
+        assert_eq!(
+            parse_io("rchar: 10\nwchar: 20\nread_bytes: 4096\nwrite_bytes: 8192\n"),
+            Ok((4096, 8192))
+        );
+        assert_eq!(
+            parse_cmdline("nginx\0-g\0daemon off;\0"),
+            "nginx -g daemon off;"
+        );
+        assert_eq!(
+            parse_cgroup("12:pids:/user.slice\n0::/system.slice/nginx.service\n"),
+            Some("/system.slice/nginx.service")
+        );
+        assert_eq!(
+            parse_cgroup("12:pids:/user.slice\n11:cpu:/\n"),
+            Some("/user.slice")
+        );
+        assert_eq!(parse_cgroup(""), None);
+    }
+
+    #[test]
+    fn bounds_groups() {
+        let stats = ProcessStats {
+            processes: 1,
+            threads: 2,
+            open_fds: Some(3),
+            ..Default::default()
+        };
+        let groups = ["a", "b", "c", "d"]
+            .into_iter()
+            .map(|name| (name.to_owned(), stats.clone()))
+            .collect();
+
+        let bounded = bound_groups(groups, 2);
+
+        assert_eq!(
+            bounded.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["a", "b", OTHER_GROUP]
+        );
+        assert_eq!(
+            bounded[OTHER_GROUP],
+            ProcessStats {
+                processes: 2,
+                threads: 4,
+                open_fds: Some(6),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn accumulates_counters_of_exited_processes() {
+        let counters = |user_ticks| ProcessCounters {
+            user_ticks,
+            read_bytes: Some(user_ticks * 10),
+            ..Default::default()
+        };
+        let mut state = ProcessState::default();
+
+        let groups = state.update([
+            ((1, 0), "nginx".to_owned(), counters(100)),
+            ((2, 0), "nginx".to_owned(), counters(50)),
+        ]);
+        assert_eq!(groups["nginx"], counters(150));
+
+        // The second process exited, and its PID was reused by a new one.
+        let groups = state.update([
+            ((1, 0), "nginx".to_owned(), counters(120)),
+            ((2, 10), "nginx".to_owned(), counters(5)),
+        ]);
+        assert_eq!(groups["nginx"], counters(175));
+
+        // Groups are forgotten once they have no running processes.
+        let groups = state.update([((3, 20), "bash".to_owned(), counters(1))]);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups["bash"], counters(1));
+    }
+
+    #[tokio::test]
+    async fn generates_process_metrics() {
+        let config: HostMetricsConfig = toml::from_str(r#"collectors = ["process"]"#).unwrap();
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(config).process_metrics(&mut buffer).await;
+        let metrics = buffer.metrics;
+
+        assert!(!metrics.is_empty());
+        assert_eq!(count_tag(&metrics, "name"), metrics.len());
+        assert_ne!(count_name(&metrics, "process_count"), 0);
+        assert_ne!(count_name(&metrics, "process_cpu_seconds_total"), 0);
+        assert_ne!(count_name(&metrics, "process_resident_memory_bytes"), 0);
+        assert_ne!(count_name(&metrics, "process_threads"), 0);
+    }
+
+    struct Setup(TempDir);
+
+    impl Setup {
+        fn new() -> Self {
+            Self(tempfile::tempdir().unwrap())
+        }
+
+        fn process(&self, pid: u32, name: &str, cmdline: &str, cgroup: &str) {
+            let dir = self.0.path().join(pid.to_string());
+            std::fs::create_dir_all(dir.join("fd")).unwrap();
+            for fd in 0..3 {
+                std::fs::write(dir.join("fd").join(fd.to_string()), "").unwrap();
+            }
+            let files = [
+                ("comm", format!("{name}\n")),
+                ("cmdline", cmdline.replace(' ', "\0")),
+                ("cgroup", format!("0::{cgroup}\n")),
+                (
+                    "stat",
+                    format!("{pid} ({name}) S 1 1 1 0 -1 0 0 0 0 0 250 50 0 0 20 0 2 0 100 4096"),
+                ),
+                (
+                    "status",
+                    format!("Name:\t{name}\nVmRSS:\t1 kB\nThreads:\t2\n"),
+                ),
+                ("io", "read_bytes: 10\nwrite_bytes: 20\n".to_owned()),
+            ];
+            for (filename, contents) in files {
+                std::fs::write(dir.join(filename), contents).unwrap();
+            }
+        }
+
+        async fn metrics(&self, options: &str) -> Vec<Metric> {
+            let path = self.0.path();
+            let config: HostMetricsConfig = toml::from_str(&format!(
+                r#"
+                collectors = ["process"]
+                process.base_dir = {path:?}
+                {options}
+                "#
+            ))
+            .unwrap();
+            let mut buffer = MetricsBuffer::new(None);
+            HostMetrics::new(config).process_metrics(&mut buffer).await;
+            buffer.metrics
+        }
+    }
+
+    fn value(metrics: &[Metric], name: &str, group: &str) -> Option<f64> {
+        metrics
+            .iter()
+            .find(|metric| {
+                metric.name() == name
+                    && metric.tag_value("name").as_deref() == Some(group)
+                    && metric.tag_value("mode").as_deref() != Some("system")
+            })
+            .map(|metric| match metric.value() {
+                MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+                other => panic!("unexpected value {:?}", other),
+            })
+    }
+
+    fn groups(metrics: &[Metric]) -> Vec<String> {
+        let mut groups = metrics
+            .iter()
+            .filter_map(|metric| metric.tag_value("name"))
+            .collect::<Vec<_>>();
+        groups.sort();
+        groups.dedup();
+        groups
+    }
+
+    #[tokio::test]
+    async fn sums_processes_by_name() {
+        let setup = Setup::new();
+        setup.process(
+            10,
+            "nginx",
+            "nginx -g daemon",
+            "/system.slice/nginx.service",
+        );
+        setup.process(
+            11,
+            "nginx",
+            "nginx -g daemon",
+            "/system.slice/nginx.service",
+        );
+        setup.process(20, "bash", "-bash", "/user.slice");
+        // Entries other than process directories are ignored.
+        std::fs::create_dir_all(setup.0.path().join("sys")).unwrap();
+
+        let metrics = setup.metrics("").await;
+
+        assert_eq!(groups(&metrics), vec!["bash", "nginx"]);
+        assert_eq!(value(&metrics, "process_count", "nginx"), Some(2.0));
+        assert_eq!(
+            value(&metrics, "process_cpu_seconds_total", "nginx"),
+            Some(5.0)
+        );
+        assert_eq!(
+            value(&metrics, "process_resident_memory_bytes", "nginx"),
+            Some(2048.0)
+        );
+        assert_eq!(value(&metrics, "process_threads", "nginx"), Some(4.0));
+        assert_eq!(value(&metrics, "process_open_fds", "nginx"), Some(6.0));
+        assert_eq!(
+            value(&metrics, "process_read_bytes_total", "nginx"),
+            Some(20.0)
+        );
+        assert_eq!(
+            value(&metrics, "process_written_bytes_total", "bash"),
+            Some(20.0)
+        );
+        assert!(metrics
+            .iter()
+            .all(|metric| metric.tag_value("collector").as_deref() == Some("process")));
+    }
+
+    #[tokio::test]
+    async fn filters_processes() {
+        let setup = Setup::new();
+        setup.process(
+            10,
+            "nginx",
+            "nginx -g daemon",
+            "/system.slice/nginx.service",
+        );
+        setup.process(20, "bash", "-bash", "/user.slice");
+        setup.process(
+            30,
+            "python3",
+            "python3 worker.py",
+            "/system.slice/worker.service",
+        );
+
+        let names = setup.metrics(r#"process.names.excludes = ["ba*"]"#).await;
+        assert_eq!(groups(&names), vec!["nginx", "python3"]);
+
+        let cmdline = setup.metrics(r#"process.cmdline = "worker\\.py$""#).await;
+        assert_eq!(groups(&cmdline), vec!["python3"]);
+
+        let cgroups = setup
+            .metrics(r#"process.cgroups.includes = ["/system.slice/*"]"#)
+            .await;
+        assert_eq!(groups(&cgroups), vec!["nginx", "python3"]);
+
+        let bounded = setup.metrics("process.max_groups = 1").await;
+        assert_eq!(groups(&bounded), vec!["bash", OTHER_GROUP]);
+        assert_eq!(value(&bounded, "process_count", OTHER_GROUP), Some(2.0));
+    }
+
+    #[test]
+    fn rejects_invalid_cmdline() {
+        let config: HostMetricsConfig = toml::from_str(r#"process.cmdline = "(""#).unwrap();
+        assert!(config.process.cmdline_regex().is_err());
+        assert!(HostMetrics::try_new(config).is_err());
+    }
+}
@@ -0,0 +1,632 @@
+use std::{
+    collections::BTreeMap,
+    io,
+    os::unix::io::RawFd,
+    path::{Path, PathBuf},
+};
+
+use nix::sys::socket::{self, AddressFamily, MsgFlags, SockFlag, SockProtocol, SockType};
+use snafu::Snafu;
+use tokio::fs;
+use vector_common::btreemap;
+use vector_config::configurable_component;
+
+use super::{filter_result_sync, FilterList, HostMetrics, MetricsBuffer};
+
+/// The names of the connection states, indexed by their value in `/proc/net/tcp`.
+const STATES: [&str; 13] = [
+    "unknown",
+    "established",
+    "syn_sent",
+    "syn_recv",
+    "fin_wait1",
+    "fin_wait2",
+    "time_wait",
+    "close",
+    "close_wait",
+    "last_ack",
+    "listen",
+    "closing",
+    "new_syn_recv",
+];
+
+/// The value of `TCP_LISTEN` in the connection states.
+const LISTEN: usize = 10;
+
+/// The counters of the `Tcp` section of `/proc/net/snmp`, and the metrics they're reported as.
+const SNMP_COUNTERS: [(&str, &str); 9] = [
+    ("ActiveOpens", "tcp_active_opens_total"),
+    ("PassiveOpens", "tcp_passive_opens_total"),
+    ("AttemptFails", "tcp_attempt_fails_total"),
+    ("EstabResets", "tcp_established_resets_total"),
+    ("InSegs", "tcp_received_segments_total"),
+    ("OutSegs", "tcp_sent_segments_total"),
+    ("RetransSegs", "tcp_retransmitted_segments_total"),
+    ("InErrs", "tcp_receive_errors_total"),
+    ("OutRsts", "tcp_sent_resets_total"),
+];
+
+/// The counters of the `TcpExt` section of `/proc/net/netstat`, and the metrics they're reported
+/// as.
+const NETSTAT_COUNTERS: [(&str, &str); 6] = [
+    ("ListenOverflows", "tcp_listen_overflows_total"),
+    ("ListenDrops", "tcp_listen_drops_total"),
+    ("TCPTimeouts", "tcp_timeouts_total"),
+    ("TCPSynRetrans", "tcp_syn_retransmits_total"),
+    ("TCPLostRetransmit", "tcp_lost_retransmits_total"),
+    ("SyncookiesSent", "tcp_syncookies_sent_total"),
+];
+
+/// Options for the “tcp” metrics collector.
+///
+/// This collector is only available on Linux systems, and isn't enabled by default, as listing the
+/// connections of busy hosts is expensive.
+///
+/// Connections are listed through the `sock_diag` netlink interface, and read from
+/// `/proc/net/tcp` and `/proc/net/tcp6` when it isn't available.
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(default)]
+pub(crate) struct TcpConfig {
+    /// Lists of local port patterns to include or exclude.
+    ///
+    /// Only the connections whose local port matches are counted in the connection metrics. The
+    /// protocol counters cover all connections.
+    ports: FilterList,
+
+    /// Base procfs directory, for testing use only
+    #[serde(skip_serializing)]
+    base_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Snafu)]
+enum TcpError {
+    #[snafu(display("Could not read TCP data file {:?}.", filename))]
+    Reading {
+        filename: PathBuf,
+        source: io::Error,
+    },
+    #[snafu(display("Could not parse TCP data file {:?}.", filename))]
+    Parsing { filename: PathBuf },
+}
+
+type TcpResult<T> = Result<T, TcpError>;
+
+/// The connections of a state.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+struct ConnectionStats {
+    connections: u64,
+    tx_queued_bytes: u64,
+    rx_queued_bytes: u64,
+}
+
+impl HostMetrics {
+    pub(super) async fn tcp_metrics(&self, output: &mut MetricsBuffer) {
+        output.name = "tcp";
+        let net = self
+            .config
+            .tcp
+            .base_dir
+            .clone()
+            .unwrap_or_else(|| Path::new(&heim::os::linux::procfs_root()).into())
+            .join("net");
+
+        if let Some(states) = filter_result_sync(
+            self.load_connections(&net).await,
+            "Failed to load TCP connections.",
+        ) {
+            for (state, stats) in STATES.iter().zip(states).skip(1) {
+                let tags = btreemap! { "state" => *state };
+                output.gauge("tcp_connections", stats.connections as f64, tags.clone());
+                output.gauge(
+                    "tcp_tx_queued_bytes",
+                    stats.tx_queued_bytes as f64,
+                    tags.clone(),
+                );
+                output.gauge("tcp_rx_queued_bytes", stats.rx_queued_bytes as f64, tags);
+            }
+        }
+
+        let sections = [
+            ("snmp", "Tcp", &SNMP_COUNTERS[..]),
+            ("netstat", "TcpExt", &NETSTAT_COUNTERS[..]),
+        ];
+        for (filename, section, counters) in sections {
+            if let Some(Some(values)) = filter_result_sync(
+                load_counters(&net.join(filename), section).await,
+                "Failed to load TCP counters.",
+            ) {
+                for (field, name) in counters {
+                    if let Some(value) = values.get(*field) {
+                        output.counter(name, *value as f64, BTreeMap::new());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sums the connections of the host by state.
+    async fn load_connections(&self, net: &Path) -> TcpResult<[ConnectionStats; STATES.len()]> {
+        // The procfs files of tests are read directly.
+        let connections = if self.config.tcp.base_dir.is_none() {
+            let result = tokio::task::spawn_blocking(sock_diag_connections)
+                .await
+                .unwrap_or_else(|error| Err(io::Error::new(io::ErrorKind::Other, error)));
+            match result {
+                Ok(connections) => Some(connections),
+                Err(error) => {
+                    debug!(message = "Failed to list TCP connections through netlink, reading procfs instead.", %error);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let connections = match connections {
+            Some(connections) => connections,
+            None => load_proc_connections(net).await?,
+        };
+
+        let mut states = [ConnectionStats::default(); STATES.len()];
+        for connection in connections {
+            if !self
+                .config
+                .tcp
+                .ports
+                .contains_str(Some(&connection.local_port.to_string()))
+            {
+                continue;
+            }
+            if let Some(stats) = states.get_mut(connection.state) {
+                stats.connections += 1;
+                stats.tx_queued_bytes += connection.tx_queued_bytes;
+                stats.rx_queued_bytes += connection.rx_queued_bytes;
+            }
+        }
+        Ok(states)
+    }
+}
+
+/// Reads the connections of `/proc/net/tcp` and `/proc/net/tcp6`.
+async fn load_proc_connections(net: &Path) -> TcpResult<Vec<Connection>> {
+    let mut connections = Vec::new();
+    for filename in ["tcp", "tcp6"] {
+        let path = net.join(filename);
+        let contents = match fs::read_to_string(&path).await {
+            Ok(contents) => contents,
+            // `tcp6` is missing when IPv6 is disabled.
+            Err(error) if error.kind() == io::ErrorKind::NotFound && filename == "tcp6" => continue,
+            Err(source) => {
+                return Err(TcpError::Reading {
+                    filename: path,
+                    source,
+                })
+            }
+        };
+
+        for line in contents.lines().skip(1) {
+            let connection = parse_connection(line).ok_or_else(|| TcpError::Parsing {
+                filename: path.clone(),
+            })?;
+            connections.push(connection);
+        }
+    }
+    Ok(connections)
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Connection {
+    local_port: u16,
+    state: usize,
+    tx_queued_bytes: u64,
+    rx_queued_bytes: u64,
+}
+
+/// Parses a line of `/proc/net/tcp`, such as
+/// `0: 0100007F:0CEA 00000000:0000 0A 00000000:00000001 00:00000000 00000000 ...`.
+fn parse_connection(line: &str) -> Option<Connection> {
+    let mut fields = line.split_whitespace().skip(1);
+    let local_address = fields.next()?;
+    let _remote_address = fields.next()?;
+    let state = fields.next()?;
+    let (tx_queue, rx_queue) = fields.next()?.split_once(':')?;
+
+    let (_, local_port) = local_address.rsplit_once(':')?;
+    Some(Connection {
+        local_port: u16::from_str_radix(local_port, 16).ok()?,
+        state: usize::from_str_radix(state, 16).ok()?,
+        tx_queued_bytes: u64::from_str_radix(tx_queue, 16).ok()?,
+        rx_queued_bytes: u64::from_str_radix(rx_queue, 16).ok()?,
+    })
+}
+
+// The netlink constants of `linux/netlink.h`, `linux/sock_diag.h` and `linux/inet_diag.h`.
+const NLMSG_HEADER_SIZE: usize = 16;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const NLM_F_REQUEST: u16 = 0x1;
+const NLM_F_DUMP: u16 = 0x300;
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const INET_DIAG_REQ_SIZE: usize = 56;
+const INET_DIAG_MSG_SIZE: usize = 72;
+const AF_INET: u8 = 2;
+const AF_INET6: u8 = 10;
+const IPPROTO_TCP: u8 = 6;
+
+/// A netlink socket, closed when dropped.
+struct NetlinkSocket(RawFd);
+
+impl Drop for NetlinkSocket {
+    fn drop(&mut self) {
+        let _ = nix::unistd::close(self.0);
+    }
+}
+
+/// Lists the connections of the host through the `sock_diag` netlink interface, which is much
+/// cheaper than formatting and parsing `/proc/net/tcp` on hosts with many connections.
+fn sock_diag_connections() -> io::Result<Vec<Connection>> {
+    let socket = NetlinkSocket(socket::socket(
+        AddressFamily::Netlink,
+        SockType::Datagram,
+        SockFlag::SOCK_CLOEXEC,
+        SockProtocol::NetlinkSockDiag,
+    )?);
+
+    let mut connections = Vec::new();
+    let mut buffer = vec![0; 64 * 1024];
+    for family in [AF_INET, AF_INET6] {
+        socket::send(socket.0, &sock_diag_request(family), MsgFlags::empty())?;
+        loop {
+            let length = socket::recv(socket.0, &mut buffer, MsgFlags::empty())?;
+            if parse_sock_diag_response(&buffer[..length], &mut connections)? {
+                break;
+            }
+        }
+    }
+    Ok(connections)
+}
+
+/// Builds the request dumping the TCP sockets of an address family, in any state.
+fn sock_diag_request(family: u8) -> Vec<u8> {
+    let length = NLMSG_HEADER_SIZE + INET_DIAG_REQ_SIZE;
+    let mut request = Vec::with_capacity(length);
+    request.extend((length as u32).to_ne_bytes());
+    request.extend(SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    request.extend((NLM_F_REQUEST | NLM_F_DUMP).to_ne_bytes());
+    // The sequence number and port ID.
+    request.extend([0; 8]);
+    // The family, protocol, extensions and padding, followed by the states to dump.
+    request.extend([family, IPPROTO_TCP, 0, 0]);
+    request.extend(u32::MAX.to_ne_bytes());
+    // The socket ID, left empty to match any socket.
+    request.resize(length, 0);
+    request
+}
+
+/// Parses the netlink messages of a response to [`sock_diag_request`], returning `true` once the
+/// dump is complete.
+fn parse_sock_diag_response(
+    mut buffer: &[u8],
+    connections: &mut Vec<Connection>,
+) -> io::Result<bool> {
+    let invalid = || io::Error::new(io::ErrorKind::InvalidData, "invalid sock_diag response");
+
+    while buffer.len() >= NLMSG_HEADER_SIZE {
+        let length = u32::from_ne_bytes(buffer[0..4].try_into().expect("4 bytes")) as usize;
+        let kind = u16::from_ne_bytes(buffer[4..6].try_into().expect("2 bytes"));
+        if length < NLMSG_HEADER_SIZE || length > buffer.len() {
+            return Err(invalid());
+        }
+        let payload = &buffer[NLMSG_HEADER_SIZE..length];
+
+        match kind {
+            NLMSG_DONE => return Ok(true),
+            NLMSG_ERROR => {
+                let code = payload.get(0..4).ok_or_else(invalid)?;
+                let code = i32::from_ne_bytes(code.try_into().expect("4 bytes"));
+                return Err(io::Error::from_raw_os_error(-code));
+            }
+            SOCK_DIAG_BY_FAMILY => {
+                if payload.len() < INET_DIAG_MSG_SIZE {
+                    return Err(invalid());
+                }
+                let state = payload[1] as usize;
+                let queue = |offset: usize| {
+                    u32::from_ne_bytes(payload[offset..offset + 4].try_into().expect("4 bytes"))
+                };
+                connections.push(Connection {
+                    local_port: u16::from_be_bytes([payload[4], payload[5]]),
+                    state,
+                    // The send queue of listening sockets holds their maximum backlog, which
+                    // `/proc/net/tcp` doesn't report.
+                    tx_queued_bytes: if state == LISTEN { 0 } else { queue(60) as u64 },
+                    rx_queued_bytes: queue(56) as u64,
+                });
+            }
+            _ => {}
+        }
+
+        // Messages are aligned on 4 bytes.
+        buffer = buffer.get((length + 3) & !3..).unwrap_or_default();
+    }
+    Ok(false)
+}
+
+/// Loads the counters of a section of `/proc/net/snmp` or `/proc/net/netstat`. Returns `Ok(None)`
+/// if the file doesn't exist.
+async fn load_counters(path: &Path, section: &str) -> TcpResult<Option<BTreeMap<String, u64>>> {
+    match fs::read_to_string(path).await {
+        Ok(contents) => Ok(Some(parse_counters(&contents, section))),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(TcpError::Reading {
+            filename: path.into(),
+            source,
+        }),
+    }
+}
+
+/// Parses a section of `/proc/net/snmp` or `/proc/net/netstat`, made of a line of field names
+/// followed by a line of values, both prefixed with the name of the section.
+fn parse_counters(contents: &str, section: &str) -> BTreeMap<String, u64> {
+    let prefix = format!("{}:", section);
+    let mut lines = contents
+        .lines()
+        .filter_map(|line| line.strip_prefix(prefix.as_str()));
+    match (lines.next(), lines.next()) {
+        (Some(names), Some(values)) => names
+            .split_whitespace()
+            .zip(values.split_whitespace())
+            // Some counters, such as `MaxConn`, may be negative, and aren't reported anyway.
+            .filter_map(|(name, value)| Some((name.to_owned(), value.parse().ok()?)))
+            .collect(),
+        _ => BTreeMap::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::{
+        super::{tests::count_name, Collector, HostMetrics, HostMetricsConfig, MetricsBuffer},
+        *,
+    };
+    use crate::event::metric::{Metric, MetricValue};
+
+    const TCP: &str = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000003 00:00000000 00000000     0        0 1 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1F90 0100007F:D2F0 01 00000010:00000000 00:00000000 00000000     0        0 2 1 0000000000000000 20 4 30 10 -1
+   2: 0100007F:0016 0100007F:D2F2 01 00000000:00000020 00:00000000 00000000     0        0 3 1 0000000000000000 20 4 30 10 -1
+";
+
+    const TCP6: &str = "  sl  local_address                         remote_address                        st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000000000000000000001000000:1F90 00000000000000000000000000000000:0000 06 00000000:00000000 00:00000000 00000000     0        0 4 1 0000000000000000 100 0 0 10 0
+";
+
+    const SNMP: &str = "Ip: Forwarding DefaultTTL
+Ip: 1 64
+Tcp: RtoAlgorithm RtoMin RtoMax MaxConn ActiveOpens PassiveOpens AttemptFails EstabResets CurrEstab InSegs OutSegs RetransSegs InErrs OutRsts InCsumErrors
+Tcp: 1 200 120000 -1 10 20 1 2 3 1000 900 7 0 4 0
+";
+
+    const NETSTAT: &str =
+        "TcpExt: SyncookiesSent SyncookiesRecv ListenOverflows ListenDrops TCPTimeouts
+TcpExt: 0 0 5 6 8
+IpExt: InNoRoutes
+IpExt: 0
+";
+
+    #[test]
+    fn parses_connections() {
+        assert_eq!(
+            parse_connection(TCP.lines().nth(2).unwrap()),
+            Some(Connection {
+                local_port: 8080,
+                state: 1,
+                tx_queued_bytes: 16,
+                rx_queued_bytes: 0,
+            })
+        );
+        assert_eq!(parse_connection("   0: 0100007F:1F90"), None);
+    }
+
+    /// Builds a netlink message of the given type holding `payload`.
+    fn netlink_message(kind: u16, payload: &[u8]) -> Vec<u8> {
+        let mut message = ((NLMSG_HEADER_SIZE + payload.len()) as u32)
+            .to_ne_bytes()
+            .to_vec();
+        message.extend(kind.to_ne_bytes());
+        message.extend([0; 10]);
+        message.extend(payload);
+        message
+    }
+
+    fn inet_diag_msg(state: u8, port: u16, rqueue: u32, wqueue: u32) -> Vec<u8> {
+        let mut message = vec![0; INET_DIAG_MSG_SIZE];
+        message[0] = AF_INET;
+        message[1] = state;
+        message[4..6].copy_from_slice(&port.to_be_bytes());
+        message[56..60].copy_from_slice(&rqueue.to_ne_bytes());
+        message[60..64].copy_from_slice(&wqueue.to_ne_bytes());
+        message
+    }
+
+    #[test]
+    fn parses_sock_diag_responses() {
+        let request = sock_diag_request(AF_INET6);
+        assert_eq!(request.len(), NLMSG_HEADER_SIZE + INET_DIAG_REQ_SIZE);
+        assert_eq!(request[16], AF_INET6);
+
+        let mut response = netlink_message(SOCK_DIAG_BY_FAMILY, &inet_diag_msg(10, 8080, 3, 128));
+        response.extend(netlink_message(
+            SOCK_DIAG_BY_FAMILY,
+            &inet_diag_msg(1, 22, 32, 16),
+        ));
+        let mut connections = Vec::new();
+        assert!(!parse_sock_diag_response(&response, &mut connections).unwrap());
+        assert_eq!(
+            connections,
+            vec![
+                Connection {
+                    local_port: 8080,
+                    state: LISTEN,
+                    tx_queued_bytes: 0,
+                    rx_queued_bytes: 3,
+                },
+                Connection {
+                    local_port: 22,
+                    state: 1,
+                    tx_queued_bytes: 16,
+                    rx_queued_bytes: 32,
+                },
+            ]
+        );
+
+        let done = netlink_message(NLMSG_DONE, &[0; 4]);
+        assert!(parse_sock_diag_response(&done, &mut connections).unwrap());
+
+        let error = netlink_message(NLMSG_ERROR, &(-13i32).to_ne_bytes());
+        assert_eq!(
+            parse_sock_diag_response(&error, &mut connections)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::PermissionDenied
+        );
+        assert!(parse_sock_diag_response(&response[..20], &mut connections).is_err());
+    }
+
+    #[test]
+    fn parses_counters() {
+        let counters = parse_counters(SNMP, "Tcp");
+        assert_eq!(counters.get("RetransSegs"), Some(&7));
+        assert_eq!(counters.get("MaxConn"), None);
+        assert_eq!(
+            parse_counters(NETSTAT, "TcpExt").get("ListenOverflows"),
+            Some(&5)
+        );
+        assert!(parse_counters(NETSTAT, "Tcp").is_empty());
+    }
+
+    #[test]
+    fn is_not_a_default_collector() {
+        assert!(!HostMetricsConfig::default().has_collector(Collector::Tcp));
+    }
+
+    #[tokio::test]
+    async fn generates_tcp_metrics() {
+        let config: HostMetricsConfig = toml::from_str(r#"collectors = ["tcp"]"#).unwrap();
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(config).tcp_metrics(&mut buffer).await;
+        let metrics = buffer.metrics;
+
+        assert_eq!(count_name(&metrics, "tcp_connections"), STATES.len() - 1);
+        assert_ne!(count_name(&metrics, "tcp_retransmitted_segments_total"), 0);
+    }
+
+    async fn load_metrics(options: &str) -> Vec<Metric> {
+        let dir = TempDir::new().unwrap();
+        let net = dir.path().join("net");
+        std::fs::create_dir(&net).unwrap();
+        for (filename, contents) in [
+            ("tcp", TCP),
+            ("tcp6", TCP6),
+            ("snmp", SNMP),
+            ("netstat", NETSTAT),
+        ] {
+            std::fs::write(net.join(filename), contents).unwrap();
+        }
+
+        let path = dir.path();
+        let config: HostMetricsConfig = toml::from_str(&format!(
+            r#"
+            collectors = ["tcp"]
+            tcp.base_dir = {path:?}
+            {options}
+            "#
+        ))
+        .unwrap();
+        let mut buffer = MetricsBuffer::new(None);
+        HostMetrics::new(config).tcp_metrics(&mut buffer).await;
+        buffer.metrics
+    }
+
+    fn value(metrics: &[Metric], name: &str, state: Option<&str>) -> Option<f64> {
+        metrics
+            .iter()
+            .find(|metric| metric.name() == name && metric.tag_value("state").as_deref() == state)
+            .map(|metric| match metric.value() {
+                MetricValue::Counter { value } | MetricValue::Gauge { value } => *value,
+                other => panic!("unexpected value {:?}", other),
+            })
+    }
+
+    #[tokio::test]
+    async fn counts_connections_by_state() {
+        let metrics = load_metrics("").await;
+
+        assert_eq!(
+            value(&metrics, "tcp_connections", Some("listen")),
+            Some(1.0)
+        );
+        assert_eq!(
+            value(&metrics, "tcp_connections", Some("established")),
+            Some(2.0)
+        );
+        assert_eq!(
+            value(&metrics, "tcp_connections", Some("time_wait")),
+            Some(1.0)
+        );
+        assert_eq!(
+            value(&metrics, "tcp_connections", Some("closing")),
+            Some(0.0)
+        );
+        assert_eq!(
+            value(&metrics, "tcp_rx_queued_bytes", Some("listen")),
+            Some(3.0)
+        );
+        assert_eq!(
+            value(&metrics, "tcp_tx_queued_bytes", Some("established")),
+            Some(16.0)
+        );
+        assert_eq!(value(&metrics, "tcp_active_opens_total", None), Some(10.0));
+        assert_eq!(
+            value(&metrics, "tcp_retransmitted_segments_total", None),
+            Some(7.0)
+        );
+        assert_eq!(
+            value(&metrics, "tcp_listen_overflows_total", None),
+            Some(5.0)
+        );
+        assert_eq!(value(&metrics, "tcp_syn_retransmits_total", None), None);
+        assert!(metrics
+            .iter()
+            .all(|metric| metric.tag_value("collector").as_deref() == Some("tcp")));
+    }
+
+    #[tokio::test]
+    async fn filters_on_local_port() {
+        let metrics = load_metrics(r#"tcp.ports.excludes = ["22"]"#).await;
+        assert_eq!(
+            value(&metrics, "tcp_connections", Some("established")),
+            Some(1.0)
+        );
+        assert_eq!(
+            value(&metrics, "tcp_rx_queued_bytes", Some("established")),
+            Some(0.0)
+        );
+
+        let metrics = load_metrics(r#"tcp.ports.includes = ["22", "44*"]"#).await;
+        assert_eq!(
+            value(&metrics, "tcp_connections", Some("established")),
+            Some(1.0)
+        );
+        assert_eq!(
+            value(&metrics, "tcp_connections", Some("listen")),
+            Some(0.0)
+        );
+        assert_eq!(
+            value(&metrics, "tcp_connections", Some("time_wait")),
+            Some(0.0)
+        );
+    }
+}
@@ -61,6 +61,8 @@ const RECEIVED_TIMESTAMP: &str = "__REALTIME_TIMESTAMP";
 
 const BACKOFF_DURATION: Duration = Duration::from_secs(1);
 
+mod native;
+
 static JOURNALCTL: Lazy<PathBuf> = Lazy::new(|| "journalctl".into());
 
 #[derive(Debug, Snafu)]
@@ -84,6 +86,22 @@ enum BuildError {
 
 type Matches = HashMap<String, HashSet<String>>;
 
+/// How the journal is read.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum JournalReader {
+    /// Spawn `journalctl` and read its JSON output.
+    #[derivative(Default)]
+    Journalctl,
+
+    /// Read the journal files directly, without requiring `journalctl`.
+    ///
+    /// Files compressed with LZ4 or zstd are supported, but not those compressed with XZ, which the reader refuses to start on.
+    Native,
+}
+
 /// Configuration for the `journald` source.
 #[configurable_component(source("journald"))]
 #[derive(Clone, Debug, Default)]
@@ -137,9 +155,14 @@ pub struct JournaldConfig {
 
     /// The full path of the journal directory.
     ///
-    /// If not set, `journalctl` will use the default system journal paths.
+    /// If not set, the default system journal paths are used, which the native reader reads from `/var/log/journal` and `/run/log/journal`.
     pub journal_directory: Option<PathBuf>,
 
+    /// How the journal is read.
+    ///
+    /// The checkpoints of both readers are compatible, so the reader can be changed without reading the journal again.
+    pub reader: JournalReader,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: AcknowledgementsConfig,
@@ -220,12 +243,21 @@ impl SourceConfig for JournaldConfig {
             .clone()
             .unwrap_or_else(|| JOURNALCTL.clone());
 
-        let starter = StartJournalctl::new(
-            journalctl_path,
-            self.journal_directory.clone(),
-            self.current_boot_only.unwrap_or(true),
-            self.since_now.unwrap_or(false),
-        );
+        let current_boot_only = self.current_boot_only.unwrap_or(true);
+        let since_now = self.since_now.unwrap_or(false);
+        let starter = match self.reader {
+            JournalReader::Journalctl => StartReader::Journalctl(StartJournalctl::new(
+                journalctl_path,
+                self.journal_directory.clone(),
+                current_boot_only,
+                since_now,
+            )),
+            JournalReader::Native => StartReader::Native(native::StartNative::new(
+                self.journal_directory.clone(),
+                current_boot_only,
+                since_now,
+            )),
+        };
 
         let batch_size = self.batch_size.unwrap_or(DEFAULT_BATCH_SIZE);
         let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
@@ -262,7 +294,7 @@ struct JournaldSource {
     remap_priority: bool,
     out: SourceSender,
     acknowledgements: bool,
-    starter: StartJournalctl,
+    starter: StartReader,
 }
 
 impl JournaldSource {
@@ -303,7 +335,10 @@ impl JournaldSource {
                 break;
             }
 
-            info!("Starting journalctl.");
+            match self.starter {
+                StartReader::Journalctl(_) => info!("Starting journalctl."),
+                StartReader::Native(_) => info!("Starting native journal reader."),
+            }
             let cursor = checkpointer.lock().await.cursor.clone();
             match self.starter.start(cursor.as_deref()) {
                 Ok((stream, running)) => {
@@ -318,8 +353,8 @@ impl JournaldSource {
                 }
             }
 
-            // journalctl process should never stop,
-            // so it is an error if we reach here.
+            // Neither the journalctl process nor the native reader should
+            // ever stop, so it is an error if we reach here.
             tokio::select! {
                 _ = &mut shutdown => break,
                 _ = sleep(BACKOFF_DURATION) => (),
@@ -472,6 +507,27 @@ impl<'a> Batch<'a> {
 
 type JournalStream = BoxStream<'static, Result<Bytes, BoxedFramingError>>;
 
+enum StartReader {
+    Journalctl(StartJournalctl),
+    Native(native::StartNative),
+}
+
+impl StartReader {
+    /// Starts reading the journal after the given cursor. The returned process, if any, must be
+    /// kept alive while the stream is read.
+    fn start(
+        &mut self,
+        checkpoint: Option<&str>,
+    ) -> crate::Result<(JournalStream, Option<RunningJournalctl>)> {
+        match self {
+            Self::Journalctl(starter) => starter
+                .start(checkpoint)
+                .map(|(stream, running)| (stream, Some(running))),
+            Self::Native(starter) => starter.start(checkpoint).map(|stream| (stream, None)),
+        }
+    }
+}
+
 struct StartJournalctl {
     path: PathBuf,
     journal_dir: Option<PathBuf>,
@@ -1107,6 +1163,78 @@ mod tests {
         assert_eq!(checkpointer.get().await.unwrap().as_deref(), Some("8"));
     }
 
+    #[tokio::test]
+    async fn native_reader_reads_journal_files() {
+        use native::tests::{write_journal, TestEntry};
+
+        let journal_dir = tempdir().unwrap();
+        let journal_path = journal_dir.path().join("system.journal");
+        let data_dir = tempdir().unwrap();
+        const UNIT_A: &[&[u8]] = &[b"MESSAGE=from a", b"_SYSTEMD_UNIT=a.service"];
+        const UNIT_B: &[&[u8]] = &[b"MESSAGE=from b", b"_SYSTEMD_UNIT=b.service"];
+        let entry = |seqnum, fields| TestEntry {
+            seqnum,
+            realtime: 1578529839140001 + seqnum,
+            boot_id: [1; 16],
+            fields,
+        };
+
+        let run = || async {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let (cx, shutdown) =
+                SourceContext::new_shutdown(&ComponentKey::from(TEST_COMPONENT), tx);
+            let config = JournaldConfig {
+                reader: JournalReader::Native,
+                journal_directory: Some(journal_dir.path().into()),
+                current_boot_only: Some(false),
+                include_units: vec!["a".into()],
+                data_dir: Some(data_dir.path().into()),
+                acknowledgements: false.into(),
+                ..Default::default()
+            };
+            let source = config.build(cx).await.unwrap();
+            tokio::spawn(async move { source.await.unwrap() });
+
+            sleep(Duration::from_millis(100)).await;
+            shutdown
+                .shutdown_all(Instant::now() + Duration::from_secs(1))
+                .await;
+
+            timeout(Duration::from_secs(1), rx.collect::<Vec<_>>())
+                .await
+                .unwrap()
+        };
+
+        write_journal(
+            &journal_path,
+            1,
+            1,
+            true,
+            &[entry(1, UNIT_A), entry(2, UNIT_B), entry(3, UNIT_A)],
+        );
+        let received = run().await;
+        assert_eq!(received.len(), 2);
+        assert_eq!(message(&received[0]), Value::Bytes("from a".into()));
+        assert_eq!(timestamp(&received[0]), value_ts(1578529839, 140002000));
+
+        // The checkpoint is the cursor of the last entry, which is read after it.
+        write_journal(
+            &journal_path,
+            1,
+            1,
+            true,
+            &[
+                entry(1, UNIT_A),
+                entry(2, UNIT_B),
+                entry(3, UNIT_A),
+                entry(4, UNIT_A),
+            ],
+        );
+        let received = run().await;
+        assert_eq!(received.len(), 1);
+        assert_eq!(timestamp(&received[0]), value_ts(1578529839, 140005000));
+    }
+
     #[test]
     fn filter_matches_works_correctly() {
         let empty: Matches = HashMap::new();
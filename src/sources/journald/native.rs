@@ -0,0 +1,1000 @@
+//! A reader of the journal files of a directory, which doesn't need `journalctl`.
+//!
+//! Entries are read through the entry arrays of each [journal file][format], and rendered like
+//! the JSON output of `journalctl`, including its cursors, so that the source handles both readers
+//! the same way and can switch from one to the other without losing its position.
+//!
+//! The files are polled for new entries, and are tracked by their file ID, so that a file renamed
+//! when the journal is rotated isn't read again.
+//!
+//! [format]: https://systemd.io/JOURNAL_FILE_FORMAT/
+
+use std::{
+    collections::{hash_map::Entry as MapEntry, BTreeMap, HashMap, HashSet},
+    fs::File,
+    io::{self, Read},
+    os::unix::fs::FileExt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::{stream, StreamExt};
+use serde_json::{Map as JsonMap, Value as JsonValue};
+
+use super::JournalStream;
+use crate::internal_events::JournaldReadError;
+
+/// The directories holding the persistent and volatile journals.
+const DEFAULT_DIRECTORIES: [&str; 2] = ["/var/log/journal", "/run/log/journal"];
+const BOOT_ID_PATH: &str = "/proc/sys/kernel/random/boot_id";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+/// The maximum number of entries read from each file between two checks of the directories.
+const MAX_ENTRIES_PER_FILE: usize = 1024;
+
+const SIGNATURE: &[u8; 8] = b"LPKSHHRH";
+/// The size of the part of the header holding the fields used by the reader.
+const MIN_HEADER_SIZE: u64 = 184;
+
+const HEADER_INCOMPATIBLE_COMPRESSED_XZ: u32 = 1 << 0;
+const HEADER_INCOMPATIBLE_COMPACT: u32 = 1 << 4;
+/// The incompatible flags understood by the reader: the LZ4 and zstd compression algorithms (which
+/// are also flagged on each object), keyed hashes (which the reader doesn't use), and the compact
+/// mode.
+const HEADER_INCOMPATIBLE_SUPPORTED: u32 = 0b11110;
+
+const OBJECT_DATA: u8 = 1;
+const OBJECT_ENTRY: u8 = 3;
+const OBJECT_ENTRY_ARRAY: u8 = 6;
+
+const OBJECT_COMPRESSED_LZ4: u8 = 1 << 1;
+const OBJECT_COMPRESSED_ZSTD: u8 = 1 << 2;
+
+/// The largest field value read, after decompression, to bound the memory used by corrupt files.
+const MAX_FIELD_SIZE: u64 = 64 * 1024 * 1024;
+
+const OBJECT_HEADER_SIZE: u64 = 16;
+const ENTRY_ITEMS_OFFSET: usize = 64;
+const ENTRY_ARRAY_ITEMS_OFFSET: u64 = 24;
+
+/// Starts native readers of the journal.
+pub(super) struct StartNative {
+    journal_dir: Option<PathBuf>,
+    current_boot_only: bool,
+    since_now: bool,
+}
+
+impl StartNative {
+    pub(super) const fn new(
+        journal_dir: Option<PathBuf>,
+        current_boot_only: bool,
+        since_now: bool,
+    ) -> Self {
+        Self {
+            journal_dir,
+            current_boot_only,
+            since_now,
+        }
+    }
+
+    /// Returns a stream of the entries of the journal, as JSON lines.
+    pub(super) fn start(&self, checkpoint: Option<&str>) -> crate::Result<JournalStream> {
+        let directories = match &self.journal_dir {
+            Some(dir) => vec![dir.clone()],
+            None => DEFAULT_DIRECTORIES
+                .iter()
+                .map(PathBuf::from)
+                .filter(|dir| dir.is_dir())
+                .collect(),
+        };
+        if directories.is_empty() {
+            return Err("No journal directory found.".into());
+        }
+
+        // Refuse to start rather than skip the entries of files the reader can't decompress.
+        let mut paths = Vec::new();
+        for directory in &directories {
+            // Directories that can't be listed are reported by the reader.
+            let _ = list_journal_files(directory, &mut paths);
+        }
+        for path in &paths {
+            if let Err(error) = JournalFile::open(path) {
+                if error.kind() == io::ErrorKind::Unsupported {
+                    return Err(with_path(error, path).into());
+                }
+            }
+        }
+
+        let boot_id = if self.current_boot_only {
+            let boot_id = std::fs::read_to_string(BOOT_ID_PATH)?;
+            Some(boot_id.trim().replace('-', ""))
+        } else {
+            None
+        };
+
+        let start = match checkpoint {
+            Some(cursor) => match Cursor::parse(cursor) {
+                Some(cursor) => Start::After(cursor),
+                None => {
+                    warn!(message = "Ignoring invalid journal cursor.", %cursor);
+                    Start::Head
+                }
+            },
+            None if self.since_now => Start::Tail,
+            None => Start::Head,
+        };
+
+        let follower = Follower {
+            directories,
+            boot_id,
+            start: Some(start),
+            files: HashMap::new(),
+        };
+
+        Ok(stream::unfold(Some(follower), |follower| async move {
+            let mut follower = follower?;
+            loop {
+                let joined = tokio::task::spawn_blocking(move || {
+                    let result = follower.poll();
+                    (follower, result)
+                })
+                .await;
+                // The reader is lost if it panicked, so the stream ends after the error, and the
+                // source starts a new reader from the last checkpoint.
+                let (returned, result) = match joined {
+                    Ok(joined) => joined,
+                    Err(error) => {
+                        let error = io::Error::new(io::ErrorKind::Other, error);
+                        return Some((vec![Err(error.into())], None));
+                    }
+                };
+                follower = returned;
+
+                match result {
+                    Ok(lines) if lines.is_empty() => tokio::time::sleep(POLL_INTERVAL).await,
+                    Ok(lines) => {
+                        return Some((lines.into_iter().map(Ok).collect(), Some(follower)))
+                    }
+                    Err(error) => return Some((vec![Err(error.into())], Some(follower))),
+                }
+            }
+        })
+        .flat_map(stream::iter)
+        .boxed())
+    }
+}
+
+/// The position the files found by the first poll are read from.
+#[derive(Debug)]
+enum Start {
+    Head,
+    Tail,
+    After(Cursor),
+}
+
+/// The fields of a `journalctl` cursor locating an entry.
+#[derive(Debug, Eq, PartialEq)]
+struct Cursor {
+    seqnum_id: String,
+    seqnum: u64,
+    realtime: u64,
+}
+
+impl Cursor {
+    /// Parses cursors such as `s=<seqnum ID>;i=<seqnum>;b=<boot ID>;m=<monotonic>;t=<realtime>;x=<hash>`.
+    fn parse(cursor: &str) -> Option<Self> {
+        let mut seqnum_id = None;
+        let mut seqnum = None;
+        let mut realtime = None;
+        for item in cursor.split(';') {
+            match item.split_once('=')? {
+                ("s", value) => seqnum_id = Some(value.to_owned()),
+                ("i", value) => seqnum = Some(u64::from_str_radix(value, 16).ok()?),
+                ("t", value) => realtime = Some(u64::from_str_radix(value, 16).ok()?),
+                _ => (),
+            }
+        }
+        Some(Self {
+            seqnum_id: seqnum_id?,
+            seqnum: seqnum?,
+            realtime: realtime?,
+        })
+    }
+
+    /// Whether the given entry of a file is this entry, or was written before it.
+    fn reached(&self, file: &JournalFile, entry: &EntryHeader) -> bool {
+        if file.seqnum_id == self.seqnum_id {
+            entry.seqnum <= self.seqnum
+        } else {
+            entry.realtime <= self.realtime
+        }
+    }
+}
+
+struct FileState {
+    path: PathBuf,
+    /// The number of entries of the file already read.
+    read: u64,
+}
+
+struct Follower {
+    directories: Vec<PathBuf>,
+    /// When set, only the entries of this boot are read.
+    boot_id: Option<String>,
+    /// The position of the files found by the first poll, unset afterwards. Files found later are
+    /// read from their first entry.
+    start: Option<Start>,
+    files: HashMap<String, FileState>,
+}
+
+impl Follower {
+    /// Reads the entries appended to the journal files since the last poll, ordered by time.
+    fn poll(&mut self) -> io::Result<Vec<Bytes>> {
+        let mut paths = Vec::new();
+        for directory in &self.directories {
+            list_journal_files(directory, &mut paths)?;
+        }
+
+        let mut entries = Vec::new();
+        let mut seen = HashSet::new();
+        for path in &paths {
+            match self.read_file(path, &mut entries) {
+                Ok(file_id) => {
+                    seen.insert(file_id);
+                }
+                Err(error) => emit!(JournaldReadError {
+                    error: with_path(error, path).into(),
+                }),
+            }
+        }
+        self.start = None;
+
+        // Forget the files that were deleted, keeping those that merely failed to be read.
+        self.files
+            .retain(|file_id, state| seen.contains(file_id) || paths.contains(&state.path));
+
+        entries.sort_by_key(|(realtime, _)| *realtime);
+        Ok(entries.into_iter().map(|(_, line)| line).collect())
+    }
+
+    /// Reads the new entries of a file, and returns its ID.
+    fn read_file(&mut self, path: &Path, output: &mut Vec<(u64, Bytes)>) -> io::Result<String> {
+        let file = JournalFile::open(path)?;
+        let mut read = match self.files.entry(file.file_id.clone()) {
+            MapEntry::Occupied(entry) => entry.get().read,
+            MapEntry::Vacant(entry) => {
+                let read = match &self.start {
+                    None | Some(Start::Head) => 0,
+                    Some(Start::Tail) => file.n_entries,
+                    Some(Start::After(cursor)) => file.position_after(cursor)?,
+                };
+                entry
+                    .insert(FileState {
+                        path: path.into(),
+                        read,
+                    })
+                    .read
+            }
+        };
+
+        if read < file.n_entries {
+            for offset in file.entry_offsets(read, MAX_ENTRIES_PER_FILE)? {
+                read += 1;
+                match file.read_entry(offset) {
+                    Ok(entry) => {
+                        let current_boot = self
+                            .boot_id
+                            .as_ref()
+                            .map_or(true, |boot_id| *boot_id == entry.header.boot_id);
+                        if current_boot {
+                            output.push((entry.header.realtime, entry.render(&file)));
+                        }
+                    }
+                    // Skip invalid entries, rather than failing on them at every poll.
+                    Err(error) => emit!(JournaldReadError {
+                        error: with_path(error, path).into(),
+                    }),
+                }
+            }
+        }
+
+        self.files.insert(
+            file.file_id.clone(),
+            FileState {
+                path: path.into(),
+                read,
+            },
+        );
+        Ok(file.file_id)
+    }
+}
+
+/// Adds the journal files of a directory, and of its subdirectories named after machine IDs, to
+/// `paths`.
+fn list_journal_files(directory: &Path, paths: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in std::fs::read_dir(directory).map_err(|error| with_path(error, directory))? {
+        let path = entry?.path();
+        if path.is_dir() {
+            // Subdirectories may be removed, or not be readable, which doesn't prevent reading
+            // the others.
+            if let Ok(entries) = std::fs::read_dir(&path) {
+                paths.extend(
+                    entries
+                        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                        .filter(|path| is_journal_file(path)),
+                );
+            }
+        } else if is_journal_file(&path) {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_journal_file(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |extension| extension == "journal")
+}
+
+fn with_path(error: io::Error, path: &Path) -> io::Error {
+    io::Error::new(error.kind(), format!("{}: {}", path.display(), error))
+}
+
+fn invalid_data(message: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
+
+fn le32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("4 bytes"))
+}
+
+fn le64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().expect("8 bytes"))
+}
+
+fn id128(bytes: &[u8], offset: usize) -> String {
+    format!(
+        "{:032x}",
+        u128::from_be_bytes(bytes[offset..offset + 16].try_into().expect("16 bytes"))
+    )
+}
+
+/// An open journal file, and the fields of its header read when it was opened.
+struct JournalFile {
+    file: File,
+    length: u64,
+    compact: bool,
+    file_id: String,
+    seqnum_id: String,
+    n_entries: u64,
+    entry_array_offset: u64,
+}
+
+impl JournalFile {
+    fn open(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let mut header = [0; MIN_HEADER_SIZE as usize];
+        file.read_exact_at(&mut header, 0)?;
+        // The file only grows, so anything referenced by the header is below its current length.
+        let length = file.metadata()?.len();
+
+        if &header[0..8] != SIGNATURE {
+            return Err(invalid_data("not a journal file"));
+        }
+        let incompatible_flags = le32(&header, 12);
+        if incompatible_flags & HEADER_INCOMPATIBLE_COMPRESSED_XZ != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "journal files compressed with XZ aren't supported by the native reader",
+            ));
+        }
+        if incompatible_flags & !HEADER_INCOMPATIBLE_SUPPORTED != 0 {
+            return Err(invalid_data(format!(
+                "unsupported journal file features {:#x}",
+                incompatible_flags
+            )));
+        }
+        if le64(&header, 88) < MIN_HEADER_SIZE {
+            return Err(invalid_data("truncated journal file header"));
+        }
+
+        Ok(Self {
+            file,
+            length,
+            compact: incompatible_flags & HEADER_INCOMPATIBLE_COMPACT != 0,
+            file_id: id128(&header, 24),
+            seqnum_id: id128(&header, 72),
+            n_entries: le64(&header, 152),
+            entry_array_offset: le64(&header, 176),
+        })
+    }
+
+    fn read_at(&self, offset: u64, length: u64) -> io::Result<Vec<u8>> {
+        if offset
+            .checked_add(length)
+            .map_or(true, |end| end > self.length)
+        {
+            return Err(invalid_data(format!(
+                "object at offset {} is out of bounds",
+                offset
+            )));
+        }
+        let mut buffer = vec![0; length as usize];
+        self.file.read_exact_at(&mut buffer, offset)?;
+        Ok(buffer)
+    }
+
+    /// Reads the object at the given offset, or only its first `limit` bytes.
+    fn read_object(&self, offset: u64, kind: u8, limit: Option<u64>) -> io::Result<Vec<u8>> {
+        let header = self.read_at(offset, OBJECT_HEADER_SIZE)?;
+        if header[0] != kind {
+            return Err(invalid_data(format!(
+                "expected object of type {} at offset {}, found {}",
+                kind, offset, header[0]
+            )));
+        }
+        let size = le64(&header, 8);
+        if size < OBJECT_HEADER_SIZE || size > self.length - offset {
+            return Err(invalid_data(format!("invalid object at offset {}", offset)));
+        }
+        self.read_at(offset, limit.map_or(size, |limit| size.min(limit)))
+    }
+
+    /// Returns the offsets of at most `limit` entries, starting with the `skip`th one.
+    fn entry_offsets(&self, skip: u64, limit: usize) -> io::Result<Vec<u64>> {
+        let item_size = if self.compact { 4 } else { 8 };
+        let mut offsets = Vec::new();
+        // The index of the first entry of the current array.
+        let mut index = 0;
+        let mut array = self.entry_array_offset;
+
+        while array != 0 && index < self.n_entries && offsets.len() < limit {
+            let header =
+                self.read_object(array, OBJECT_ENTRY_ARRAY, Some(ENTRY_ARRAY_ITEMS_OFFSET))?;
+            let capacity = le64(&header, 8).saturating_sub(ENTRY_ARRAY_ITEMS_OFFSET) / item_size;
+            let next = le64(&header, 16);
+
+            let end = index
+                .checked_add(capacity)
+                .ok_or_else(|| invalid_data(format!("invalid entry array at offset {}", array)))?;
+
+            let first = skip.saturating_sub(index);
+            let last = capacity
+                .min(self.n_entries - index)
+                .min(first.saturating_add((limit - offsets.len()) as u64));
+            if end > skip && last > first {
+                let items = self.read_at(
+                    array + ENTRY_ARRAY_ITEMS_OFFSET + first * item_size,
+                    (last - first) * item_size,
+                )?;
+                for item in items.chunks_exact(item_size as usize) {
+                    let offset = if self.compact {
+                        le32(item, 0) as u64
+                    } else {
+                        le64(item, 0)
+                    };
+                    // Unused items of the last array are zeroed.
+                    if offset == 0 {
+                        return Ok(offsets);
+                    }
+                    offsets.push(offset);
+                }
+            }
+
+            // Arrays are appended to the file, so a link backwards is a loop.
+            if next != 0 && next <= array {
+                return Err(invalid_data(format!(
+                    "invalid entry array link at offset {}",
+                    array
+                )));
+            }
+            index = end;
+            array = next;
+        }
+        Ok(offsets)
+    }
+
+    /// Finds the number of entries of the file at or before the cursor.
+    fn position_after(&self, cursor: &Cursor) -> io::Result<u64> {
+        let offsets = self.entry_offsets(0, usize::MAX)?;
+        // The entries of a file are ordered, so the boundary can be bisected.
+        let (mut low, mut high) = (0, offsets.len());
+        while low < high {
+            let middle = (low + high) / 2;
+            if cursor.reached(self, &self.read_entry_header(offsets[middle])?) {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        Ok(low as u64)
+    }
+
+    fn read_entry_header(&self, offset: u64) -> io::Result<EntryHeader> {
+        let object = self.read_object(offset, OBJECT_ENTRY, Some(ENTRY_ITEMS_OFFSET as u64))?;
+        EntryHeader::parse(&object, offset)
+    }
+
+    fn read_entry(&self, offset: u64) -> io::Result<Entry> {
+        let object = self.read_object(offset, OBJECT_ENTRY, None)?;
+        let header = EntryHeader::parse(&object, offset)?;
+
+        let item_size = if self.compact { 4 } else { 16 };
+        let fields = object[ENTRY_ITEMS_OFFSET..]
+            .chunks_exact(item_size)
+            .map(|item| {
+                let offset = if self.compact {
+                    le32(item, 0) as u64
+                } else {
+                    le64(item, 0)
+                };
+                self.read_field(offset)
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Entry { header, fields })
+    }
+
+    /// Reads the data object at the given offset, holding a `NAME=value` field.
+    fn read_field(&self, offset: u64) -> io::Result<(String, Vec<u8>)> {
+        let object = self.read_object(offset, OBJECT_DATA, None)?;
+        let start = if self.compact { 72 } else { 64 };
+        let payload = object
+            .get(start..)
+            .ok_or_else(|| invalid_data(format!("truncated data object at offset {}", offset)))?;
+
+        let payload = match object[1] {
+            0 => payload.to_vec(),
+            OBJECT_COMPRESSED_ZSTD => {
+                let mut decoded = Vec::new();
+                zstd::stream::read::Decoder::new(payload)?
+                    .take(MAX_FIELD_SIZE + 1)
+                    .read_to_end(&mut decoded)?;
+                if decoded.len() as u64 > MAX_FIELD_SIZE {
+                    return Err(invalid_data(format!(
+                        "data object at offset {} is larger than {} bytes",
+                        offset, MAX_FIELD_SIZE
+                    )));
+                }
+                decoded
+            }
+            OBJECT_COMPRESSED_LZ4 => {
+                // LZ4 blocks are prefixed with their decompressed size.
+                let size = payload.get(..8).map(|size| le64(size, 0)).ok_or_else(|| {
+                    invalid_data(format!("truncated data object at offset {}", offset))
+                })?;
+                if size > MAX_FIELD_SIZE {
+                    return Err(invalid_data(format!(
+                        "data object at offset {} is larger than {} bytes",
+                        offset, MAX_FIELD_SIZE
+                    )));
+                }
+                lz4_flex::block::decompress(&payload[8..], size as usize).map_err(|error| {
+                    invalid_data(format!(
+                        "invalid data object at offset {}: {}",
+                        offset, error
+                    ))
+                })?
+            }
+            _ => {
+                return Err(invalid_data(format!(
+                    "invalid data object at offset {}",
+                    offset
+                )))
+            }
+        };
+
+        let separator = payload
+            .iter()
+            .position(|byte| *byte == b'=')
+            .ok_or_else(|| invalid_data(format!("invalid field at offset {}", offset)))?;
+        let name = String::from_utf8_lossy(&payload[..separator]).into_owned();
+        Ok((name, payload[separator + 1..].to_vec()))
+    }
+}
+
+#[derive(Debug)]
+struct EntryHeader {
+    seqnum: u64,
+    realtime: u64,
+    monotonic: u64,
+    boot_id: String,
+    xor_hash: u64,
+}
+
+impl EntryHeader {
+    fn parse(object: &[u8], offset: u64) -> io::Result<Self> {
+        if object.len() < ENTRY_ITEMS_OFFSET {
+            return Err(invalid_data(format!(
+                "truncated entry object at offset {}",
+                offset
+            )));
+        }
+        Ok(Self {
+            seqnum: le64(object, 16),
+            realtime: le64(object, 24),
+            monotonic: le64(object, 32),
+            boot_id: id128(object, 40),
+            xor_hash: le64(object, 56),
+        })
+    }
+}
+
+struct Entry {
+    header: EntryHeader,
+    fields: Vec<(String, Vec<u8>)>,
+}
+
+impl Entry {
+    /// Renders the entry like `journalctl --output=json --show-cursor`: fields with several values
+    /// hold an array of them, and values that aren't valid UTF-8 are arrays of bytes.
+    fn render(self, file: &JournalFile) -> Bytes {
+        let header = self.header;
+
+        let mut fields = BTreeMap::<String, Vec<JsonValue>>::new();
+        for (name, value) in self.fields {
+            let value = match String::from_utf8(value) {
+                Ok(value) => JsonValue::String(value),
+                Err(error) => error
+                    .into_bytes()
+                    .into_iter()
+                    .map(JsonValue::from)
+                    .collect(),
+            };
+            fields.entry(name).or_default().push(value);
+        }
+
+        let mut record = fields
+            .into_iter()
+            .map(|(name, mut values)| {
+                let value = if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    JsonValue::Array(values)
+                };
+                (name, value)
+            })
+            .collect::<JsonMap<_, _>>();
+
+        let cursor = format!(
+            "s={};i={:x};b={};m={:x};t={:x};x={:x}",
+            file.seqnum_id,
+            header.seqnum,
+            header.boot_id,
+            header.monotonic,
+            header.realtime,
+            header.xor_hash
+        );
+        record.insert(super::CURSOR.into(), cursor.into());
+        record.insert(
+            super::RECEIVED_TIMESTAMP.into(),
+            header.realtime.to_string().into(),
+        );
+        record.insert(
+            "__MONOTONIC_TIMESTAMP".into(),
+            header.monotonic.to_string().into(),
+        );
+        record.insert("_BOOT_ID".into(), header.boot_id.into());
+
+        serde_json::to_vec(&record)
+            .expect("JSON values are serializable")
+            .into()
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+
+    /// An entry to write to a test journal file.
+    pub(in crate::sources::journald) struct TestEntry<'a> {
+        pub(in crate::sources::journald) seqnum: u64,
+        pub(in crate::sources::journald) realtime: u64,
+        pub(in crate::sources::journald) boot_id: [u8; 16],
+        pub(in crate::sources::journald) fields: &'a [&'a [u8]],
+    }
+
+    /// Writes a journal file holding the given entries, without the hash tables used to look up
+    /// fields, which the reader doesn't need. Fields longer than 64 bytes are compressed, with zstd
+    /// in compact files, and with LZ4 otherwise.
+    pub(in crate::sources::journald) fn write_journal(
+        path: &Path,
+        file_id: u8,
+        seqnum_id: u8,
+        compact: bool,
+        entries: &[TestEntry<'_>],
+    ) {
+        let header_size = if compact { 272 } else { 256 };
+        let mut buffer = vec![0; header_size];
+
+        fn append(buffer: &mut Vec<u8>, kind: u8, flags: u8, body: &[u8]) -> u64 {
+            let offset = buffer.len() as u64;
+            buffer.extend([kind, flags, 0, 0, 0, 0, 0, 0]);
+            buffer.extend((OBJECT_HEADER_SIZE + body.len() as u64).to_le_bytes());
+            buffer.extend(body);
+            buffer.resize((buffer.len() + 7) / 8 * 8, 0);
+            offset
+        }
+
+        let mut entry_offsets = Vec::new();
+        for entry in entries {
+            let mut body = Vec::new();
+            body.extend(entry.seqnum.to_le_bytes());
+            body.extend(entry.realtime.to_le_bytes());
+            body.extend((entry.realtime / 1000).to_le_bytes());
+            body.extend(entry.boot_id);
+            body.extend(0u64.to_le_bytes());
+
+            for field in entry.fields {
+                let (flags, payload) = if field.len() > 64 && compact {
+                    (OBJECT_COMPRESSED_ZSTD, zstd::encode_all(*field, 0).unwrap())
+                } else if field.len() > 64 {
+                    let mut payload = (field.len() as u64).to_le_bytes().to_vec();
+                    payload.extend(lz4_flex::block::compress(field));
+                    (OBJECT_COMPRESSED_LZ4, payload)
+                } else {
+                    (0, field.to_vec())
+                };
+                let mut data = vec![0; if compact { 56 } else { 48 }];
+                data.extend(payload);
+                let offset = append(&mut buffer, OBJECT_DATA, flags, &data);
+
+                if compact {
+                    body.extend((offset as u32).to_le_bytes());
+                } else {
+                    body.extend(offset.to_le_bytes());
+                    body.extend(0u64.to_le_bytes());
+                }
+            }
+            entry_offsets.push(append(&mut buffer, OBJECT_ENTRY, 0, &body));
+        }
+
+        // Two linked arrays, the first one holding a single entry, and the second one the others
+        // followed by an unused item.
+        let split = entry_offsets.len().min(1);
+        let mut arrays = Vec::new();
+        for (items, unused) in [(&entry_offsets[..split], 0), (&entry_offsets[split..], 1)] {
+            let mut body = 0u64.to_le_bytes().to_vec();
+            for offset in items.iter().chain(&[0][..unused]) {
+                if compact {
+                    body.extend((*offset as u32).to_le_bytes());
+                } else {
+                    body.extend(offset.to_le_bytes());
+                }
+            }
+            arrays.push(append(&mut buffer, OBJECT_ENTRY_ARRAY, 0, &body) as usize);
+        }
+        buffer[arrays[0] + 16..arrays[0] + 24].copy_from_slice(&(arrays[1] as u64).to_le_bytes());
+
+        buffer[0..8].copy_from_slice(SIGNATURE);
+        let flags = if compact {
+            HEADER_INCOMPATIBLE_COMPACT | (1 << 3)
+        } else {
+            1 << 1
+        };
+        buffer[12..16].copy_from_slice(&flags.to_le_bytes());
+        buffer[24..40].copy_from_slice(&[file_id; 16]);
+        buffer[72..88].copy_from_slice(&[seqnum_id; 16]);
+        buffer[88..96].copy_from_slice(&(header_size as u64).to_le_bytes());
+        buffer[152..160].copy_from_slice(&(entries.len() as u64).to_le_bytes());
+        buffer[176..184].copy_from_slice(&(arrays[0] as u64).to_le_bytes());
+
+        std::fs::write(path, buffer).unwrap();
+    }
+
+    fn entry<'a>(seqnum: u64, realtime: u64, fields: &'a [&'a [u8]]) -> TestEntry<'a> {
+        TestEntry {
+            seqnum,
+            realtime,
+            boot_id: [2; 16],
+            fields,
+        }
+    }
+
+    fn records(lines: &[Bytes]) -> Vec<JsonMap<String, JsonValue>> {
+        lines
+            .iter()
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect()
+    }
+
+    fn new_follower(directory: &Path, start: Start) -> Follower {
+        Follower {
+            directories: vec![directory.into()],
+            boot_id: None,
+            start: Some(start),
+            files: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn parses_cursors() {
+        assert_eq!(
+            Cursor::parse("s=0123abcd;i=1f;b=22;m=3e8;t=59baa1e544ca1;x=0"),
+            Some(Cursor {
+                seqnum_id: "0123abcd".into(),
+                seqnum: 31,
+                realtime: 1578529839140001,
+            })
+        );
+        assert_eq!(Cursor::parse("1"), None);
+        assert_eq!(Cursor::parse("s=01;i=zz;t=1"), None);
+    }
+
+    #[test]
+    fn reads_entries() {
+        for compact in [false, true] {
+            let dir = tempfile::tempdir().unwrap();
+            let long = [b"LONG=".as_slice(), &[b'x'; 100]].concat();
+            let fields: [&[u8]; 5] = [b"MESSAGE=hello", b"TAG=a", b"TAG=b", b"RAW=\xff\xfe", &long];
+            write_journal(
+                &dir.path().join("system.journal"),
+                1,
+                1,
+                compact,
+                &[entry(1, 1000, &fields), entry(2, 2000, &[b"MESSAGE=world"])],
+            );
+
+            let records = records(&new_follower(dir.path(), Start::Head).poll().unwrap());
+
+            assert_eq!(records.len(), 2, "compact={}", compact);
+            let first = &records[0];
+            assert_eq!(first["MESSAGE"], "hello");
+            assert_eq!(first["TAG"], serde_json::json!(["a", "b"]));
+            assert_eq!(first["RAW"], serde_json::json!([255, 254]));
+            assert_eq!(first["LONG"], "x".repeat(100));
+            assert_eq!(first["__REALTIME_TIMESTAMP"], "1000");
+            assert_eq!(first["__MONOTONIC_TIMESTAMP"], "1");
+            assert_eq!(first["_BOOT_ID"], "02".repeat(16));
+            assert_eq!(
+                first["__CURSOR"],
+                format!(
+                    "s={};i=1;b={};m=1;t=3e8;x=0",
+                    "01".repeat(16),
+                    "02".repeat(16)
+                )
+            );
+            assert_eq!(records[1]["MESSAGE"], "world");
+        }
+    }
+
+    #[test]
+    fn rejects_corrupt_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("system.journal");
+        write_journal(
+            &path,
+            1,
+            1,
+            false,
+            &[
+                entry(1, 1000, &[b"MESSAGE=first"]),
+                entry(2, 2000, &[b"MESSAGE=second"]),
+            ],
+        );
+
+        // The first entry array claims to be larger than the file.
+        let mut buffer = std::fs::read(&path).unwrap();
+        let array = le64(&buffer, 176) as usize;
+        buffer[array + 8..array + 16].copy_from_slice(&u64::MAX.to_le_bytes());
+        std::fs::write(&path, buffer).unwrap();
+
+        let file = JournalFile::open(&path).unwrap();
+        let error = file.entry_offsets(0, usize::MAX).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn refuses_xz_compressed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("system.journal");
+        write_journal(&path, 1, 1, false, &[entry(1, 1000, &[b"MESSAGE=first"])]);
+
+        let mut buffer = std::fs::read(&path).unwrap();
+        buffer[12..16].copy_from_slice(&HEADER_INCOMPATIBLE_COMPRESSED_XZ.to_le_bytes());
+        std::fs::write(&path, buffer).unwrap();
+
+        let error = JournalFile::open(&path).err().unwrap();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+        assert!(StartNative::new(Some(dir.path().into()), false, false)
+            .start(None)
+            .is_err());
+    }
+
+    #[test]
+    fn follows_new_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("system.journal");
+        write_journal(&path, 1, 1, true, &[entry(1, 1000, &[b"MESSAGE=first"])]);
+
+        let mut follower = new_follower(dir.path(), Start::Head);
+        assert_eq!(records(&follower.poll().unwrap()).len(), 1);
+        assert!(follower.poll().unwrap().is_empty());
+
+        write_journal(
+            &path,
+            1,
+            1,
+            true,
+            &[
+                entry(1, 1000, &[b"MESSAGE=first"]),
+                entry(2, 2000, &[b"MESSAGE=second"]),
+            ],
+        );
+        let records = records(&follower.poll().unwrap());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0]["MESSAGE"], "second");
+
+        // A rotated file is recognized by its ID.
+        std::fs::rename(&path, dir.path().join("system@archived.journal")).unwrap();
+        assert!(follower.poll().unwrap().is_empty());
+    }
+
+    #[test]
+    fn starts_at_position() {
+        let dir = tempfile::tempdir().unwrap();
+        write_journal(
+            &dir.path().join("system.journal"),
+            1,
+            1,
+            false,
+            &[
+                entry(1, 1000, &[b"MESSAGE=first"]),
+                entry(2, 2000, &[b"MESSAGE=second"]),
+                entry(3, 3000, &[b"MESSAGE=third"]),
+            ],
+        );
+        let messages = |start| {
+            records(&new_follower(dir.path(), start).poll().unwrap())
+                .into_iter()
+                .map(|record| record["MESSAGE"].as_str().unwrap().to_owned())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(messages(Start::Tail).is_empty());
+        let seqnum_id = "01".repeat(16);
+        let cursor = Cursor::parse(&format!("s={};i=2;t=0", seqnum_id)).unwrap();
+        assert_eq!(messages(Start::After(cursor)), vec!["third"]);
+        // Files of other sequences are positioned by time.
+        let cursor = Cursor::parse("s=ff;i=3;t=3e8").unwrap();
+        assert_eq!(messages(Start::After(cursor)), vec!["second", "third"]);
+    }
+
+    #[test]
+    fn merges_files_by_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let machine = dir.path().join("0123456789abcdef0123456789abcdef");
+        std::fs::create_dir(&machine).unwrap();
+        write_journal(
+            &machine.join("system.journal"),
+            1,
+            1,
+            true,
+            &[
+                entry(1, 1000, &[b"MESSAGE=one"]),
+                entry(2, 3000, &[b"MESSAGE=three"]),
+            ],
+        );
+        write_journal(
+            &machine.join("user-1000.journal"),
+            2,
+            1,
+            true,
+            &[entry(3, 2000, &[b"MESSAGE=two"])],
+        );
+        std::fs::write(machine.join("system.journal~"), b"corrupted").unwrap();
+
+        let mut follower = new_follower(dir.path(), Start::Head);
+        follower.boot_id = Some("02".repeat(16));
+        let messages = records(&follower.poll().unwrap())
+            .into_iter()
+            .map(|record| record["MESSAGE"].as_str().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["one", "two", "three"]);
+
+        let mut follower = new_follower(dir.path(), Start::Head);
+        follower.boot_id = Some("03".repeat(16));
+        assert!(follower.poll().unwrap().is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 use enum_dispatch::enum_dispatch;
 use snafu::Snafu;
 
+#[cfg(feature = "sources-amqp")]
+pub mod amqp;
 #[cfg(feature = "sources-apache_metrics")]
 pub mod apache_metrics;
 #[cfg(feature = "sources-aws_ecs_metrics")]
@@ -58,6 +60,8 @@ pub mod logstash;
 pub mod mongodb_metrics;
 #[cfg(all(feature = "sources-nats"))]
 pub mod nats;
+#[cfg(feature = "sources-netflow")]
+pub mod netflow;
 #[cfg(feature = "sources-nginx_metrics")]
 pub mod nginx_metrics;
 #[cfg(feature = "sources-opentelemetry")]
@@ -68,6 +72,8 @@ pub mod postgresql_metrics;
 pub mod prometheus;
 #[cfg(feature = "sources-redis")]
 pub mod redis;
+#[cfg(feature = "sources-snmp_trap")]
+pub mod snmp_trap;
 #[cfg(feature = "sources-socket")]
 pub mod socket;
 #[cfg(feature = "sources-splunk_hec")]
@@ -104,6 +110,10 @@ enum BuildError {
 #[serde(tag = "type", rename_all = "snake_case")]
 #[enum_dispatch(SourceConfig)]
 pub enum Sources {
+    /// AMQP.
+    #[cfg(feature = "sources-amqp")]
+    Amqp(#[configurable(derived)] amqp::AmqpSourceConfig),
+
     /// Apache HTTP Server (HTTPD) Metrics.
     #[cfg(feature = "sources-apache_metrics")]
     ApacheMetrics(#[configurable(derived)] apache_metrics::ApacheMetricsConfig),
@@ -214,6 +224,10 @@ pub enum Sources {
     #[cfg(all(feature = "sources-nats"))]
     Nats(#[configurable(derived)] nats::NatsSourceConfig),
 
+    /// NetFlow.
+    #[cfg(feature = "sources-netflow")]
+    Netflow(#[configurable(derived)] netflow::NetflowConfig),
+
     /// NGINX Metrics.
     #[cfg(feature = "sources-nginx_metrics")]
     NginxMetrics(#[configurable(derived)] nginx_metrics::NginxMetricsConfig),
@@ -260,6 +274,10 @@ pub enum Sources {
     #[cfg(test)]
     TestTripwire(#[configurable(derived)] crate::test_util::mock::sources::TripwireSourceConfig),
 
+    /// SNMP Trap.
+    #[cfg(feature = "sources-snmp_trap")]
+    SnmpTrap(#[configurable(derived)] snmp_trap::SnmpTrapConfig),
+
     /// Socket.
     #[cfg(feature = "sources-socket")]
     Socket(#[configurable(derived)] socket::SocketConfig),
@@ -297,6 +315,8 @@ impl NamedComponent for Sources {
 
     fn get_component_name(&self) -> &'static str {
         match self {
+            #[cfg(feature = "sources-amqp")]
+            Self::Amqp(config) => config.get_component_name(),
             #[cfg(feature = "sources-apache_metrics")]
             Self::ApacheMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-aws_ecs_metrics")]
@@ -351,6 +371,8 @@ impl NamedComponent for Sources {
             Self::MongodbMetrics(config) => config.get_component_name(),
             #[cfg(all(feature = "sources-nats"))]
             Self::Nats(config) => config.get_component_name(),
+            #[cfg(feature = "sources-netflow")]
+            Self::Netflow(config) => config.get_component_name(),
             #[cfg(feature = "sources-nginx_metrics")]
             Self::NginxMetrics(config) => config.get_component_name(),
             #[cfg(feature = "sources-opentelemetry")]
@@ -373,6 +395,8 @@ impl NamedComponent for Sources {
             Self::TestPanic(config) => config.get_component_name(),
             #[cfg(test)]
             Self::TestTripwire(config) => config.get_component_name(),
+            #[cfg(feature = "sources-snmp_trap")]
+            Self::SnmpTrap(config) => config.get_component_name(),
             #[cfg(feature = "sources-socket")]
             Self::Socket(config) => config.get_component_name(),
             #[cfg(feature = "sources-splunk_hec")]
@@ -1,27 +1,39 @@
+use std::{io, time::Duration};
+
 use bytes::Bytes;
 use chrono::Utc;
 use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
 use futures::{pin_mut, stream, Stream, StreamExt};
+use nats::jetstream::{AckKind, PullSubscribeOptions, SubscribeOptions};
 use snafu::{ResultExt, Snafu};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::codec::FramedRead;
-use vector_common::internal_event::{
-    ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol,
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{ByteSize, BytesReceived, EventsReceived, InternalEventHandle as _, Protocol},
 };
 use vector_config::configurable_component;
 use vector_core::{config::LogNamespace, ByteSizeOf};
 
 use crate::{
     codecs::{Decoder, DecodingConfig},
-    config::{log_schema, GenerateConfig, Output, SourceConfig, SourceContext},
-    event::Event,
-    internal_events::StreamClosedError,
+    config::{
+        log_schema, AcknowledgementsConfig, GenerateConfig, Output, SourceConfig, SourceContext,
+    },
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{NatsJetStreamAckError, NatsJetStreamReceiveError, StreamClosedError},
     nats::{from_tls_auth_config, NatsAuthConfig, NatsConfigError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
     tls::TlsEnableableConfig,
     SourceSender,
 };
 
+/// How long the JetStream receiver blocks waiting for messages before checking whether the source is
+/// shutting down.
+const JETSTREAM_POLL_TIMEOUT: Duration = Duration::from_millis(500);
+
 #[derive(Debug, Snafu)]
 enum BuildError {
     #[snafu(display("NATS Config Error: {}", source))]
@@ -32,6 +44,53 @@ enum BuildError {
     Subscribe { source: std::io::Error },
 }
 
+/// The kind of JetStream consumer to create.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NatsJetStreamConsumerMode {
+    /// The server pushes messages to the consumer as they become available.
+    #[derivative(Default)]
+    Push,
+
+    /// The consumer explicitly fetches batches of messages from the server.
+    Pull,
+}
+
+/// JetStream configuration.
+///
+/// When set, messages are read from a durable JetStream consumer instead of a core NATS subscription, so
+/// messages published while Vector is not running are delivered once it starts again. Each message is
+/// acknowledged once the events decoded from it have been processed.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NatsJetStreamConfig {
+    /// The name of the stream to consume from.
+    ///
+    /// The stream must already exist.
+    stream: String,
+
+    /// The name of the durable consumer.
+    ///
+    /// The consumer is created if it does not already exist. Its position in the stream is kept by the
+    /// server, so restarting Vector resumes from the first unacknowledged message.
+    durable_name: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    mode: NatsJetStreamConsumerMode,
+
+    /// The maximum number of messages to fetch at once, when using a pull consumer.
+    #[serde(default = "default_batch_size")]
+    batch_size: usize,
+}
+
+const fn default_batch_size() -> usize {
+    100
+}
+
 /// Configuration for the `nats` source.
 #[configurable_component(source("nats"))]
 #[derive(Clone, Debug, Derivative)]
@@ -69,6 +128,13 @@ pub struct NatsSourceConfig {
     #[serde(default = "default_decoding")]
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    jetstream: Option<NatsJetStreamConfig>,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: AcknowledgementsConfig,
 }
 
 impl GenerateConfig for NatsSourceConfig {
@@ -86,7 +152,6 @@ impl GenerateConfig for NatsSourceConfig {
 #[async_trait::async_trait]
 impl SourceConfig for NatsSourceConfig {
     async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
-        let (connection, subscription) = create_subscription(self).await?;
         let decoder = DecodingConfig::new(
             self.framing.clone(),
             self.decoding.clone(),
@@ -94,6 +159,22 @@ impl SourceConfig for NatsSourceConfig {
         )
         .build();
 
+        if let Some(jetstream) = &self.jetstream {
+            let subscription = create_jetstream_subscription(self, jetstream).await?;
+            let acknowledgements = cx.do_acknowledgements(&self.acknowledgements);
+
+            return Ok(Box::pin(nats_jetstream_source(
+                subscription,
+                jetstream.batch_size,
+                decoder,
+                cx.shutdown,
+                cx.out,
+                acknowledgements,
+            )));
+        }
+
+        let (connection, subscription) = create_subscription(self).await?;
+
         Ok(Box::pin(nats_source(
             connection,
             subscription,
@@ -108,7 +189,7 @@ impl SourceConfig for NatsSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        self.jetstream.is_some()
     }
 }
 
@@ -117,6 +198,16 @@ impl NatsSourceConfig {
         let options: nats::asynk::Options = self.try_into().context(ConfigSnafu)?;
         options.connect(&self.url).await.context(ConnectSnafu)
     }
+
+    /// Connects with the blocking client, which is the only one exposing JetStream.
+    async fn connect_blocking(&self) -> Result<nats::Connection, BuildError> {
+        let options: nats::Options = self.try_into().context(ConfigSnafu)?;
+        let url = self.url.clone();
+        tokio::task::spawn_blocking(move || options.connect(&url))
+            .await
+            .expect("NATS connect task panicked")
+            .context(ConnectSnafu)
+    }
 }
 
 impl std::convert::TryFrom<&NatsSourceConfig> for nats::asynk::Options {
@@ -127,6 +218,14 @@ impl std::convert::TryFrom<&NatsSourceConfig> for nats::asynk::Options {
     }
 }
 
+impl std::convert::TryFrom<&NatsSourceConfig> for nats::Options {
+    type Error = NatsConfigError;
+
+    fn try_from(config: &NatsSourceConfig) -> Result<Self, Self::Error> {
+        from_tls_auth_config(&config.connection_name, &config.auth, &config.tls)
+    }
+}
+
 fn get_subscription_stream(
     subscription: nats::asynk::Subscription,
 ) -> impl Stream<Item = nats::asynk::Message> {
@@ -185,6 +284,197 @@ async fn nats_source(
     Ok(())
 }
 
+enum JetStreamSubscription {
+    Push(nats::jetstream::PushSubscription),
+    Pull(nats::jetstream::PullSubscription),
+}
+
+/// Reads messages from a JetStream subscription on a blocking thread.
+///
+/// The thread stops once the returned stream is dropped.
+fn get_jetstream_stream(
+    subscription: JetStreamSubscription,
+    batch_size: usize,
+) -> impl Stream<Item = nats::Message> {
+    let (tx, rx) = mpsc::channel(batch_size.max(1));
+
+    tokio::task::spawn_blocking(move || {
+        let receive = |tx: &mpsc::Sender<nats::Message>| -> io::Result<()> {
+            while !tx.is_closed() {
+                let messages = match &subscription {
+                    JetStreamSubscription::Push(subscription) => {
+                        match subscription.next_timeout(JETSTREAM_POLL_TIMEOUT) {
+                            Ok(message) => vec![message],
+                            Err(error) if error.kind() == io::ErrorKind::TimedOut => continue,
+                            Err(error) => return Err(error),
+                        }
+                    }
+                    JetStreamSubscription::Pull(subscription) => {
+                        match subscription.timeout_fetch(batch_size, JETSTREAM_POLL_TIMEOUT) {
+                            Ok(batch) => batch.collect(),
+                            Err(error) if error.kind() == io::ErrorKind::TimedOut => continue,
+                            Err(error) => return Err(error),
+                        }
+                    }
+                };
+
+                for message in messages {
+                    if tx.blocking_send(message).is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+            Ok(())
+        };
+
+        if let Err(error) = receive(&tx) {
+            emit!(NatsJetStreamReceiveError { error });
+        }
+    });
+
+    ReceiverStream::new(rx)
+}
+
+struct FinalizerEntry {
+    message: nats::Message,
+}
+
+/// Acknowledges a message once the events decoded from it have been processed.
+///
+/// Messages whose events failed to be delivered are negatively acknowledged so the server redelivers
+/// them, while rejected messages are terminated so they are not redelivered.
+async fn ack_message(message: nats::Message, status: BatchStatus) {
+    let kind = match status {
+        BatchStatus::Delivered => AckKind::Ack,
+        BatchStatus::Errored => AckKind::Nak,
+        BatchStatus::Rejected => AckKind::Term,
+    };
+
+    let result = tokio::task::spawn_blocking(move || message.ack_kind(kind))
+        .await
+        .expect("NATS ack task panicked");
+    if let Err(error) = result {
+        emit!(NatsJetStreamAckError { error });
+    }
+}
+
+async fn nats_jetstream_source(
+    subscription: JetStreamSubscription,
+    batch_size: usize,
+    decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+    acknowledgements: bool,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) =
+        UnorderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, shutdown.clone());
+    let stream = get_jetstream_stream(subscription, batch_size);
+    pin_mut!(stream);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+
+    loop {
+        tokio::select! {
+            biased;
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => {
+                if let Some((status, entry)) = entry {
+                    ack_message(entry.message, status).await;
+                }
+            },
+            message = stream.next() => {
+                let message = match message {
+                    Some(message) => message,
+                    None => break,
+                };
+                bytes_received.emit(ByteSize(message.data.len()));
+
+                let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+                let mut stream = FramedRead::new(message.data.as_ref(), decoder.clone());
+                while let Some(next) = stream.next().await {
+                    match next {
+                        Ok((events, _byte_size)) => {
+                            let count = events.len();
+                            emit!(EventsReceived {
+                                count,
+                                byte_size: events.size_of()
+                            });
+
+                            let now = Utc::now();
+
+                            let events = events.into_iter().map(|mut event| {
+                                if let Event::Log(ref mut log) = event {
+                                    log.try_insert(
+                                        log_schema().source_type_key(),
+                                        Bytes::from("nats"),
+                                    );
+                                    log.try_insert(log_schema().timestamp_key(), now);
+                                }
+                                event.with_batch_notifier_option(&batch)
+                            });
+
+                            out.send_batch(events).await.map_err(|error| {
+                                emit!(StreamClosedError { error, count });
+                            })?;
+                        }
+                        Err(error) => {
+                            // Error is logged by `crate::codecs`, no further
+                            // handling is needed here.
+                            if !error.can_continue() {
+                                break;
+                            }
+                        }
+                    }
+                }
+                drop(stream);
+                drop(batch);
+
+                match (&finalizer, receiver) {
+                    (Some(finalizer), Some(receiver)) => {
+                        finalizer.add(FinalizerEntry { message }, receiver);
+                    }
+                    _ => ack_message(message, BatchStatus::Delivered).await,
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+async fn create_jetstream_subscription(
+    config: &NatsSourceConfig,
+    jetstream: &NatsJetStreamConfig,
+) -> Result<JetStreamSubscription, BuildError> {
+    let nc = config.connect_blocking().await?;
+    let js = nats::jetstream::new(nc);
+
+    let subject = config.subject.clone();
+    let queue = config.queue.clone();
+    let jetstream = jetstream.clone();
+    tokio::task::spawn_blocking(move || match jetstream.mode {
+        NatsJetStreamConsumerMode::Push => {
+            let options = SubscribeOptions::bind_stream(jetstream.stream)
+                .durable_name(jetstream.durable_name)
+                .manual_ack();
+            match queue {
+                None => js.subscribe_with_options(&subject, &options),
+                Some(queue) => js.queue_subscribe_with_options(&subject, &queue, &options),
+            }
+            .map(JetStreamSubscription::Push)
+        }
+        NatsJetStreamConsumerMode::Pull => {
+            let options = PullSubscribeOptions::new()
+                .bind_stream(jetstream.stream)
+                .durable_name(jetstream.durable_name);
+            js.pull_subscribe_with_options(&subject, &options)
+                .map(JetStreamSubscription::Pull)
+        }
+    })
+    .await
+    .expect("NATS subscribe task panicked")
+    .context(SubscribeSnafu)
+}
+
 async fn create_subscription(
     config: &NatsSourceConfig,
 ) -> Result<(nats::asynk::Connection, nats::asynk::Subscription), BuildError> {
@@ -210,6 +500,28 @@ mod tests {
     fn generate_config() {
         crate::test_util::test_generate_config::<NatsSourceConfig>();
     }
+
+    #[test]
+    fn parse_jetstream_config() {
+        let config: NatsSourceConfig = toml::from_str(
+            r#"
+            connection_name = "vector"
+            subject = "from.vector"
+            url = "nats://127.0.0.1:4222"
+            jetstream.stream = "logs"
+            jetstream.durable_name = "vector"
+            jetstream.mode = "pull"
+            "#,
+        )
+        .unwrap();
+
+        let jetstream = config.jetstream.as_ref().unwrap();
+        assert_eq!(jetstream.stream, "logs");
+        assert_eq!(jetstream.durable_name, "vector");
+        assert_eq!(jetstream.mode, NatsJetStreamConsumerMode::Pull);
+        assert_eq!(jetstream.batch_size, 100);
+        assert!(config.can_acknowledge());
+    }
 }
 
 #[cfg(feature = "nats-integration-tests")]
@@ -218,6 +530,7 @@ mod integration_tests {
     #![allow(clippy::print_stdout)] //tests
 
     use super::*;
+    use crate::event::EventStatus;
     use crate::nats::{NatsAuthCredentialsFile, NatsAuthNKey, NatsAuthToken, NatsAuthUserPassword};
     use crate::test_util::{
         collect_n,
@@ -262,6 +575,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -287,6 +602,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -317,6 +634,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -347,6 +666,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -376,6 +697,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -405,6 +728,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -435,6 +760,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -465,6 +792,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -496,6 +825,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -521,6 +852,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -554,6 +887,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -585,6 +920,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -620,6 +957,8 @@ mod integration_tests {
             connection_name: "".to_owned(),
             subject: subject.clone(),
             url,
+            jetstream: None,
+            acknowledgements: Default::default(),
             queue: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
@@ -644,4 +983,97 @@ mod integration_tests {
             r
         );
     }
+
+    async fn jetstream_publish_and_check(mode: NatsJetStreamConsumerMode) {
+        let subject = format!("test-{}", random_string(10));
+        let stream = format!("test-{}", random_string(10));
+        let url = std::env::var("NATS_JETSTREAM_ADDRESS")
+            .unwrap_or_else(|_| String::from("nats://localhost:4222"));
+        let messages = vec!["first", "second", "third"];
+
+        // Publish before the source is started, so the messages have to be read back from the stream.
+        let js = {
+            let (url, stream, subject) = (url.clone(), stream.clone(), subject.clone());
+            let messages = messages.clone();
+            tokio::task::spawn_blocking(move || {
+                let js = nats::jetstream::new(nats::connect(&url).unwrap());
+                js.add_stream(nats::jetstream::StreamConfig {
+                    name: stream,
+                    subjects: vec![subject.clone()],
+                    ..Default::default()
+                })
+                .unwrap();
+                for message in messages {
+                    js.publish(&subject, message).unwrap();
+                }
+                js
+            })
+            .await
+            .unwrap()
+        };
+
+        let conf = NatsSourceConfig {
+            connection_name: "".to_owned(),
+            subject,
+            url,
+            jetstream: Some(NatsJetStreamConfig {
+                stream: stream.clone(),
+                durable_name: "vector".to_owned(),
+                mode,
+                batch_size: 10,
+            }),
+            acknowledgements: true.into(),
+            ..Default::default()
+        };
+
+        let count = messages.len();
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test_finalize(EventStatus::Delivered);
+            let decoder = DecodingConfig::new(
+                conf.framing.clone(),
+                conf.decoding.clone(),
+                LogNamespace::Legacy,
+            )
+            .build();
+            let jetstream = conf.jetstream.as_ref().unwrap();
+            let subscription = create_jetstream_subscription(&conf, jetstream)
+                .await
+                .unwrap();
+            tokio::spawn(nats_jetstream_source(
+                subscription,
+                jetstream.batch_size,
+                decoder,
+                ShutdownSignal::noop(),
+                tx,
+                true,
+            ));
+
+            collect_n(rx, count).await
+        })
+        .await;
+
+        let received = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(received, messages);
+
+        // Give the source a moment to acknowledge the messages.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let info = tokio::task::spawn_blocking(move || js.consumer_info(&stream, "vector"))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.num_ack_pending, 0);
+    }
+
+    #[tokio::test]
+    async fn nats_jetstream_push_consumer() {
+        jetstream_publish_and_check(NatsJetStreamConsumerMode::Push).await;
+    }
+
+    #[tokio::test]
+    async fn nats_jetstream_pull_consumer() {
+        jetstream_publish_and_check(NatsJetStreamConsumerMode::Pull).await;
+    }
 }
@@ -0,0 +1,352 @@
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use netflow_parser::{
+    flow::{FieldValue, FlowPacket},
+    flow_parser::FlowParser,
+};
+use tokio::net::UdpSocket;
+use vector_common::internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::{Event, LogEvent, Value},
+    internal_events::{
+        EventsReceived, NetflowMissingTemplates, NetflowParseError, NetflowReceiveError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    udp, SourceSender,
+};
+
+/// Configuration for the `netflow` source.
+#[configurable_component(source("netflow"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct NetflowConfig {
+    /// The address to listen for flow packets on.
+    address: SocketAddr,
+
+    /// The maximum size, in bytes, of incoming packets.
+    ///
+    /// Packets larger than this are discarded.
+    #[serde(default = "crate::serde::default_max_length")]
+    max_length: usize,
+
+    /// The size, in bytes, of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+
+    /// The maximum number of NetFlow v9 and IPFIX templates kept, across all exporters.
+    ///
+    /// Templates announced once this limit is reached are discarded, and the records relying on
+    /// them cannot be decoded.
+    #[serde(default = "default_max_templates")]
+    max_templates: usize,
+
+    /// Overrides the name of the log field used to add the exporter's address to each event.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    host_key: Option<String>,
+}
+
+const fn default_max_templates() -> usize {
+    10_000
+}
+
+impl NetflowConfig {
+    pub fn from_address(address: SocketAddr) -> Self {
+        Self {
+            address,
+            max_length: crate::serde::default_max_length(),
+            receive_buffer_bytes: None,
+            max_templates: default_max_templates(),
+            host_key: None,
+        }
+    }
+}
+
+impl GenerateConfig for NetflowConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::from_address("0.0.0.0:2055".parse().unwrap())).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for NetflowConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let host_key = self
+            .host_key
+            .clone()
+            .unwrap_or_else(|| log_schema().host_key().to_string());
+        Ok(Box::pin(netflow(
+            self.clone(),
+            host_key,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::udp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn netflow(
+    config: NetflowConfig,
+    host_key: String,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let socket = UdpSocket::bind(&config.address)
+        .await
+        .expect("Failed to bind to udp listener socket");
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    let max_length = match config.receive_buffer_bytes {
+        Some(receive_buffer_bytes) => std::cmp::min(config.max_length, receive_buffer_bytes),
+        None => config.max_length,
+    };
+
+    let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+
+    info!(message = "Listening.", address = %config.address);
+
+    // Templates are only valid for the exporter that sent them, which the parser keys them by.
+    let mut parser = FlowParser::new(config.max_templates);
+
+    // We add 1 to the max_length in order to determine if the received packet has been truncated.
+    let mut buf = vec![0; max_length + 1];
+    loop {
+        let (byte_size, exporter) = tokio::select! {
+            recv = socket.recv_from(&mut buf) => match recv {
+                Ok(res) => res,
+                Err(error) => {
+                    emit!(NetflowReceiveError { error });
+                    return Err(());
+                }
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+
+        bytes_received.emit(ByteSize(byte_size));
+
+        if byte_size > max_length {
+            warn!(
+                message = "Discarding packet larger than max_length.",
+                max_length = max_length,
+                internal_log_rate_secs = 30
+            );
+            continue;
+        }
+
+        let packet = match parser.parse(exporter, &buf[..byte_size]) {
+            Ok(packet) => packet,
+            Err(error) => {
+                emit!(NetflowParseError { error, exporter });
+                continue;
+            }
+        };
+
+        if !packet.missing_templates.is_empty() {
+            emit!(NetflowMissingTemplates {
+                template_ids: &packet.missing_templates,
+                exporter,
+            });
+        }
+
+        let events = packet_to_events(packet, exporter, &host_key);
+        if events.is_empty() {
+            continue;
+        }
+
+        let count = events.len();
+        emit!(EventsReceived {
+            count,
+            byte_size: events.size_of(),
+        });
+
+        tokio::select! {
+            result = out.send_batch(events) => {
+                if let Err(error) = result {
+                    emit!(StreamClosedError { error, count });
+                    return Ok(());
+                }
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+/// Converts every record of a packet into a log event, alongside the packet's header fields.
+fn packet_to_events(packet: FlowPacket, exporter: SocketAddr, host_key: &str) -> Vec<Event> {
+    let timestamp = packet
+        .export_time
+        .and_then(|secs| Utc.timestamp_opt(secs.into(), 0).single())
+        .unwrap_or_else(Utc::now);
+    let header = packet
+        .header
+        .into_iter()
+        .map(|(name, value)| (name, field_value(value)))
+        .collect::<Value>();
+
+    packet
+        .records
+        .into_iter()
+        .map(|record| {
+            let mut log = LogEvent::default();
+            for (name, value) in record.fields {
+                log.insert(name.as_str(), field_value(value));
+            }
+            log.insert("flow_protocol", packet.protocol.as_str());
+            log.insert("record_type", record.kind.as_str());
+            log.insert("header", header.clone());
+            log.insert(host_key, exporter.ip().to_string());
+            log.insert("port", exporter.port());
+            log.insert(log_schema().source_type_key(), Bytes::from("netflow"));
+            log.insert(log_schema().timestamp_key(), timestamp);
+            Event::Log(log)
+        })
+        .collect()
+}
+
+fn field_value(value: FieldValue) -> Value {
+    match value {
+        // Counters may use all 64 bits, which don't fit in an integer.
+        FieldValue::Unsigned(value) => {
+            i64::try_from(value).map_or_else(|_| Value::from(value as f64), Value::from)
+        }
+        FieldValue::Timestamp(since_epoch) => i64::try_from(since_epoch.as_secs())
+            .ok()
+            .and_then(|secs| Utc.timestamp_opt(secs, since_epoch.subsec_nanos()).single())
+            .map_or_else(|| value.to_string().into(), Value::from),
+        // Addresses and strings, and the raw bytes of unknown fields encoded as hex.
+        value => value.to_string().into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        config::ComponentKey,
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<NetflowConfig>();
+    }
+
+    fn netflow_v5_packet(count: u16) -> Vec<u8> {
+        let mut data = vec![];
+        data.extend_from_slice(&5u16.to_be_bytes());
+        data.extend_from_slice(&count.to_be_bytes());
+        data.extend_from_slice(&1000u32.to_be_bytes()); // sys_uptime
+        data.extend_from_slice(&1_600_000_000u32.to_be_bytes()); // unix_secs
+        data.extend_from_slice(&[0; 8]); // unix_nsecs, flow_sequence
+        data.extend_from_slice(&[0; 4]); // engine_type, engine_id, sampling
+        for _ in 0..count {
+            data.extend_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2, 0, 0, 0, 0]);
+            data.extend_from_slice(&[0, 1, 0, 2]); // interfaces
+            data.extend_from_slice(&3u32.to_be_bytes()); // packets
+            data.extend_from_slice(&180u32.to_be_bytes()); // octets
+            data.extend_from_slice(&[0; 8]); // first, last
+            data.extend_from_slice(&12345u16.to_be_bytes());
+            data.extend_from_slice(&443u16.to_be_bytes());
+            data.extend_from_slice(&[0, 0x12, 6, 0]); // pad, flags, proto, tos
+            data.extend_from_slice(&[0; 8]); // autonomous systems, masks, pad
+        }
+        data
+    }
+
+    #[tokio::test]
+    async fn receives_netflow_v5() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
+            let address = next_addr();
+            let (tx, rx) = SourceSender::new_test();
+            let (cx, _shutdown) = SourceContext::new_shutdown(&ComponentKey::from("netflow"), tx);
+            let source = NetflowConfig::from_address(address)
+                .build(cx)
+                .await
+                .unwrap();
+            tokio::spawn(source);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind(next_addr()).await.unwrap();
+            socket
+                .send_to(&netflow_v5_packet(2), address)
+                .await
+                .unwrap();
+
+            let events = collect_n(rx, 2).await;
+            assert_eq!(events.len(), 2);
+
+            let log = events[0].as_log();
+            assert_eq!(log["flow_protocol"], "netflow_v5".into());
+            assert_eq!(log["record_type"], "flow".into());
+            assert_eq!(log["source_ipv4_address"], "10.0.0.1".into());
+            assert_eq!(log["destination_transport_port"], 443.into());
+            assert_eq!(log["octet_delta_count"], 180.into());
+            assert_eq!(log["header.sys_uptime"], 1000.into());
+            assert_eq!(log[log_schema().source_type_key()], "netflow".into());
+            assert_eq!(
+                log[log_schema().timestamp_key()],
+                Utc.timestamp(1_600_000_000, 0).into()
+            );
+        })
+        .await;
+    }
+
+    #[test]
+    fn converts_field_values() {
+        assert_eq!(field_value(FieldValue::Unsigned(6)), Value::from(6));
+        assert_eq!(
+            field_value(FieldValue::Unsigned(u64::MAX)),
+            Value::from(u64::MAX as f64)
+        );
+        assert_eq!(
+            field_value(FieldValue::Ipv4("10.0.0.1".parse().unwrap())),
+            Value::from("10.0.0.1")
+        );
+        assert_eq!(
+            field_value(FieldValue::Timestamp(std::time::Duration::from_millis(
+                1_600_000_000_123
+            ))),
+            Value::from(Utc.timestamp_millis(1_600_000_000_123))
+        );
+        assert_eq!(
+            field_value(FieldValue::Mac([0, 0x1b, 0x21, 0x3c, 0x4d, 0x5e])),
+            Value::from("00:1b:21:3c:4d:5e")
+        );
+        assert_eq!(
+            field_value(FieldValue::Bytes(vec![0xab, 0xcd])),
+            Value::from("abcd")
+        );
+    }
+}
@@ -0,0 +1,385 @@
+//! A minimal BER codec, covering the subset of ASN.1 used by SNMP messages.
+
+use std::fmt;
+
+use snafu::Snafu;
+
+pub const INTEGER: u8 = 0x02;
+pub const OCTET_STRING: u8 = 0x04;
+pub const NULL: u8 = 0x05;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+pub const IP_ADDRESS: u8 = 0x40;
+pub const COUNTER32: u8 = 0x41;
+pub const GAUGE32: u8 = 0x42;
+pub const TIME_TICKS: u8 = 0x43;
+pub const OPAQUE: u8 = 0x44;
+pub const COUNTER64: u8 = 0x46;
+pub const NO_SUCH_OBJECT: u8 = 0x80;
+pub const NO_SUCH_INSTANCE: u8 = 0x81;
+pub const END_OF_MIB_VIEW: u8 = 0x82;
+
+#[derive(Debug, Snafu)]
+pub enum BerError {
+    #[snafu(display("Unexpected end of data"))]
+    Truncated,
+    #[snafu(display("Expected tag {:#04x}, found {:#04x}", expected, found))]
+    UnexpectedTag { expected: u8, found: u8 },
+    #[snafu(display("Invalid length encoding"))]
+    InvalidLength,
+    #[snafu(display("Integer does not fit in 64 bits"))]
+    IntegerOverflow,
+    #[snafu(display("Invalid object identifier"))]
+    InvalidOid,
+}
+
+pub type BerResult<T> = Result<T, BerError>;
+
+/// An object identifier, such as `1.3.6.1.2.1.1.3.0`.
+#[derive(Clone, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Oid(pub Vec<u32>);
+
+impl Oid {
+    pub fn from_slice(arcs: &[u32]) -> Self {
+        Self(arcs.to_vec())
+    }
+}
+
+impl fmt::Display for Oid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, arc) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", arc)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single tag-length-value element.
+#[derive(Clone, Copy, Debug)]
+pub struct Tlv<'a> {
+    pub tag: u8,
+    pub value: &'a [u8],
+    /// The offset of `value` from the start of the outermost message.
+    pub offset: usize,
+}
+
+/// Reads consecutive elements, keeping track of their offset in the outermost message so that
+/// authentication parameters can be located for USM message authentication.
+#[derive(Clone, Debug)]
+pub struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    /// Reads the elements contained within a constructed element.
+    pub const fn nested(tlv: &Tlv<'a>) -> Self {
+        Self {
+            data: tlv.value,
+            offset: tlv.offset,
+        }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn read(&mut self) -> BerResult<Tlv<'a>> {
+        let (&tag, rest) = self.data.split_first().ok_or(BerError::Truncated)?;
+        let (&first, rest) = rest.split_first().ok_or(BerError::Truncated)?;
+
+        let (length, rest) = if first & 0x80 == 0 {
+            (usize::from(first), rest)
+        } else {
+            // Long form. Indefinite lengths are not allowed in SNMP.
+            let count = usize::from(first & 0x7f);
+            if count == 0 || count > 4 {
+                return Err(BerError::InvalidLength);
+            }
+            if rest.len() < count {
+                return Err(BerError::Truncated);
+            }
+            let length = rest[..count]
+                .iter()
+                .fold(0usize, |length, &byte| (length << 8) | usize::from(byte));
+            (length, &rest[count..])
+        };
+
+        if rest.len() < length {
+            return Err(BerError::Truncated);
+        }
+
+        let header_length = self.data.len() - rest.len();
+        let tlv = Tlv {
+            tag,
+            value: &rest[..length],
+            offset: self.offset + header_length,
+        };
+        self.data = &rest[length..];
+        self.offset += header_length + length;
+        Ok(tlv)
+    }
+
+    pub fn expect(&mut self, expected: u8) -> BerResult<Tlv<'a>> {
+        let tlv = self.read()?;
+        if tlv.tag == expected {
+            Ok(tlv)
+        } else {
+            Err(BerError::UnexpectedTag {
+                expected,
+                found: tlv.tag,
+            })
+        }
+    }
+
+    pub fn sequence(&mut self) -> BerResult<Reader<'a>> {
+        self.expect(SEQUENCE).map(|tlv| Reader::nested(&tlv))
+    }
+
+    pub fn integer(&mut self) -> BerResult<i64> {
+        self.expect(INTEGER)
+            .and_then(|tlv| decode_integer(tlv.value))
+    }
+
+    pub fn unsigned(&mut self, tag: u8) -> BerResult<u64> {
+        self.expect(tag).and_then(|tlv| decode_unsigned(tlv.value))
+    }
+
+    pub fn octet_string(&mut self) -> BerResult<&'a [u8]> {
+        self.expect(OCTET_STRING).map(|tlv| tlv.value)
+    }
+
+    pub fn oid(&mut self) -> BerResult<Oid> {
+        self.expect(OBJECT_IDENTIFIER)
+            .and_then(|tlv| decode_oid(tlv.value))
+    }
+}
+
+pub fn decode_integer(bytes: &[u8]) -> BerResult<i64> {
+    if bytes.len() > 8 {
+        return Err(BerError::IntegerOverflow);
+    }
+    let initial = match bytes.first() {
+        Some(byte) if byte & 0x80 != 0 => -1,
+        _ => 0,
+    };
+    Ok(bytes
+        .iter()
+        .fold(initial, |value: i64, &byte| (value << 8) | i64::from(byte)))
+}
+
+pub fn decode_unsigned(mut bytes: &[u8]) -> BerResult<u64> {
+    // Values with their most significant bit set are prefixed by a zero byte.
+    while bytes.len() > 1 && bytes[0] == 0 {
+        bytes = &bytes[1..];
+    }
+    if bytes.len() > 8 {
+        return Err(BerError::IntegerOverflow);
+    }
+    Ok(bytes
+        .iter()
+        .fold(0, |value: u64, &byte| (value << 8) | u64::from(byte)))
+}
+
+pub fn decode_oid(bytes: &[u8]) -> BerResult<Oid> {
+    let mut arcs = Vec::with_capacity(bytes.len() + 1);
+    let mut value: u32 = 0;
+    let mut pending = false;
+    for &byte in bytes {
+        if value > u32::MAX >> 7 {
+            return Err(BerError::InvalidOid);
+        }
+        value = (value << 7) | u32::from(byte & 0x7f);
+        pending = byte & 0x80 != 0;
+        if !pending {
+            if arcs.is_empty() {
+                // The first sub-identifier encodes the first two arcs.
+                let first = std::cmp::min(value / 40, 2);
+                arcs.push(first);
+                arcs.push(value - first * 40);
+            } else {
+                arcs.push(value);
+            }
+            value = 0;
+        }
+    }
+    if pending || arcs.is_empty() {
+        return Err(BerError::InvalidOid);
+    }
+    Ok(Oid(arcs))
+}
+
+/// Builds BER encoded elements, used to acknowledge informs.
+#[derive(Debug, Default)]
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+
+    pub fn write(&mut self, tag: u8, value: &[u8]) {
+        self.buf.push(tag);
+        let length = value.len();
+        if length < 0x80 {
+            self.buf.push(length as u8);
+        } else {
+            let bytes = (length as u32).to_be_bytes();
+            let skip = bytes.iter().take_while(|&&byte| byte == 0).count();
+            self.buf.push(0x80 | (bytes.len() - skip) as u8);
+            self.buf.extend_from_slice(&bytes[skip..]);
+        }
+        self.buf.extend_from_slice(value);
+    }
+
+    /// Writes a constructed element, whose content is written by `f`.
+    pub fn constructed(&mut self, tag: u8, f: impl FnOnce(&mut Writer)) {
+        let mut inner = Writer::new();
+        f(&mut inner);
+        self.write(tag, &inner.buf);
+    }
+
+    pub fn sequence(&mut self, f: impl FnOnce(&mut Writer)) {
+        self.constructed(SEQUENCE, f);
+    }
+
+    pub fn integer(&mut self, value: i64) {
+        let bytes = value.to_be_bytes();
+        // Keep the shortest two's complement encoding that preserves the sign.
+        let skip = bytes
+            .windows(2)
+            .take_while(|pair| {
+                (pair[0] == 0 && pair[1] & 0x80 == 0) || (pair[0] == 0xff && pair[1] & 0x80 != 0)
+            })
+            .count();
+        self.write(INTEGER, &bytes[skip..]);
+    }
+
+    pub fn unsigned(&mut self, tag: u8, value: u64) {
+        let bytes = value.to_be_bytes();
+        let skip = bytes[..7].iter().take_while(|&&byte| byte == 0).count();
+        if bytes[skip] & 0x80 != 0 {
+            let mut value = vec![0];
+            value.extend_from_slice(&bytes[skip..]);
+            self.write(tag, &value);
+        } else {
+            self.write(tag, &bytes[skip..]);
+        }
+    }
+
+    pub fn octet_string(&mut self, value: &[u8]) {
+        self.write(OCTET_STRING, value);
+    }
+
+    pub fn null(&mut self) {
+        self.write(NULL, &[]);
+    }
+
+    pub fn oid(&mut self, oid: &Oid) {
+        let mut value = Vec::new();
+        let arcs = &oid.0;
+        let first = match arcs.len() {
+            0 => 0,
+            1 => arcs[0] * 40,
+            _ => arcs[0] * 40 + arcs[1],
+        };
+        for arc in std::iter::once(first).chain(arcs.iter().skip(2).copied()) {
+            let mut chunk = vec![(arc & 0x7f) as u8];
+            let mut rest = arc >> 7;
+            while rest > 0 {
+                chunk.push(0x80 | (rest & 0x7f) as u8);
+                rest >>= 7;
+            }
+            value.extend(chunk.iter().rev());
+        }
+        self.write(OBJECT_IDENTIFIER, &value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_integers() {
+        for value in [0, 1, 127, 128, 255, 256, -1, -128, -129, i64::MAX, i64::MIN] {
+            let mut writer = Writer::new();
+            writer.integer(value);
+            let bytes = writer.into_bytes();
+            assert_eq!(Reader::new(&bytes).integer().unwrap(), value, "{}", value);
+        }
+    }
+
+    #[test]
+    fn round_trips_unsigned() {
+        for value in [0, 127, 128, u64::from(u32::MAX), u64::MAX] {
+            let mut writer = Writer::new();
+            writer.unsigned(COUNTER64, value);
+            let bytes = writer.into_bytes();
+            assert_eq!(
+                Reader::new(&bytes).unsigned(COUNTER64).unwrap(),
+                value,
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn round_trips_oids() {
+        let oid = Oid(vec![1, 3, 6, 1, 4, 1, 2021, 4294967295]);
+        let mut writer = Writer::new();
+        writer.oid(&oid);
+        let bytes = writer.into_bytes();
+        assert_eq!(bytes[..4], [OBJECT_IDENTIFIER, 12, 0x2b, 6]);
+        assert_eq!(Reader::new(&bytes).oid().unwrap(), oid);
+        assert_eq!(oid.to_string(), "1.3.6.1.4.1.2021.4294967295");
+    }
+
+    #[test]
+    fn reads_long_lengths_and_offsets() {
+        let content = vec![0xaa; 300];
+        let mut writer = Writer::new();
+        writer.sequence(|writer| {
+            writer.null();
+            writer.octet_string(&content);
+        });
+        let bytes = writer.into_bytes();
+
+        let mut sequence = Reader::new(&bytes).sequence().unwrap();
+        sequence.expect(NULL).unwrap();
+        let tlv = sequence.expect(OCTET_STRING).unwrap();
+        assert_eq!(tlv.value, &content[..]);
+        assert_eq!(&bytes[tlv.offset..tlv.offset + 300], &content[..]);
+        assert!(sequence.is_empty());
+    }
+
+    #[test]
+    fn rejects_malformed_elements() {
+        assert!(matches!(
+            Reader::new(&[OCTET_STRING, 5, 1]).read(),
+            Err(BerError::Truncated)
+        ));
+        assert!(matches!(
+            Reader::new(&[OCTET_STRING, 0x80]).read(),
+            Err(BerError::InvalidLength)
+        ));
+        assert!(matches!(
+            Reader::new(&[OBJECT_IDENTIFIER, 2, 0x2b, 0x86]).oid(),
+            Err(BerError::InvalidOid)
+        ));
+    }
+}
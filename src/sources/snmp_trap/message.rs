@@ -0,0 +1,584 @@
+use std::net::Ipv4Addr;
+
+use snafu::Snafu;
+
+use super::{
+    ber::{self, BerError, Oid, Reader, Writer},
+    usm::{Usm, UsmError, UsmParams},
+};
+
+const TRAP_V1: u8 = 0xa4;
+const INFORM: u8 = 0xa6;
+const TRAP_V2: u8 = 0xa7;
+const RESPONSE: u8 = 0xa2;
+
+/// The user-based security model, the only one defined for SNMPv3.
+const USM_SECURITY_MODEL: i64 = 3;
+const AUTH_FLAG: u8 = 0x01;
+const PRIV_FLAG: u8 = 0x02;
+
+#[derive(Debug, Snafu)]
+pub enum SnmpError {
+    #[snafu(display("Malformed message: {}", source))]
+    Malformed { source: BerError },
+    #[snafu(display("Unsupported SNMP version {}", version))]
+    UnsupportedVersion { version: i64 },
+    #[snafu(display("Unsupported PDU type {:#04x}", tag))]
+    UnsupportedPdu { tag: u8 },
+    #[snafu(display("Unsupported security model {}", model))]
+    UnsupportedSecurityModel { model: i64 },
+    #[snafu(display("Invalid message flags"))]
+    InvalidFlags,
+    #[snafu(display("{}", source))]
+    Usm { source: UsmError },
+}
+
+impl From<BerError> for SnmpError {
+    fn from(source: BerError) -> Self {
+        Self::Malformed { source }
+    }
+}
+
+impl From<UsmError> for SnmpError {
+    fn from(source: UsmError) -> Self {
+        Self::Usm { source }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Version {
+    V1,
+    V2c,
+    V3,
+}
+
+impl Version {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::V1 => "1",
+            Self::V2c => "2c",
+            Self::V3 => "3",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PduType {
+    Trap,
+    TrapV2,
+    Inform,
+}
+
+impl PduType {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Trap => "trap",
+            Self::TrapV2 => "trap_v2",
+            Self::Inform => "inform",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(i64),
+    OctetString(Vec<u8>),
+    Null,
+    Oid(Oid),
+    IpAddress(Ipv4Addr),
+    Counter32(u64),
+    Gauge32(u64),
+    TimeTicks(u64),
+    Opaque(Vec<u8>),
+    Counter64(u64),
+    NoSuchObject,
+    NoSuchInstance,
+    EndOfMibView,
+    Unknown { tag: u8, value: Vec<u8> },
+}
+
+impl Value {
+    fn decode(tlv: ber::Tlv<'_>) -> Result<Self, BerError> {
+        Ok(match tlv.tag {
+            ber::INTEGER => Self::Integer(ber::decode_integer(tlv.value)?),
+            ber::OCTET_STRING => Self::OctetString(tlv.value.to_vec()),
+            ber::NULL => Self::Null,
+            ber::OBJECT_IDENTIFIER => Self::Oid(ber::decode_oid(tlv.value)?),
+            ber::IP_ADDRESS => match <[u8; 4]>::try_from(tlv.value) {
+                Ok(octets) => Self::IpAddress(octets.into()),
+                Err(_) => Self::OctetString(tlv.value.to_vec()),
+            },
+            ber::COUNTER32 => Self::Counter32(ber::decode_unsigned(tlv.value)?),
+            ber::GAUGE32 => Self::Gauge32(ber::decode_unsigned(tlv.value)?),
+            ber::TIME_TICKS => Self::TimeTicks(ber::decode_unsigned(tlv.value)?),
+            ber::OPAQUE => Self::Opaque(tlv.value.to_vec()),
+            ber::COUNTER64 => Self::Counter64(ber::decode_unsigned(tlv.value)?),
+            ber::NO_SUCH_OBJECT => Self::NoSuchObject,
+            ber::NO_SUCH_INSTANCE => Self::NoSuchInstance,
+            ber::END_OF_MIB_VIEW => Self::EndOfMibView,
+            tag => Self::Unknown {
+                tag,
+                value: tlv.value.to_vec(),
+            },
+        })
+    }
+
+    fn encode(&self, writer: &mut Writer) {
+        match self {
+            Self::Integer(value) => writer.integer(*value),
+            Self::OctetString(value) => writer.octet_string(value),
+            Self::Null => writer.null(),
+            Self::Oid(oid) => writer.oid(oid),
+            Self::IpAddress(address) => writer.write(ber::IP_ADDRESS, &address.octets()),
+            Self::Counter32(value) => writer.unsigned(ber::COUNTER32, *value),
+            Self::Gauge32(value) => writer.unsigned(ber::GAUGE32, *value),
+            Self::TimeTicks(value) => writer.unsigned(ber::TIME_TICKS, *value),
+            Self::Opaque(value) => writer.write(ber::OPAQUE, value),
+            Self::Counter64(value) => writer.unsigned(ber::COUNTER64, *value),
+            Self::NoSuchObject => writer.write(ber::NO_SUCH_OBJECT, &[]),
+            Self::NoSuchInstance => writer.write(ber::NO_SUCH_INSTANCE, &[]),
+            Self::EndOfMibView => writer.write(ber::END_OF_MIB_VIEW, &[]),
+            Self::Unknown { tag, value } => writer.write(*tag, value),
+        }
+    }
+
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Self::Integer(_) => "integer",
+            Self::OctetString(_) => "octet_string",
+            Self::Null => "null",
+            Self::Oid(_) => "object_identifier",
+            Self::IpAddress(_) => "ip_address",
+            Self::Counter32(_) => "counter32",
+            Self::Gauge32(_) => "gauge32",
+            Self::TimeTicks(_) => "time_ticks",
+            Self::Opaque(_) => "opaque",
+            Self::Counter64(_) => "counter64",
+            Self::NoSuchObject => "no_such_object",
+            Self::NoSuchInstance => "no_such_instance",
+            Self::EndOfMibView => "end_of_mib_view",
+            Self::Unknown { .. } => "unknown",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Varbind {
+    pub oid: Oid,
+    pub value: Value,
+}
+
+/// The fields specific to SNMPv1 trap PDUs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrapV1 {
+    pub enterprise: Oid,
+    pub agent_address: Ipv4Addr,
+    pub generic_trap: i64,
+    pub specific_trap: i64,
+    pub time_stamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pdu {
+    pub pdu_type: PduType,
+    /// Always zero for SNMPv1 traps, which have no request ID.
+    pub request_id: i64,
+    pub trap_v1: Option<TrapV1>,
+    pub varbinds: Vec<Varbind>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Security {
+    Community(Vec<u8>),
+    Usm {
+        user_name: Vec<u8>,
+        engine_id: Vec<u8>,
+        authenticated: bool,
+        encrypted: bool,
+        context_engine_id: Vec<u8>,
+        context_name: Vec<u8>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    pub version: Version,
+    pub security: Security,
+    pub pdu: Pdu,
+}
+
+impl Message {
+    /// Parses a notification, authenticating and decrypting it first if it is an SNMPv3 message.
+    pub fn parse(data: &[u8], usm: &Usm) -> Result<Self, SnmpError> {
+        let mut message = Reader::new(data).sequence()?;
+        let version = match message.integer()? {
+            0 => Version::V1,
+            1 => Version::V2c,
+            3 => return parse_v3(data, message, usm),
+            version => return Err(SnmpError::UnsupportedVersion { version }),
+        };
+
+        let community = message.octet_string()?.to_vec();
+        let pdu = parse_pdu(&mut message, version)?;
+        Ok(Self {
+            version,
+            security: Security::Community(community),
+            pdu,
+        })
+    }
+
+    /// Builds the response acknowledging an SNMPv2c inform.
+    ///
+    /// SNMPv3 informs are sent to the receiver's own engine, which requires engine ID discovery
+    /// that this source does not implement, so they are not acknowledged.
+    pub fn inform_response(&self) -> Option<Vec<u8>> {
+        let community = match (&self.security, self.pdu.pdu_type) {
+            (Security::Community(community), PduType::Inform) => community,
+            _ => return None,
+        };
+
+        let mut writer = Writer::new();
+        writer.sequence(|writer| {
+            writer.integer(1);
+            writer.octet_string(community);
+            writer.constructed(RESPONSE, |writer| {
+                writer.integer(self.pdu.request_id);
+                writer.integer(0); // error-status
+                writer.integer(0); // error-index
+                encode_varbinds(writer, &self.pdu.varbinds);
+            });
+        });
+        Some(writer.into_bytes())
+    }
+}
+
+fn parse_v3(data: &[u8], mut message: Reader<'_>, usm: &Usm) -> Result<Message, SnmpError> {
+    let mut global = message.sequence()?;
+    let _message_id = global.integer()?;
+    let _max_size = global.integer()?;
+    let flags = match global.octet_string()? {
+        [flags] => *flags,
+        _ => return Err(SnmpError::InvalidFlags),
+    };
+    let model = global.integer()?;
+    if model != USM_SECURITY_MODEL {
+        return Err(SnmpError::UnsupportedSecurityModel { model });
+    }
+
+    let authenticated = flags & AUTH_FLAG != 0;
+    let encrypted = flags & PRIV_FLAG != 0;
+    if encrypted && !authenticated {
+        return Err(SnmpError::InvalidFlags);
+    }
+
+    let security_parameters = message.expect(ber::OCTET_STRING)?;
+    let mut usm_reader = Reader::nested(&security_parameters).sequence()?;
+    let engine_id = usm_reader.octet_string()?;
+    let engine_boots = usm_reader.integer()?;
+    let engine_time = usm_reader.integer()?;
+    let user_name = usm_reader.octet_string()?;
+    let auth_parameters = usm_reader.expect(ber::OCTET_STRING)?;
+    let privacy_parameters = usm_reader.octet_string()?;
+    let params = UsmParams {
+        engine_id,
+        engine_boots: u32::try_from(engine_boots).map_err(|_| BerError::IntegerOverflow)?,
+        engine_time: u32::try_from(engine_time).map_err(|_| BerError::IntegerOverflow)?,
+        user_name,
+        auth_parameters: auth_parameters.value,
+        auth_parameters_offset: auth_parameters.offset,
+        privacy_parameters,
+    };
+
+    let encrypted_pdu = if encrypted {
+        Some(message.octet_string()?)
+    } else {
+        None
+    };
+    let plaintext = usm.process(data, &params, authenticated, encrypted_pdu)?;
+
+    let mut scoped_pdu = match &plaintext {
+        Some(plaintext) => Reader::new(plaintext).sequence()?,
+        None => message.sequence()?,
+    };
+    let context_engine_id = scoped_pdu.octet_string()?.to_vec();
+    let context_name = scoped_pdu.octet_string()?.to_vec();
+    let pdu = parse_pdu(&mut scoped_pdu, Version::V3)?;
+
+    Ok(Message {
+        version: Version::V3,
+        security: Security::Usm {
+            user_name: user_name.to_vec(),
+            engine_id: engine_id.to_vec(),
+            authenticated,
+            encrypted,
+            context_engine_id,
+            context_name,
+        },
+        pdu,
+    })
+}
+
+fn parse_pdu(reader: &mut Reader<'_>, version: Version) -> Result<Pdu, SnmpError> {
+    let tlv = reader.read()?;
+    let mut pdu = Reader::nested(&tlv);
+
+    match (tlv.tag, version) {
+        (TRAP_V1, Version::V1) => {
+            let enterprise = pdu.oid()?;
+            let agent_address = match <[u8; 4]>::try_from(pdu.expect(ber::IP_ADDRESS)?.value) {
+                Ok(octets) => octets.into(),
+                Err(_) => Ipv4Addr::UNSPECIFIED,
+            };
+            let generic_trap = pdu.integer()?;
+            let specific_trap = pdu.integer()?;
+            let time_stamp = pdu.unsigned(ber::TIME_TICKS)?;
+            Ok(Pdu {
+                pdu_type: PduType::Trap,
+                request_id: 0,
+                trap_v1: Some(TrapV1 {
+                    enterprise,
+                    agent_address,
+                    generic_trap,
+                    specific_trap,
+                    time_stamp,
+                }),
+                varbinds: parse_varbinds(&mut pdu)?,
+            })
+        }
+        (TRAP_V2 | INFORM, Version::V2c | Version::V3) => {
+            let request_id = pdu.integer()?;
+            let _error_status = pdu.integer()?;
+            let _error_index = pdu.integer()?;
+            Ok(Pdu {
+                pdu_type: if tlv.tag == INFORM {
+                    PduType::Inform
+                } else {
+                    PduType::TrapV2
+                },
+                request_id,
+                trap_v1: None,
+                varbinds: parse_varbinds(&mut pdu)?,
+            })
+        }
+        (tag, _) => Err(SnmpError::UnsupportedPdu { tag }),
+    }
+}
+
+fn parse_varbinds(pdu: &mut Reader<'_>) -> Result<Vec<Varbind>, BerError> {
+    let mut list = pdu.sequence()?;
+    let mut varbinds = Vec::new();
+    while !list.is_empty() {
+        let mut varbind = list.sequence()?;
+        let oid = varbind.oid()?;
+        let value = Value::decode(varbind.read()?)?;
+        varbinds.push(Varbind { oid, value });
+    }
+    Ok(varbinds)
+}
+
+fn encode_varbinds(writer: &mut Writer, varbinds: &[Varbind]) {
+    writer.sequence(|writer| {
+        for varbind in varbinds {
+            writer.sequence(|writer| {
+                writer.oid(&varbind.oid);
+                varbind.value.encode(writer);
+            });
+        }
+    });
+}
+
+/// Encodes notifications, for tests.
+#[cfg(test)]
+pub mod test_util {
+    use super::*;
+
+    pub fn v1_trap(community: &str, trap: &TrapV1, varbinds: &[Varbind]) -> Vec<u8> {
+        let mut writer = Writer::new();
+        writer.sequence(|writer| {
+            writer.integer(0);
+            writer.octet_string(community.as_bytes());
+            writer.constructed(TRAP_V1, |writer| {
+                writer.oid(&trap.enterprise);
+                writer.write(ber::IP_ADDRESS, &trap.agent_address.octets());
+                writer.integer(trap.generic_trap);
+                writer.integer(trap.specific_trap);
+                writer.unsigned(ber::TIME_TICKS, trap.time_stamp);
+                encode_varbinds(writer, varbinds);
+            });
+        });
+        writer.into_bytes()
+    }
+
+    pub fn v2c_notification(community: &str, inform: bool, varbinds: &[Varbind]) -> Vec<u8> {
+        let mut writer = Writer::new();
+        writer.sequence(|writer| {
+            writer.integer(1);
+            writer.octet_string(community.as_bytes());
+            writer.constructed(if inform { INFORM } else { TRAP_V2 }, |writer| {
+                writer.integer(42);
+                writer.integer(0);
+                writer.integer(0);
+                encode_varbinds(writer, varbinds);
+            });
+        });
+        writer.into_bytes()
+    }
+
+    /// Builds an unauthenticated SNMPv3 trap.
+    pub fn v3_trap(user_name: &str, varbinds: &[Varbind]) -> Vec<u8> {
+        let mut security_parameters = Writer::new();
+        security_parameters.sequence(|writer| {
+            writer.octet_string(b"engine");
+            writer.integer(1);
+            writer.integer(100);
+            writer.octet_string(user_name.as_bytes());
+            writer.octet_string(&[]);
+            writer.octet_string(&[]);
+        });
+
+        let mut writer = Writer::new();
+        writer.sequence(|writer| {
+            writer.integer(3);
+            writer.sequence(|writer| {
+                writer.integer(1);
+                writer.integer(65507);
+                writer.octet_string(&[0]);
+                writer.integer(USM_SECURITY_MODEL);
+            });
+            writer.octet_string(&security_parameters.into_bytes());
+            writer.sequence(|writer| {
+                writer.octet_string(b"engine");
+                writer.octet_string(b"");
+                writer.constructed(TRAP_V2, |writer| {
+                    writer.integer(7);
+                    writer.integer(0);
+                    writer.integer(0);
+                    encode_varbinds(writer, varbinds);
+                });
+            });
+        });
+        writer.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{test_util::*, *};
+    use crate::sources::snmp_trap::usm::UsmUserConfig;
+
+    fn varbinds() -> Vec<Varbind> {
+        vec![
+            Varbind {
+                oid: Oid(vec![1, 3, 6, 1, 2, 1, 1, 3, 0]),
+                value: Value::TimeTicks(12345),
+            },
+            Varbind {
+                oid: Oid(vec![1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0]),
+                value: Value::Oid(Oid(vec![1, 3, 6, 1, 6, 3, 1, 1, 5, 3])),
+            },
+            Varbind {
+                oid: Oid(vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 2, 3]),
+                value: Value::OctetString(b"eth0".to_vec()),
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_v1_traps() {
+        let trap = TrapV1 {
+            enterprise: Oid(vec![1, 3, 6, 1, 4, 1, 8072]),
+            agent_address: Ipv4Addr::new(192, 0, 2, 10),
+            generic_trap: 6,
+            specific_trap: 17,
+            time_stamp: 500,
+        };
+        let data = v1_trap("public", &trap, &varbinds()[2..]);
+        let message = Message::parse(&data, &Usm::default()).unwrap();
+
+        assert_eq!(message.version, Version::V1);
+        assert_eq!(message.security, Security::Community(b"public".to_vec()));
+        assert_eq!(message.pdu.pdu_type, PduType::Trap);
+        assert_eq!(message.pdu.trap_v1, Some(trap));
+        assert_eq!(message.pdu.varbinds, &varbinds()[2..]);
+        assert_eq!(message.inform_response(), None);
+    }
+
+    #[test]
+    fn parses_and_acknowledges_v2c_informs() {
+        let data = v2c_notification("public", true, &varbinds());
+        let message = Message::parse(&data, &Usm::default()).unwrap();
+
+        assert_eq!(message.version, Version::V2c);
+        assert_eq!(message.pdu.pdu_type, PduType::Inform);
+        assert_eq!(message.pdu.request_id, 42);
+        assert_eq!(message.pdu.varbinds, varbinds());
+
+        let response = message.inform_response().unwrap();
+        let mut reader = Reader::new(&response).sequence().unwrap();
+        assert_eq!(reader.integer().unwrap(), 1);
+        assert_eq!(reader.octet_string().unwrap(), b"public");
+        let mut pdu = Reader::nested(&reader.expect(RESPONSE).unwrap());
+        assert_eq!(pdu.integer().unwrap(), 42);
+    }
+
+    #[test]
+    fn parses_v3_traps() {
+        let usm = Usm::new(&[UsmUserConfig {
+            name: "monitor".to_owned(),
+            auth_protocol: None,
+            auth_passphrase: None,
+            privacy_protocol: None,
+            privacy_passphrase: None,
+        }])
+        .unwrap();
+
+        let message = Message::parse(&v3_trap("monitor", &varbinds()), &usm).unwrap();
+        assert_eq!(message.version, Version::V3);
+        assert_eq!(message.pdu.request_id, 7);
+        assert_eq!(message.pdu.varbinds, varbinds());
+        assert!(matches!(
+            message.security,
+            Security::Usm {
+                authenticated: false,
+                ..
+            }
+        ));
+
+        assert!(matches!(
+            Message::parse(&v3_trap("intruder", &varbinds()), &usm),
+            Err(SnmpError::Usm {
+                source: UsmError::UnknownUser { .. }
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_unsupported_messages() {
+        let mut writer = Writer::new();
+        writer.sequence(|writer| {
+            writer.integer(2);
+        });
+        assert!(matches!(
+            Message::parse(&writer.into_bytes(), &Usm::default()),
+            Err(SnmpError::UnsupportedVersion { version: 2 })
+        ));
+
+        // SNMPv1 trap PDUs are not valid in SNMPv2c messages.
+        let mut data = v1_trap(
+            "public",
+            &TrapV1 {
+                enterprise: Oid(vec![1, 3]),
+                agent_address: Ipv4Addr::LOCALHOST,
+                generic_trap: 0,
+                specific_trap: 0,
+                time_stamp: 0,
+            },
+            &[],
+        );
+        data[4] = 1;
+        assert!(matches!(
+            Message::parse(&data, &Usm::default()),
+            Err(SnmpError::UnsupportedPdu { tag: TRAP_V1 })
+        ));
+    }
+}
@@ -0,0 +1,477 @@
+//! Translates OIDs to names using the object definitions of MIB modules.
+//!
+//! Only the OID assignments of each definition are parsed, which is all that is needed to name
+//! OIDs: the rest of the SMI grammar is skipped over.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use snafu::{ResultExt, Snafu};
+
+use super::ber::Oid;
+
+/// The macros whose invocations assign an OID to a descriptor.
+const DEFINITION_MACROS: &[&str] = &[
+    "AGENT-CAPABILITIES",
+    "MODULE-COMPLIANCE",
+    "MODULE-IDENTITY",
+    "NOTIFICATION-GROUP",
+    "NOTIFICATION-TYPE",
+    "OBJECT-GROUP",
+    "OBJECT-IDENTITY",
+    "OBJECT-TYPE",
+];
+
+/// Nodes defined by the SMI itself, so that modules can be loaded without `SNMPv2-SMI`.
+const WELL_KNOWN_NODES: &[(&str, &[u32])] = &[
+    ("ccitt", &[0]),
+    ("iso", &[1]),
+    ("joint-iso-ccitt", &[2]),
+    ("org", &[1, 3]),
+    ("dod", &[1, 3, 6]),
+    ("internet", &[1, 3, 6, 1]),
+    ("directory", &[1, 3, 6, 1, 1]),
+    ("mgmt", &[1, 3, 6, 1, 2]),
+    ("mib-2", &[1, 3, 6, 1, 2, 1]),
+    ("transmission", &[1, 3, 6, 1, 2, 1, 10]),
+    ("experimental", &[1, 3, 6, 1, 3]),
+    ("private", &[1, 3, 6, 1, 4]),
+    ("enterprises", &[1, 3, 6, 1, 4, 1]),
+    ("security", &[1, 3, 6, 1, 5]),
+    ("snmpV2", &[1, 3, 6, 1, 6]),
+    ("snmpDomains", &[1, 3, 6, 1, 6, 1]),
+    ("snmpProxys", &[1, 3, 6, 1, 6, 2]),
+    ("snmpModules", &[1, 3, 6, 1, 6, 3]),
+];
+
+#[derive(Debug, Snafu)]
+pub enum MibError {
+    #[snafu(display("Unable to read MIB file {:?}: {}", path, source))]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+struct Definition {
+    module: String,
+    name: String,
+    /// The parent node, or `None` when `arcs` is an absolute OID.
+    parent: Option<String>,
+    arcs: Vec<u32>,
+}
+
+/// OID names loaded from MIB files.
+#[derive(Debug, Default)]
+pub struct Mibs {
+    names: HashMap<Vec<u32>, String>,
+}
+
+impl Mibs {
+    /// Loads every MIB file at the given paths, reading all files of directories.
+    ///
+    /// Definitions can refer to nodes defined in other files, so all files are parsed before
+    /// resolving any OID. Definitions whose parent is never defined are ignored.
+    pub fn load(paths: &[PathBuf]) -> Result<Self, MibError> {
+        let mut definitions = Vec::new();
+        for path in paths {
+            for file in files(path)? {
+                let text = fs::read(&file).context(ReadSnafu { path: file.clone() })?;
+                definitions.extend(parse_module(&String::from_utf8_lossy(&text)));
+            }
+        }
+
+        info!(
+            message = "Loaded MIB definitions.",
+            count = definitions.len()
+        );
+        Ok(Self::from_definitions(definitions))
+    }
+
+    fn from_definitions(mut definitions: Vec<Definition>) -> Self {
+        let mut oids = WELL_KNOWN_NODES
+            .iter()
+            .map(|(name, arcs)| (name.to_string(), arcs.to_vec()))
+            .collect::<HashMap<_, _>>();
+        // The root nodes are left unnamed, as naming them would name every OID.
+        let mut names = WELL_KNOWN_NODES
+            .iter()
+            .filter(|(_, arcs)| arcs.len() > 1)
+            .map(|(name, arcs)| (arcs.to_vec(), format!("SNMPv2-SMI::{}", name)))
+            .collect::<HashMap<_, _>>();
+
+        // Resolve definitions whose parent is known, until no more progress can be made.
+        loop {
+            let count = definitions.len();
+            definitions.retain(|definition| {
+                let mut oid = match &definition.parent {
+                    None => Vec::new(),
+                    Some(parent) => match oids.get(parent) {
+                        Some(oid) => oid.clone(),
+                        None => return true,
+                    },
+                };
+                oid.extend_from_slice(&definition.arcs);
+                names.insert(
+                    oid.clone(),
+                    format!("{}::{}", definition.module, definition.name),
+                );
+                oids.insert(definition.name.clone(), oid);
+                false
+            });
+            if definitions.is_empty() || definitions.len() == count {
+                break;
+            }
+        }
+
+        Self { names }
+    }
+
+    /// Names an OID after its longest known prefix, such as `IF-MIB::ifDescr.3`.
+    pub fn translate(&self, oid: &Oid) -> Option<String> {
+        (1..=oid.0.len()).rev().find_map(|length| {
+            let name = self.names.get(&oid.0[..length])?;
+            let suffix = &oid.0[length..];
+            Some(if suffix.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", name, Oid::from_slice(suffix))
+            })
+        })
+    }
+}
+
+fn files(path: &Path) -> Result<Vec<PathBuf>, MibError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files = fs::read_dir(path)
+        .context(ReadSnafu { path })?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .collect::<Vec<_>>();
+    files.sort();
+    Ok(files)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Token<'a> {
+    Word(&'a str),
+    Number(u32),
+    Assign,
+    Symbol(u8),
+}
+
+fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        match bytes[i] {
+            byte if byte.is_ascii_whitespace() => i += 1,
+            // Comments run from `--` to the end of the line or the next `--`.
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i += 2;
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    if bytes[i] == b'-' && bytes.get(i + 1) == Some(&b'-') {
+                        i += 1;
+                        break;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            // Quoted strings, such as descriptions, are skipped entirely.
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b':' if bytes[i..].starts_with(b"::=") => {
+                tokens.push(Token::Assign);
+                i += 3;
+            }
+            byte if byte.is_ascii_digit() => {
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                // Numbers too large to be sub-identifiers, such as range bounds, are irrelevant.
+                tokens.push(
+                    text[start..i]
+                        .parse()
+                        .map_or(Token::Symbol(b'#'), Token::Number),
+                );
+            }
+            byte if byte.is_ascii_alphabetic() => {
+                i += 1;
+                while i < bytes.len() {
+                    match bytes[i] {
+                        byte if byte.is_ascii_alphanumeric() || byte == b'_' => i += 1,
+                        // Identifiers never contain two consecutive hyphens, which start a comment.
+                        b'-' if bytes.get(i + 1).map_or(false, u8::is_ascii_alphanumeric) => i += 1,
+                        _ => break,
+                    }
+                }
+                tokens.push(Token::Word(&text[start..i]));
+            }
+            byte => {
+                tokens.push(Token::Symbol(byte));
+                i += 1;
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Object descriptors start with a lowercase letter, unlike type and module names.
+fn is_descriptor(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_lowercase())
+}
+
+fn parse_module(text: &str) -> Vec<Definition> {
+    let tokens = tokenize(text);
+    let mut definitions = Vec::new();
+    let mut module = String::new();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let parsed = match tokens[i..] {
+            [Token::Word(name), Token::Word("DEFINITIONS"), ..] => {
+                module = name.to_owned();
+                None
+            }
+            [Token::Word(name), Token::Word("OBJECT"), Token::Word("IDENTIFIER"), Token::Assign, ..]
+                if is_descriptor(name) =>
+            {
+                parse_assignment(&tokens[i + 3..])
+                    .map(|(parent, arcs, length)| (name, parent, arcs, i + 3 + length))
+            }
+            [Token::Word(name), Token::Word(keyword), ..]
+                if is_descriptor(name) && DEFINITION_MACROS.contains(&keyword) =>
+            {
+                parse_assignment(&tokens[i + 2..])
+                    .map(|(parent, arcs, length)| (name, parent, arcs, i + 2 + length))
+            }
+            [Token::Word(name), Token::Word("TRAP-TYPE"), ..] if is_descriptor(name) => {
+                parse_trap_type(&tokens[i + 2..]).map(|(enterprise, arcs, length)| {
+                    (name, Some(enterprise), arcs, i + 2 + length)
+                })
+            }
+            _ => None,
+        };
+
+        match parsed {
+            Some((name, parent, arcs, next)) => {
+                definitions.push(Definition {
+                    module: module.clone(),
+                    name: name.to_owned(),
+                    parent: parent.map(str::to_owned),
+                    arcs,
+                });
+                i = next;
+            }
+            None => i += 1,
+        }
+    }
+
+    definitions
+}
+
+/// Parses the value assigned by a definition, such as `::= { ifEntry 2 }` or
+/// `::= { iso(1) org(3) 6 }`, returning the parent, the arcs and the number of tokens read.
+fn parse_assignment<'a>(tokens: &[Token<'a>]) -> Option<(Option<&'a str>, Vec<u32>, usize)> {
+    let assign = tokens
+        .iter()
+        .position(|token| matches!(token, Token::Assign | Token::Word("MACRO")))?;
+    if tokens.get(assign) != Some(&Token::Assign)
+        || tokens.get(assign + 1) != Some(&Token::Symbol(b'{'))
+    {
+        return None;
+    }
+
+    let mut parent = None;
+    let mut arcs = Vec::new();
+    let mut i = assign + 2;
+    loop {
+        match tokens.get(i..)? {
+            [Token::Symbol(b'}'), ..] => return Some((parent, arcs, i + 1)),
+            [Token::Number(number), ..] => {
+                arcs.push(*number);
+                i += 1;
+            }
+            // A name and number form, such as `iso(1)`, only the number matters.
+            [Token::Word(_), Token::Symbol(b'('), Token::Number(number), Token::Symbol(b')'), ..] =>
+            {
+                arcs.push(*number);
+                i += 4;
+            }
+            [Token::Word(name), ..] if i == assign + 2 => {
+                parent = Some(*name);
+                i += 1;
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Parses an SNMPv1 `TRAP-TYPE` definition, whose OID is derived from its enterprise as described
+/// in RFC 3584.
+fn parse_trap_type<'a>(tokens: &[Token<'a>]) -> Option<(&'a str, Vec<u32>, usize)> {
+    let mut enterprise = None;
+    for (i, pair) in tokens.windows(2).enumerate() {
+        match pair {
+            [Token::Word("ENTERPRISE"), Token::Word(name)] => enterprise = Some(*name),
+            [Token::Assign, Token::Number(number)] => {
+                return enterprise.map(|enterprise| (enterprise, vec![0, *number], i + 2))
+            }
+            [Token::Word("MACRO"), _] => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: &str = r#"
+TEST-MIB DEFINITIONS ::= BEGIN
+
+IMPORTS
+    MODULE-IDENTITY, OBJECT-TYPE, NOTIFICATION-TYPE, enterprises
+        FROM SNMPv2-SMI;
+
+testMIB MODULE-IDENTITY
+    LAST-UPDATED "202201010000Z"
+    ORGANIZATION "Example -- not a comment"
+    DESCRIPTION  "A module used in tests ::= { nothing 1 }."
+    ::= { enterprises 99999 }
+
+testObjects OBJECT IDENTIFIER ::= { testMIB 1 } -- trailing comment
+testNotifications OBJECT IDENTIFIER ::= { testMIB 2 }
+
+testTable OBJECT-TYPE
+    SYNTAX      SEQUENCE OF TestEntry
+    MAX-ACCESS  not-accessible
+    STATUS      current
+    DESCRIPTION "A table."
+    ::= { testObjects 1 }
+
+testStatus OBJECT-TYPE
+    SYNTAX      INTEGER { up(1), down(2) }
+    MAX-ACCESS  read-only
+    STATUS      current
+    DESCRIPTION "A status."
+    ::= { testTable 2 }
+
+testStatusChange NOTIFICATION-TYPE
+    OBJECTS { testStatus }
+    STATUS  current
+    DESCRIPTION "The status changed."
+    ::= { testNotifications 1 }
+
+testLegacyTrap TRAP-TYPE
+    ENTERPRISE testMIB
+    VARIABLES { testStatus }
+    ::= 3
+
+orphan OBJECT IDENTIFIER ::= { unknownNode 1 }
+absolute OBJECT IDENTIFIER ::= { iso(1) org(3) 42 }
+
+END
+"#;
+
+    fn mibs() -> Mibs {
+        Mibs::from_definitions(parse_module(MIB))
+    }
+
+    fn translate(arcs: &[u32]) -> Option<String> {
+        mibs().translate(&Oid::from_slice(arcs))
+    }
+
+    #[test]
+    fn parses_definitions() {
+        let definitions = parse_module(MIB);
+        let names = definitions
+            .iter()
+            .map(|definition| definition.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "testMIB",
+                "testObjects",
+                "testNotifications",
+                "testTable",
+                "testStatus",
+                "testStatusChange",
+                "testLegacyTrap",
+                "orphan",
+                "absolute"
+            ]
+        );
+        assert_eq!(
+            definitions[0],
+            Definition {
+                module: "TEST-MIB".to_owned(),
+                name: "testMIB".to_owned(),
+                parent: Some("enterprises".to_owned()),
+                arcs: vec![99999],
+            }
+        );
+    }
+
+    #[test]
+    fn translates_oids() {
+        assert_eq!(
+            translate(&[1, 3, 6, 1, 4, 1, 99999, 1, 1, 2, 7]).as_deref(),
+            Some("TEST-MIB::testStatus.7")
+        );
+        assert_eq!(
+            translate(&[1, 3, 6, 1, 4, 1, 99999, 2, 1]).as_deref(),
+            Some("TEST-MIB::testStatusChange")
+        );
+        assert_eq!(
+            translate(&[1, 3, 6, 1, 4, 1, 99999, 0, 3]).as_deref(),
+            Some("TEST-MIB::testLegacyTrap")
+        );
+        assert_eq!(
+            translate(&[1, 3, 42]).as_deref(),
+            Some("TEST-MIB::absolute")
+        );
+        assert_eq!(
+            translate(&[1, 3, 6, 1, 4, 1, 12345]).as_deref(),
+            Some("SNMPv2-SMI::enterprises.12345")
+        );
+        assert_eq!(translate(&[2, 5]), None);
+    }
+
+    #[test]
+    fn loads_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("TEST-MIB.txt"), MIB).unwrap();
+
+        let mibs = Mibs::load(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(
+            mibs.translate(&Oid(vec![1, 3, 6, 1, 4, 1, 99999]))
+                .as_deref(),
+            Some("TEST-MIB::testMIB")
+        );
+
+        assert!(matches!(
+            Mibs::load(&[dir.path().join("missing")]),
+            Err(MibError::Read { .. })
+        ));
+    }
+}
@@ -0,0 +1,526 @@
+use std::{collections::BTreeMap, net::SocketAddr, path::PathBuf};
+
+use bytes::Bytes;
+use chrono::Utc;
+use tokio::net::UdpSocket;
+use vector_common::{
+    internal_event::{ByteSize, BytesReceived, InternalEventHandle as _, Protocol},
+    sensitive_string::SensitiveString,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, ByteSizeOf};
+
+use self::{
+    ber::Oid,
+    message::{Message, Security, Value as SnmpValue, Varbind},
+    mib::Mibs,
+    usm::{Usm, UsmUserConfig},
+};
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, Output, Resource, SourceConfig, SourceContext},
+    event::{Event, LogEvent, Value},
+    internal_events::{
+        EventsReceived, SnmpTrapInformResponseError, SnmpTrapParseError, SnmpTrapReceiveError,
+        StreamClosedError,
+    },
+    shutdown::ShutdownSignal,
+    udp, SourceSender,
+};
+
+mod ber;
+mod message;
+mod mib;
+mod usm;
+
+/// `sysUpTime.0`, the first varbind of SNMPv2 notifications.
+const SYS_UP_TIME: &[u32] = &[1, 3, 6, 1, 2, 1, 1, 3, 0];
+/// `snmpTrapOID.0`, the second varbind of SNMPv2 notifications.
+const SNMP_TRAP_OID: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 4, 1, 0];
+/// `snmpTraps`, the parent of the generic traps of SNMPv1.
+const SNMP_TRAPS: &[u32] = &[1, 3, 6, 1, 6, 3, 1, 1, 5];
+/// The generic trap number of enterprise specific SNMPv1 traps.
+const ENTERPRISE_SPECIFIC: i64 = 6;
+
+/// Configuration for the `snmp_trap` source.
+#[configurable_component(source("snmp_trap"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SnmpTrapConfig {
+    /// The address to listen for traps on.
+    address: SocketAddr,
+
+    /// The maximum size, in bytes, of incoming messages.
+    ///
+    /// Messages larger than this are discarded.
+    #[serde(default = "crate::serde::default_max_length")]
+    max_length: usize,
+
+    /// The size, in bytes, of the receive buffer used for the listening socket.
+    ///
+    /// This should not typically needed to be changed.
+    receive_buffer_bytes: Option<usize>,
+
+    /// Overrides the name of the log field used to add the address of the trap sender to each event.
+    ///
+    /// By default, the [global `log_schema.host_key` option][global_host_key] is used.
+    ///
+    /// [global_host_key]: https://vector.dev/docs/reference/configuration/global-options/#log_schema.host_key
+    host_key: Option<String>,
+
+    /// The communities accepted for SNMPv1 and SNMPv2c traps.
+    ///
+    /// When empty, traps are accepted regardless of their community.
+    #[serde(default)]
+    communities: Vec<SensitiveString>,
+
+    /// The users accepted for SNMPv3 traps.
+    ///
+    /// SNMPv3 traps from other users are rejected.
+    #[serde(default)]
+    users: Vec<UsmUserConfig>,
+
+    /// Paths of MIB files, or directories containing MIB files, used to translate OIDs to names.
+    ///
+    /// MIB files are only read when the source starts.
+    #[serde(default)]
+    mib_paths: Vec<PathBuf>,
+}
+
+impl SnmpTrapConfig {
+    pub fn from_address(address: SocketAddr) -> Self {
+        Self {
+            address,
+            max_length: crate::serde::default_max_length(),
+            receive_buffer_bytes: None,
+            host_key: None,
+            communities: Vec::new(),
+            users: Vec::new(),
+            mib_paths: Vec::new(),
+        }
+    }
+}
+
+impl GenerateConfig for SnmpTrapConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self::from_address("0.0.0.0:162".parse().unwrap())).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl SourceConfig for SnmpTrapConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let usm = Usm::new(&self.users)?;
+        let mibs = Mibs::load(&self.mib_paths)?;
+        let host_key = self
+            .host_key
+            .clone()
+            .unwrap_or_else(|| log_schema().host_key().to_string());
+
+        Ok(Box::pin(snmp_trap(
+            self.clone(),
+            SnmpTrapDecoder {
+                usm,
+                mibs,
+                host_key,
+            },
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, _global_log_namespace: LogNamespace) -> Vec<Output> {
+        vec![Output::default(DataType::Log)]
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![Resource::udp(self.address)]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+async fn snmp_trap(
+    config: SnmpTrapConfig,
+    decoder: SnmpTrapDecoder,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let socket = UdpSocket::bind(&config.address)
+        .await
+        .expect("Failed to bind to udp listener socket");
+
+    if let Some(receive_buffer_bytes) = config.receive_buffer_bytes {
+        if let Err(error) = udp::set_receive_buffer_size(&socket, receive_buffer_bytes) {
+            warn!(message = "Failed configuring receive buffer size on UDP socket.", %error);
+        }
+    }
+
+    let max_length = match config.receive_buffer_bytes {
+        Some(receive_buffer_bytes) => std::cmp::min(config.max_length, receive_buffer_bytes),
+        None => config.max_length,
+    };
+
+    let bytes_received = register!(BytesReceived::from(Protocol::UDP));
+
+    info!(message = "Listening.", address = %config.address);
+
+    // We add 1 to the max_length in order to determine if the received message has been truncated.
+    let mut buf = vec![0; max_length + 1];
+    loop {
+        let (byte_size, peer) = tokio::select! {
+            recv = socket.recv_from(&mut buf) => match recv {
+                Ok(res) => res,
+                Err(error) => {
+                    emit!(SnmpTrapReceiveError { error });
+                    return Err(());
+                }
+            },
+            _ = &mut shutdown => return Ok(()),
+        };
+
+        bytes_received.emit(ByteSize(byte_size));
+
+        if byte_size > max_length {
+            warn!(
+                message = "Discarding message larger than max_length.",
+                max_length = max_length,
+                internal_log_rate_secs = 30
+            );
+            continue;
+        }
+
+        let message = match Message::parse(&buf[..byte_size], &decoder.usm) {
+            Ok(message) => message,
+            Err(error) => {
+                emit!(SnmpTrapParseError { error, peer });
+                continue;
+            }
+        };
+
+        if let Security::Community(community) = &message.security {
+            if !config.communities.is_empty()
+                && !config
+                    .communities
+                    .iter()
+                    .any(|accepted| accepted.inner().as_bytes() == community.as_slice())
+            {
+                emit!(SnmpTrapParseError {
+                    error: "Unknown community",
+                    peer
+                });
+                continue;
+            }
+        }
+
+        if let Some(response) = message.inform_response() {
+            if let Err(error) = socket.send_to(&response, peer).await {
+                emit!(SnmpTrapInformResponseError { error, peer });
+            }
+        }
+
+        let event = Event::Log(decoder.message_to_log(message, peer));
+        emit!(EventsReceived {
+            count: 1,
+            byte_size: event.size_of(),
+        });
+
+        tokio::select! {
+            result = out.send_event(event) => {
+                if let Err(error) = result {
+                    emit!(StreamClosedError { error, count: 1 });
+                    return Ok(());
+                }
+            }
+            _ = &mut shutdown => return Ok(()),
+        }
+    }
+}
+
+struct SnmpTrapDecoder {
+    usm: Usm,
+    mibs: Mibs,
+    host_key: String,
+}
+
+impl SnmpTrapDecoder {
+    fn message_to_log(&self, message: Message, peer: SocketAddr) -> LogEvent {
+        let mut log = LogEvent::default();
+        log.insert("version", message.version.as_str());
+        log.insert("pdu_type", message.pdu.pdu_type.as_str());
+
+        if let Security::Usm {
+            user_name,
+            authenticated,
+            encrypted,
+            context_engine_id,
+            context_name,
+            ..
+        } = &message.security
+        {
+            let security_level = match (authenticated, encrypted) {
+                (true, true) => "auth_priv",
+                (true, false) => "auth_no_priv",
+                _ => "no_auth_no_priv",
+            };
+            log.insert("user", String::from_utf8_lossy(user_name).into_owned());
+            log.insert("security_level", security_level);
+            log.insert("context_engine_id", hex::encode(context_engine_id));
+            log.insert(
+                "context_name",
+                String::from_utf8_lossy(context_name).into_owned(),
+            );
+        }
+
+        let mut varbinds = message.pdu.varbinds;
+        let (uptime, trap_oid) = match &message.pdu.trap_v1 {
+            Some(trap) => {
+                log.insert("enterprise", trap.enterprise.to_string());
+                log.insert("agent_address", trap.agent_address.to_string());
+                log.insert("generic_trap", trap.generic_trap);
+                log.insert("specific_trap", trap.specific_trap);
+                (Some(trap.time_stamp), Some(v1_trap_oid(trap)))
+            }
+            None => {
+                log.insert("request_id", message.pdu.request_id);
+                // These are always present in SNMPv2 notifications, they are not part of the payload.
+                let uptime =
+                    take_varbind(&mut varbinds, SYS_UP_TIME).and_then(|value| match value {
+                        SnmpValue::TimeTicks(ticks) => Some(ticks),
+                        _ => None,
+                    });
+                let trap_oid =
+                    take_varbind(&mut varbinds, SNMP_TRAP_OID).and_then(|value| match value {
+                        SnmpValue::Oid(oid) => Some(oid),
+                        _ => None,
+                    });
+                (uptime, trap_oid)
+            }
+        };
+
+        if let Some(uptime) = uptime {
+            log.insert("uptime", uptime);
+        }
+        if let Some(trap_oid) = trap_oid {
+            if let Some(name) = self.mibs.translate(&trap_oid) {
+                log.insert("trap_name", name);
+            }
+            log.insert("trap_oid", trap_oid.to_string());
+        }
+
+        log.insert(
+            "varbinds",
+            varbinds
+                .into_iter()
+                .map(|varbind| self.varbind_to_value(varbind))
+                .collect::<Vec<_>>(),
+        );
+        log.insert(self.host_key.as_str(), peer.ip().to_string());
+        log.insert("port", peer.port());
+        log.insert(log_schema().source_type_key(), Bytes::from("snmp_trap"));
+        log.insert(log_schema().timestamp_key(), Utc::now());
+        log
+    }
+
+    fn varbind_to_value(&self, varbind: Varbind) -> Value {
+        let mut object = BTreeMap::new();
+        if let Some(name) = self.mibs.translate(&varbind.oid) {
+            object.insert("name".to_owned(), name.into());
+        }
+        object.insert("oid".to_owned(), varbind.oid.to_string().into());
+        object.insert("type".to_owned(), varbind.value.type_name().into());
+        object.insert("value".to_owned(), snmp_value_to_value(varbind.value));
+        Value::Object(object)
+    }
+}
+
+/// Removes the varbind with the given OID, returning its value.
+fn take_varbind(varbinds: &mut Vec<Varbind>, oid: &[u32]) -> Option<SnmpValue> {
+    let index = varbinds.iter().position(|varbind| varbind.oid.0 == oid)?;
+    Some(varbinds.remove(index).value)
+}
+
+/// Maps SNMPv1 trap identification to an SNMPv2 trap OID, as described in RFC 3584.
+fn v1_trap_oid(trap: &message::TrapV1) -> Oid {
+    if trap.generic_trap == ENTERPRISE_SPECIFIC {
+        let mut oid = trap.enterprise.clone();
+        oid.0.extend([0, trap.specific_trap as u32]);
+        oid
+    } else {
+        let mut oid = Oid::from_slice(SNMP_TRAPS);
+        oid.0.push(trap.generic_trap as u32 + 1);
+        oid
+    }
+}
+
+fn snmp_value_to_value(value: SnmpValue) -> Value {
+    match value {
+        SnmpValue::Integer(value) => value.into(),
+        SnmpValue::OctetString(value) => octet_string_to_value(value),
+        SnmpValue::Oid(oid) => oid.to_string().into(),
+        SnmpValue::IpAddress(address) => address.to_string().into(),
+        SnmpValue::Counter32(value)
+        | SnmpValue::Gauge32(value)
+        | SnmpValue::TimeTicks(value)
+        | SnmpValue::Counter64(value) => value.into(),
+        SnmpValue::Opaque(value) | SnmpValue::Unknown { value, .. } => hex::encode(value).into(),
+        SnmpValue::Null
+        | SnmpValue::NoSuchObject
+        | SnmpValue::NoSuchInstance
+        | SnmpValue::EndOfMibView => Value::Null,
+    }
+}
+
+/// Octet strings hold both text and binary data, such as MAC addresses. Binary data is hex encoded.
+fn octet_string_to_value(value: Vec<u8>) -> Value {
+    match String::from_utf8(value) {
+        Ok(text)
+            if !text
+                .chars()
+                .any(|c| c.is_control() && !matches!(c, '\t' | '\r' | '\n')) =>
+        {
+            text.into()
+        }
+        Ok(text) => hex::encode(text).into(),
+        Err(error) => hex::encode(error.into_bytes()).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::Ipv4Addr, time::Duration};
+
+    use super::{message::test_util::*, *};
+    use crate::{
+        config::ComponentKey,
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS},
+            next_addr,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SnmpTrapConfig>();
+    }
+
+    fn link_down_varbinds() -> Vec<Varbind> {
+        vec![
+            Varbind {
+                oid: Oid(SYS_UP_TIME.to_vec()),
+                value: SnmpValue::TimeTicks(12345),
+            },
+            Varbind {
+                oid: Oid(SNMP_TRAP_OID.to_vec()),
+                value: SnmpValue::Oid(Oid(vec![1, 3, 6, 1, 6, 3, 1, 1, 5, 3])),
+            },
+            Varbind {
+                oid: Oid(vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 1, 3]),
+                value: SnmpValue::Integer(3),
+            },
+            Varbind {
+                oid: Oid(vec![1, 3, 6, 1, 2, 1, 2, 2, 1, 6, 3]),
+                value: SnmpValue::OctetString(vec![0, 0x1b, 0x21, 0x3c, 0x4d, 0x5e]),
+            },
+        ]
+    }
+
+    fn decoder() -> SnmpTrapDecoder {
+        SnmpTrapDecoder {
+            usm: Usm::default(),
+            mibs: Mibs::default(),
+            host_key: "host".to_owned(),
+        }
+    }
+
+    #[test]
+    fn converts_v2c_traps() {
+        let data = v2c_notification("public", false, &link_down_varbinds());
+        let message = Message::parse(&data, &Usm::default()).unwrap();
+        let log = decoder().message_to_log(message, "192.0.2.1:50000".parse().unwrap());
+
+        assert_eq!(log["version"], "2c".into());
+        assert_eq!(log["pdu_type"], "trap_v2".into());
+        assert_eq!(log["uptime"], 12345.into());
+        assert_eq!(log["trap_oid"], "1.3.6.1.6.3.1.1.5.3".into());
+        assert_eq!(log["host"], "192.0.2.1".into());
+        assert_eq!(log["varbinds[0].oid"], "1.3.6.1.2.1.2.2.1.1.3".into());
+        assert_eq!(log["varbinds[0].type"], "integer".into());
+        assert_eq!(log["varbinds[0].value"], 3.into());
+        assert_eq!(log["varbinds[1].value"], "001b213c4d5e".into());
+        assert!(log.get("varbinds[2]").is_none());
+        assert!(log.get("community").is_none());
+    }
+
+    #[test]
+    fn converts_v1_traps() {
+        let trap = message::TrapV1 {
+            enterprise: Oid(vec![1, 3, 6, 1, 4, 1, 8072]),
+            agent_address: Ipv4Addr::new(192, 0, 2, 10),
+            generic_trap: ENTERPRISE_SPECIFIC,
+            specific_trap: 17,
+            time_stamp: 500,
+        };
+        let data = v1_trap("public", &trap, &[]);
+        let message = Message::parse(&data, &Usm::default()).unwrap();
+        let log = decoder().message_to_log(message, "192.0.2.1:50000".parse().unwrap());
+
+        assert_eq!(log["version"], "1".into());
+        assert_eq!(log["agent_address"], "192.0.2.10".into());
+        assert_eq!(log["specific_trap"], 17.into());
+        assert_eq!(log["uptime"], 500.into());
+        assert_eq!(log["trap_oid"], "1.3.6.1.4.1.8072.0.17".into());
+
+        let generic = message::TrapV1 {
+            generic_trap: 2,
+            ..trap
+        };
+        assert_eq!(v1_trap_oid(&generic).to_string(), "1.3.6.1.6.3.1.1.5.3");
+    }
+
+    #[test]
+    fn converts_octet_strings() {
+        assert_eq!(
+            octet_string_to_value(b"eth0\n".to_vec()),
+            Value::from("eth0\n")
+        );
+        assert_eq!(octet_string_to_value(vec![0xff, 0xfe]), Value::from("fffe"));
+    }
+
+    #[tokio::test]
+    async fn receives_traps_and_acknowledges_informs() {
+        assert_source_compliance(&SOCKET_HIGH_CARDINALITY_PUSH_SOURCE_TAGS, async {
+            let address = next_addr();
+            let mut config = SnmpTrapConfig::from_address(address);
+            config.communities = vec![String::from("public").into()];
+
+            let (tx, rx) = SourceSender::new_test();
+            let (cx, _shutdown) = SourceContext::new_shutdown(&ComponentKey::from("snmp_trap"), tx);
+            tokio::spawn(config.build(cx).await.unwrap());
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let socket = UdpSocket::bind(next_addr()).await.unwrap();
+            // Rejected, due to its community.
+            let trap = v2c_notification("private", false, &link_down_varbinds());
+            socket.send_to(&trap, address).await.unwrap();
+            let inform = v2c_notification("public", true, &link_down_varbinds());
+            socket.send_to(&inform, address).await.unwrap();
+
+            let mut response = [0; 1024];
+            let size = tokio::time::timeout(Duration::from_secs(5), socket.recv(&mut response))
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(size > 0);
+
+            let events = collect_n(rx, 1).await;
+            let log = events[0].as_log();
+            assert_eq!(log["pdu_type"], "inform".into());
+            assert_eq!(log["request_id"], 42.into());
+            assert_eq!(log[log_schema().source_type_key()], "snmp_trap".into());
+        })
+        .await;
+    }
+}
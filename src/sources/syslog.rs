@@ -6,8 +6,9 @@ use bytes::Bytes;
 use chrono::Utc;
 use codecs::{
     decoding::{Deserializer, Framer},
-    BytesDecoder, OctetCountingDecoder, SyslogDeserializer,
+    BytesDecoder, OctetCountingDecoder, OctetCountingFraming, SyslogDeserializer,
 };
+use derivative::Derivative;
 use futures::StreamExt;
 use smallvec::SmallVec;
 use tokio::net::UdpSocket;
@@ -42,6 +43,10 @@ pub struct SyslogConfig {
     #[serde(default = "crate::serde::default_max_length")]
     max_length: usize,
 
+    #[configurable(derived)]
+    #[serde(default)]
+    framing: SyslogFraming,
+
     /// Overrides the name of the log field used to add the peer host to each event.
     ///
     /// If using TCP or UDP, the value will be the peer host's address, including the port i.e. `1.2.3.4:9000`. If using
@@ -105,12 +110,54 @@ pub enum Mode {
     },
 }
 
+/// How messages are framed on TCP and Unix socket connections.
+///
+/// Not used in `udp` mode, where each datagram holds a single message.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFraming {
+    /// Detect the framing from the first message of each connection.
+    ///
+    /// Connections starting with a non-zero digit use octet counting, others are newline delimited.
+    #[derivative(Default)]
+    Auto,
+
+    /// Each message is prefixed with its length in bytes, as described in [RFC 6587][rfc6587].
+    ///
+    /// This is the framing required by [RFC 5425][rfc5425] for syslog over TLS.
+    ///
+    /// [rfc6587]: https://datatracker.ietf.org/doc/html/rfc6587#section-3.4.1
+    /// [rfc5425]: https://datatracker.ietf.org/doc/html/rfc5425#section-4.3
+    OctetCounting,
+
+    /// Each message is terminated by a newline.
+    NonTransparent,
+}
+
+impl SyslogFraming {
+    fn decoder(self, max_length: usize) -> Decoder {
+        let framer = OctetCountingDecoder::new_with_max_length(max_length);
+        let framer = match self {
+            Self::Auto => framer.detect_per_stream(),
+            Self::OctetCounting => framer.with_framing(OctetCountingFraming::OctetCounting),
+            Self::NonTransparent => framer.with_framing(OctetCountingFraming::NonTransparent),
+        };
+        Decoder::new(
+            Framer::OctetCounting(framer),
+            Deserializer::Syslog(SyslogDeserializer),
+        )
+    }
+}
+
 impl SyslogConfig {
     pub fn from_mode(mode: Mode) -> Self {
         Self {
             mode,
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            framing: SyslogFraming::default(),
         }
     }
 }
@@ -127,6 +174,7 @@ impl GenerateConfig for SyslogConfig {
             },
             host_key: None,
             max_length: crate::serde::default_max_length(),
+            framing: SyslogFraming::default(),
         })
         .unwrap()
     }
@@ -150,6 +198,7 @@ impl SourceConfig for SyslogConfig {
             } => {
                 let source = SyslogTcpSource {
                     max_length: self.max_length,
+                    framing: self.framing,
                     host_key,
                 };
                 let shutdown_secs = 30;
@@ -184,23 +233,14 @@ impl SourceConfig for SyslogConfig {
             Mode::Unix {
                 path,
                 socket_file_mode,
-            } => {
-                let decoder = Decoder::new(
-                    Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(
-                        self.max_length,
-                    )),
-                    Deserializer::Syslog(SyslogDeserializer),
-                );
-
-                build_unix_stream_source(
-                    path,
-                    socket_file_mode,
-                    decoder,
-                    move |events, host| handle_events(events, &host_key, host),
-                    cx.shutdown,
-                    cx.out,
-                )
-            }
+            } => build_unix_stream_source(
+                path,
+                socket_file_mode,
+                self.framing.decoder(self.max_length),
+                move |events, host| handle_events(events, &host_key, host),
+                cx.shutdown,
+                cx.out,
+            ),
         }
     }
 
@@ -225,6 +265,7 @@ impl SourceConfig for SyslogConfig {
 #[derive(Debug, Clone)]
 struct SyslogTcpSource {
     max_length: usize,
+    framing: SyslogFraming,
     host_key: String,
 }
 
@@ -235,10 +276,7 @@ impl TcpSource for SyslogTcpSource {
     type Acker = TcpNullAcker;
 
     fn decoder(&self) -> Self::Decoder {
-        Decoder::new(
-            Framer::OctetCounting(OctetCountingDecoder::new_with_max_length(self.max_length)),
-            Deserializer::Syslog(SyslogDeserializer),
-        )
+        self.framing.decoder(self.max_length)
     }
 
     fn handle_events(&self, events: &mut [Event], host: SocketAddr) {
@@ -372,10 +410,12 @@ mod test {
         config::log_schema,
         event::{Event, LogEvent},
         test_util::{
+            collect_n,
             components::{assert_source_compliance, SOCKET_PUSH_SOURCE_TAGS},
-            next_addr, random_maps, random_string, send_encodable, send_lines, wait_for_tcp,
-            CountReceiver,
+            next_addr, random_maps, random_string, send_encodable, send_lines, send_lines_tls,
+            wait_for_tcp, CountReceiver,
         },
+        tls::{self, TlsConfig, TlsEnableableConfig},
     };
 
     fn event_from_bytes(
@@ -467,6 +507,28 @@ mod test {
         assert_eq!(keepalive.time_secs, Some(7200));
     }
 
+    #[test]
+    fn config_tcp_framing() {
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+            framing = "octet_counting"
+          "#,
+        )
+        .unwrap();
+        assert_eq!(config.framing, SyslogFraming::OctetCounting);
+
+        let config: SyslogConfig = toml::from_str(
+            r#"
+            mode = "tcp"
+            address = "127.0.0.1:1235"
+          "#,
+        )
+        .unwrap();
+        assert_eq!(config.framing, SyslogFraming::Auto);
+    }
+
     #[test]
     fn config_udp() {
         let config: SyslogConfig = toml::from_str(
@@ -846,6 +908,116 @@ mod test {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_tcp_syslog_framing_per_connection() {
+        assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let in_addr = next_addr();
+
+            let config = SyslogConfig::from_mode(Mode::Tcp {
+                address: in_addr.into(),
+                keepalive: None,
+                tls: None,
+                receive_buffer_bytes: None,
+                connection_limit: None,
+            });
+
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .expect("source should not fail to build");
+            tokio::spawn(source);
+            wait_for_tcp(in_addr).await;
+
+            let message =
+                |msg: &str| format!("<13>1 2019-02-13T19:48:34+00:00 host app 1 - - {}", msg);
+
+            // Octet counted frames, followed by a newline as some senders do.
+            let counted = ["counted one", "counted\ntwo"].into_iter().map(|msg| {
+                let message = message(msg);
+                format!("{} {}", message.len(), message)
+            });
+            send_lines(in_addr, counted).await.unwrap();
+
+            // Newline delimited frames, on their own connection.
+            let delimited = ["delimited one", "delimited two"].into_iter().map(message);
+            send_lines(in_addr, delimited).await.unwrap();
+
+            let mut messages: Vec<String> = collect_n(rx, 4)
+                .await
+                .into_iter()
+                .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+                .collect();
+            messages.sort();
+            assert_eq!(
+                messages,
+                vec![
+                    "counted\ntwo",
+                    "counted one",
+                    "delimited one",
+                    "delimited two"
+                ]
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_tcp_syslog_tls_peer_subject() {
+        assert_source_compliance(&SOCKET_PUSH_SOURCE_TAGS, async {
+            let in_addr = next_addr();
+
+            let config = SyslogConfig::from_mode(Mode::Tcp {
+                address: in_addr.into(),
+                keepalive: None,
+                tls: Some(TlsSourceConfig {
+                    tls_config: TlsEnableableConfig {
+                        enabled: Some(true),
+                        options: TlsConfig {
+                            verify_certificate: Some(true),
+                            crt_file: Some(tls::TEST_PEM_CRT_PATH.into()),
+                            key_file: Some(tls::TEST_PEM_KEY_PATH.into()),
+                            ca_file: Some(tls::TEST_PEM_CA_PATH.into()),
+                            ..Default::default()
+                        },
+                    },
+                    client_metadata_key: Some("tls_peer".into()),
+                }),
+                receive_buffer_bytes: None,
+                connection_limit: None,
+            });
+
+            let (tx, rx) = SourceSender::new_test();
+            let source = config
+                .build(SourceContext::new_test(tx, None))
+                .await
+                .expect("source should not fail to build");
+            tokio::spawn(source);
+            wait_for_tcp(in_addr).await;
+
+            let message = "<13>1 2019-02-13T19:48:34+00:00 host app 1 - - over tls";
+            send_lines_tls(
+                in_addr,
+                "localhost".into(),
+                std::iter::once(format!("{} {}", message.len(), message)),
+                std::path::Path::new(tls::TEST_PEM_CA_PATH),
+                std::path::Path::new(tls::TEST_PEM_CLIENT_CRT_PATH),
+                std::path::Path::new(tls::TEST_PEM_CLIENT_KEY_PATH),
+            )
+            .await
+            .unwrap();
+
+            let events = collect_n(rx, 1).await;
+            let log = events[0].as_log();
+            assert_eq!(log[log_schema().message_key()], "over tls".into());
+            assert_eq!(
+                log["tls_peer.subject"],
+                "CN=localhost,OU=Vector,O=Datadog,L=New York,ST=New York,C=US".into()
+            );
+        })
+        .await;
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn test_unix_stream_syslog() {
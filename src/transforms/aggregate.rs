@@ -1,5 +1,5 @@
 use std::{
-    collections::{btree_map::Entry, BTreeMap},
+    collections::{btree_map::Entry, BTreeMap, HashMap, HashSet},
     pin::Pin,
     time::Duration,
 };
@@ -18,7 +18,7 @@ use crate::{
 
 /// Configuration for the `aggregate` transform.
 #[configurable_component(transform("aggregate"))]
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 #[serde(deny_unknown_fields, default)]
 pub struct AggregateConfig {
     /// The interval between flushes, in milliseconds.
@@ -26,12 +26,94 @@ pub struct AggregateConfig {
     /// Over this period metrics with the same series data (name, namespace, tags, …) will be aggregated.
     #[serde(default = "default_interval_ms")]
     pub interval_ms: u64,
+
+    /// How the metrics of a series are aggregated over an interval.
+    pub mode: AggregationMode,
+
+    /// The aggregation mode of specific metrics, by metric name.
+    ///
+    /// Metrics not listed here are aggregated according to `mode`.
+    pub metric_modes: HashMap<String, AggregationMode>,
+
+    /// The quantiles computed by the `summary` mode, between `0` and `1`.
+    #[serde(default = "default_quantiles")]
+    pub quantiles: Vec<f64>,
+
+    /// The tags to group series by.
+    ///
+    /// The other tags are dropped before aggregating, so that the series only differing by them are
+    /// rolled up into a single series. When unset, all tags are kept.
+    ///
+    /// With the `auto` mode, the absolute counters and gauges rolled up into a series are summed: the
+    /// latest value of each of them is kept, and the series is flushed with their sum.
+    pub group_by: Option<Vec<String>>,
+}
+
+/// How the metrics of a series are aggregated.
+///
+/// Except for `auto`, the modes only apply to counters and gauges. The metrics of other types are
+/// always aggregated as by `auto`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMode {
+    /// Sum incremental metrics, and keep the latest value of absolute metrics.
+    Auto,
+
+    /// Sum the values.
+    Sum,
+
+    /// Keep the latest value.
+    Latest,
+
+    /// Count the values, as a gauge.
+    Count,
+
+    /// Keep the minimum value, as a gauge.
+    Min,
+
+    /// Keep the maximum value, as a gauge.
+    Max,
+
+    /// Compute the mean of the values, as a gauge.
+    Mean,
+
+    /// Compute the population standard deviation of the values, as a gauge.
+    Stdev,
+
+    /// Collect the values into a distribution.
+    Distribution,
+
+    /// Compute the configured `quantiles` of the values, as an aggregated summary.
+    Summary,
+}
+
+impl Default for AggregationMode {
+    fn default() -> Self {
+        Self::Auto
+    }
 }
 
 const fn default_interval_ms() -> u64 {
     10 * 1000
 }
 
+fn default_quantiles() -> Vec<f64> {
+    vec![0.5, 0.9, 0.99]
+}
+
+impl Default for AggregateConfig {
+    fn default() -> Self {
+        Self {
+            interval_ms: default_interval_ms(),
+            mode: AggregationMode::default(),
+            metric_modes: HashMap::new(),
+            quantiles: default_quantiles(),
+            group_by: None,
+        }
+    }
+}
+
 impl_generate_config_from_default!(AggregateConfig);
 
 #[async_trait::async_trait]
@@ -49,54 +131,367 @@ impl TransformConfig for AggregateConfig {
     }
 }
 
-type MetricEntry = (metric::MetricData, EventMetadata);
+/// The values of the counters or gauges of a series, aggregated by a mode other than `auto`.
+#[derive(Debug)]
+struct Values {
+    mode: AggregationMode,
+    kind: metric::MetricKind,
+    counter: bool,
+    timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    latest: f64,
+    /// The running mean and sum of squared differences from it, as by Welford's algorithm.
+    mean: f64,
+    m2: f64,
+    /// The values themselves, only kept by the modes that need all of them.
+    samples: Vec<f64>,
+}
+
+impl Values {
+    fn new(mode: AggregationMode, data: &metric::MetricData, counter: bool) -> Self {
+        Self {
+            mode,
+            kind: data.kind,
+            counter,
+            timestamp: data.time.timestamp,
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            latest: 0.0,
+            mean: 0.0,
+            m2: 0.0,
+            samples: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, value: f64, timestamp: Option<chrono::DateTime<chrono::Utc>>) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.latest = value;
+        self.timestamp = timestamp.or(self.timestamp);
+
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+
+        if matches!(
+            self.mode,
+            AggregationMode::Distribution | AggregationMode::Summary
+        ) {
+            self.samples.push(value);
+        }
+    }
+
+    /// Returns the value the series is flushed with.
+    fn into_data(self, quantiles: &[f64]) -> metric::MetricData {
+        let scalar = |value| {
+            if self.counter {
+                metric::MetricValue::Counter { value }
+            } else {
+                metric::MetricValue::Gauge { value }
+            }
+        };
+        let gauge = |value| metric::MetricValue::Gauge { value };
+
+        let (kind, value) = match self.mode {
+            AggregationMode::Auto | AggregationMode::Sum => (self.kind, scalar(self.sum)),
+            AggregationMode::Latest => (self.kind, scalar(self.latest)),
+            AggregationMode::Count => (metric::MetricKind::Absolute, gauge(self.count as f64)),
+            AggregationMode::Min => (metric::MetricKind::Absolute, gauge(self.min)),
+            AggregationMode::Max => (metric::MetricKind::Absolute, gauge(self.max)),
+            AggregationMode::Mean => (metric::MetricKind::Absolute, gauge(self.mean)),
+            AggregationMode::Stdev => (
+                metric::MetricKind::Absolute,
+                gauge((self.m2 / self.count as f64).sqrt()),
+            ),
+            AggregationMode::Distribution => (
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Distribution {
+                    samples: self
+                        .samples
+                        .iter()
+                        .map(|&value| metric::Sample { value, rate: 1 })
+                        .collect(),
+                    statistic: metric::StatisticKind::Histogram,
+                },
+            ),
+            AggregationMode::Summary => {
+                let mut samples = self.samples;
+                samples.sort_by(f64::total_cmp);
+
+                (
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::AggregatedSummary {
+                        quantiles: quantiles
+                            .iter()
+                            .map(|&quantile| metric::Quantile {
+                                quantile,
+                                value: quantile_of(&samples, quantile),
+                            })
+                            .collect(),
+                        count: self.count,
+                        sum: self.sum,
+                    },
+                )
+            }
+        };
+
+        metric::MetricData {
+            time: metric::MetricTime {
+                timestamp: self.timestamp,
+                interval_ms: None,
+            },
+            kind,
+            value,
+        }
+    }
+}
+
+/// The absolute counters or gauges rolled up into a series by `group_by`, aggregated by the `auto`
+/// mode.
+#[derive(Debug)]
+struct Rollup {
+    counter: bool,
+    timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    /// The latest value of each rolled up series, by its original tags.
+    latest: BTreeMap<Option<metric::MetricTags>, f64>,
+}
+
+impl Rollup {
+    fn new(counter: bool) -> Self {
+        Self {
+            counter,
+            timestamp: None,
+            latest: BTreeMap::new(),
+        }
+    }
+
+    fn add(
+        &mut self,
+        tags: Option<metric::MetricTags>,
+        value: f64,
+        timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    ) {
+        self.latest.insert(tags, value);
+        self.timestamp = timestamp.or(self.timestamp);
+    }
+
+    /// Returns the sum of the latest values of the rolled up series.
+    fn into_data(self) -> metric::MetricData {
+        let value = self.latest.values().sum();
+
+        metric::MetricData {
+            time: metric::MetricTime {
+                timestamp: self.timestamp,
+                interval_ms: None,
+            },
+            kind: metric::MetricKind::Absolute,
+            value: if self.counter {
+                metric::MetricValue::Counter { value }
+            } else {
+                metric::MetricValue::Gauge { value }
+            },
+        }
+    }
+}
+
+/// Returns the `quantile` of the sorted `samples`, interpolating between the closest ones.
+fn quantile_of(samples: &[f64], quantile: f64) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+
+    let position = quantile * (samples.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    samples[lower] + (samples[upper] - samples[lower]) * (position - lower as f64)
+}
+
+#[derive(Debug)]
+enum MetricEntry {
+    /// A metric aggregated by the `auto` mode.
+    Metric(metric::MetricData),
+    /// The values of counters or gauges aggregated by another mode.
+    Values(Values),
+    /// The absolute counters or gauges rolled up by `group_by` and aggregated by the `auto` mode.
+    Rollup(Rollup),
+}
 
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
-    map: BTreeMap<metric::MetricSeries, MetricEntry>,
+    mode: AggregationMode,
+    metric_modes: HashMap<String, AggregationMode>,
+    quantiles: Vec<f64>,
+    group_by: Option<HashSet<String>>,
+    map: BTreeMap<metric::MetricSeries, (MetricEntry, EventMetadata)>,
 }
 
 impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
+        if let Some(quantile) = config
+            .quantiles
+            .iter()
+            .find(|quantile| !(0.0..=1.0).contains(*quantile))
+        {
+            return Err(format!("quantile {} must be between 0 and 1", quantile).into());
+        }
+
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
+            mode: config.mode,
+            metric_modes: config.metric_modes.clone(),
+            quantiles: config.quantiles.clone(),
+            group_by: config
+                .group_by
+                .as_ref()
+                .map(|tags| tags.iter().cloned().collect()),
             map: BTreeMap::new(),
         })
     }
 
     fn record(&mut self, event: Event) {
-        let (series, data, metadata) = event.into_metric().into_parts();
+        let (mut series, data, metadata) = event.into_metric().into_parts();
+
+        let mode = self
+            .metric_modes
+            .get(&series.name.name)
+            .copied()
+            .unwrap_or(self.mode);
+        let scalar = match data.value {
+            metric::MetricValue::Counter { value } => Some((value, true)),
+            metric::MetricValue::Gauge { value } => Some((value, false)),
+            _ => None,
+        };
+
+        // The absolute series rolled up together are told apart by their original tags
+        let rolled_up_tags = (self.group_by.is_some()
+            && mode == AggregationMode::Auto
+            && data.kind == metric::MetricKind::Absolute
+            && scalar.is_some())
+        .then(|| series.tags.clone());
+
+        if let (Some(group_by), Some(tags)) = (&self.group_by, &mut series.tags) {
+            tags.retain(|tag, _| group_by.contains(tag));
+        }
+
+        match (mode, scalar) {
+            (AggregationMode::Auto, Some((value, counter))) if rolled_up_tags.is_some() => {
+                let tags = rolled_up_tags.flatten();
+                let timestamp = data.time.timestamp;
+                match self.map.entry(series) {
+                    Entry::Occupied(mut entry) => {
+                        let existing = entry.get_mut();
+                        match &mut existing.0 {
+                            MetricEntry::Rollup(rollup) if rollup.counter == counter => {
+                                rollup.add(tags, value, timestamp);
+                                existing.1.merge(metadata);
+                            }
+                            _ => {
+                                emit!(AggregateUpdateFailed);
+                                let mut rollup = Rollup::new(counter);
+                                rollup.add(tags, value, timestamp);
+                                *existing = (MetricEntry::Rollup(rollup), metadata);
+                            }
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        let mut rollup = Rollup::new(counter);
+                        rollup.add(tags, value, timestamp);
+                        entry.insert((MetricEntry::Rollup(rollup), metadata));
+                    }
+                }
+            }
+            (AggregationMode::Auto, _) | (_, None) => self.record_metric(series, data, metadata),
+            (mode, Some((value, counter))) => {
+                let timestamp = data.time.timestamp;
+                match self.map.entry(series) {
+                    Entry::Occupied(mut entry) => {
+                        let existing = entry.get_mut();
+                        // In order to add the new value, the kinds and value types must match
+                        let added = match &mut existing.0 {
+                            MetricEntry::Values(values)
+                                if values.counter == counter && values.kind == data.kind =>
+                            {
+                                values.add(value, timestamp);
+                                true
+                            }
+                            _ => false,
+                        };
+
+                        if added {
+                            existing.1.merge(metadata);
+                        } else {
+                            emit!(AggregateUpdateFailed);
+                            let mut values = Values::new(mode, &data, counter);
+                            values.add(value, timestamp);
+                            *existing = (MetricEntry::Values(values), metadata);
+                        }
+                    }
+                    Entry::Vacant(entry) => {
+                        let mut values = Values::new(mode, &data, counter);
+                        values.add(value, timestamp);
+                        entry.insert((MetricEntry::Values(values), metadata));
+                    }
+                }
+            }
+        }
 
+        emit!(AggregateEventRecorded);
+    }
+
+    fn record_metric(
+        &mut self,
+        series: metric::MetricSeries,
+        data: metric::MetricData,
+        metadata: EventMetadata,
+    ) {
         match data.kind {
             metric::MetricKind::Incremental => match self.map.entry(series) {
                 Entry::Occupied(mut entry) => {
                     let existing = entry.get_mut();
                     // In order to update (add) the new and old kind's must match
-                    if existing.0.kind == data.kind && existing.0.update(&data) {
+                    let updated = match &mut existing.0 {
+                        MetricEntry::Metric(existing_data) => {
+                            existing_data.kind == data.kind && existing_data.update(&data)
+                        }
+                        MetricEntry::Values(_) | MetricEntry::Rollup(_) => false,
+                    };
+
+                    if updated {
                         existing.1.merge(metadata);
                     } else {
                         emit!(AggregateUpdateFailed);
-                        *existing = (data, metadata);
+                        *existing = (MetricEntry::Metric(data), metadata);
                     }
                 }
                 Entry::Vacant(entry) => {
-                    entry.insert((data, metadata));
+                    entry.insert((MetricEntry::Metric(data), metadata));
                 }
             },
             metric::MetricKind::Absolute => {
                 // Always replace/store
-                self.map.insert(series, (data, metadata));
+                self.map
+                    .insert(series, (MetricEntry::Metric(data), metadata));
             }
         };
-
-        emit!(AggregateEventRecorded);
     }
 
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let map = std::mem::take(&mut self.map);
-        for (series, entry) in map.into_iter() {
-            let metric = metric::Metric::from_parts(series, entry.0, entry.1);
+        for (series, (entry, metadata)) in map.into_iter() {
+            let data = match entry {
+                MetricEntry::Metric(data) => data,
+                MetricEntry::Values(values) => values.into_data(&self.quantiles),
+                MetricEntry::Rollup(rollup) => rollup.into_data(),
+            };
+            let metric = metric::Metric::from_parts(series, data, metadata);
             output.push(Event::Metric(metric));
         }
 
@@ -172,6 +567,7 @@ mod tests {
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -242,6 +638,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -307,6 +704,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -364,6 +762,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            ..Default::default()
         })
         .unwrap();
 
@@ -414,6 +813,223 @@ mod tests {
         assert_eq!(&summed, &out[0]);
     }
 
+    fn aggregate(config: &AggregateConfig, events: Vec<Event>) -> Vec<Event> {
+        let mut agg = Aggregate::new(config).unwrap();
+        for event in events {
+            agg.record(event);
+        }
+
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        out
+    }
+
+    fn gauges(name: &'static str, values: &[f64]) -> Vec<Event> {
+        values
+            .iter()
+            .map(|&value| {
+                make_metric(
+                    name,
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::Gauge { value },
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn modes() {
+        for (mode, expected) in [
+            (AggregationMode::Sum, 10.0),
+            (AggregationMode::Latest, 4.0),
+            (AggregationMode::Count, 4.0),
+            (AggregationMode::Min, 1.0),
+            (AggregationMode::Max, 4.0),
+            (AggregationMode::Mean, 2.5),
+            (AggregationMode::Stdev, 1.25_f64.sqrt()),
+        ] {
+            let config = AggregateConfig {
+                mode,
+                ..Default::default()
+            };
+
+            assert_eq!(
+                aggregate(&config, gauges("gauge_a", &[1.0, 2.0, 3.0, 4.0])),
+                gauges("gauge_a", &[expected]),
+                "{:?}",
+                mode
+            );
+        }
+    }
+
+    #[test]
+    fn summary_mode() {
+        let config = AggregateConfig {
+            mode: AggregationMode::Summary,
+            quantiles: vec![0.125, 0.5, 1.0],
+            ..Default::default()
+        };
+
+        let out = aggregate(&config, gauges("gauge_a", &[5.0, 1.0, 4.0, 2.0, 3.0]));
+
+        assert_eq!(
+            out,
+            vec![make_metric(
+                "gauge_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::AggregatedSummary {
+                    quantiles: vec![
+                        metric::Quantile {
+                            quantile: 0.125,
+                            value: 1.5,
+                        },
+                        metric::Quantile {
+                            quantile: 0.5,
+                            value: 3.0,
+                        },
+                        metric::Quantile {
+                            quantile: 1.0,
+                            value: 5.0,
+                        },
+                    ],
+                    count: 5,
+                    sum: 15.0,
+                },
+            )]
+        );
+    }
+
+    #[test]
+    fn distribution_mode() {
+        let config = AggregateConfig {
+            mode: AggregationMode::Distribution,
+            ..Default::default()
+        };
+        let counters = [1.0, 2.0]
+            .into_iter()
+            .map(|value| {
+                make_metric(
+                    "counter_a",
+                    metric::MetricKind::Incremental,
+                    metric::MetricValue::Counter { value },
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            aggregate(&config, counters),
+            vec![make_metric(
+                "counter_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Distribution {
+                    samples: vec![
+                        metric::Sample {
+                            value: 1.0,
+                            rate: 1
+                        },
+                        metric::Sample {
+                            value: 2.0,
+                            rate: 1
+                        },
+                    ],
+                    statistic: metric::StatisticKind::Histogram,
+                },
+            )]
+        );
+    }
+
+    #[test]
+    fn metric_modes() {
+        let config = AggregateConfig {
+            mode: AggregationMode::Sum,
+            metric_modes: HashMap::from([("gauge_b".to_owned(), AggregationMode::Max)]),
+            ..Default::default()
+        };
+        let mut events = gauges("gauge_a", &[1.0, 2.0]);
+        events.extend(gauges("gauge_b", &[1.0, 2.0]));
+
+        let mut expected = gauges("gauge_a", &[3.0]);
+        expected.extend(gauges("gauge_b", &[2.0]));
+        assert_eq!(aggregate(&config, events), expected);
+    }
+
+    #[test]
+    fn group_by_tags() {
+        let config = AggregateConfig {
+            mode: AggregationMode::Sum,
+            group_by: Some(vec!["region".to_owned()]),
+            ..Default::default()
+        };
+        let tagged = |value, host: &str| {
+            Event::Metric(
+                Metric::new(
+                    "gauge_a",
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::Gauge { value },
+                )
+                .with_tags(Some(BTreeMap::from([
+                    ("host".to_owned(), host.to_owned()),
+                    ("region".to_owned(), "eu".to_owned()),
+                ]))),
+            )
+        };
+
+        assert_eq!(
+            aggregate(&config, vec![tagged(1.0, "a"), tagged(2.0, "b")]),
+            vec![Event::Metric(
+                Metric::new(
+                    "gauge_a",
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::Gauge { value: 3.0 },
+                )
+                .with_tags(Some(BTreeMap::from([(
+                    "region".to_owned(),
+                    "eu".to_owned()
+                )]))),
+            )]
+        );
+    }
+
+    #[test]
+    fn group_by_tags_sums_absolute_series() {
+        let config = AggregateConfig {
+            group_by: Some(vec!["region".to_owned()]),
+            ..Default::default()
+        };
+        let tagged = |value, host: &str| {
+            Event::Metric(
+                Metric::new(
+                    "gauge_a",
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::Gauge { value },
+                )
+                .with_tags(Some(BTreeMap::from([
+                    ("host".to_owned(), host.to_owned()),
+                    ("region".to_owned(), "eu".to_owned()),
+                ]))),
+            )
+        };
+
+        // Only the latest value of each host is summed
+        assert_eq!(
+            aggregate(
+                &config,
+                vec![tagged(1.0, "a"), tagged(5.0, "b"), tagged(2.0, "a")]
+            ),
+            vec![Event::Metric(
+                Metric::new(
+                    "gauge_a",
+                    metric::MetricKind::Absolute,
+                    metric::MetricValue::Gauge { value: 7.0 },
+                )
+                .with_tags(Some(BTreeMap::from([(
+                    "region".to_owned(),
+                    "eu".to_owned()
+                )]))),
+            )]
+        );
+    }
+
     #[tokio::test]
     async fn transform_shutdown() {
         let agg = toml::from_str::<AggregateConfig>(
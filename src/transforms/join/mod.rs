@@ -0,0 +1,288 @@
+use indexmap::IndexMap;
+use vector_common::config::ComponentKey;
+use vector_config::configurable_component;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, InnerTopology, InnerTopologyTransform, Input, Output,
+        TransformConfig, TransformContext,
+    },
+    schema,
+    template::Template,
+    transforms::{reduce::MergeStrategy, Transform},
+};
+
+mod transform;
+
+use self::transform::{JoinOutput, JoinSide, JoinWindow};
+
+/// The output events left unmatched are sent to.
+pub(crate) const UNMATCHED_OUTPUT: &str = "unmatched";
+
+const fn default_window_ms() -> u64 {
+    30_000
+}
+
+/// Configuration for the `join` transform.
+#[configurable_component(transform("join"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JoinConfig {
+    #[configurable(derived)]
+    pub left: JoinInputConfig,
+
+    #[configurable(derived)]
+    pub right: JoinInputConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub join_type: JoinType,
+
+    /// The maximum period of time to wait for the matching event of the other side, in
+    /// milliseconds.
+    ///
+    /// The window of each event starts when it was received. Events left unmatched at the end of
+    /// their window are sent to the `unmatched` output, unless kept by a left join.
+    #[serde(default = "default_window_ms")]
+    pub window_ms: u64,
+
+    /// The maximum number of events waiting for a match, across all keys.
+    ///
+    /// When exceeded, the oldest event waiting for a match is left unmatched without waiting for the
+    /// end of its window. When unset, the number of waiting events is only bounded by the window.
+    #[serde(default)]
+    pub max_pending: Option<usize>,
+
+    /// A map of field names to custom merge strategies.
+    ///
+    /// For each field specified, the given strategy is used to combine the values of the left and
+    /// right events. Fields present in both events, and without a merge strategy, keep the value of
+    /// the left event.
+    #[serde(default)]
+    pub merge_strategies: IndexMap<String, MergeStrategy>,
+}
+
+/// Configuration for one side of the join.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JoinInputConfig {
+    /// The inputs of this side of the join.
+    pub inputs: Vec<String>,
+
+    /// The key events of this side are joined by.
+    ///
+    /// Events of both sides rendering the same key are joined.
+    pub key: Template,
+}
+
+/// The type of join.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinType {
+    /// Only joined events are sent to the default output.
+    ///
+    /// Events of both sides left unmatched are sent to the `unmatched` output.
+    Inner,
+
+    /// Joined events, and events of the left side left unmatched, are sent to the default output.
+    ///
+    /// Events of the right side left unmatched are sent to the `unmatched` output.
+    Left,
+}
+
+impl Default for JoinType {
+    fn default() -> Self {
+        Self::Inner
+    }
+}
+
+impl GenerateConfig for JoinConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(indoc::indoc! {r#"
+            left.inputs = ["requests"]
+            left.key = "{{ request_id }}"
+            right.inputs = ["responses"]
+            right.key = "{{ request_id }}"
+        "#})
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for JoinConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Err("this transform must be expanded".into())
+    }
+
+    fn expand(
+        &mut self,
+        name: &ComponentKey,
+        inputs: &[String],
+    ) -> crate::Result<Option<InnerTopology>> {
+        if !inputs.is_empty() {
+            return Err("the inputs of the join must be set in `left` and `right`".into());
+        }
+        if self.window_ms == 0 {
+            return Err("`window_ms` must be non-zero".into());
+        }
+        if self.max_pending == Some(0) {
+            return Err("`max_pending` must be non-zero".into());
+        }
+
+        let left = name.join("left");
+        let right = name.join("right");
+        let window = name.join("window");
+
+        let stages = [
+            (left.clone(), self.left.inputs.clone(), JoinStage::Left),
+            (right.clone(), self.right.inputs.clone(), JoinStage::Right),
+            (
+                window.clone(),
+                vec![left.to_string(), right.to_string()],
+                JoinStage::Window,
+            ),
+            (name.clone(), vec![window.to_string()], JoinStage::Output),
+        ];
+
+        let mut result = InnerTopology {
+            inner: Default::default(),
+            outputs: vec![(name.clone(), self.outputs(&schema::Definition::any()))],
+        };
+        for (key, inputs, stage) in stages {
+            let config = JoinStageConfig {
+                stage,
+                join: self.clone(),
+            };
+            result.inner.insert(
+                key,
+                InnerTopologyTransform {
+                    inputs,
+                    inner: config.into(),
+                },
+            );
+        }
+
+        Ok(Some(result))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![
+            Output::default(DataType::Log),
+            Output::default(DataType::Log).with_port(UNMATCHED_OUTPUT),
+        ]
+    }
+}
+
+/// A stage of an expanded `join` transform.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinStage {
+    /// Computes the key of the events of the left side.
+    Left,
+
+    /// Computes the key of the events of the right side.
+    Right,
+
+    /// Buffers events until they're joined or expire.
+    Window,
+
+    /// Routes unmatched events to their output.
+    Output,
+}
+
+/// Configuration for a stage of an expanded `join` transform.
+#[configurable_component(transform("join_stage"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JoinStageConfig {
+    #[configurable(derived)]
+    stage: JoinStage,
+
+    #[configurable(derived)]
+    join: JoinConfig,
+}
+
+impl GenerateConfig for JoinStageConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            stage: JoinStage::Output,
+            join: toml::Value::try_into(JoinConfig::generate_config()).unwrap(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for JoinStageConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(match self.stage {
+            JoinStage::Left => Transform::function(JoinSide::left(self.join.left.key.clone())),
+            JoinStage::Right => Transform::function(JoinSide::right(self.join.right.key.clone())),
+            JoinStage::Window => Transform::event_task(JoinWindow::new(&self.join)),
+            JoinStage::Output => Transform::synchronous(JoinOutput),
+        })
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, definition: &schema::Definition) -> Vec<Output> {
+        match self.stage {
+            JoinStage::Output => self.join.outputs(definition),
+            _ => vec![Output::default(DataType::Log)],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JoinConfig>();
+    }
+
+    #[test]
+    fn expanding() {
+        let mut config: JoinConfig = toml::Value::try_into(JoinConfig::generate_config()).unwrap();
+        let name = ComponentKey::from("correlated");
+
+        let topology = config.expand(&name, &[]).unwrap().unwrap();
+
+        let stages = topology
+            .inner
+            .iter()
+            .map(|(key, transform)| (key.to_string(), transform.inputs.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stages,
+            vec![
+                ("correlated.left".to_owned(), vec!["requests".to_owned()]),
+                ("correlated.right".to_owned(), vec!["responses".to_owned()]),
+                (
+                    "correlated.window".to_owned(),
+                    vec!["correlated.left".to_owned(), "correlated.right".to_owned()]
+                ),
+                (
+                    "correlated".to_owned(),
+                    vec!["correlated.window".to_owned()]
+                ),
+            ]
+        );
+        assert_eq!(
+            topology.outputs(),
+            vec!["correlated".to_owned(), "correlated.unmatched".to_owned()]
+        );
+
+        assert!(config.expand(&name, &["requests".to_owned()]).is_err());
+    }
+}
@@ -0,0 +1,603 @@
+use std::{collections::VecDeque, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use indexmap::{map::Entry, IndexMap};
+use lookup::metadata_path;
+use tokio::time::Instant;
+
+use super::{JoinConfig, JoinType, UNMATCHED_OUTPUT};
+use crate::{
+    event::{Event, LogEvent, Value},
+    expiring_hash_map::ExpiringHashMap,
+    internal_events::TemplateRenderingError,
+    template::Template,
+    transforms::{
+        reduce::{get_value_merger, MergeStrategy, ReduceValueMerger},
+        FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, TransformOutputsBuf,
+    },
+};
+
+/// The metadata field holding the side of the join an event comes from.
+const SIDE_FIELD: &str = "side";
+
+/// The metadata field holding the rendered key of an event.
+const KEY_FIELD: &str = "key";
+
+/// The metadata field marking the events left unmatched, but not sent to the default output.
+const UNMATCHED_FIELD: &str = "unmatched";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+}
+
+/// Annotates the events of one side of the join with their side and key.
+#[derive(Clone, Debug)]
+pub(super) struct JoinSide {
+    side: Side,
+    key: Template,
+}
+
+impl JoinSide {
+    pub(super) const fn left(key: Template) -> Self {
+        Self {
+            side: Side::Left,
+            key,
+        }
+    }
+
+    pub(super) const fn right(key: Template) -> Self {
+        Self {
+            side: Side::Right,
+            key,
+        }
+    }
+}
+
+impl FunctionTransform for JoinSide {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        let mut log = event.into_log();
+
+        // Events without a key can't be joined, and are left unmatched by the window.
+        match self.key.render_string(&log) {
+            Ok(key) => {
+                log.insert(metadata_path!("vector", "join", KEY_FIELD), key);
+            }
+            Err(error) => emit!(TemplateRenderingError {
+                error,
+                field: Some("key"),
+                drop_event: false,
+            }),
+        }
+        log.insert(
+            metadata_path!("vector", "join", SIDE_FIELD),
+            self.side.as_str(),
+        );
+
+        output.push(Event::Log(log));
+    }
+}
+
+/// The events of one side waiting for a match, oldest first, with the time they were received.
+///
+/// The deadline of the key is always the end of the window of the oldest event.
+#[derive(Debug)]
+struct Pending {
+    side: Side,
+    events: VecDeque<(Instant, LogEvent)>,
+}
+
+/// Joins the events of both sides by key, within a window.
+pub(super) struct JoinWindow {
+    join_type: JoinType,
+    window: Duration,
+    merge_strategies: IndexMap<String, MergeStrategy>,
+    max_pending: Option<usize>,
+    pending: ExpiringHashMap<String, Pending>,
+    /// The number of events waiting for a match, across all keys.
+    pending_count: usize,
+}
+
+impl JoinWindow {
+    pub(super) fn new(config: &JoinConfig) -> Self {
+        Self {
+            join_type: config.join_type,
+            window: Duration::from_millis(config.window_ms),
+            merge_strategies: config.merge_strategies.clone(),
+            max_pending: config.max_pending,
+            pending: ExpiringHashMap::default(),
+            pending_count: 0,
+        }
+    }
+
+    fn handle(&mut self, mut log: LogEvent, output: &mut Vec<Event>) {
+        let side = match log.remove_prune(metadata_path!("vector", "join", SIDE_FIELD), true) {
+            Some(side) if side == Value::from(Side::Right.as_str()) => Side::Right,
+            _ => Side::Left,
+        };
+        let key = match log.remove_prune(metadata_path!("vector", "join", KEY_FIELD), true) {
+            Some(key) => key.to_string_lossy(),
+            None => return self.unmatched(side, log, output),
+        };
+
+        let now = Instant::now();
+        match self.pending.get(&key).map(|pending| pending.side) {
+            Some(pending_side) if pending_side != side => {
+                let (_, other) = self
+                    .pop_front(&key)
+                    .expect("pending events must not be empty");
+
+                let (left, right) = match side {
+                    Side::Left => (log, other),
+                    Side::Right => (other, log),
+                };
+                output.push(Event::Log(self.merge(left, right)));
+            }
+            Some(_) => {
+                self.pending
+                    .get_mut(&key)
+                    .expect("pending key must exist")
+                    .events
+                    .push_back((now, log));
+                self.pending_count += 1;
+                self.evict_oldest(output);
+            }
+            None => {
+                self.pending.insert(
+                    key,
+                    Pending {
+                        side,
+                        events: VecDeque::from([(now, log)]),
+                    },
+                    self.window,
+                );
+                self.pending_count += 1;
+                self.evict_oldest(output);
+            }
+        }
+    }
+
+    /// Removes the oldest event waiting for a match with the given key, moving the deadline of the
+    /// key to the end of the window of the next one.
+    fn pop_front(&mut self, key: &str) -> Option<(Side, LogEvent)> {
+        let pending = self.pending.get_mut(key)?;
+        let side = pending.side;
+        let (_, log) = pending.events.pop_front()?;
+        let next = pending.events.front().map(|(received, _)| *received);
+        self.pending_count -= 1;
+
+        match next {
+            Some(received) => {
+                self.pending
+                    .reset_at(key, (received + self.window).into_std());
+            }
+            None => {
+                self.pending.remove(key);
+            }
+        }
+        Some((side, log))
+    }
+
+    /// Leaves the oldest event waiting for a match unmatched, if there are more than `max_pending`.
+    fn evict_oldest(&mut self, output: &mut Vec<Event>) {
+        if !matches!(self.max_pending, Some(max_pending) if self.pending_count > max_pending) {
+            return;
+        }
+
+        let oldest = self
+            .pending
+            .iter_mut()
+            .filter_map(|(key, pending)| {
+                pending.events.front().map(|(received, _)| (*received, key))
+            })
+            .min_by_key(|(received, _)| *received)
+            .map(|(_, key)| key.clone());
+        if let Some((side, log)) = oldest.and_then(|key| self.pop_front(&key)) {
+            self.unmatched(side, log, output);
+        }
+    }
+
+    /// Leaves the events of an expired key unmatched once their window is over, keeping the others
+    /// waiting for a match.
+    fn expire(&mut self, key: String, mut pending: Pending, output: &mut Vec<Event>) {
+        let now = Instant::now();
+        while let Some((received, _)) = pending.events.front() {
+            if *received + self.window > now {
+                break;
+            }
+            let (_, log) = pending.events.pop_front().expect("front event must exist");
+            self.pending_count -= 1;
+            self.unmatched(pending.side, log, output);
+        }
+
+        if let Some((received, _)) = pending.events.front() {
+            let deadline = (*received + self.window).into_std();
+            self.pending.insert_at(key, pending, deadline);
+        }
+    }
+
+    fn merge(&self, left: LogEvent, right: LogEvent) -> LogEvent {
+        let (left, mut metadata) = left.into_parts();
+        let (right, right_metadata) = right.into_parts();
+        metadata.merge(right_metadata);
+
+        let mut fields = IndexMap::<String, Box<dyn ReduceValueMerger>>::new();
+        for (key, value) in into_fields(left).into_iter().chain(into_fields(right)) {
+            match fields.entry(key) {
+                Entry::Occupied(mut entry) => {
+                    if let Err(error) = entry.get_mut().add(value) {
+                        warn!(message = "Failed to merge value.", %error);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    let strategy = self
+                        .merge_strategies
+                        .get(entry.key())
+                        .unwrap_or(&MergeStrategy::Discard);
+                    match get_value_merger(value, strategy) {
+                        Ok(merger) => {
+                            entry.insert(merger);
+                        }
+                        Err(error) => {
+                            warn!(message = "Failed to create merger.", field = ?entry.key(), %error);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut event = LogEvent::new_with_metadata(metadata);
+        for (key, merger) in fields {
+            if let Err(error) = merger.insert_into(key, &mut event) {
+                warn!(message = "Failed to merge values for field.", %error);
+            }
+        }
+        event
+    }
+
+    fn unmatched(&self, side: Side, mut log: LogEvent, output: &mut Vec<Event>) {
+        if !(self.join_type == JoinType::Left && side == Side::Left) {
+            log.insert(metadata_path!("vector", "join", UNMATCHED_FIELD), true);
+        }
+        output.push(Event::Log(log));
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let pending = self
+            .pending
+            .iter_mut()
+            .map(|(_, pending)| (pending.side, std::mem::take(&mut pending.events)))
+            .collect::<Vec<_>>();
+        for (side, events) in pending {
+            for (_, log) in events {
+                self.unmatched(side, log, output);
+            }
+        }
+        self.pending = ExpiringHashMap::default();
+        self.pending_count = 0;
+    }
+}
+
+fn into_fields(value: Value) -> Vec<(String, Value)> {
+    match value {
+        Value::Object(fields) => fields.into_iter().collect(),
+        _ => Vec::new(),
+    }
+}
+
+impl TaskTransform<Event> for JoinWindow {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.handle(event.into_log(), &mut output);
+                          false
+                        }
+                      }
+                    }
+                    expired = me.pending.next_expired(), if !me.pending.is_empty() => {
+                      if let Some((pending, key)) = expired {
+                        me.expire(key.into_inner(), pending, &mut output);
+                      }
+                      false
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+/// Sends the events marked as unmatched by the window to the `unmatched` output.
+#[derive(Clone, Debug)]
+pub(super) struct JoinOutput;
+
+impl SyncTransform for JoinOutput {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let mut log = event.into_log();
+        if log
+            .remove_prune(metadata_path!("vector", "join", UNMATCHED_FIELD), true)
+            .is_some()
+        {
+            output.push_named(UNMATCHED_OUTPUT, Event::Log(log));
+        } else {
+            output.push(Event::Log(log));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, task::Poll};
+
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    use super::*;
+    use crate::{
+        config::{GenerateConfig, TransformConfig},
+        schema,
+        transforms::test::transform_one,
+    };
+
+    fn config(join_type: JoinType) -> JoinConfig {
+        let mut config: JoinConfig = toml::Value::try_into(JoinConfig::generate_config()).unwrap();
+        config.join_type = join_type;
+        config
+    }
+
+    fn event(side: &mut JoinSide, fields: &[(&str, Value)]) -> Event {
+        let log = LogEvent::from(
+            fields
+                .iter()
+                .map(|(key, value)| ((*key).to_owned(), value.clone()))
+                .collect::<BTreeMap<_, _>>(),
+        );
+        transform_one(side, log.into()).unwrap()
+    }
+
+    /// Runs the window and output stages, returning the default and `unmatched` outputs.
+    async fn run(config: &JoinConfig, events: Vec<Event>) -> (Vec<Event>, Vec<Event>) {
+        let window = Box::new(JoinWindow::new(config));
+        let windowed = window
+            .transform(Box::pin(stream::iter(events)))
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut buf = TransformOutputsBuf::new_with_capacity(
+            config.outputs(&schema::Definition::any()),
+            windowed.len(),
+        );
+        for event in windowed {
+            JoinOutput.transform(event, &mut buf);
+        }
+        (
+            buf.drain().collect(),
+            buf.drain_named(UNMATCHED_OUTPUT).collect(),
+        )
+    }
+
+    fn sides() -> (JoinSide, JoinSide) {
+        let key = Template::try_from("{{ request_id }}").unwrap();
+        (JoinSide::left(key.clone()), JoinSide::right(key))
+    }
+
+    #[tokio::test]
+    async fn inner_join() {
+        let (mut left, mut right) = sides();
+        let events = vec![
+            event(
+                &mut right,
+                &[("request_id", "a".into()), ("status", 200.into())],
+            ),
+            event(
+                &mut left,
+                &[("request_id", "b".into()), ("path", "/b".into())],
+            ),
+            event(
+                &mut left,
+                &[("request_id", "a".into()), ("path", "/a".into())],
+            ),
+            event(&mut right, &[("status", 500.into())]),
+        ];
+
+        let (joined, unmatched) = run(&config(JoinType::Inner), events).await;
+
+        assert_eq!(joined.len(), 1);
+        let log = joined[0].as_log();
+        assert_eq!(log["request_id"], "a".into());
+        assert_eq!(log["path"], "/a".into());
+        assert_eq!(log["status"], 200.into());
+        assert!(log
+            .get(metadata_path!("vector", "join", SIDE_FIELD))
+            .is_none());
+        assert!(log
+            .get(metadata_path!("vector", "join", KEY_FIELD))
+            .is_none());
+
+        let mut unmatched = unmatched
+            .iter()
+            .map(|event| event.as_log().get("path").map(Value::to_string_lossy))
+            .collect::<Vec<_>>();
+        unmatched.sort();
+        assert_eq!(unmatched, vec![None, Some("/b".to_owned())]);
+    }
+
+    #[tokio::test]
+    async fn left_join() {
+        let (mut left, mut right) = sides();
+        let events = vec![
+            event(&mut left, &[("request_id", "a".into())]),
+            event(&mut right, &[("request_id", "b".into())]),
+        ];
+
+        let (joined, unmatched) = run(&config(JoinType::Left), events).await;
+
+        assert_eq!(joined.len(), 1);
+        assert_eq!(joined[0].as_log()["request_id"], "a".into());
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].as_log()["request_id"], "b".into());
+    }
+
+    #[tokio::test]
+    async fn pairs_events_in_order() {
+        let (mut left, mut right) = sides();
+        let events = vec![
+            event(&mut left, &[("request_id", "a".into()), ("n", 1.into())]),
+            event(&mut left, &[("request_id", "a".into()), ("n", 2.into())]),
+            event(&mut right, &[("request_id", "a".into()), ("m", 1.into())]),
+            event(&mut right, &[("request_id", "a".into()), ("m", 2.into())]),
+        ];
+
+        let (joined, unmatched) = run(&config(JoinType::Inner), events).await;
+
+        let pairs = joined
+            .iter()
+            .map(|event| (event.as_log()["n"].clone(), event.as_log()["m"].clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            pairs,
+            vec![
+                (Value::from(1), Value::from(1)),
+                (Value::from(2), Value::from(2))
+            ]
+        );
+        assert!(unmatched.is_empty());
+    }
+
+    #[tokio::test]
+    async fn window_starts_when_each_event_is_received() {
+        tokio::time::pause();
+        let (mut left, mut right) = sides();
+        let (tx, rx) = mpsc::channel(10);
+        let mut out = Box::new(JoinWindow::new(&config(JoinType::Inner)))
+            .transform(Box::pin(ReceiverStream::new(rx)));
+
+        let left_a = |left: &mut JoinSide, n: i64| {
+            event(left, &[("request_id", "a".into()), ("n", n.into())])
+        };
+        let right_a = |right: &mut JoinSide| event(right, &[("request_id", "a".into())]);
+
+        tx.send(left_a(&mut left, 1)).await.unwrap();
+        assert_eq!(Poll::Pending, futures::poll!(out.next()));
+        tokio::time::advance(Duration::from_secs(20)).await;
+        tx.send(left_a(&mut left, 2)).await.unwrap();
+        assert_eq!(Poll::Pending, futures::poll!(out.next()));
+        tokio::time::advance(Duration::from_secs(5)).await;
+        tx.send(right_a(&mut right)).await.unwrap();
+        assert_eq!(out.next().await.unwrap().as_log()["n"], 1.into());
+
+        // The second event keeps waiting past the end of the window of the first one.
+        tokio::time::advance(Duration::from_secs(10)).await;
+        assert_eq!(Poll::Pending, futures::poll!(out.next()));
+        tx.send(right_a(&mut right)).await.unwrap();
+        let joined = out.next().await.unwrap();
+        assert_eq!(joined.as_log()["n"], 2.into());
+        assert!(joined
+            .as_log()
+            .get(metadata_path!("vector", "join", UNMATCHED_FIELD))
+            .is_none());
+
+        // Its own window still ends 30 seconds after it was received.
+        tx.send(left_a(&mut left, 3)).await.unwrap();
+        assert_eq!(Poll::Pending, futures::poll!(out.next()));
+        tokio::time::advance(Duration::from_secs(31)).await;
+        let unmatched = out.next().await.unwrap();
+        assert_eq!(unmatched.as_log()["n"], 3.into());
+        assert!(unmatched
+            .as_log()
+            .get(metadata_path!("vector", "join", UNMATCHED_FIELD))
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn max_pending() {
+        let mut config = config(JoinType::Inner);
+        config.max_pending = Some(1);
+
+        let (mut left, mut right) = sides();
+        let events = vec![
+            event(&mut left, &[("request_id", "a".into())]),
+            event(&mut left, &[("request_id", "b".into())]),
+            event(&mut right, &[("request_id", "b".into())]),
+        ];
+
+        let (joined, unmatched) = run(&config, events).await;
+
+        assert_eq!(joined.len(), 1);
+        assert_eq!(joined[0].as_log()["request_id"], "b".into());
+        assert_eq!(unmatched.len(), 1);
+        assert_eq!(unmatched[0].as_log()["request_id"], "a".into());
+    }
+
+    #[tokio::test]
+    async fn merge_strategies() {
+        let mut config = config(JoinType::Inner);
+        config
+            .merge_strategies
+            .insert("duration_ms".to_owned(), MergeStrategy::Sum);
+        config
+            .merge_strategies
+            .insert("message".to_owned(), MergeStrategy::Array);
+
+        let (mut left, mut right) = sides();
+        let events = vec![
+            event(
+                &mut left,
+                &[
+                    ("request_id", "a".into()),
+                    ("duration_ms", 10.into()),
+                    ("message", "request".into()),
+                ],
+            ),
+            event(
+                &mut right,
+                &[
+                    ("request_id", "a".into()),
+                    ("duration_ms", 5.into()),
+                    ("message", "response".into()),
+                ],
+            ),
+        ];
+
+        let (joined, _) = run(&config, events).await;
+
+        let log = joined[0].as_log();
+        assert_eq!(log["duration_ms"], 15.into());
+        assert_eq!(
+            log["message"],
+            Value::from(vec![Value::from("request"), Value::from("response")])
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Conversion of JSON Schemas into the [`Kind`] of the values they accept.
+//!
+//! Only the keywords constraining the type of values are taken into account. Keywords that can't
+//! be represented by a `Kind`, such as `pattern` or `minimum`, are ignored, and references are not
+//! followed, so the resulting kind may accept more values than the schema does, but never fewer.
+
+use std::collections::BTreeMap;
+
+use serde_json::{Map, Value};
+use value::{
+    kind::{
+        collection::{Field, Index},
+        Collection,
+    },
+    Kind,
+};
+
+/// Returns the kind of the values accepted by `schema`.
+pub(super) fn from_schema(schema: &Value) -> Kind {
+    match schema {
+        Value::Bool(false) => Kind::never(),
+        Value::Object(schema) => from_object_schema(schema),
+        _ => Kind::any(),
+    }
+}
+
+fn from_object_schema(schema: &Map<String, Value>) -> Kind {
+    if schema.contains_key("$ref") {
+        return Kind::any();
+    }
+
+    if let Some(value) = schema.get("const") {
+        return from_value(value);
+    }
+
+    if let Some(Value::Array(values)) = schema.get("enum") {
+        return union(values.iter().map(from_value));
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(Value::Array(schemas)) = schema.get(keyword) {
+            return union(schemas.iter().map(from_schema));
+        }
+    }
+
+    match schema.get("type") {
+        Some(Value::String(name)) => from_type(name, schema),
+        Some(Value::Array(names)) => union(
+            names
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|name| from_type(name, schema)),
+        ),
+        _ => Kind::any(),
+    }
+}
+
+fn from_type(name: &str, schema: &Map<String, Value>) -> Kind {
+    match name {
+        // Timestamps are represented as strings in the JSON form of events.
+        "string" => Kind::bytes().or_timestamp(),
+        // JSON Schema accepts numbers with a zero fractional part, such as `1.0`, as integers.
+        "integer" | "number" => Kind::integer().or_float(),
+        "boolean" => Kind::boolean(),
+        "null" => Kind::null(),
+        "array" => Kind::array(array_collection(schema)),
+        "object" => Kind::object(object_collection(schema)),
+        _ => Kind::any(),
+    }
+}
+
+fn object_collection(schema: &Map<String, Value>) -> Collection<Field> {
+    let required = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| {
+            required
+                .iter()
+                .filter_map(Value::as_str)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let known = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| {
+            properties
+                .iter()
+                .map(|(name, property)| {
+                    let kind = from_schema(property);
+                    let kind = if required.contains(&name.as_str()) {
+                        kind
+                    } else {
+                        kind.or_undefined()
+                    };
+                    (Field::from(name.as_str()), kind)
+                })
+                .collect::<BTreeMap<_, _>>()
+        })
+        .unwrap_or_default();
+
+    let unknown = if schema.contains_key("patternProperties") {
+        Kind::any()
+    } else {
+        match schema.get("additionalProperties") {
+            Some(Value::Bool(false)) => Kind::undefined(),
+            Some(additional) => from_schema(additional).or_undefined(),
+            None => Kind::any(),
+        }
+    };
+
+    Collection::from_parts(known, unknown)
+}
+
+fn array_collection(schema: &Map<String, Value>) -> Collection<Index> {
+    // Draft 2020-12 describes the leading items of arrays with `prefixItems`, and the remaining ones
+    // with `items`, while draft 7 uses `items` and `additionalItems` respectively.
+    let (prefix, rest) = match (schema.get("prefixItems"), schema.get("items")) {
+        (Some(Value::Array(prefix)), rest) => (prefix.as_slice(), rest),
+        (None, Some(Value::Array(prefix))) => (prefix.as_slice(), schema.get("additionalItems")),
+        (_, rest) => (&[][..], rest),
+    };
+
+    let known = prefix
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (Index::from(index), from_schema(item).or_undefined()))
+        .collect::<BTreeMap<_, _>>();
+
+    let unknown = match rest {
+        Some(Value::Bool(false)) => Kind::undefined(),
+        Some(rest) => from_schema(rest).or_undefined(),
+        None => Kind::any(),
+    };
+
+    Collection::from_parts(known, unknown)
+}
+
+/// Returns the kind of a value listed by the `const` or `enum` keywords.
+fn from_value(value: &Value) -> Kind {
+    match value {
+        Value::Null => Kind::null(),
+        Value::Bool(_) => Kind::boolean(),
+        Value::Number(number) if number.is_f64() => Kind::float(),
+        Value::Number(_) => Kind::integer(),
+        Value::String(_) => Kind::bytes().or_timestamp(),
+        Value::Array(values) => Kind::array(
+            values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (Index::from(index), from_value(value)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+        Value::Object(fields) => Kind::object(
+            fields
+                .iter()
+                .map(|(name, value)| (Field::from(name.as_str()), from_value(value)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+    }
+}
+
+fn union(kinds: impl Iterator<Item = Kind>) -> Kind {
+    kinds
+        .reduce(|kind, other| kind.union(other))
+        .unwrap_or_else(Kind::never)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn converts_objects() {
+        let kind = from_schema(&json!({
+            "type": "object",
+            "properties": {
+                "message": { "type": "string" },
+                "status": { "type": "integer" },
+                "tags": { "type": "array", "items": { "type": "string" } }
+            },
+            "required": ["message"],
+            "additionalProperties": false
+        }));
+
+        assert_eq!(
+            kind,
+            Kind::object(Collection::from_parts(
+                BTreeMap::from([
+                    ("message".into(), Kind::bytes().or_timestamp()),
+                    ("status".into(), Kind::integer().or_float().or_undefined()),
+                    (
+                        "tags".into(),
+                        Kind::array(Collection::from_unknown(
+                            Kind::bytes().or_timestamp().or_undefined()
+                        ))
+                        .or_undefined()
+                    ),
+                ]),
+                Kind::undefined(),
+            ))
+        );
+    }
+
+    #[test]
+    fn converts_type_unions() {
+        assert_eq!(
+            from_schema(&json!({ "type": ["number", "null"] })),
+            Kind::integer().or_float().or_null()
+        );
+        assert_eq!(
+            from_schema(&json!({ "anyOf": [{ "type": "boolean" }, { "const": "auto" }] })),
+            Kind::boolean().or_bytes().or_timestamp()
+        );
+    }
+
+    #[test]
+    fn keeps_unsupported_schemas_open() {
+        assert_eq!(
+            from_schema(&json!({ "$ref": "#/$defs/event" })),
+            Kind::any()
+        );
+        assert_eq!(from_schema(&json!({ "minLength": 1 })), Kind::any());
+        assert_eq!(from_schema(&json!(false)), Kind::never());
+    }
+}
@@ -0,0 +1,366 @@
+use std::{collections::BTreeMap, fs, path::PathBuf, sync::Arc};
+
+use jsonschema::JSONSchema;
+use lookup::lookup_v2::ValuePath;
+use lookup::{metadata_path, path, LookupBuf, PathPrefix};
+use snafu::{ResultExt, Snafu};
+use value::{kind::Collection, Kind};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
+    },
+    event::{Event, LogEvent},
+    schema,
+    transforms::{SyncTransform, Transform, TransformOutputsBuf},
+};
+
+mod kind;
+
+/// The output events failing validation are sent to.
+pub(crate) const INVALID_OUTPUT: &str = "_invalid";
+
+/// The field invalid events are annotated with, in their metadata.
+const ERRORS_FIELD: &str = "validation_errors";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Unable to read schema file {:?}: {}", path, source))]
+    ReadSchema {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Schema file {:?} isn't valid JSON: {}", path, source))]
+    ParseSchema {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("Invalid JSON Schema in {:?}: {}", path, message))]
+    InvalidSchema { path: PathBuf, message: String },
+}
+
+/// A draft of the JSON Schema specification.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Draft {
+    /// Draft 7.
+    #[serde(rename = "draft-07")]
+    Draft7,
+
+    /// Draft 2020-12.
+    #[serde(rename = "2020-12")]
+    Draft202012,
+}
+
+impl From<Draft> for jsonschema::Draft {
+    fn from(draft: Draft) -> Self {
+        match draft {
+            Draft::Draft7 => jsonschema::Draft::Draft7,
+            Draft::Draft202012 => jsonschema::Draft::Draft202012,
+        }
+    }
+}
+
+/// Configuration for the `json_schema` transform.
+#[configurable_component(transform("json_schema"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JsonSchemaConfig {
+    /// The path of the file holding the JSON Schema events are validated against.
+    ///
+    /// Valid events are sent to the default output of the transform. Invalid events are sent to
+    /// the `<transform_name>._invalid` output, annotated with the validation errors in the
+    /// `validation_errors` field of their metadata.
+    schema_file: PathBuf,
+
+    /// The draft of the JSON Schema specification the schema follows.
+    ///
+    /// When unset, the draft is detected from the `$schema` keyword of the schema, and defaults to
+    /// draft 7.
+    #[serde(default)]
+    draft: Option<Draft>,
+}
+
+impl JsonSchemaConfig {
+    fn load_schema(&self) -> crate::Result<serde_json::Value> {
+        let path = &self.schema_file;
+        let schema = fs::read(path).context(ReadSchemaSnafu { path })?;
+
+        Ok(serde_json::from_slice(&schema).context(ParseSchemaSnafu { path })?)
+    }
+}
+
+impl GenerateConfig for JsonSchemaConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"schema_file = "/etc/vector/schema.json""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for JsonSchemaConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        let schema = self.load_schema()?;
+
+        let mut options = JSONSchema::options();
+        if let Some(draft) = self.draft {
+            options.with_draft(draft.into());
+        }
+        let validator = options
+            .compile(&schema)
+            .map_err(|error| BuildError::InvalidSchema {
+                path: self.schema_file.clone(),
+                message: error.to_string(),
+            })?;
+
+        Ok(Transform::synchronous(JsonSchema {
+            validator: Arc::new(validator),
+        }))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, merged_definition: &schema::Definition) -> Vec<Output> {
+        // Events going through the default output are known to match the schema. Schemas that
+        // can't be loaded are reported when the transform is built.
+        let valid_definition = match self.load_schema().map(|schema| kind::from_schema(&schema)) {
+            Ok(kind) if !kind.is_any() => merged_definition.clone().with_event_kind(kind),
+            _ => merged_definition.clone(),
+        };
+
+        let errors_kind = Kind::array(Collection::from_unknown(Kind::object(BTreeMap::from([
+            ("path".into(), Kind::bytes()),
+            ("message".into(), Kind::bytes()),
+        ]))));
+
+        let mut invalid_definition = schema::Definition::new_with_default_metadata(
+            Kind::never(),
+            merged_definition.log_namespaces().clone(),
+        );
+
+        if merged_definition
+            .log_namespaces()
+            .contains(&LogNamespace::Legacy)
+        {
+            invalid_definition = invalid_definition.merge(merged_definition.clone().with_field(
+                LookupBuf::from_segments(vec![
+                    log_schema().metadata_key().into(),
+                    ERRORS_FIELD.into(),
+                ]),
+                errors_kind.clone(),
+                None,
+            ));
+        }
+
+        if merged_definition
+            .log_namespaces()
+            .contains(&LogNamespace::Vector)
+        {
+            invalid_definition =
+                invalid_definition.merge(merged_definition.clone().with_metadata_field(
+                    LookupBuf::from_segments(vec!["vector".into(), ERRORS_FIELD.into()]),
+                    errors_kind,
+                ));
+        }
+
+        vec![
+            Output::default(DataType::Log).with_schema_definition(valid_definition),
+            Output::default(DataType::Log)
+                .with_schema_definition(invalid_definition)
+                .with_port(INVALID_OUTPUT),
+        ]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Derivative)]
+#[derivative(Debug)]
+pub struct JsonSchema {
+    #[derivative(Debug = "ignore")]
+    validator: Arc<JSONSchema>,
+}
+
+impl JsonSchema {
+    /// Validates the event, returning the errors found.
+    fn validate(&self, log: &LogEvent) -> Vec<serde_json::Value> {
+        let instance = match serde_json::to_value(log.value()) {
+            Ok(instance) => instance,
+            Err(error) => {
+                return vec![serde_json::json!({
+                    "path": "",
+                    "message": format!("event can't be represented as JSON: {}", error),
+                })]
+            }
+        };
+
+        match self.validator.validate(&instance) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors
+                .map(|error| {
+                    serde_json::json!({
+                        "path": error.instance_path.to_string(),
+                        "message": error.to_string(),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl SyncTransform for JsonSchema {
+    fn transform(&mut self, event: Event, output: &mut TransformOutputsBuf) {
+        let mut log = event.into_log();
+        let errors = self.validate(&log);
+
+        if errors.is_empty() {
+            output.push(Event::Log(log));
+            return;
+        }
+
+        match log.namespace() {
+            LogNamespace::Legacy => {
+                log.insert(
+                    (
+                        PathPrefix::Event,
+                        log_schema().metadata_key().concat(path!(ERRORS_FIELD)),
+                    ),
+                    serde_json::Value::Array(errors),
+                );
+            }
+            LogNamespace::Vector => {
+                log.insert(
+                    metadata_path!("vector", ERRORS_FIELD),
+                    serde_json::Value::Array(errors),
+                );
+            }
+        }
+        output.push_named(INVALID_OUTPUT, Event::Log(log));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use vector_core::transform::TransformOutputsBuf;
+
+    use super::*;
+    use crate::test_util::temp_file;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<JsonSchemaConfig>();
+    }
+
+    fn config(schema: serde_json::Value) -> JsonSchemaConfig {
+        let schema_file = temp_file();
+        fs::write(&schema_file, schema.to_string()).unwrap();
+
+        JsonSchemaConfig {
+            schema_file,
+            draft: None,
+        }
+    }
+
+    fn schema() -> serde_json::Value {
+        serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {
+                "message": { "type": "string" },
+                "status": { "type": "integer", "minimum": 100 }
+            },
+            "required": ["message", "status"]
+        })
+    }
+
+    async fn run(config: &JsonSchemaConfig, event: Event) -> (Vec<Event>, Vec<Event>) {
+        let mut transform = config
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_sync();
+        let mut outputs =
+            TransformOutputsBuf::new_with_capacity(config.outputs(&schema::Definition::any()), 1);
+
+        transform.transform(event, &mut outputs);
+        (
+            outputs.drain().collect(),
+            outputs.drain_named(INVALID_OUTPUT).collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn sends_valid_events_to_default_output() {
+        let event = Event::from(LogEvent::from(BTreeMap::from([
+            ("message".to_owned(), value::Value::from("ok")),
+            ("status".to_owned(), value::Value::from(200)),
+        ])));
+
+        let (valid, invalid) = run(&config(schema()), event.clone()).await;
+
+        assert_eq!(valid, vec![event]);
+        assert!(invalid.is_empty());
+    }
+
+    #[tokio::test]
+    async fn annotates_invalid_events() {
+        let event = Event::from(LogEvent::from(BTreeMap::from([(
+            "status".to_owned(),
+            value::Value::from(42),
+        )])));
+
+        let (valid, invalid) = run(&config(schema()), event).await;
+
+        assert!(valid.is_empty());
+        assert_eq!(invalid.len(), 1);
+
+        let errors = invalid[0]
+            .as_log()
+            .get((
+                PathPrefix::Event,
+                log_schema().metadata_key().concat(path!(ERRORS_FIELD)),
+            ))
+            .and_then(value::Value::as_array)
+            .unwrap();
+        let mut paths = errors
+            .iter()
+            .map(|error| error.as_object().unwrap()["path"].to_string_lossy())
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(paths, vec!["", "/status"]);
+    }
+
+    #[test]
+    fn derives_definition_from_schema() {
+        let outputs = config(schema()).outputs(&schema::Definition::any());
+
+        assert_eq!(
+            outputs[0]
+                .log_schema_definition
+                .as_ref()
+                .unwrap()
+                .event_kind(),
+            &Kind::object(Collection::from_parts(
+                BTreeMap::from([
+                    ("message".into(), Kind::bytes().or_timestamp()),
+                    ("status".into(), Kind::integer().or_float()),
+                ]),
+                Kind::any(),
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_schemas() {
+        let config = config(serde_json::json!({ "type": "text" }));
+
+        assert!(config.build(&TransformContext::default()).await.is_err());
+    }
+}
@@ -0,0 +1,261 @@
+//! An online log clustering algorithm, based on [Drain][drain].
+//!
+//! Messages are split into tokens, and grouped by their number of tokens and their leading tokens.
+//! Within a group, a message joins the cluster whose template it is the most similar to, provided
+//! the similarity reaches a threshold, or starts a new cluster otherwise. Tokens of the template
+//! that differ from the message are then replaced by a wildcard.
+//!
+//! Unlike the original algorithm, the parse tree is flattened into a map keyed by the leading
+//! tokens, and doesn't limit the number of children of its nodes. Clusters are instead aged out by
+//! the caller, through [`Drain::remove`].
+//!
+//! [drain]: https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf
+
+use std::collections::HashMap;
+
+/// The token replacing the variable parts of templates.
+pub(super) const WILDCARD: &str = "<*>";
+
+/// The tokens a message is grouped by.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct GroupKey {
+    length: usize,
+    prefix: Vec<String>,
+}
+
+#[derive(Debug)]
+struct Cluster {
+    group: GroupKey,
+    tokens: Vec<String>,
+}
+
+impl Cluster {
+    fn template(&self) -> String {
+        self.tokens.join(" ")
+    }
+
+    /// Returns the share of tokens of `tokens` equal to the template, and the number of wildcards
+    /// of the template.
+    fn similarity(&self, tokens: &[&str]) -> (f64, usize) {
+        if tokens.is_empty() {
+            return (1.0, 0);
+        }
+
+        let mut equal = 0;
+        let mut wildcards = 0;
+        for (template, token) in self.tokens.iter().zip(tokens) {
+            if template == WILDCARD {
+                wildcards += 1;
+            } else if template.as_str() == *token {
+                equal += 1;
+            }
+        }
+
+        (equal as f64 / tokens.len() as f64, wildcards)
+    }
+}
+
+/// A cluster a message was added to.
+#[derive(Debug, Eq, PartialEq)]
+pub(super) struct Match {
+    pub(super) id: String,
+    pub(super) template: String,
+    pub(super) created: bool,
+}
+
+#[derive(Debug)]
+pub(super) struct Drain {
+    prefix_tokens: usize,
+    similarity_threshold: f64,
+    groups: HashMap<GroupKey, Vec<String>>,
+    clusters: HashMap<String, Cluster>,
+}
+
+impl Drain {
+    /// Creates a clusterer grouping messages by their first `prefix_tokens` tokens, and adding
+    /// messages to clusters once at least `similarity_threshold` of their tokens match.
+    pub(super) fn new(prefix_tokens: usize, similarity_threshold: f64) -> Self {
+        Self {
+            prefix_tokens,
+            similarity_threshold,
+            groups: HashMap::new(),
+            clusters: HashMap::new(),
+        }
+    }
+
+    /// Adds `message` to the cluster it's the most similar to, creating one if there is none.
+    pub(super) fn add(&mut self, message: &str) -> Match {
+        let tokens = message.split_whitespace().collect::<Vec<_>>();
+        let group = GroupKey {
+            length: tokens.len(),
+            prefix: tokens
+                .iter()
+                .take(self.prefix_tokens)
+                .map(|token| mask(token).to_owned())
+                .collect(),
+        };
+
+        let best = self.groups.get(&group).and_then(|ids| {
+            ids.iter()
+                .map(|id| (id, self.clusters[id].similarity(&tokens)))
+                .filter(|(_, (similarity, _))| *similarity >= self.similarity_threshold)
+                .max_by(|(_, a), (_, b)| {
+                    a.0.partial_cmp(&b.0)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .then(a.1.cmp(&b.1))
+                })
+                .map(|(id, _)| id.clone())
+        });
+
+        match best {
+            Some(id) => {
+                let cluster = self.clusters.get_mut(&id).expect("cluster must exist");
+                for (template, token) in cluster.tokens.iter_mut().zip(&tokens) {
+                    if template.as_str() != *token {
+                        *template = WILDCARD.to_owned();
+                    }
+                }
+
+                Match {
+                    template: cluster.template(),
+                    id,
+                    created: false,
+                }
+            }
+            None => {
+                let cluster = Cluster {
+                    group: group.clone(),
+                    tokens: tokens.iter().map(|token| (*token).to_owned()).collect(),
+                };
+                let template = cluster.template();
+                let id = self.new_id(&tokens);
+
+                self.groups.entry(group).or_default().push(id.clone());
+                self.clusters.insert(id.clone(), cluster);
+
+                Match {
+                    id,
+                    template,
+                    created: true,
+                }
+            }
+        }
+    }
+
+    /// Removes the cluster with the given ID.
+    pub(super) fn remove(&mut self, id: &str) {
+        if let Some(cluster) = self.clusters.remove(id) {
+            if let Some(ids) = self.groups.get_mut(&cluster.group) {
+                ids.retain(|other| other != id);
+                if ids.is_empty() {
+                    self.groups.remove(&cluster.group);
+                }
+            }
+        }
+    }
+
+    /// Derives the ID of a new cluster from the tokens of its first message, with the tokens
+    /// holding digits masked, so that messages only differing by numbers, such as counters, IP
+    /// addresses or timestamps, start clusters with the same ID across restarts and instances.
+    fn new_id(&self, tokens: &[&str]) -> String {
+        let masked = tokens.iter().map(|token| mask(token)).collect::<Vec<_>>();
+        let mut hash = seahash::hash(masked.join(" ").as_bytes());
+        loop {
+            let id = format!("{:016x}", hash);
+            if !self.clusters.contains_key(&id) {
+                return id;
+            }
+            hash = hash.wrapping_add(1);
+        }
+    }
+}
+
+/// Replaces tokens holding digits, which are most likely variables, by the wildcard.
+fn mask(token: &str) -> &str {
+    if token.bytes().any(|byte| byte.is_ascii_digit()) {
+        WILDCARD
+    } else {
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clusters_similar_messages() {
+        let mut drain = Drain::new(2, 0.5);
+
+        let first = drain.add("login succeeded for user alice from 10.0.0.1");
+        let second = drain.add("login succeeded for user bob from 10.0.0.2");
+
+        assert!(first.created);
+        assert_eq!(
+            first.template,
+            "login succeeded for user alice from 10.0.0.1"
+        );
+        assert_eq!(
+            second,
+            Match {
+                id: first.id,
+                template: "login succeeded for user <*> from <*>".to_owned(),
+                created: false,
+            }
+        );
+    }
+
+    #[test]
+    fn separates_dissimilar_messages() {
+        let mut drain = Drain::new(2, 0.6);
+
+        let connected = drain.add("connection opened to db");
+        let longer = drain.add("connection opened to db replica");
+        let different = drain.add("connection opened by peer");
+        let other_prefix = drain.add("request opened to db");
+
+        assert!(longer.created);
+        assert!(different.created);
+        assert!(other_prefix.created);
+        assert_ne!(connected.id, different.id);
+    }
+
+    #[test]
+    fn groups_numeric_prefixes() {
+        let mut drain = Drain::new(2, 0.5);
+
+        let first = drain.add("200 GET /index.html");
+        let second = drain.add("404 GET /index.html");
+
+        assert_eq!(second.id, first.id);
+        assert_eq!(second.template, "<*> GET /index.html");
+    }
+
+    #[test]
+    fn ids_are_stable() {
+        let first = Drain::new(2, 0.5).add("cache miss for key");
+        let second = Drain::new(2, 0.5).add("cache miss for key");
+
+        assert_eq!(first.id, second.id);
+    }
+
+    #[test]
+    fn ids_do_not_depend_on_the_first_message() {
+        let first = Drain::new(2, 0.5).add("request 1234 from 10.0.0.1 took 15ms");
+        let second = Drain::new(2, 0.5).add("request 5678 from 10.0.0.2 took 20ms");
+
+        assert_ne!(first.template, second.template);
+        assert_eq!(first.id, second.id);
+    }
+
+    #[test]
+    fn removes_clusters() {
+        let mut drain = Drain::new(2, 0.5);
+
+        let first = drain.add("job started");
+        drain.remove(&first.id);
+
+        assert!(drain.add("job finished").created);
+        assert!(drain.groups.len() == 1 && drain.clusters.len() == 1);
+    }
+}
@@ -0,0 +1,453 @@
+use std::{
+    collections::BTreeMap,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use chrono::{DateTime, Utc};
+use futures::{stream, Stream, StreamExt};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{log_schema, DataType, Input, Output, TransformConfig, TransformContext},
+    event::{
+        metric::{Metric, MetricKind, MetricValue},
+        Event, LogEvent,
+    },
+    expiring_hash_map::ExpiringHashMap,
+    internal_events::{LogPatternCreated, LogPatternEvicted, LogPatternExpired},
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+mod drain;
+
+use self::drain::Drain;
+
+const fn default_similarity_threshold() -> f64 {
+    0.5
+}
+
+const fn default_prefix_tokens() -> usize {
+    2
+}
+
+const fn default_expire_after_ms() -> u64 {
+    3_600_000
+}
+
+const fn default_max_patterns() -> usize {
+    10_000
+}
+
+fn default_pattern_id_field() -> String {
+    "pattern_id".to_owned()
+}
+
+fn default_pattern_field() -> String {
+    "pattern".to_owned()
+}
+
+/// Configuration for the `log_patterns` transform.
+#[configurable_component(transform("log_patterns"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LogPatternsConfig {
+    /// The field holding the message to cluster.
+    ///
+    /// Defaults to the global `log_schema.message_key` option.
+    pub field: Option<String>,
+
+    /// The field the ID of the pattern of the event is written to.
+    ///
+    /// Pattern IDs are derived from the first message of each pattern, with the tokens holding digits
+    /// masked, so that messages only differing by numbers get the same pattern ID across restarts,
+    /// and across Vector instances.
+    #[serde(default = "default_pattern_id_field")]
+    pub pattern_id_field: String,
+
+    /// The field the template of the pattern of the event is written to.
+    ///
+    /// The variable parts of the template are replaced by `<*>`.
+    #[serde(default = "default_pattern_field")]
+    pub pattern_field: String,
+
+    /// The share of tokens a message must have in common with the template of a pattern to be
+    /// added to it, between `0` and `1`.
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+
+    /// The number of leading tokens messages must have in common to be added to the same pattern.
+    ///
+    /// Tokens holding digits match any token.
+    #[serde(default = "default_prefix_tokens")]
+    pub prefix_tokens: usize,
+
+    /// The period of time after which a pattern without new events is forgotten, in milliseconds.
+    #[serde(default = "default_expire_after_ms")]
+    pub expire_after_ms: u64,
+
+    /// The maximum number of patterns kept in memory.
+    ///
+    /// Once reached, the pattern that has gone without new events the longest is forgotten to make
+    /// room for new ones.
+    #[serde(default = "default_max_patterns")]
+    pub max_patterns: usize,
+
+    /// The interval at which a metric is emitted for each pattern, in seconds.
+    ///
+    /// The metrics hold the number of events of the pattern, and when it was first and last seen.
+    /// When unset, no metrics are emitted.
+    pub metrics_interval_secs: Option<u64>,
+}
+
+impl Default for LogPatternsConfig {
+    fn default() -> Self {
+        Self {
+            field: None,
+            pattern_id_field: default_pattern_id_field(),
+            pattern_field: default_pattern_field(),
+            similarity_threshold: default_similarity_threshold(),
+            prefix_tokens: default_prefix_tokens(),
+            expire_after_ms: default_expire_after_ms(),
+            max_patterns: default_max_patterns(),
+            metrics_interval_secs: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(LogPatternsConfig);
+
+#[async_trait::async_trait]
+impl TransformConfig for LogPatternsConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        LogPatterns::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        if self.metrics_interval_secs.is_some() {
+            vec![Output::default(DataType::Log | DataType::Metric)]
+        } else {
+            vec![Output::default(DataType::Log)]
+        }
+    }
+}
+
+/// What is known about the events of a pattern.
+#[derive(Debug)]
+struct PatternStats {
+    count: u64,
+    first_seen: DateTime<Utc>,
+    last_seen: DateTime<Utc>,
+}
+
+pub struct LogPatterns {
+    field: String,
+    pattern_id_field: String,
+    pattern_field: String,
+    expire_after: Duration,
+    max_patterns: usize,
+    metrics_interval: Option<Duration>,
+    drain: Drain,
+    patterns: ExpiringHashMap<String, PatternStats>,
+}
+
+impl LogPatterns {
+    pub fn new(config: &LogPatternsConfig) -> crate::Result<Self> {
+        if !(0.0..=1.0).contains(&config.similarity_threshold) {
+            return Err("`similarity_threshold` must be between 0 and 1".into());
+        }
+        if config.max_patterns == 0 {
+            return Err("`max_patterns` must be non-zero".into());
+        }
+        if config.metrics_interval_secs == Some(0) {
+            return Err("`metrics_interval_secs` must be non-zero".into());
+        }
+
+        Ok(Self {
+            field: config
+                .field
+                .clone()
+                .unwrap_or_else(|| log_schema().message_key().to_owned()),
+            pattern_id_field: config.pattern_id_field.clone(),
+            pattern_field: config.pattern_field.clone(),
+            expire_after: Duration::from_millis(config.expire_after_ms),
+            max_patterns: config.max_patterns,
+            metrics_interval: config.metrics_interval_secs.map(Duration::from_secs),
+            drain: Drain::new(config.prefix_tokens, config.similarity_threshold),
+            patterns: ExpiringHashMap::default(),
+        })
+    }
+
+    fn transform_one(&mut self, mut log: LogEvent) -> LogEvent {
+        // Events without a message are forwarded as is.
+        let message = match log.get(self.field.as_str()) {
+            Some(message) => message.to_string_lossy(),
+            None => return log,
+        };
+
+        let matched = self.drain.add(&message);
+        let now = Utc::now();
+
+        if matched.created {
+            emit!(LogPatternCreated);
+        }
+
+        match self
+            .patterns
+            .reset_at(&matched.id, Instant::now() + self.expire_after)
+        {
+            Some(stats) => {
+                stats.count += 1;
+                stats.last_seen = now;
+            }
+            None => {
+                if self.patterns.len() >= self.max_patterns {
+                    self.evict_least_recently_seen();
+                }
+
+                self.patterns.insert(
+                    matched.id.clone(),
+                    PatternStats {
+                        count: 1,
+                        first_seen: now,
+                        last_seen: now,
+                    },
+                    self.expire_after,
+                );
+            }
+        }
+
+        log.insert(self.pattern_id_field.as_str(), matched.id);
+        log.insert(self.pattern_field.as_str(), matched.template);
+        log
+    }
+
+    fn expire(&mut self, id: &str) {
+        self.drain.remove(id);
+        emit!(LogPatternExpired);
+    }
+
+    fn evict_least_recently_seen(&mut self) {
+        let id = self
+            .patterns
+            .iter_mut()
+            .min_by_key(|(_, stats)| stats.last_seen)
+            .map(|(id, _)| id.clone());
+
+        if let Some(id) = id {
+            self.patterns.remove(&id);
+            self.drain.remove(&id);
+            emit!(LogPatternEvicted);
+        }
+    }
+
+    fn metrics_into(&mut self, output: &mut Vec<Event>) {
+        let timestamp = Utc::now();
+
+        for (id, stats) in self.patterns.iter_mut() {
+            // Templates change as patterns generalize, so only the ID tags the series.
+            let tags = BTreeMap::from([("pattern_id".to_owned(), id.clone())]);
+
+            let metrics = [
+                (
+                    "log_pattern_events_total",
+                    MetricValue::Counter {
+                        value: stats.count as f64,
+                    },
+                ),
+                (
+                    "log_pattern_first_seen_timestamp_seconds",
+                    MetricValue::Gauge {
+                        value: stats.first_seen.timestamp() as f64,
+                    },
+                ),
+                (
+                    "log_pattern_last_seen_timestamp_seconds",
+                    MetricValue::Gauge {
+                        value: stats.last_seen.timestamp() as f64,
+                    },
+                ),
+            ];
+
+            output.extend(metrics.into_iter().map(|(name, value)| {
+                Event::Metric(
+                    Metric::new(name, MetricKind::Absolute, value)
+                        .with_tags(Some(tags.clone()))
+                        .with_timestamp(Some(timestamp)),
+                )
+            }));
+        }
+    }
+}
+
+impl TaskTransform<Event> for LogPatterns {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let emit_metrics = me.metrics_interval.is_some();
+        let metrics_interval = me.metrics_interval.unwrap_or(Duration::from_secs(60));
+        let mut metrics_stream = tokio::time::interval_at(
+            tokio::time::Instant::now() + metrics_interval,
+            metrics_interval,
+        );
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          if emit_metrics {
+                            me.metrics_into(&mut output);
+                          }
+                          true
+                        }
+                        Some(event) => {
+                          output.push(Event::Log(me.transform_one(event.into_log())));
+                          false
+                        }
+                      }
+                    }
+                    expired = me.patterns.next_expired(), if !me.patterns.is_empty() => {
+                      if let Some((_, id)) = expired {
+                        me.expire(id.get_ref());
+                      }
+                      false
+                    }
+                    _ = metrics_stream.tick(), if emit_metrics => {
+                      me.metrics_into(&mut output);
+                      false
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Value;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<LogPatternsConfig>();
+    }
+
+    async fn run(config: &LogPatternsConfig, messages: &[&str]) -> Vec<Event> {
+        let transform = config
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_task();
+        let events = messages
+            .iter()
+            .map(|message| Event::from(LogEvent::from(*message)))
+            .collect::<Vec<_>>();
+
+        transform
+            .transform_events(Box::pin(stream::iter(events)))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn attaches_patterns() {
+        let output = run(
+            &LogPatternsConfig::default(),
+            &[
+                "worker 1 started job 42",
+                "worker 2 started job 43",
+                "disk full",
+            ],
+        )
+        .await;
+
+        let patterns = output
+            .iter()
+            .map(|event| {
+                let log = event.as_log();
+                (
+                    log["pattern_id"].to_string_lossy(),
+                    log["pattern"].to_string_lossy(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(patterns[0].1, "worker 1 started job 42");
+        assert_eq!(patterns[1].1, "worker <*> started job <*>");
+        assert_eq!(patterns[0].0, patterns[1].0);
+        assert_ne!(patterns[2].0, patterns[0].0);
+        assert_eq!(output[2].as_log()["message"], Value::from("disk full"));
+    }
+
+    #[tokio::test]
+    async fn emits_pattern_metrics() {
+        let config = LogPatternsConfig {
+            metrics_interval_secs: Some(3600),
+            ..LogPatternsConfig::default()
+        };
+        let output = run(&config, &["cache miss", "cache hit", "cache miss"]).await;
+        let id = output[0].as_log()["pattern_id"].to_string_lossy();
+
+        let counts = output
+            .iter()
+            .filter_map(|event| match event {
+                Event::Metric(metric) => Some(metric),
+                _ => None,
+            })
+            .filter(|metric| metric.name() == "log_pattern_events_total")
+            .map(|metric| (metric.tags().unwrap().clone(), metric.value().clone()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            counts,
+            vec![(
+                BTreeMap::from([("pattern_id".to_owned(), id)]),
+                MetricValue::Counter { value: 3.0 }
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn evicts_least_recently_seen_patterns() {
+        let config = LogPatternsConfig {
+            max_patterns: 1,
+            metrics_interval_secs: Some(3600),
+            ..LogPatternsConfig::default()
+        };
+        let output = run(&config, &["cache miss", "disk full", "disk full"]).await;
+        let id = output[1].as_log()["pattern_id"].to_string_lossy();
+
+        let patterns = output
+            .iter()
+            .filter_map(|event| match event {
+                Event::Metric(metric) if metric.name() == "log_pattern_events_total" => Some((
+                    metric.tags().unwrap()["pattern_id"].clone(),
+                    metric.value().clone(),
+                )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(patterns, vec![(id, MetricValue::Counter { value: 2.0 })]);
+    }
+}
@@ -1,6 +1,14 @@
-use std::{collections::BTreeMap, convert::TryFrom, num::ParseFloatError};
+use std::{
+    collections::{btree_map, BTreeMap, VecDeque},
+    convert::TryFrom,
+    num::{NonZeroU32, ParseFloatError},
+    pin::Pin,
+    time::Duration,
+};
 
+use async_stream::stream;
 use chrono::Utc;
+use futures::{Stream, StreamExt};
 use indexmap::IndexMap;
 use vector_config::configurable_component;
 
@@ -9,8 +17,8 @@ use crate::{
         log_schema, DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext,
     },
     event::{
-        metric::{Metric, MetricKind, MetricValue, StatisticKind},
-        Event, Value,
+        metric::{Metric, MetricData, MetricKind, MetricSeries, MetricValue, StatisticKind},
+        Event, EventMetadata, Value,
     },
     internal_events::{
         LogToMetricFieldNullError, LogToMetricParseFloatError, LogToMetricTemplateParseError,
@@ -18,7 +26,7 @@ use crate::{
     },
     schema,
     template::{Template, TemplateParseError, TemplateRenderingError},
-    transforms::{FunctionTransform, OutputBuffer, Transform},
+    transforms::{FunctionTransform, OutputBuffer, TaskTransform, Transform},
 };
 
 /// Configuration for the `log_to_metric` transform.
@@ -28,6 +36,65 @@ use crate::{
 pub struct LogToMetricConfig {
     /// A list of metrics to generate.
     pub metrics: Vec<MetricConfig>,
+
+    /// Aggregates the generated metrics inside the transform, over a window.
+    ///
+    /// When unset, a metric is emitted for each metric configuration matching each event.
+    #[configurable(derived)]
+    pub window: Option<WindowConfig>,
+}
+
+/// Configuration for aggregating the generated metrics over a window.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WindowConfig {
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: WindowMode,
+
+    /// The length of the window, in seconds.
+    #[serde(default = "default_window_length_secs")]
+    pub length_secs: u64,
+
+    /// The interval at which sliding windows are emitted, in seconds.
+    ///
+    /// Required for sliding windows, and must divide `length_secs`.
+    pub step_secs: Option<u64>,
+
+    /// Whether to emit histograms and summaries as sketches.
+    ///
+    /// Values are inserted into the sketch of their series as they are received, which bounds the
+    /// memory used by the window, and the size of the emitted metrics.
+    #[serde(default)]
+    pub sketches: bool,
+}
+
+/// The type of window metrics are aggregated over.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WindowMode {
+    /// Consecutive windows, which don't overlap.
+    ///
+    /// The metrics of each series are emitted once per window, with their original kind.
+    Tumbling,
+
+    /// Windows covering the last `length_secs`, emitted every `step_secs`.
+    ///
+    /// As consecutive windows overlap, metrics are emitted as absolute values, and counters as
+    /// gauges.
+    Sliding,
+}
+
+impl Default for WindowMode {
+    fn default() -> Self {
+        Self::Tumbling
+    }
+}
+
+const fn default_window_length_secs() -> u64 {
+    60
 }
 
 /// Specification of a counter derived from a log event.
@@ -190,6 +257,7 @@ impl GenerateConfig for LogToMetricConfig {
                 kind: MetricKind::Incremental,
                 tags: None,
             })],
+            window: None,
         })
         .unwrap()
     }
@@ -198,7 +266,12 @@ impl GenerateConfig for LogToMetricConfig {
 #[async_trait::async_trait]
 impl TransformConfig for LogToMetricConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(LogToMetric::new(self.clone())))
+        match &self.window {
+            None => Ok(Transform::function(LogToMetric::new(self.clone()))),
+            Some(window) => {
+                WindowedLogToMetric::new(self.metrics.clone(), window).map(Transform::event_task)
+            }
+        }
     }
 
     fn input(&self) -> Input {
@@ -210,7 +283,7 @@ impl TransformConfig for LogToMetricConfig {
     }
 
     fn enable_concurrency(&self) -> bool {
-        true
+        self.window.is_none()
     }
 }
 
@@ -447,42 +520,227 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
     }
 }
 
+/// Generates the metrics of `configs` for `event`, emitting an internal event for each failure.
+fn to_metrics<'a>(
+    configs: &'a [MetricConfig],
+    event: &'a Event,
+) -> impl Iterator<Item = Metric> + 'a {
+    configs
+        .iter()
+        .filter_map(move |config| match to_metric(config, event) {
+            Ok(metric) => Some(metric),
+            Err(TransformError::FieldNull { field }) => {
+                emit!(LogToMetricFieldNullError {
+                    field: field.as_ref()
+                });
+                None
+            }
+            Err(TransformError::FieldNotFound { field }) => {
+                emit!(ParserMissingFieldError::<DROP_EVENT> {
+                    field: field.as_ref()
+                });
+                None
+            }
+            Err(TransformError::ParseFloatError { field, error }) => {
+                emit!(LogToMetricParseFloatError {
+                    field: field.as_ref(),
+                    error
+                });
+                None
+            }
+            Err(TransformError::TemplateRenderingError(error)) => {
+                emit!(crate::internal_events::TemplateRenderingError {
+                    error,
+                    drop_event: false,
+                    field: None,
+                });
+                None
+            }
+            Err(TransformError::TemplateParseError(error)) => {
+                emit!(LogToMetricTemplateParseError { error });
+                None
+            }
+        })
+}
+
 impl FunctionTransform for LogToMetric {
     fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
-        for config in self.config.metrics.iter() {
-            match to_metric(config, &event) {
-                Ok(metric) => {
-                    output.push(Event::Metric(metric));
-                }
-                Err(TransformError::FieldNull { field }) => emit!(LogToMetricFieldNullError {
-                    field: field.as_ref()
-                }),
-                Err(TransformError::FieldNotFound { field }) => {
-                    emit!(ParserMissingFieldError::<DROP_EVENT> {
-                        field: field.as_ref()
-                    })
-                }
-                Err(TransformError::ParseFloatError { field, error }) => {
-                    emit!(LogToMetricParseFloatError {
-                        field: field.as_ref(),
-                        error
-                    })
+        for metric in to_metrics(&self.config.metrics, &event) {
+            output.push(Event::Metric(metric));
+        }
+    }
+}
+
+/// The metrics generated over a period of time, by series.
+type Bucket = BTreeMap<MetricSeries, (MetricData, EventMetadata)>;
+
+/// Adds a metric to `bucket`, summing incremental values and keeping the latest absolute ones.
+fn add_to_bucket(
+    bucket: &mut Bucket,
+    series: MetricSeries,
+    data: MetricData,
+    metadata: EventMetadata,
+) {
+    match bucket.entry(series) {
+        btree_map::Entry::Occupied(mut entry) => {
+            let (existing, existing_metadata) = entry.get_mut();
+            // Metrics whose value type changed replace the previous value.
+            if data.kind == MetricKind::Absolute || !existing.update(&data) {
+                *existing = data;
+            }
+            existing_metadata.merge(metadata);
+        }
+        btree_map::Entry::Vacant(entry) => {
+            entry.insert((data, metadata));
+        }
+    }
+}
+
+pub struct WindowedLogToMetric {
+    metrics: Vec<MetricConfig>,
+    mode: WindowMode,
+    length: Duration,
+    step: Duration,
+    sketches: bool,
+    /// The buckets of the window, oldest first. The last bucket is the one being filled.
+    buckets: VecDeque<Bucket>,
+    buckets_per_window: usize,
+}
+
+impl WindowedLogToMetric {
+    pub fn new(metrics: Vec<MetricConfig>, config: &WindowConfig) -> crate::Result<Self> {
+        if config.length_secs == 0 {
+            return Err("`window.length_secs` must be non-zero".into());
+        }
+
+        let step_secs = match (config.mode, config.step_secs) {
+            (WindowMode::Tumbling, _) => config.length_secs,
+            (WindowMode::Sliding, Some(step_secs))
+                if step_secs > 0 && config.length_secs % step_secs == 0 =>
+            {
+                step_secs
+            }
+            (WindowMode::Sliding, _) => {
+                return Err(
+                    "sliding windows require a non-zero `window.step_secs` dividing `window.length_secs`"
+                        .into(),
+                )
+            }
+        };
+
+        Ok(Self {
+            metrics,
+            mode: config.mode,
+            length: Duration::from_secs(config.length_secs),
+            step: Duration::from_secs(step_secs),
+            sketches: config.sketches,
+            buckets: VecDeque::from([Bucket::new()]),
+            buckets_per_window: (config.length_secs / step_secs) as usize,
+        })
+    }
+
+    fn record(&mut self, event: &Event) {
+        let bucket = self
+            .buckets
+            .back_mut()
+            .expect("there must be a bucket being filled");
+
+        for metric in to_metrics(&self.metrics, event) {
+            let sketch = self
+                .sketches
+                .then(|| metric.value().distribution_to_sketch())
+                .flatten();
+            let metric = match sketch {
+                Some(sketch) => metric.with_value(sketch),
+                None => metric,
+            };
+            let (series, data, metadata) = metric.into_parts();
+            add_to_bucket(bucket, series, data, metadata);
+        }
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let window = match self.mode {
+            WindowMode::Tumbling => std::mem::take(
+                self.buckets
+                    .back_mut()
+                    .expect("there must be a bucket being filled"),
+            ),
+            WindowMode::Sliding => {
+                let mut window = Bucket::new();
+                for bucket in &mut self.buckets {
+                    for (series, (data, metadata)) in bucket.iter_mut() {
+                        // Events are acknowledged with the first window they're part of.
+                        let finalizers = metadata.take_finalizers();
+                        let mut metadata = metadata.clone();
+                        metadata.merge_finalizers(finalizers);
+                        add_to_bucket(&mut window, series.clone(), data.clone(), metadata);
+                    }
                 }
-                Err(TransformError::TemplateRenderingError(error)) => {
-                    emit!(crate::internal_events::TemplateRenderingError {
-                        error,
-                        drop_event: false,
-                        field: None,
-                    })
+
+                self.buckets.push_back(Bucket::new());
+                while self.buckets.len() > self.buckets_per_window {
+                    self.buckets.pop_front();
                 }
-                Err(TransformError::TemplateParseError(error)) => {
-                    emit!(LogToMetricTemplateParseError { error })
+                window
+            }
+        };
+
+        let timestamp = Utc::now();
+        let interval_ms = u32::try_from(self.length.as_millis())
+            .ok()
+            .and_then(NonZeroU32::new);
+        for (series, (mut data, metadata)) in window {
+            data.time.timestamp = Some(timestamp);
+            data.time.interval_ms = interval_ms;
+            if self.mode == WindowMode::Sliding {
+                data.kind = MetricKind::Absolute;
+                if let MetricValue::Counter { value } = data.value {
+                    data.value = MetricValue::Gauge { value };
                 }
             }
+            output.push(Event::Metric(Metric::from_parts(series, data, metadata)));
         }
     }
 }
 
+impl TaskTransform<Event> for WindowedLogToMetric {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream =
+            tokio::time::interval_at(tokio::time::Instant::now() + self.step, self.step);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(&event),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::{offset::TimeZone, DateTime, Utc};
@@ -491,7 +749,7 @@ mod tests {
     use crate::{
         config::log_schema,
         event::{
-            metric::{Metric, MetricKind, MetricValue, StatisticKind},
+            metric::{Metric, MetricKind, MetricSketch, MetricValue, StatisticKind},
             Event, LogEvent,
         },
         transforms::test::transform_one,
@@ -970,4 +1228,152 @@ mod tests {
             .with_timestamp(Some(ts()))
         );
     }
+
+    fn counts(output: &[Event]) -> Vec<(String, MetricKind, MetricValue)> {
+        output
+            .iter()
+            .map(|event| {
+                let metric = event.as_metric();
+                (
+                    metric.tag_value("status").unwrap_or_default(),
+                    metric.kind(),
+                    metric.value().clone(),
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn tumbling_window() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "counter"
+            field = "status"
+            tags = {status = "{{status}}"}
+
+            [window]
+            length_secs = 60
+            "#,
+        );
+        let transform = config
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_task();
+
+        let events = vec![
+            create_event("status", "200"),
+            create_event("status", "404"),
+            create_event("status", "200"),
+        ];
+        let output = transform
+            .transform_events(Box::pin(futures::stream::iter(events)))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            counts(&output),
+            vec![
+                (
+                    "200".to_owned(),
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 2.0 }
+                ),
+                (
+                    "404".to_owned(),
+                    MetricKind::Incremental,
+                    MetricValue::Counter { value: 1.0 }
+                ),
+            ]
+        );
+        assert_eq!(output[0].as_metric().interval_ms(), NonZeroU32::new(60_000));
+    }
+
+    #[test]
+    fn sliding_window() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "counter"
+            field = "status"
+            tags = {status = "{{status}}"}
+            "#,
+        );
+        let window = WindowConfig {
+            mode: WindowMode::Sliding,
+            length_secs: 120,
+            step_secs: Some(60),
+            sketches: false,
+        };
+        let mut transform = WindowedLogToMetric::new(config.metrics, &window).unwrap();
+        let flush = |transform: &mut WindowedLogToMetric| {
+            let mut output = Vec::new();
+            transform.flush_into(&mut output);
+            counts(&output)
+        };
+        let gauge = |value| {
+            vec![(
+                "200".to_owned(),
+                MetricKind::Absolute,
+                MetricValue::Gauge { value },
+            )]
+        };
+
+        transform.record(&create_event("status", "200"));
+        assert_eq!(flush(&mut transform), gauge(1.0));
+
+        transform.record(&create_event("status", "200"));
+        transform.record(&create_event("status", "200"));
+        assert_eq!(flush(&mut transform), gauge(3.0));
+        assert_eq!(flush(&mut transform), gauge(2.0));
+        assert_eq!(flush(&mut transform), vec![]);
+    }
+
+    #[test]
+    fn window_validation() {
+        let window = |mode, step_secs| WindowConfig {
+            mode,
+            length_secs: 60,
+            step_secs,
+            sketches: false,
+        };
+
+        assert!(WindowedLogToMetric::new(vec![], &window(WindowMode::Tumbling, None)).is_ok());
+        assert!(WindowedLogToMetric::new(vec![], &window(WindowMode::Sliding, Some(15))).is_ok());
+        assert!(WindowedLogToMetric::new(vec![], &window(WindowMode::Sliding, None)).is_err());
+        assert!(WindowedLogToMetric::new(vec![], &window(WindowMode::Sliding, Some(7))).is_err());
+    }
+
+    #[test]
+    fn window_sketches() {
+        let config = parse_config(
+            r#"
+            [[metrics]]
+            type = "summary"
+            field = "response_time"
+            "#,
+        );
+        let window = WindowConfig {
+            mode: WindowMode::Tumbling,
+            length_secs: 60,
+            step_secs: None,
+            sketches: true,
+        };
+        let mut transform = WindowedLogToMetric::new(config.metrics, &window).unwrap();
+
+        for value in ["2.5", "3.5", "10"] {
+            transform.record(&create_event("response_time", value));
+        }
+        let mut output = Vec::new();
+        transform.flush_into(&mut output);
+
+        assert_eq!(output.len(), 1);
+        match output[0].as_metric().value() {
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(sketch),
+            } => assert_eq!(sketch.count(), 3),
+            value => panic!("unexpected value: {:?}", value),
+        }
+    }
 }
@@ -14,6 +14,12 @@ pub mod dedupe;
 pub mod filter;
 #[cfg(feature = "transforms-geoip")]
 pub mod geoip;
+#[cfg(feature = "transforms-join")]
+pub mod join;
+#[cfg(feature = "transforms-json_schema")]
+pub mod json_schema;
+#[cfg(feature = "transforms-log_patterns")]
+pub mod log_patterns;
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
@@ -23,6 +29,8 @@ pub mod metric_to_log;
 pub mod pipelines;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
+#[cfg(feature = "transforms-relabel")]
+pub mod relabel;
 #[cfg(feature = "transforms-remap")]
 pub mod remap;
 #[cfg(feature = "transforms-route")]
@@ -33,6 +41,8 @@ pub mod sample;
 pub mod tag_cardinality_limit;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-trace_sample")]
+pub mod trace_sample;
 
 use vector_common::config::ComponentKey;
 use vector_config::{configurable_component, NamedComponent};
@@ -82,6 +92,23 @@ pub enum Transforms {
     #[cfg(feature = "transforms-geoip")]
     Geoip(#[configurable(derived)] geoip::GeoipConfig),
 
+    /// Join.
+    #[cfg(feature = "transforms-join")]
+    Join(#[configurable(derived)] join::JoinConfig),
+
+    /// Join. (inner)
+    #[cfg(feature = "transforms-join")]
+    #[configurable(metadata(skip_docs))]
+    JoinStage(#[configurable(derived)] join::JoinStageConfig),
+
+    /// JSON Schema.
+    #[cfg(feature = "transforms-json_schema")]
+    JsonSchema(#[configurable(derived)] json_schema::JsonSchemaConfig),
+
+    /// Log patterns.
+    #[cfg(feature = "transforms-log_patterns")]
+    LogPatterns(#[configurable(derived)] log_patterns::LogPatternsConfig),
+
     /// Log to metric.
     LogToMetric(#[configurable(derived)] log_to_metric::LogToMetricConfig),
 
@@ -106,6 +133,10 @@ pub enum Transforms {
     #[cfg(feature = "transforms-reduce")]
     Reduce(#[configurable(derived)] reduce::ReduceConfig),
 
+    /// Relabel.
+    #[cfg(feature = "transforms-relabel")]
+    Relabel(#[configurable(derived)] relabel::RelabelConfig),
+
     /// Remap.
     #[cfg(feature = "transforms-remap")]
     Remap(#[configurable(derived)] remap::RemapConfig),
@@ -133,6 +164,10 @@ pub enum Transforms {
     /// Throttle.
     #[cfg(feature = "transforms-throttle")]
     Throttle(#[configurable(derived)] throttle::ThrottleConfig),
+
+    /// Trace sample.
+    #[cfg(feature = "transforms-trace_sample")]
+    TraceSample(#[configurable(derived)] trace_sample::TraceSampleConfig),
 }
 
 // We can't use `enum_dispatch` here because it doesn't support associated constants.
@@ -151,6 +186,14 @@ impl NamedComponent for Transforms {
             Transforms::Filter(config) => config.get_component_name(),
             #[cfg(feature = "transforms-geoip")]
             Transforms::Geoip(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-join")]
+            Transforms::Join(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-join")]
+            Transforms::JoinStage(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-json_schema")]
+            Transforms::JsonSchema(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-log_patterns")]
+            Transforms::LogPatterns(config) => config.get_component_name(),
             Transforms::LogToMetric(config) => config.get_component_name(),
             #[cfg(feature = "transforms-lua")]
             Transforms::Lua(config) => config.get_component_name(),
@@ -162,6 +205,8 @@ impl NamedComponent for Transforms {
             Transforms::Pipelines(config) => config.get_component_name(),
             #[cfg(feature = "transforms-reduce")]
             Transforms::Reduce(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-relabel")]
+            Transforms::Relabel(config) => config.get_component_name(),
             #[cfg(feature = "transforms-remap")]
             Transforms::Remap(config) => config.get_component_name(),
             #[cfg(feature = "transforms-route")]
@@ -176,6 +221,8 @@ impl NamedComponent for Transforms {
             Transforms::TestNoop(config) => config.get_component_name(),
             #[cfg(feature = "transforms-throttle")]
             Transforms::Throttle(config) => config.get_component_name(),
+            #[cfg(feature = "transforms-trace_sample")]
+            Transforms::TraceSample(config) => config.get_component_name(),
             #[allow(unreachable_patterns)]
             _ => unimplemented!(),
         }
@@ -0,0 +1,483 @@
+use std::collections::BTreeMap;
+
+use md5::{Digest, Md5};
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, Metric},
+    internal_events::RelabelEventsDropped,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// The label holding the name of the metric, as in Prometheus.
+const NAME_LABEL: &str = "__name__";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid regex in relabel rule {}: {}", index, source))]
+    InvalidRegex { index: usize, source: regex::Error },
+
+    #[snafu(display(
+        "Relabel rule {} requires a `target_label` for the `{}` action",
+        index,
+        action
+    ))]
+    MissingTargetLabel { index: usize, action: &'static str },
+
+    #[snafu(display(
+        "Relabel rule {} requires a non-zero `modulus` for the `hashmod` action",
+        index
+    ))]
+    MissingModulus { index: usize },
+}
+
+/// Configuration for the `relabel` transform.
+#[configurable_component(transform("relabel"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelConfig {
+    /// The relabeling rules, applied in order.
+    ///
+    /// The rules follow the format of the Prometheus `metric_relabel_configs` option, so that they
+    /// can be copied from a Prometheus configuration as is.
+    #[serde(alias = "metric_relabel_configs")]
+    pub relabel_configs: Vec<RelabelRuleConfig>,
+}
+
+/// A relabeling rule.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RelabelRuleConfig {
+    /// The labels whose values are concatenated and matched against `regex`.
+    ///
+    /// The name of the metric is available as the `__name__` label. Missing labels are treated as
+    /// empty strings.
+    #[serde(default)]
+    pub source_labels: Vec<String>,
+
+    /// The separator placed between the values of `source_labels`.
+    #[serde(default = "default_separator")]
+    pub separator: String,
+
+    /// The label the result of the `replace` and `hashmod` actions is written to.
+    ///
+    /// References to the capture groups of `regex` are expanded for the `replace` action.
+    pub target_label: Option<String>,
+
+    /// The regular expression the concatenated values are matched against.
+    ///
+    /// As in Prometheus, the expression is anchored on both ends.
+    #[serde(default = "default_regex")]
+    pub regex: String,
+
+    /// The modulus applied to the hash of the concatenated values by the `hashmod` action.
+    pub modulus: Option<u64>,
+
+    /// The value written by the `replace` and `labelmap` actions.
+    ///
+    /// References to the capture groups of `regex`, such as `$1` or `${name}`, are expanded.
+    #[serde(default = "default_replacement")]
+    pub replacement: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub action: RelabelAction,
+}
+
+fn default_separator() -> String {
+    ";".to_owned()
+}
+
+fn default_regex() -> String {
+    "(.*)".to_owned()
+}
+
+fn default_replacement() -> String {
+    "$1".to_owned()
+}
+
+/// The action of a relabeling rule.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelabelAction {
+    /// Write `replacement` to `target_label` when `regex` matches the concatenated values.
+    ///
+    /// When `replacement` expands to an empty string, `target_label` is removed instead.
+    Replace,
+
+    /// Drop metrics for which `regex` doesn't match the concatenated values.
+    Keep,
+
+    /// Drop metrics for which `regex` matches the concatenated values.
+    Drop,
+
+    /// Write the hash of the concatenated values modulo `modulus` to `target_label`.
+    HashMod,
+
+    /// Copy the labels whose name matches `regex` to the labels named by `replacement`.
+    LabelMap,
+
+    /// Remove the labels whose name matches `regex`.
+    LabelDrop,
+
+    /// Remove the labels whose name doesn't match `regex`.
+    LabelKeep,
+}
+
+impl Default for RelabelAction {
+    fn default() -> Self {
+        Self::Replace
+    }
+}
+
+impl RelabelAction {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Replace => "replace",
+            Self::Keep => "keep",
+            Self::Drop => "drop",
+            Self::HashMod => "hashmod",
+            Self::LabelMap => "labelmap",
+            Self::LabelDrop => "labeldrop",
+            Self::LabelKeep => "labelkeep",
+        }
+    }
+}
+
+impl GenerateConfig for RelabelConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"[[relabel_configs]]
+            source_labels = ["__name__"]
+            regex = "go_.*"
+            action = "drop""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for RelabelConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(Relabel::new(&self.relabel_configs)?))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Metric)]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    source_labels: Vec<String>,
+    separator: String,
+    target_label: String,
+    regex: Regex,
+    modulus: u64,
+    replacement: String,
+    action: RelabelAction,
+}
+
+impl Rule {
+    fn new(index: usize, config: &RelabelRuleConfig) -> Result<Self, BuildError> {
+        let regex =
+            Regex::new(&format!("^(?:{})$", config.regex)).context(InvalidRegexSnafu { index })?;
+
+        let target_label = match (config.action, &config.target_label) {
+            (RelabelAction::Replace | RelabelAction::HashMod, None) => {
+                return Err(BuildError::MissingTargetLabel {
+                    index,
+                    action: config.action.as_str(),
+                })
+            }
+            (_, target_label) => target_label.clone().unwrap_or_default(),
+        };
+
+        let modulus = match (config.action, config.modulus) {
+            (RelabelAction::HashMod, None | Some(0)) => {
+                return Err(BuildError::MissingModulus { index })
+            }
+            (_, modulus) => modulus.unwrap_or_default(),
+        };
+
+        Ok(Self {
+            source_labels: config.source_labels.clone(),
+            separator: config.separator.clone(),
+            target_label,
+            regex,
+            modulus,
+            replacement: config.replacement.clone(),
+            action: config.action,
+        })
+    }
+
+    /// Applies the rule to `labels`, returning `false` if the metric must be dropped.
+    fn apply(&self, labels: &mut BTreeMap<String, String>) -> bool {
+        let value = || {
+            self.source_labels
+                .iter()
+                .map(|name| labels.get(name).map(String::as_str).unwrap_or_default())
+                .collect::<Vec<_>>()
+                .join(&self.separator)
+        };
+
+        match self.action {
+            RelabelAction::Replace => {
+                let value = value();
+                if let Some(captures) = self.regex.captures(&value) {
+                    let mut target = String::new();
+                    captures.expand(&self.target_label, &mut target);
+                    if is_valid_label_name(&target) {
+                        let mut replacement = String::new();
+                        captures.expand(&self.replacement, &mut replacement);
+                        if replacement.is_empty() {
+                            labels.remove(&target);
+                        } else {
+                            labels.insert(target, replacement);
+                        }
+                    }
+                }
+            }
+            RelabelAction::Keep => return self.regex.is_match(&value()),
+            RelabelAction::Drop => return !self.regex.is_match(&value()),
+            RelabelAction::HashMod => {
+                // Same as Prometheus: the last 8 bytes of the MD5 digest, read as big endian.
+                let digest = Md5::digest(value().as_bytes());
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(&digest[8..]);
+                let hash = u64::from_be_bytes(bytes) % self.modulus;
+                labels.insert(self.target_label.clone(), hash.to_string());
+            }
+            RelabelAction::LabelMap => {
+                let mapped = labels
+                    .iter()
+                    .filter(|(name, _)| self.regex.is_match(name))
+                    .map(|(name, value)| {
+                        let name = self.regex.replace(name, self.replacement.as_str());
+                        (name.into_owned(), value.clone())
+                    })
+                    .collect::<Vec<_>>();
+                labels.extend(mapped);
+            }
+            RelabelAction::LabelDrop => labels.retain(|name, _| !self.regex.is_match(name)),
+            RelabelAction::LabelKeep => labels.retain(|name, _| self.regex.is_match(name)),
+        }
+
+        true
+    }
+}
+
+/// Whether `name` is a valid Prometheus label name.
+fn is_valid_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map_or(false, |first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[derive(Clone, Debug)]
+pub struct Relabel {
+    rules: Vec<Rule>,
+}
+
+impl Relabel {
+    fn new(configs: &[RelabelRuleConfig]) -> crate::Result<Self> {
+        let rules = configs
+            .iter()
+            .enumerate()
+            .map(|(index, config)| Rule::new(index, config))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { rules })
+    }
+
+    /// Relabels `metric`, returning `None` if it's dropped by a rule or loses its name.
+    fn relabel(&self, metric: Metric) -> Option<Metric> {
+        let mut labels = metric.tags().cloned().unwrap_or_default();
+        labels.insert(NAME_LABEL.to_owned(), metric.name().to_owned());
+
+        for rule in &self.rules {
+            if !rule.apply(&mut labels) {
+                return None;
+            }
+        }
+
+        let name = labels.remove(NAME_LABEL).filter(|name| !name.is_empty())?;
+        let tags = if labels.is_empty() {
+            None
+        } else {
+            Some(labels)
+        };
+        Some(metric.with_name(name).with_tags(tags))
+    }
+}
+
+impl FunctionTransform for Relabel {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        match self.relabel(event.into_metric()) {
+            Some(metric) => output.push(Event::Metric(metric)),
+            None => emit!(RelabelEventsDropped),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::metric::{MetricKind, MetricValue},
+        transforms::test::transform_one,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RelabelConfig>();
+    }
+
+    fn relabel(rules: &str, name: &str, tags: &[(&str, &str)]) -> Option<Metric> {
+        let config: RelabelConfig = serde_yaml::from_str(rules).unwrap();
+        let mut transform = Relabel::new(&config.relabel_configs).unwrap();
+
+        let tags = tags
+            .iter()
+            .map(|(name, value)| ((*name).to_owned(), (*value).to_owned()))
+            .collect();
+        let metric = Metric::new(
+            name,
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.0 },
+        )
+        .with_tags(Some(tags));
+
+        transform_one(&mut transform, metric.into()).map(Event::into_metric)
+    }
+
+    fn tags(metric: &Metric) -> Vec<(&str, &str)> {
+        metric
+            .tags()
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn keep_and_drop() {
+        let rules = r#"
+            metric_relabel_configs:
+              - source_labels: [__name__]
+                regex: "go_.*"
+                action: drop
+              - source_labels: [job, instance]
+                regex: "node;.*"
+                action: keep
+        "#;
+
+        assert!(relabel(rules, "go_goroutines", &[("job", "node")]).is_none());
+        assert!(relabel(rules, "up", &[("job", "api")]).is_none());
+        assert!(relabel(rules, "up", &[("job", "node"), ("instance", "a")]).is_some());
+        // Anchored: the regex must match the whole value.
+        assert!(relabel(rules, "up_go_x", &[("job", "node")]).is_some());
+    }
+
+    #[test]
+    fn replace() {
+        let rules = r#"
+            relabel_configs:
+              - source_labels: [instance]
+                regex: "([^:]+):\\d+"
+                target_label: host
+              - source_labels: [__name__]
+                regex: "node_(.*)"
+                target_label: __name__
+                replacement: "host_$1"
+              - source_labels: [missing]
+                target_label: instance
+        "#;
+
+        let metric = relabel(rules, "node_load1", &[("instance", "web-1:9100")]).unwrap();
+
+        assert_eq!(metric.name(), "host_load1");
+        assert_eq!(tags(&metric), vec![("host", "web-1")]);
+    }
+
+    #[test]
+    fn hashmod() {
+        let rules = r#"
+            relabel_configs:
+              - source_labels: [instance]
+                modulus: 8
+                target_label: __tmp_hash
+                action: hashmod
+        "#;
+
+        let first = relabel(rules, "up", &[("instance", "web-1:9100")]).unwrap();
+        let second = relabel(rules, "up", &[("instance", "web-1:9100")]).unwrap();
+        let hash = first.tag_value("__tmp_hash").unwrap();
+
+        assert!(hash.parse::<u64>().unwrap() < 8);
+        assert_eq!(second.tag_value("__tmp_hash"), Some(hash));
+    }
+
+    #[test]
+    fn label_actions() {
+        let rules = r#"
+            relabel_configs:
+              - regex: "__meta_(.+)"
+                action: labelmap
+              - regex: "__meta_.*"
+                action: labeldrop
+              - regex: "__name__|zone|pod"
+                action: labelkeep
+        "#;
+
+        let metric = relabel(
+            rules,
+            "up",
+            &[("__meta_zone", "eu"), ("__meta_pod", "p1"), ("other", "x")],
+        )
+        .unwrap();
+
+        assert_eq!(metric.name(), "up");
+        assert_eq!(tags(&metric), vec![("pod", "p1"), ("zone", "eu")]);
+    }
+
+    #[test]
+    fn drops_metrics_without_name() {
+        let rules = r#"
+            relabel_configs:
+              - regex: "__name__"
+                action: labeldrop
+        "#;
+
+        assert!(relabel(rules, "up", &[]).is_none());
+    }
+
+    #[test]
+    fn validates_rules() {
+        let rules = |rules: &str| {
+            let config: RelabelConfig = serde_yaml::from_str(rules).unwrap();
+            Relabel::new(&config.relabel_configs).map(|_| ())
+        };
+
+        assert!(rules("relabel_configs: [{regex: \"(\"}]").is_err());
+        assert!(rules("relabel_configs: [{action: replace}]").is_err());
+        assert!(rules("relabel_configs: [{action: hashmod, target_label: a}]").is_err());
+        assert!(rules("relabel_configs: [{action: labeldrop, regex: a}]").is_ok());
+    }
+}
@@ -4,10 +4,13 @@ use std::{
     fs::File,
     io::{self, Read},
     path::PathBuf,
+    time::Duration,
 };
 
+use chrono::{DateTime, Utc};
 use lookup::lookup_v2::ValuePath;
 use lookup::{metadata_path, path, PathPrefix};
+use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use value::Kind;
 use vector_common::TimeZone;
@@ -17,11 +20,12 @@ use vector_core::config::LogNamespace;
 use vector_core::schema::Definition;
 
 use vector_vrl_functions::set_semantic_meaning::MeaningList;
+use vector_vrl_functions::state_store::{PersistedEntry, StateLimits, StateStore};
 use vrl::prelude::state::TypeState;
 use vrl::{
     diagnostic::{Formatter, Note},
     prelude::{DiagnosticMessage, ExpressionError},
-    CompileConfig, Program, Runtime, Terminate, VrlRuntime,
+    CompileConfig, Cost, Program, Runtime, Terminate, VrlRuntime,
 };
 
 use crate::{
@@ -47,6 +51,9 @@ pub struct RemapConfig {
     ///
     /// Required if `file` is missing.
     ///
+    /// Functions are shared between `remap` transforms by defining them in a VRL file, imported by
+    /// each program with an `import` statement.
+    ///
     /// [vrl]: https://vector.dev/docs/reference/vrl
     pub source: Option<String>,
 
@@ -108,9 +115,78 @@ pub struct RemapConfig {
     /// fields describing why the event was dropped.
     pub reroute_dropped: bool,
 
+    /// The directory relative paths in VRL `import` statements are resolved against.
+    ///
+    /// By default, it's the directory of the configuration file defining the transform.
+    pub import_root: Option<PathBuf>,
+
     #[configurable(derived)]
     #[serde(default)]
     pub runtime: VrlRuntime,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub state: StateConfig,
+}
+
+/// Configuration of the state kept across events by the `state_*` VRL functions.
+///
+/// The state is scoped to the transform, and shared by all events it processes.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct StateConfig {
+    /// The maximum number of keys in the state.
+    ///
+    /// When the limit is reached, expired keys are dropped first, followed by the least recently
+    /// written ones.
+    pub max_keys: usize,
+
+    /// The maximum estimated size of the state, in bytes.
+    ///
+    /// When the limit is reached, expired keys are dropped first, followed by the least recently
+    /// written ones.
+    pub max_bytes: usize,
+
+    /// Whether the state is persisted while the transform runs and when it shuts down, and restored
+    /// when it starts.
+    ///
+    /// The state is stored in a subdirectory of the data directory named after the component ID.
+    /// If Vector is killed or crashes, the changes made since the state was last written are lost.
+    pub persist: bool,
+
+    /// How often the state is persisted while the transform runs, in seconds.
+    pub persist_interval_secs: u64,
+
+    /// The directory used to persist the state.
+    ///
+    /// By default, the [global `data_dir` option][global_data_dir] is used.
+    ///
+    /// [global_data_dir]: https://vector.dev/docs/reference/configuration/global-options/#data_dir
+    pub data_dir: Option<PathBuf>,
+}
+
+impl Default for StateConfig {
+    fn default() -> Self {
+        let limits = StateLimits::default();
+
+        Self {
+            max_keys: limits.max_keys,
+            max_bytes: limits.max_bytes,
+            persist: false,
+            persist_interval_secs: 60,
+            data_dir: None,
+        }
+    }
+}
+
+impl StateConfig {
+    const fn limits(&self) -> StateLimits {
+        StateLimits {
+            max_keys: self.max_keys,
+            max_bytes: self.max_bytes,
+        }
+    }
 }
 
 impl RemapConfig {
@@ -118,6 +194,8 @@ impl RemapConfig {
         &self,
         enrichment_tables: enrichment::TableRegistry,
         merged_schema_definition: schema::Definition,
+        state_store: StateStore,
+        performance_lints: bool,
     ) -> Result<(
         vrl::Program,
         String,
@@ -154,6 +232,11 @@ impl RemapConfig {
 
         config.set_custom(enrichment_tables);
         config.set_custom(MeaningList::default());
+        config.set_custom(state_store);
+        if let Some(root) = &self.import_root {
+            config.set_import_root(root);
+        }
+        config.set_performance_lints(performance_lints);
 
         compile_vrl(&source, &functions, &state, config)
             .map_err(|diagnostics| {
@@ -171,6 +254,21 @@ impl RemapConfig {
                 )
             })
     }
+
+    /// Estimates the cost of running the program on an event. The costly patterns used by the
+    /// program are returned alongside, as rendered compilation warnings.
+    pub(crate) fn cost_estimate(
+        &self,
+        merged_schema_definition: &schema::Definition,
+    ) -> Result<(Cost, String)> {
+        self.compile_vrl_program(
+            enrichment::TableRegistry::default(),
+            merged_schema_definition.clone(),
+            StateStore::default(),
+            true,
+        )
+        .map(|(program, warnings, _, _)| (program.cost().clone(), warnings))
+    }
 }
 
 impl_generate_config_from_default!(RemapConfig);
@@ -208,6 +306,8 @@ impl TransformConfig for RemapConfig {
             .compile_vrl_program(
                 enrichment::TableRegistry::default(),
                 input_definition.clone(),
+                StateStore::default(),
+                false,
             )
             .map(|(program, _, _, external_context)| {
                 let meaning = external_context
@@ -308,6 +408,137 @@ where
     default_schema_definition: Arc<schema::Definition>,
     dropped_schema_definition: Arc<schema::Definition>,
     runner: Runner,
+    state_persistence: Option<Arc<StatePersistence>>,
+}
+
+/// Persists the state of the `state_*` VRL functions of a transform.
+///
+/// The state is written periodically, and when the last copy of the transform is dropped.
+#[derive(Debug)]
+struct StatePersistence {
+    store: StateStore,
+    path: PathBuf,
+}
+
+/// An entry of the persisted state, as written to disk.
+#[derive(Deserialize, Serialize)]
+struct PersistedStateEntry {
+    key: String,
+    value: value::Value,
+    #[serde(default)]
+    expires_at: Option<DateTime<Utc>>,
+}
+
+impl StatePersistence {
+    const FILE_NAME: &'static str = "vrl_state.json";
+
+    fn new(
+        config: &StateConfig,
+        context: &TransformContext,
+        store: StateStore,
+    ) -> crate::Result<Self> {
+        let id = context.key.as_ref().map_or("remap", ComponentKey::id);
+        let path = context
+            .globals
+            .resolve_and_make_data_subdir(config.data_dir.as_ref(), id)?
+            .join(Self::FILE_NAME);
+
+        Ok(Self { store, path })
+    }
+
+    /// Writes the state every `interval` until the transform is dropped. This requires a Tokio
+    /// runtime, without which the state is only written when the transform is dropped.
+    fn spawn_periodic_persist(self: &Arc<Self>, interval: Duration) {
+        let handle = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => handle,
+            Err(_) => return,
+        };
+        let persistence = Arc::downgrade(self);
+
+        handle.spawn(async move {
+            let start = tokio::time::Instant::now() + interval;
+            let mut interval = tokio::time::interval_at(start, interval);
+
+            loop {
+                interval.tick().await;
+                let persistence = match persistence.upgrade() {
+                    Some(persistence) => persistence,
+                    None => break,
+                };
+
+                let result = tokio::task::spawn_blocking(move || {
+                    let result = persistence.persist();
+                    (persistence.path.clone(), result)
+                })
+                .await;
+                if let Ok((path, Err(error))) = result {
+                    error!(
+                        message = "Failed to persist VRL state.",
+                        ?path,
+                        %error,
+                    );
+                }
+            }
+        });
+    }
+
+    /// Loads the persisted state into the store. A missing file is expected on first start, any
+    /// other failure is logged and results in an empty state.
+    fn restore(&self) {
+        let entries = match std::fs::read(&self.path) {
+            Ok(contents) => serde_json::from_slice::<Vec<PersistedStateEntry>>(&contents)
+                .map_err(|error| error.to_string()),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return,
+            Err(error) => Err(error.to_string()),
+        };
+
+        match entries {
+            Ok(entries) => self
+                .store
+                .restore(entries.into_iter().map(|entry| PersistedEntry {
+                    key: entry.key,
+                    value: entry.value,
+                    expires_at: entry.expires_at.map(Into::into),
+                })),
+            Err(error) => warn!(
+                message = "Failed to restore VRL state, starting with an empty state.",
+                path = ?self.path,
+                %error,
+            ),
+        }
+    }
+
+    fn persist(&self) -> std::result::Result<(), String> {
+        let entries = self
+            .store
+            .snapshot()
+            .into_iter()
+            .map(|entry| PersistedStateEntry {
+                key: entry.key,
+                value: entry.value,
+                expires_at: entry.expires_at.map(Into::into),
+            })
+            .collect::<Vec<_>>();
+
+        let contents = serde_json::to_vec(&entries).map_err(|error| error.to_string())?;
+
+        // Write to a temporary file first, so a crash while writing doesn't corrupt the state.
+        let tmp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, contents).map_err(|error| error.to_string())?;
+        std::fs::rename(&tmp_path, &self.path).map_err(|error| error.to_string())
+    }
+}
+
+impl Drop for StatePersistence {
+    fn drop(&mut self) {
+        if let Err(error) = self.persist() {
+            error!(
+                message = "Failed to persist VRL state.",
+                path = ?self.path,
+                %error,
+            );
+        }
+    }
 }
 
 pub trait VrlRunner {
@@ -350,15 +581,33 @@ impl Remap<AstRunner> {
         config: RemapConfig,
         context: &TransformContext,
     ) -> crate::Result<(Self, String)> {
+        let state_store = StateStore::new(config.state.limits());
+        let state_persistence = if config.state.persist {
+            let persistence = StatePersistence::new(&config.state, context, state_store.clone())?;
+            persistence.restore();
+
+            let persistence = Arc::new(persistence);
+            let interval = Duration::from_secs(config.state.persist_interval_secs.max(1));
+            persistence.spawn_periodic_persist(interval);
+            Some(persistence)
+        } else {
+            None
+        };
+
         let (program, warnings, _, _) = config.compile_vrl_program(
             context.enrichment_tables.clone(),
             context.merged_schema_definition.clone(),
+            state_store,
+            false,
         )?;
 
         let runtime = Runtime::default();
         let runner = AstRunner { runtime };
 
-        Self::new(config, context, program, runner).map(|remap| (remap, warnings))
+        Self::new(config, context, program, runner).map(|mut remap| {
+            remap.state_persistence = state_persistence;
+            (remap, warnings)
+        })
     }
 }
 
@@ -395,6 +644,7 @@ where
             default_schema_definition: Arc::new(default_schema_definition),
             dropped_schema_definition: Arc::new(dropped_schema_definition),
             runner,
+            state_persistence: None,
         })
     }
 
@@ -701,6 +951,73 @@ mod tests {
         assert!(tform.runner().runtime.is_empty());
     }
 
+    #[test]
+    fn check_remap_shares_vrl_state_between_events() {
+        let conf = RemapConfig {
+            source: Some(r#".count = state_incr!("count")"#.to_string()),
+            ..Default::default()
+        };
+        let mut tform = remap(conf).unwrap();
+
+        for count in 1..=3 {
+            let result = transform_one(&mut tform, Event::from(LogEvent::from("event"))).unwrap();
+            assert_eq!(get_field_string(&result, "count"), count.to_string());
+        }
+    }
+
+    #[test]
+    fn check_remap_persists_vrl_state() {
+        let data_dir = crate::test_util::temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+
+        let conf = RemapConfig {
+            source: Some(r#".count = state_incr!("count")"#.to_string()),
+            state: StateConfig {
+                persist: true,
+                data_dir: Some(data_dir),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut tform = remap(conf.clone()).unwrap();
+        let result = transform_one(&mut tform, Event::from(LogEvent::from("event1"))).unwrap();
+        assert_eq!(get_field_string(&result, "count"), "1");
+        drop(tform);
+
+        let mut tform = remap(conf).unwrap();
+        let result = transform_one(&mut tform, Event::from(LogEvent::from("event2"))).unwrap();
+        assert_eq!(get_field_string(&result, "count"), "2");
+    }
+
+    #[tokio::test]
+    async fn check_remap_persists_vrl_state_periodically() {
+        let data_dir = crate::test_util::temp_dir();
+        std::fs::create_dir_all(&data_dir).unwrap();
+        let path = data_dir.join("remap").join(StatePersistence::FILE_NAME);
+
+        let conf = RemapConfig {
+            source: Some(r#".count = state_incr!("count")"#.to_string()),
+            state: StateConfig {
+                persist: true,
+                persist_interval_secs: 1,
+                data_dir: Some(data_dir),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let mut tform = remap(conf).unwrap();
+        transform_one(&mut tform, Event::from(LogEvent::from("event"))).unwrap();
+        assert!(!path.exists());
+
+        // The state is written while the transform is still running.
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains(r#""key":"count""#), "{}", contents);
+        drop(tform);
+    }
+
     #[test]
     fn check_remap_adds() {
         let event = {
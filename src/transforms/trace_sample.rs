@@ -0,0 +1,623 @@
+use std::{
+    collections::{hash_map, BTreeMap, HashMap, VecDeque},
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use vector_config::configurable_component;
+use vector_core::ByteSizeOf;
+
+use crate::{
+    config::{DataType, GenerateConfig, Input, Output, TransformConfig, TransformContext},
+    event::{Event, TraceEvent, Value},
+    internal_events::{
+        TraceSampleBufferLimitReached, TraceSampleBufferSize, TraceSampleDecision,
+        TraceSampleEventsDropped,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+
+const fn default_decision_wait_ms() -> u64 {
+    10_000
+}
+
+const fn default_max_traces() -> usize {
+    50_000
+}
+
+const fn default_max_bytes() -> usize {
+    100 * 1024 * 1024
+}
+
+/// Configuration for the `trace_sample` transform.
+#[configurable_component(transform("trace_sample"))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TraceSampleConfig {
+    /// The period of time to buffer the spans of a trace, in milliseconds, before deciding whether
+    /// the trace is kept.
+    ///
+    /// The wait starts when the first span of a trace is received. Spans received after the
+    /// decision follow it, as long as they are received within another `decision_wait_ms`.
+    #[serde(default = "default_decision_wait_ms")]
+    pub decision_wait_ms: u64,
+
+    /// The maximum number of traces buffered while waiting for a decision.
+    ///
+    /// When reached, the decision for the oldest buffered trace is made early.
+    #[serde(default = "default_max_traces")]
+    pub max_traces: usize,
+
+    /// The maximum size of the events buffered while waiting for a decision, in bytes.
+    ///
+    /// When reached, the decision for the oldest buffered trace is made early.
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: usize,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub policies: SamplePolicies,
+}
+
+/// The policies deciding whether a trace is kept.
+///
+/// Traces in error, and slow traces, are always kept. The remaining traces are sampled by rate, and
+/// limited per service.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields, default)]
+pub struct SamplePolicies {
+    /// Whether to keep all traces with at least one span in error.
+    pub keep_errors: bool,
+
+    /// Keep all traces whose longest span lasted longer than this, in milliseconds.
+    pub latency_threshold_ms: Option<u64>,
+
+    /// The rate at which the remaining traces are kept, expressed as `1/N`.
+    ///
+    /// The decision is based on a hash of the trace ID, so that all Vector instances make the same
+    /// decision for a given trace.
+    pub rate: u64,
+
+    /// The maximum number of traces kept by `rate` per second, for each service.
+    ///
+    /// The service of a trace is the service of its root span.
+    pub max_traces_per_second: Option<u32>,
+}
+
+impl Default for SamplePolicies {
+    fn default() -> Self {
+        Self {
+            keep_errors: true,
+            latency_threshold_ms: None,
+            rate: 10,
+            max_traces_per_second: None,
+        }
+    }
+}
+
+impl GenerateConfig for TraceSampleConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            decision_wait_ms: default_decision_wait_ms(),
+            max_traces: default_max_traces(),
+            max_bytes: default_max_bytes(),
+            policies: SamplePolicies::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+impl TransformConfig for TraceSampleConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TraceSample::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::new(DataType::Trace)
+    }
+
+    fn outputs(&self, _: &schema::Definition) -> Vec<Output> {
+        vec![Output::default(DataType::Trace)]
+    }
+}
+
+/// The outcome of the policies for a trace.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Decision {
+    Error,
+    Latency,
+    Probabilistic,
+    RateLimited,
+    NotSampled,
+}
+
+impl Decision {
+    const fn keep(self) -> bool {
+        matches!(self, Self::Error | Self::Latency | Self::Probabilistic)
+    }
+
+    const fn policy(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Latency => "latency",
+            Self::Probabilistic | Self::NotSampled => "probabilistic",
+            Self::RateLimited => "rate_limit",
+        }
+    }
+}
+
+/// The events of a trace waiting for a decision, along with what the policies need to know about
+/// its spans.
+struct PendingTrace {
+    events: Vec<TraceEvent>,
+    bytes: usize,
+    error: bool,
+    /// The duration of the longest span, in nanoseconds.
+    duration: i64,
+    service: Option<String>,
+    has_root: bool,
+}
+
+impl PendingTrace {
+    const fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            bytes: 0,
+            error: false,
+            duration: 0,
+            service: None,
+            has_root: false,
+        }
+    }
+
+    fn push(&mut self, trace: TraceEvent, bytes: usize) {
+        for span in spans(&trace) {
+            let integer = |name: &str| span.get(name).and_then(Value::as_integer);
+
+            self.error |= integer("error").map_or(false, |error| error != 0);
+            self.duration = self.duration.max(integer("duration").unwrap_or_default());
+
+            let is_root = integer("parent_id") == Some(0);
+            if is_root || (self.service.is_none() && !self.has_root) {
+                self.service = span.get("service").map(Value::to_string_lossy);
+            }
+            self.has_root |= is_root;
+        }
+
+        self.events.push(trace);
+        self.bytes += bytes;
+    }
+}
+
+/// Limits the number of traces kept per second for each service.
+struct ServiceRateLimiter {
+    limit: Option<u32>,
+    window_start: Instant,
+    counts: HashMap<String, u32>,
+}
+
+impl ServiceRateLimiter {
+    fn new(limit: Option<u32>) -> Self {
+        Self {
+            limit,
+            window_start: Instant::now(),
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Returns whether another trace of `service` can be kept, counting it if so.
+    fn check(&mut self, service: &str) -> bool {
+        let limit = match self.limit {
+            Some(limit) => limit,
+            None => return true,
+        };
+
+        if self.window_start.elapsed() >= Duration::from_secs(1) {
+            self.window_start = Instant::now();
+            self.counts.clear();
+        }
+
+        let count = self.counts.entry(service.to_owned()).or_default();
+        if *count >= limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+}
+
+pub struct TraceSample {
+    decision_wait: Duration,
+    max_traces: usize,
+    max_bytes: usize,
+    keep_errors: bool,
+    latency_threshold: Option<i64>,
+    rate: u64,
+    rate_limiter: ServiceRateLimiter,
+    pending: HashMap<i64, PendingTrace>,
+    /// The IDs of the pending traces, along with when their first span was received, oldest first.
+    queue: VecDeque<(Instant, i64)>,
+    buffered_bytes: usize,
+    /// The recent decisions, applied to the spans received after them.
+    decisions: HashMap<i64, bool>,
+    decided: VecDeque<(Instant, i64)>,
+}
+
+impl TraceSample {
+    pub fn new(config: &TraceSampleConfig) -> crate::Result<Self> {
+        if config.policies.rate == 0 {
+            return Err("`policies.rate` must be non-zero".into());
+        }
+        if config.max_traces == 0 {
+            return Err("`max_traces` must be non-zero".into());
+        }
+
+        let latency_threshold = config.policies.latency_threshold_ms.map(|threshold| {
+            i64::try_from(threshold.saturating_mul(1_000_000)).unwrap_or(i64::MAX)
+        });
+
+        Ok(Self {
+            decision_wait: Duration::from_millis(config.decision_wait_ms),
+            max_traces: config.max_traces,
+            max_bytes: config.max_bytes,
+            keep_errors: config.policies.keep_errors,
+            latency_threshold,
+            rate: config.policies.rate,
+            rate_limiter: ServiceRateLimiter::new(config.policies.max_traces_per_second),
+            pending: HashMap::new(),
+            queue: VecDeque::new(),
+            buffered_bytes: 0,
+            decisions: HashMap::new(),
+            decided: VecDeque::new(),
+        })
+    }
+
+    fn evaluate(&mut self, trace_id: i64, trace: &PendingTrace) -> Decision {
+        if self.keep_errors && trace.error {
+            return Decision::Error;
+        }
+
+        if matches!(self.latency_threshold, Some(threshold) if trace.duration > threshold) {
+            return Decision::Latency;
+        }
+
+        if seahash::hash(&trace_id.to_le_bytes()) % self.rate != 0 {
+            return Decision::NotSampled;
+        }
+
+        if self
+            .rate_limiter
+            .check(trace.service.as_deref().unwrap_or_default())
+        {
+            Decision::Probabilistic
+        } else {
+            Decision::RateLimited
+        }
+    }
+
+    fn decide(&mut self, trace_id: i64, trace: PendingTrace, output: &mut Vec<Event>) {
+        self.buffered_bytes -= trace.bytes;
+
+        let decision = self.evaluate(trace_id, &trace);
+        emit!(TraceSampleDecision {
+            kept: decision.keep(),
+            policy: decision.policy(),
+        });
+
+        if decision.keep() {
+            output.extend(trace.events.into_iter().map(Event::Trace));
+        } else {
+            emit!(TraceSampleEventsDropped {
+                count: trace.events.len() as u64,
+            });
+        }
+
+        self.decisions.insert(trace_id, decision.keep());
+        self.decided.push_back((Instant::now(), trace_id));
+        if self.decided.len() > self.max_traces {
+            if let Some((_, trace_id)) = self.decided.pop_front() {
+                self.decisions.remove(&trace_id);
+            }
+        }
+    }
+
+    /// Decides whether the oldest pending trace is kept, returning `false` if there is none.
+    fn decide_oldest(&mut self, output: &mut Vec<Event>) -> bool {
+        match self.queue.pop_front() {
+            Some((_, trace_id)) => {
+                if let Some(trace) = self.pending.remove(&trace_id) {
+                    self.decide(trace_id, trace, output);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let now = Instant::now();
+
+        while let Some(&(received, _)) = self.queue.front() {
+            if now.duration_since(received) < self.decision_wait {
+                break;
+            }
+            self.decide_oldest(output);
+        }
+
+        while let Some(&(decided, trace_id)) = self.decided.front() {
+            if now.duration_since(decided) < self.decision_wait {
+                break;
+            }
+            self.decided.pop_front();
+            self.decisions.remove(&trace_id);
+        }
+
+        emit!(TraceSampleBufferSize {
+            traces: self.pending.len(),
+            bytes: self.buffered_bytes,
+        });
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        while self.decide_oldest(output) {}
+    }
+
+    fn transform_one(&mut self, output: &mut Vec<Event>, event: Event) {
+        let trace = event.into_trace();
+
+        // Spans that can't be grouped with the rest of their trace are forwarded as is.
+        let trace_id = match trace_id(&trace) {
+            Some(trace_id) => trace_id,
+            None => {
+                output.push(Event::Trace(trace));
+                return;
+            }
+        };
+
+        if let Some(&keep) = self.decisions.get(&trace_id) {
+            if keep {
+                output.push(Event::Trace(trace));
+            } else {
+                emit!(TraceSampleEventsDropped { count: 1 });
+            }
+            return;
+        }
+
+        let bytes = trace.size_of();
+        self.buffered_bytes += bytes;
+        match self.pending.entry(trace_id) {
+            hash_map::Entry::Occupied(mut entry) => entry.get_mut().push(trace, bytes),
+            hash_map::Entry::Vacant(entry) => {
+                entry.insert(PendingTrace::new()).push(trace, bytes);
+                self.queue.push_back((Instant::now(), trace_id));
+            }
+        }
+
+        while self.pending.len() > self.max_traces || self.buffered_bytes > self.max_bytes {
+            emit!(TraceSampleBufferLimitReached);
+            if !self.decide_oldest(output) {
+                break;
+            }
+        }
+    }
+}
+
+/// Returns the ID of the trace the spans of `trace` belong to.
+fn trace_id(trace: &TraceEvent) -> Option<i64> {
+    trace
+        .get("trace_id")
+        .and_then(Value::as_integer)
+        .or_else(|| spans(trace).find_map(|span| span.get("trace_id").and_then(Value::as_integer)))
+}
+
+fn spans(trace: &TraceEvent) -> impl Iterator<Item = &BTreeMap<String, Value>> {
+    trace
+        .get("spans")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+}
+
+impl TaskTransform<Event> for TraceSample {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let mut flush_stream = tokio::time::interval(Duration::from_millis(1000));
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    _ = flush_stream.tick() => {
+                      me.flush_into(&mut output);
+                      false
+                    }
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_all_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.transform_one(&mut output, event);
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TraceSampleConfig>();
+    }
+
+    fn span(trace_id: i64, span_id: i64, parent_id: i64, service: &str) -> Value {
+        Value::from(BTreeMap::from([
+            ("trace_id".to_owned(), Value::from(trace_id)),
+            ("span_id".to_owned(), Value::from(span_id)),
+            ("parent_id".to_owned(), Value::from(parent_id)),
+            ("service".to_owned(), Value::from(service)),
+            ("duration".to_owned(), Value::from(1_000_000)),
+            ("error".to_owned(), Value::from(0)),
+        ]))
+    }
+
+    fn trace(spans: Vec<Value>) -> Event {
+        Event::Trace(TraceEvent::from(BTreeMap::from([(
+            "spans".to_owned(),
+            Value::from(spans),
+        )])))
+    }
+
+    fn set(span: &mut Value, field: &str, value: i64) {
+        if let Value::Object(span) = span {
+            span.insert(field.to_owned(), Value::from(value));
+        }
+    }
+
+    /// Returns a config dropping all traces not kept by the other policies.
+    fn config(policies: SamplePolicies) -> TraceSampleConfig {
+        TraceSampleConfig {
+            decision_wait_ms: default_decision_wait_ms(),
+            max_traces: default_max_traces(),
+            max_bytes: default_max_bytes(),
+            policies: SamplePolicies {
+                rate: u64::MAX,
+                ..policies
+            },
+        }
+    }
+
+    async fn run(config: &TraceSampleConfig, events: Vec<Event>) -> Vec<Event> {
+        let transform = config
+            .build(&TransformContext::default())
+            .await
+            .unwrap()
+            .into_task();
+
+        transform
+            .transform_events(Box::pin(stream::iter(events)))
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn keeps_whole_traces_in_error() {
+        let mut failed = span(1, 2, 1, "api");
+        set(&mut failed, "error", 1);
+
+        let events = vec![
+            trace(vec![span(1, 1, 0, "api")]),
+            trace(vec![span(2, 3, 0, "api")]),
+            trace(vec![failed]),
+        ];
+        let output = run(&config(SamplePolicies::default()), events.clone()).await;
+
+        assert_eq!(output, vec![events[0].clone(), events[2].clone()]);
+    }
+
+    #[tokio::test]
+    async fn keeps_slow_traces() {
+        let mut slow = span(1, 1, 0, "api");
+        set(&mut slow, "duration", 2_000_000_000);
+
+        let events = vec![trace(vec![slow]), trace(vec![span(2, 2, 0, "api")])];
+        let output = run(
+            &config(SamplePolicies {
+                latency_threshold_ms: Some(1_000),
+                ..SamplePolicies::default()
+            }),
+            events.clone(),
+        )
+        .await;
+
+        assert_eq!(output, vec![events[0].clone()]);
+    }
+
+    #[tokio::test]
+    async fn limits_traces_per_service() {
+        let mut config = config(SamplePolicies {
+            max_traces_per_second: Some(1),
+            ..SamplePolicies::default()
+        });
+        config.policies.rate = 1;
+
+        let events = vec![
+            trace(vec![span(1, 1, 0, "api")]),
+            trace(vec![span(2, 2, 0, "api")]),
+            trace(vec![span(3, 3, 0, "db")]),
+        ];
+        let output = run(&config, events.clone()).await;
+
+        assert_eq!(output, vec![events[0].clone(), events[2].clone()]);
+    }
+
+    #[tokio::test]
+    async fn follows_earlier_decisions() {
+        let mut config = config(SamplePolicies::default());
+        config.max_traces = 1;
+
+        let mut failed = span(1, 2, 1, "api");
+        set(&mut failed, "error", 1);
+
+        // The second trace forces an early decision on the first one, which the spans received
+        // later follow.
+        let events = vec![
+            trace(vec![failed]),
+            trace(vec![span(2, 3, 0, "api")]),
+            trace(vec![span(1, 1, 0, "api")]),
+        ];
+        let output = run(&config, events.clone()).await;
+
+        assert_eq!(output, vec![events[0].clone(), events[2].clone()]);
+    }
+
+    #[test]
+    fn evaluates_root_span_service() {
+        let mut trace = PendingTrace::new();
+        for (span, service) in [(span(1, 2, 1, "db"), "db"), (span(1, 1, 0, "api"), "api")] {
+            trace.push(
+                TraceEvent::from(BTreeMap::from([(
+                    "spans".to_owned(),
+                    Value::from(vec![span]),
+                )])),
+                0,
+            );
+            assert_eq!(trace.service.as_deref(), Some(service));
+        }
+
+        trace.push(
+            TraceEvent::from(BTreeMap::from([(
+                "spans".to_owned(),
+                Value::from(vec![span(1, 3, 1, "cache")]),
+            )])),
+            0,
+        );
+        assert_eq!(trace.service.as_deref(), Some("api"));
+    }
+}
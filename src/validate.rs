@@ -100,6 +100,9 @@ pub async fn validate(opts: &Opts, color: bool) -> ExitCode {
         None => return exitcode::CONFIG,
     };
 
+    #[cfg(feature = "transforms-remap")]
+    estimate_vrl_costs(&config, &mut fmt);
+
     if !opts.no_environment {
         if let Some(tmp_directory) = create_tmp_directory(&mut config, &mut fmt) {
             validated &= validate_environment(opts, &config, &mut fmt).await;
@@ -167,6 +170,56 @@ pub fn validate_config(opts: &Opts, fmt: &mut Formatter) -> Option<Config> {
     Some(config)
 }
 
+/// Prints an estimate of the cost of the VRL programs run by `remap` transforms, along with the
+/// costly patterns they use.
+#[cfg(feature = "transforms-remap")]
+fn estimate_vrl_costs(config: &Config, fmt: &mut Formatter) {
+    use vrl::CostLevel;
+
+    use crate::{topology::schema::merged_definition, transforms::Transforms};
+
+    let mut cache = HashMap::new();
+    let estimates = config
+        .transforms()
+        .filter_map(|(key, transform)| match &transform.inner {
+            // Programs that fail to compile are reported by the component checks.
+            Transforms::Remap(remap) => remap
+                .cost_estimate(&merged_definition(&transform.inputs, config, &mut cache))
+                .ok()
+                .map(|(cost, warnings)| (key, cost, warnings)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if estimates.is_empty() {
+        return;
+    }
+
+    fmt.title("Estimated VRL cost per event");
+    for (key, cost, warnings) in estimates {
+        let mut estimate = format!("\"{}\": {}", key, cost);
+        let top_functions = cost
+            .top_functions(3)
+            .into_iter()
+            .map(|(function, units)| format!("{} ({} units)", function, units))
+            .collect::<Vec<_>>();
+        if !top_functions.is_empty() {
+            estimate.push_str(&format!(", mostly from {}", top_functions.join(", ")));
+        }
+
+        let intro = if cost.level() == CostLevel::High || !warnings.is_empty() {
+            fmt.warning_intro.clone()
+        } else {
+            fmt.success_intro.clone()
+        };
+        fmt.print(format!("{} {}\n", intro, estimate));
+        if !warnings.is_empty() {
+            fmt.print(format!("{}\n", warnings.trim_end()));
+        }
+    }
+    fmt.space();
+}
+
 async fn validate_environment(opts: &Opts, config: &Config, fmt: &mut Formatter) -> bool {
     let diff = ConfigDiff::initial(config);
 
@@ -374,7 +374,10 @@ cli: {
 		}
 
 		"validate": {
-			description: "Validate the target config, then exit"
+			description: """
+				Validate the target config, then exit. The estimated cost of running the VRL program of each
+				`remap` transform on an event is printed, along with the costly patterns the program uses.
+				"""
 
 			flags: _default_flags & {
 				"no-environment": {
@@ -455,6 +458,31 @@ cli: {
 						"""
 					type: "string"
 				}
+
+				"tap": {
+					description: """
+						Components IDs whose outputs to load events from, through the API of a
+						running Vector instance, as the objects of the REPL (comma-separated;
+						accepts glob patterns). In the REPL, the `tap` command loads events the
+						same way, and the `run` command re-runs the expressions entered so far
+						against all of the loaded events, showing how each event changed and
+						the errors that occurred.
+						"""
+					type: "list"
+				}
+
+				"tap-limit": {
+					description: "The maximum number of events to load with `--tap`."
+					type:        "integer"
+					default:     100
+				}
+
+				"url": {
+					_short:      "u"
+					description: "The Vector GraphQL API server endpoint to load events from."
+					type:        "string"
+					default:     "http://127.0.0.1:8686/graphql"
+				}
 			}
 
 			args: {
@@ -467,6 +495,46 @@ cli: {
 				}
 			}
 		}
+
+		"vrl test": {
+			description: """
+				Run the test cases of VRL programs. The test cases of a program `foo.vrl` are
+				defined in a `foo.test.toml` file next to it, as a list of `[[tests]]` tables with
+				a `name`, the `input` event and its `metadata`, and the expected `output` event,
+				`result` of the program, or (part of the) `error` it fails with.
+				"""
+
+			flags: _default_flags & {
+				"coverage": {
+					description: """
+						Report which branches of the `if` statements of the programs were executed
+						by their tests.
+						"""
+				}
+			}
+
+			options: {
+				"junit": {
+					description: "Write the results of the tests to this file, in JUnit XML format."
+					type:        "string"
+				}
+				"timezone": {
+					_short:      "z"
+					description: "The timezone used to parse dates."
+					type:        "string"
+				}
+			}
+
+			args: {
+				paths: {
+					description: """
+						Any number of VRL programs, or directories to search for VRL programs with
+						test files. If none are specified the current directory is searched.
+						"""
+					type: "list"
+				}
+			}
+		}
 	}
 
 	env_vars: {
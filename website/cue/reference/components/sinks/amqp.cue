@@ -0,0 +1,92 @@
+package metadata
+
+components: sinks: amqp: {
+	title: "AMQP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: enabled:     false
+			to: components._amqp.features.send.to
+		}
+	}
+
+	support: components._amqp.support
+
+	configuration: components._amqp.configuration & {
+		connection: type: object: options: tls: configuration._tls_connect & {_args: {
+			can_verify_certificate: false
+			can_verify_hostname:    false
+			enabled_default:        false
+			enabled_by_scheme:      true
+		}}
+		exchange: {
+			description: "The exchange to publish messages to."
+			required:    true
+			type: string: {
+				examples: ["{{ host }}", "logs"]
+				syntax: "template"
+			}
+		}
+		routing_key: {
+			common:      true
+			description: "The routing key to publish messages with. This is used by the exchange to decide which queues the message is delivered to."
+			required:    false
+			type: string: {
+				default: ""
+				examples: ["{{ application }}", "logs.info"]
+				syntax: "template"
+			}
+		}
+		persistent: {
+			common:      false
+			description: "Whether messages are published with the persistent delivery mode, so that they survive a broker restart when routed to a durable queue."
+			required:    false
+			type: bool: default: true
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	how_it_works: components._amqp.how_it_works & {
+		publisher_confirms: {
+			title: "Publisher confirms"
+			body: """
+				The sink enables publisher confirms on its channel, and only marks an event as delivered
+				once the broker has confirmed the message. Negatively acknowledged messages are reported
+				as errors.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_sent_bytes_total:       components.sources.internal_metrics.output.metrics.component_sent_bytes_total
+		component_sent_events_total:      components.sources.internal_metrics.output.metrics.component_sent_events_total
+		component_sent_event_bytes_total: components.sources.internal_metrics.output.metrics.component_sent_event_bytes_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
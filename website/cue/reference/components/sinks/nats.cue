@@ -52,7 +52,17 @@ components: sinks: nats: {
 		notices: []
 	}
 
-	configuration: components._nats.configuration & {}
+	configuration: components._nats.configuration & {
+		jetstream: {
+			common:      false
+			description: """
+				Publish messages to [JetStream](\(urls.nats_jetstream)). Each message is only considered delivered once
+				the server has acknowledged that it was stored. The stream bound to the subject must already exist.
+				"""
+			required:    false
+			type: bool: default: false
+		}
+	}
 
 	input: {
 		logs:    true
@@ -0,0 +1,164 @@
+package metadata
+
+components: sources: amqp: {
+	title: "AMQP"
+
+	features: {
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			from: components._amqp.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: true
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._amqp.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: components._amqp.configuration & {
+		acknowledgements: configuration._source_acknowledgements
+		connection: type: object: options: tls: configuration._tls_connect & {_args: {
+			can_verify_certificate: false
+			can_verify_hostname:    false
+			enabled_default:        false
+			enabled_by_scheme:      true
+		}}
+		consumer: {
+			common:      false
+			description: "The identifier for the consumer."
+			required:    false
+			type: string: {
+				default: "vector"
+				examples: ["consumer-group-name"]
+			}
+		}
+		queue: {
+			common:      true
+			description: "The name of the queue to consume."
+			required:    false
+			type: string: {
+				default: "vector"
+				examples: ["text"]
+			}
+		}
+		prefetch_count: {
+			common:      false
+			description: "The maximum number of unacknowledged deliveries the broker will push to Vector. When acknowledgements are enabled, this bounds the number of messages in flight within the topology. A value of `0` means no limit."
+			required:    false
+			type: uint: {
+				default: 100
+				examples: [10, 1000]
+				unit: null
+			}
+		}
+		routing_key_field: {
+			common:      false
+			description: "The log field name to use for the AMQP routing key."
+			required:    false
+			type: string: {
+				default: "routing"
+				examples: ["routing"]
+			}
+		}
+		exchange_key: {
+			common:      false
+			description: "The log field name to use for the AMQP exchange key."
+			required:    false
+			type: string: {
+				default: "exchange"
+				examples: ["exchange"]
+			}
+		}
+		offset_key: {
+			common:      false
+			description: "The log field name to use for the AMQP delivery tag."
+			required:    false
+			type: string: {
+				default: "offset"
+				examples: ["offset"]
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual AMQP delivery."
+		fields: {
+			message: {
+				description: "The raw line from the AMQP message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			offset: {
+				description: "The delivery tag of the AMQP message."
+				required:    true
+				type: uint: {
+					examples: [100]
+					unit: null
+				}
+			}
+			routing: {
+				description: "The AMQP routing key the message was published with."
+				required:    true
+				type: string: {
+					examples: ["logs.application"]
+				}
+			}
+			exchange: {
+				description: "The AMQP exchange the message was published to."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["amqp"]
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The timestamp property of the AMQP message or the current time if it is not set."
+			}
+		}
+	}
+
+	how_it_works: components._amqp.how_it_works & {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				When end-to-end acknowledgements are enabled, a delivery is only acknowledged to the broker
+				once all events decoded from it have been delivered by the connected sinks. Deliveries that
+				fail are requeued, while deliveries that are rejected by a sink are discarded, or
+				dead-lettered if the queue is configured to do so. Use `prefetch_count` to bound the number
+				of unacknowledged deliveries.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_in_total:                      components.sources.internal_metrics.output.metrics.events_in_total
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+	}
+}
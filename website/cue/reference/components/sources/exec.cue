@@ -63,12 +63,48 @@ components: sources: exec: {
 			description: "The directory in which to run the command."
 			type: string: default: null
 		}
+		environment: {
+			common:      false
+			required:    false
+			description: "Custom environment variables to set or update when running the command. If a variable name already exists in the environment, its value is replaced."
+			type: object: {
+				examples: [{"LANG": "en_US.UTF-8", "PATH": "/usr/local/bin:/usr/bin"}]
+				options: {
+					"*": {
+						description: "The value of the environment variable."
+						required:    true
+						type: string: {}
+					}
+				}
+			}
+		}
+		clear_environment: {
+			common:      false
+			description: "Clear the environment inherited from Vector before setting the custom `environment` variables."
+			required:    false
+			type: bool: default: false
+		}
+		stdin: {
+			common:      false
+			description: "The data written to the standard input of the command, which is closed afterwards. If not set, the standard input of the command is empty."
+			required:    false
+			type: string: {
+				default: null
+				examples: ["ping\n"]
+			}
+		}
 		include_stderr: {
 			common:      false
 			description: "Include the output of stderr when generating events."
 			required:    false
 			type: bool: default: true
 		}
+		run_metadata: {
+			common:      false
+			description: "Generate an event once each run of the command is over, holding its exit code, the duration of the run, whether it timed out, and the last 4 KiB of the output of stderr."
+			required:    false
+			type: bool: default: false
+		}
 		maximum_buffer_size_bytes: {
 			common:      false
 			description: "The maximum buffer size allowed before a log event will be generated."
@@ -95,6 +131,17 @@ components: sources: exec: {
 							unit:    "seconds"
 						}
 					}
+					timeout_secs: {
+						common:        false
+						description:   "The maximum time in seconds a scheduled command is allowed to run before it is killed. Must not be greater than `exec_interval_secs`, which is used if not set."
+						relevant_when: "mode = `scheduled`"
+						required:      false
+						type: uint: {
+							default: null
+							examples: [10]
+							unit: "seconds"
+						}
+					}
 				}
 			}
 		}
@@ -169,6 +216,67 @@ components: sources: exec: {
 		}
 	}
 
+	output: logs: run: {
+		description: "The metadata of a run of the command, generated once it is over when `run_metadata` is enabled."
+		fields: {
+			host:      fields._local_host
+			timestamp: fields._current_timestamp
+			exit_code: {
+				description: "The exit code of the command, which is missing if it was killed by a signal."
+				required:    false
+				common:      true
+				type: int: {
+					default: null
+					examples: [0, 1]
+				}
+			}
+			duration_seconds: {
+				description: "The duration of the run."
+				required:    true
+				type: float: {
+					examples: [0.25]
+					unit: "seconds"
+				}
+			}
+			timed_out: {
+				description: "Whether the command was killed because it exceeded its timeout."
+				required:    true
+				type: bool: {}
+			}
+			stderr: {
+				description: "The last 4 KiB of the output of stderr."
+				required:    true
+				type: string: {
+					examples: ["curl: (7) Failed to connect to localhost port 8080: Connection refused\n"]
+				}
+			}
+			pid: {
+				description: "The process ID of the command."
+				required:    true
+				type: uint: {
+					examples: [60085, 668]
+					unit: null
+				}
+			}
+			command: {
+				required:    true
+				description: "The command that was run."
+				type: array: {
+					items: type: string: {
+						examples: ["echo", "Hello World!", "ls", "-la"]
+					}
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["exec"]
+				}
+			}
+		}
+	}
+
 	examples: [
 		{
 			_line:      "64 bytes from 127.0.0.1: icmp_seq=0 ttl=64 time=0.060 ms"
@@ -68,11 +68,11 @@ components: sources: host_metrics: {
 
 	configuration: {
 		collectors: {
-			description: "The list of host metric collector services to use. Defaults to all collectors."
+			description: "The list of host metric collector services to use. Defaults to all collectors, except `tcp`."
 			common:      true
 			required:    false
 			type: array: {
-				default: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network"]
+				default: ["cgroups", "cpu", "disk", "filesystem", "load", "host", "memory", "network", "process"]
 				items: type: string: {
 					enum: {
 						cgroups:    "Metrics related to Linux control groups."
@@ -83,6 +83,8 @@ components: sources: host_metrics: {
 						host:       "Metrics related to host"
 						memory:     "Metrics related to memory utilization."
 						network:    "Metrics related to network utilization."
+						process:    "Metrics related to processes (Linux only)."
+						tcp:        "Metrics related to TCP connections (Linux only)."
 					}
 				}
 			}
@@ -401,6 +403,177 @@ components: sources: host_metrics: {
 				}
 			}
 		}
+		process: {
+			common: false
+			description: #"""
+				Options for the "process" metrics collector.
+
+				Processes are grouped by name, and the metrics of the processes of a group are summed, so that
+				the number of reported series doesn't depend on the number of running processes. The CPU time
+				and I/O counters of a group also keep what the processes that exited used while running, so
+				that they never decrease.
+
+				Note: this collector is only available on Linux systems.
+				"""#
+			required: false
+			type: object: options: {
+				names: {
+					common:      false
+					required:    false
+					description: "Lists of process name patterns to include or exclude. The name of a process is the name of its executable, as found in `/proc/<pid>/comm`."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to gather metrics.
+
+								Defaults to including all processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["nginx", "postgres*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of process name patterns for which to gather metrics.
+
+								Defaults to excluding no processes.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["nginx", "postgres*"]
+								}
+							}
+						}
+					}
+				}
+				cmdline: {
+					common:      false
+					required:    false
+					description: "A regular expression the command line of processes must match to be reported. The command line is made of the arguments of the process, separated by spaces."
+					type: string: {
+						default: null
+						examples: ["worker\\.py$", "^java .*kafka"]
+					}
+				}
+				cgroups: {
+					common:      false
+					required:    false
+					description: "Lists of cgroup name patterns to include or exclude. The cgroup of a process is its cgroup in the version 2 hierarchy, or in the first hierarchy listed in `/proc/<pid>/cgroup` otherwise."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of cgroup name patterns of the processes for which to gather metrics.
+
+								Defaults to including all cgroups.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["/system.slice/*", "*.service"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of cgroup name patterns of the processes for which to gather metrics.
+
+								Defaults to excluding no cgroups.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["/system.slice/*", "*.service"]
+								}
+							}
+						}
+					}
+				}
+				max_groups: {
+					common:      false
+					required:    false
+					description: "The maximum number of process groups to report metrics for. Groups are ordered by name, and the processes of the groups over the limit are reported in the `other` group."
+					type: uint: {
+						unit:    null
+						default: 50
+						examples: [10, 200]
+					}
+				}
+			}
+		}
+		tcp: {
+			common: false
+			description: #"""
+				Options for the "tcp" metrics collector.
+
+				Note: this collector is only available on Linux systems, and isn't enabled by default, as listing the connections of busy hosts is expensive.
+
+				Connections are listed through the `sock_diag` netlink interface, and read from `/proc/net/tcp` and `/proc/net/tcp6` when it isn't available.
+				"""#
+			required: false
+			type: object: options: {
+				ports: {
+					common:      false
+					required:    false
+					description: "Lists of local port patterns to include or exclude. Only the connections whose local port matches are counted in the connection metrics. The protocol counters cover all connections."
+					type: object: options: {
+						includes: {
+							required: false
+							common:   false
+							description: """
+								The list of local port patterns for which to count connections.
+
+								Defaults to including all ports.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: ["*"]
+								items: type: string: {
+									examples: ["443", "80*"]
+								}
+							}
+						}
+						excludes: {
+							required: false
+							common:   false
+							description: """
+								The list of local port patterns for which to count connections.
+
+								Defaults to excluding no ports.
+
+								The patterns are matched using globbing.
+								"""
+							type: array: {
+								default: []
+								items: type: string: {
+									examples: ["22", "80*"]
+								}
+							}
+						}
+					}
+				}
+			}
+		}
 	}
 
 	output: metrics: {
@@ -502,6 +675,43 @@ components: sources: host_metrics: {
 		network_transmit_packets_drop_total: _host & _network_nomac & {description: "The number of packets dropped during transmits on this interface."}
 		network_transmit_packets_total:      _host & _network_nomac & {description: "The number of packets transmitted on this interface."}
 
+		// Host processes
+		process_cpu_seconds_total: _host & _process_counter & {
+			description: "The total amount of CPU time used by the processes of this group in different operating modes, in seconds."
+			tags: mode: {
+				description: "Which mode the CPU was running in during the given time."
+				required:    true
+				examples: ["system", "user"]
+			}
+		}
+		process_count:                 _host & _process_gauge & {description:   "The number of processes of this group."}
+		process_open_fds:              _host & _process_gauge & {description:   "The number of open file descriptors of the processes of this group Vector is allowed to inspect."}
+		process_read_bytes_total:      _host & _process_counter & {description: "The accumulated number of bytes read from storage by the processes of this group Vector is allowed to inspect."}
+		process_resident_memory_bytes: _host & _process_gauge & {description:   "The amount of memory resident in RAM used by the processes of this group, in bytes."}
+		process_threads:               _host & _process_gauge & {description:   "The number of threads of the processes of this group."}
+		process_written_bytes_total:   _host & _process_counter & {description: "The accumulated number of bytes written to storage by the processes of this group Vector is allowed to inspect."}
+
+		// Host TCP
+		tcp_connections:     _host & _tcp_state & {description: "The number of TCP connections in this state."}
+		tcp_rx_queued_bytes: _host & _tcp_state & {description: "The number of bytes received and not yet read by the application, or the current backlog of listening sockets, summed over the TCP connections in this state."}
+		tcp_tx_queued_bytes: _host & _tcp_state & {description: "The number of bytes sent and not yet acknowledged by the peer, summed over the TCP connections in this state."}
+
+		tcp_active_opens_total:           _host & _tcp_counter & {description: "The number of connections opened by the host."}
+		tcp_attempt_fails_total:          _host & _tcp_counter & {description: "The number of connection attempts that failed."}
+		tcp_established_resets_total:     _host & _tcp_counter & {description: "The number of established connections that were reset."}
+		tcp_listen_drops_total:           _host & _tcp_counter & {description: "The number of connection requests dropped by listening sockets."}
+		tcp_listen_overflows_total:       _host & _tcp_counter & {description: "The number of times the accept queue of a listening socket overflowed."}
+		tcp_lost_retransmits_total:       _host & _tcp_counter & {description: "The number of retransmitted segments that were lost."}
+		tcp_passive_opens_total:          _host & _tcp_counter & {description: "The number of connections accepted by the host."}
+		tcp_receive_errors_total:         _host & _tcp_counter & {description: "The number of segments received in error."}
+		tcp_received_segments_total:      _host & _tcp_counter & {description: "The number of segments received."}
+		tcp_retransmitted_segments_total: _host & _tcp_counter & {description: "The number of segments retransmitted."}
+		tcp_sent_resets_total:            _host & _tcp_counter & {description: "The number of segments sent with the RST flag."}
+		tcp_sent_segments_total:          _host & _tcp_counter & {description: "The number of segments sent."}
+		tcp_syn_retransmits_total:        _host & _tcp_counter & {description: "The number of SYN and SYN-ACK segments retransmitted."}
+		tcp_syncookies_sent_total:        _host & _tcp_counter & {description: "The number of SYN cookies sent."}
+		tcp_timeouts_total:               _host & _tcp_counter & {description: "The number of retransmission timeouts."}
+
 		// Helpers
 		_host: {
 			default_namespace: "host"
@@ -591,6 +801,46 @@ components: sources: host_metrics: {
 			}
 		}
 		_network_nomac: _network_gauge & {relevant_when: "OS is not macOS"}
+		_process_counter: {
+			type:          "counter"
+			relevant_when: "OS is Linux"
+			tags: _host_metrics_tags & {
+				collector: examples: ["process"]
+				name: _process_name
+			}
+		}
+		_process_gauge: {
+			type:          "gauge"
+			relevant_when: "OS is Linux"
+			tags: _host_metrics_tags & {
+				collector: examples: ["process"]
+				name: _process_name
+			}
+		}
+		_process_name: {
+			description: "The name of the processes of the group, or `other` for the processes of the groups over `process.max_groups`."
+			required:    true
+			examples: ["nginx", "other"]
+		}
+		_tcp_counter: {
+			type:          "counter"
+			relevant_when: "OS is Linux"
+			tags: _host_metrics_tags & {
+				collector: examples: ["tcp"]
+			}
+		}
+		_tcp_state: {
+			type:          "gauge"
+			relevant_when: "OS is Linux"
+			tags: _host_metrics_tags & {
+				collector: examples: ["tcp"]
+				state: {
+					description: "The state of the TCP connections."
+					required:    true
+					examples: ["established", "listen", "time_wait"]
+				}
+			}
+		}
 	}
 
 	telemetry: metrics: {
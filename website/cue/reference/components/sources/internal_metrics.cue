@@ -478,6 +478,18 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		buffered_traces: {
+			description:       "The number of traces buffered while waiting for a sampling decision."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffered_trace_bytes: {
+			description:       "The size of the events buffered while waiting for a sampling decision, in bytes."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		buffer_discarded_events_total: {
 			description:       "The number of events dropped by this non-blocking buffer."
 			type:              "counter"
@@ -857,6 +869,24 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		log_patterns_created_total: {
+			description:       "The number of log patterns created."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		log_patterns_evicted_total: {
+			description:       "The number of log patterns forgotten to stay within the `max_patterns` limit."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		log_patterns_expired_total: {
+			description:       "The number of log patterns forgotten after receiving no events for a while."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		logging_driver_errors_total: {
 			description: """
 				The total number of logging driver errors encountered caused by not using either
@@ -978,6 +1008,31 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		sampled_traces_total: {
+			description:       "The number of traces a sampling decision was made for."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				decision: {
+					description: "Whether the trace was kept."
+					required:    true
+					enum: {
+						dropped: "The trace was dropped."
+						kept:    "The trace was kept."
+					}
+				}
+				policy: {
+					description: "The sampling policy that made the decision."
+					required:    true
+					enum: {
+						error:         "The trace has a span in error."
+						latency:       "The trace lasted longer than the latency threshold."
+						probabilistic: "The trace was sampled by rate."
+						rate_limit:    "The trace exceeded the rate limit of its service."
+					}
+				}
+			}
+		}
 		send_errors_total: {
 			description:       "The total number of errors sending messages."
 			type:              "counter"
@@ -1080,6 +1135,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		traces_decided_early_total: {
+			description:       "The number of sampling decisions made before the decision wait elapsed, because the trace buffer was full."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		uptime_seconds: {
 			description:       "The total number of seconds the Vector instance has been up."
 			type:              "gauge"
@@ -151,13 +151,25 @@ components: sources: journald: {
 		}
 		journal_directory: {
 			common:      false
-			description: "The full path of the journal directory. If not set, `journalctl` will use the default system journal paths"
+			description: "The full path of the journal directory. If not set, the default system journal paths are used, which the `native` reader reads from `/var/log/journal` and `/run/log/journal`."
 			required:    false
 			type: string: {
 				default: null
 				examples: ["/run/log/journal"]
 			}
 		}
+		reader: {
+			common:      false
+			description: "How the journal is read. The checkpoints of both readers are compatible, so the reader can be changed without reading the journal again."
+			required:    false
+			type: string: {
+				default: "journalctl"
+				enum: {
+					journalctl: "Spawn `journalctl` and read its JSON output."
+					native:     "Read the journal files directly, without requiring `journalctl`. Files compressed with LZ4 or zstd are supported, but not those compressed with XZ, which the reader refuses to start on."
+				}
+			}
+		}
 	}
 
 	output: logs: {
@@ -243,6 +255,11 @@ components: sources: journald: {
 				specify the exact location via the `journalctl_path` option. For more
 				information on this communication strategy please see
 				[issue #1473](\(urls.vector_issues)/1437).
+
+				Alternatively, setting `reader` to `native` makes Vector read the journal
+				files directly, following the entries appended to them, so that `journalctl`
+				isn't needed. The unit and field filters, and the checkpoints, work the same
+				way with both readers.
 				"""
 		}
 		non_ascii: {
@@ -4,7 +4,7 @@ components: sources: nats: {
 	title: "NATS"
 
 	features: {
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			from: components._nats.features.collect.from
@@ -32,6 +32,55 @@ components: sources: nats: {
 	}
 
 	configuration: components._nats.configuration & {
+		acknowledgements: configuration._source_acknowledgements
+		jetstream: {
+			common:      false
+			description: """
+				Consume messages from a durable [JetStream](\(urls.nats_jetstream)) consumer instead of a core NATS
+				subscription. Messages are acknowledged once the events decoded from them have been processed.
+				"""
+			required:    false
+			type: object: {
+				examples: []
+				options: {
+					stream: {
+						description: "The name of the stream to consume from. The stream must already exist."
+						required:    true
+						type: string: {
+							examples: ["logs"]
+						}
+					}
+					durable_name: {
+						description: "The name of the durable consumer. It is created if it does not already exist."
+						required:    true
+						type: string: {
+							examples: ["vector"]
+						}
+					}
+					mode: {
+						common:      false
+						description: "The kind of JetStream consumer to create."
+						required:    false
+						type: string: {
+							default: "push"
+							enum: {
+								push: "The server pushes messages to the consumer as they become available."
+								pull: "The consumer explicitly fetches batches of messages from the server."
+							}
+						}
+					}
+					batch_size: {
+						common:      false
+						description: "The maximum number of messages to fetch at once, when using a pull consumer."
+						required:    false
+						type: uint: {
+							default: 100
+							unit:    "events"
+						}
+					}
+				}
+			}
+		}
 		queue: {
 			common:      false
 			description: "NATS Queue Group to join"
@@ -74,5 +123,17 @@ components: sources: nats: {
 		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
 	}
 
-	how_it_works: components._nats.how_it_works
+	how_it_works: components._nats.how_it_works & {
+		jetstream: {
+			title: "JetStream"
+			body:  """
+				When the `jetstream` option is set, messages are read from a durable [JetStream](\(urls.nats_jetstream))
+				consumer. The server keeps track of which messages have been acknowledged, so messages published while
+				Vector is stopped are delivered once it starts again. When end-to-end acknowledgements are enabled, a
+				message is acknowledged once its events have been delivered; messages whose events failed to be delivered
+				are negatively acknowledged so they are redelivered. Otherwise messages are acknowledged as soon as they
+				are read.
+				"""
+		}
+	}
 }
@@ -0,0 +1,186 @@
+package metadata
+
+components: sources: netflow: {
+	_port: 2055
+
+	title: "NetFlow"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.netflow
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: [
+			"""
+				NetFlow v9 and IPFIX records can only be decoded once the exporter has sent the
+				template describing them. Records received before their template are dropped.
+				""",
+		]
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The address to listen for flow packets on. It _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		host_key: {
+			category:    "Context"
+			common:      false
+			description: """
+				The key name added to each event representing the address of the exporter that sent
+				the flow packet.
+
+				By default, the [global `log_schema.host_key` option](\(urls.vector_configuration)/global-options#log_schema.host_key) is used.
+				"""
+			required:    false
+			type: string: {
+				default: "host"
+			}
+		}
+		max_length: {
+			common:      false
+			description: "The maximum size of incoming packets, in bytes. Larger packets are discarded."
+			required:    false
+			type: uint: {
+				default: 102400
+				unit:    "bytes"
+			}
+		}
+		max_templates: {
+			common:      false
+			description: """
+				The maximum number of NetFlow v9 and IPFIX templates kept, across all exporters.
+				Templates announced once this limit is reached are discarded.
+				"""
+			required:    false
+			type: uint: {
+				default: 10000
+				unit:    null
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: """
+			A single flow, options or counter record. Fields decoded from the record are named
+			after their IPFIX information element, such as `source_ipv4_address` or
+			`octet_delta_count`. Fields not known to Vector are named `field_<id>`, or
+			`enterprise_<pen>_field_<id>` for enterprise specific fields, and hold the raw value
+			encoded as hex.
+
+			Numeric fields are integers, except for the rare values above the largest signed 64-bit
+			integer, which are floats. Times such as `flow_start_milliseconds` are timestamps,
+			and addresses are strings.
+			"""
+		fields: {
+			flow_protocol: {
+				description: "The protocol of the packet the record was received in."
+				required:    true
+				type: string: {
+					enum: {
+						netflow_v5: "[NetFlow v5](\(urls.netflow_v5))."
+						netflow_v9: "[NetFlow v9](\(urls.netflow_v9))."
+						ipfix:      "[IPFIX](\(urls.ipfix))."
+						sflow_v5:   "[sFlow v5](\(urls.sflow_v5))."
+					}
+				}
+			}
+			record_type: {
+				description: "The type of record."
+				required:    true
+				type: string: {
+					enum: {
+						flow:     "A flow record, or a flow sample for sFlow."
+						options:  "An options record, describing the exporter itself."
+						counters: "An sFlow counter sample."
+					}
+				}
+			}
+			header: {
+				description: "The fields of the packet header, such as `sequence_number` or `observation_domain_id`."
+				required:    true
+				type: object: {}
+			}
+			host: {
+				description: "The IP address of the exporter."
+				required:    true
+				type: string: {
+					examples: ["192.0.2.1"]
+				}
+			}
+			port: {
+				description: "The source port of the exporter."
+				required:    true
+				type: uint: {
+					unit: null
+					examples: [52348]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["netflow"]
+				}
+			}
+			timestamp: {
+				description: "The export time of the packet, or the time it was received for sFlow."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		templates: {
+			title: "Templates"
+			body: """
+				NetFlow v9 and IPFIX exporters periodically send templates describing the layout of
+				their data records. Templates are kept per exporter address and observation domain,
+				so several exporters can reuse the same template IDs. The `max_templates` option
+				bounds the memory used by templates, as any host able to reach the source can
+				announce new ones.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
@@ -0,0 +1,366 @@
+package metadata
+
+components: sources: snmp_trap: {
+	_port: 162
+
+	title: "SNMP Trap"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		acknowledgements: false
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.snmp
+				interface: socket: {
+					direction: "incoming"
+					port:      _port
+					protocols: ["udp"]
+					ssl: "disabled"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            false
+			tls: enabled:                  false
+		}
+	}
+
+	support: {
+		requirements: [
+			"""
+				Listening on port \(_port) requires Vector to run as root, or with the
+				`CAP_NET_BIND_SERVICE` capability on Linux.
+				""",
+		]
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		address: {
+			description: "The address to listen for traps on. It _must_ include a port."
+			required:    true
+			type: string: {
+				examples: ["0.0.0.0:\(_port)"]
+			}
+		}
+		communities: {
+			common:      true
+			description: """
+				The communities accepted for SNMPv1 and SNMPv2c traps. When empty, traps are accepted
+				regardless of their community.
+				"""
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["public"]
+				}
+			}
+		}
+		host_key: {
+			category:    "Context"
+			common:      false
+			description: """
+				The key name added to each event representing the address of the agent that sent
+				the trap.
+
+				By default, the [global `log_schema.host_key` option](\(urls.vector_configuration)/global-options#log_schema.host_key) is used.
+				"""
+			required:    false
+			type: string: {
+				default: "host"
+			}
+		}
+		max_length: {
+			common:      false
+			description: "The maximum size of incoming messages, in bytes. Larger messages are discarded."
+			required:    false
+			type: uint: {
+				default: 102400
+				unit:    "bytes"
+			}
+		}
+		mib_paths: {
+			common:      true
+			description: """
+				Paths of [MIB](\(urls.snmp_mib)) files, or directories containing MIB files, used to
+				translate OIDs to names. MIB files are only read when the source starts.
+				"""
+			required:    false
+			type: array: {
+				default: []
+				items: type: string: {
+					examples: ["/usr/share/snmp/mibs"]
+				}
+			}
+		}
+		users: {
+			common:      false
+			description: """
+				The [USM](\(urls.snmp_usm)) users accepted for SNMPv3 traps. SNMPv3 traps from other
+				users, or sent with a different security level than the one configured for their
+				user, are rejected.
+				"""
+			required:    false
+			type: array: {
+				default: []
+				items: type: object: {
+					examples: []
+					options: {
+						name: {
+							description: "The name of the user."
+							required:    true
+							type: string: {
+								examples: ["monitoring"]
+							}
+						}
+						auth_protocol: {
+							description: "The protocol used to authenticate messages from this user."
+							required:    false
+							type: string: {
+								default: null
+								enum: {
+									md5:    "HMAC-MD5-96."
+									sha:    "HMAC-SHA-96."
+									sha224: "HMAC-SHA-224, with a 128-bit digest."
+									sha256: "HMAC-SHA-256, with a 192-bit digest."
+									sha384: "HMAC-SHA-384, with a 256-bit digest."
+									sha512: "HMAC-SHA-512, with a 384-bit digest."
+								}
+							}
+						}
+						auth_passphrase: {
+							description:   "The passphrase used to authenticate messages from this user."
+							required:      false
+							relevant_when: "auth_protocol is set"
+							type: string: {
+								default: null
+								examples: ["${SNMP_AUTH_PASSPHRASE}"]
+							}
+						}
+						privacy_protocol: {
+							description: """
+								The protocol used to decrypt messages from this user. Requires
+								`auth_protocol` to be set.
+								"""
+							required: false
+							type: string: {
+								default: null
+								enum: {
+									des: "CBC-DES."
+									aes: "CFB128-AES-128."
+								}
+							}
+						}
+						privacy_passphrase: {
+							description:   "The passphrase used to decrypt messages from this user."
+							required:      false
+							relevant_when: "privacy_protocol is set"
+							type: string: {
+								default: null
+								examples: ["${SNMP_PRIVACY_PASSPHRASE}"]
+							}
+						}
+					}
+				}
+			}
+		}
+	}
+
+	output: logs: trap: {
+		description: "A single trap, or inform, notification."
+		fields: {
+			version: {
+				description: "The SNMP version of the message."
+				required:    true
+				type: string: {
+					enum: {
+						"1":  "SNMPv1."
+						"2c": "SNMPv2c."
+						"3":  "SNMPv3."
+					}
+				}
+			}
+			pdu_type: {
+				description: "The type of notification."
+				required:    true
+				type: string: {
+					enum: {
+						trap:    "An SNMPv1 trap."
+						trap_v2: "An SNMPv2 trap."
+						inform:  "An inform request, which is acknowledged by the source."
+					}
+				}
+			}
+			request_id: {
+				description: "The request ID of SNMPv2c and SNMPv3 notifications."
+				required:    false
+				type: int: {
+					examples: [42]
+				}
+			}
+			uptime: {
+				description: "The uptime of the agent when the notification was sent, in hundredths of a second."
+				required:    false
+				type: uint: {
+					unit: null
+					examples: [12345]
+				}
+			}
+			trap_oid: {
+				description: """
+					The OID identifying the notification. For SNMPv1 traps, it is derived from the
+					generic and specific trap numbers as described in RFC 3584.
+					"""
+				required:    true
+				type: string: {
+					examples: ["1.3.6.1.6.3.1.1.5.3"]
+				}
+			}
+			trap_name: {
+				description: "The name of the notification, when its OID is defined in a loaded MIB."
+				required:    false
+				type: string: {
+					examples: ["IF-MIB::linkDown"]
+				}
+			}
+			enterprise: {
+				description: "The enterprise OID of SNMPv1 traps."
+				required:    false
+				type: string: {
+					examples: ["1.3.6.1.4.1.8072"]
+				}
+			}
+			agent_address: {
+				description: "The agent address of SNMPv1 traps."
+				required:    false
+				type: string: {
+					examples: ["192.0.2.10"]
+				}
+			}
+			generic_trap: {
+				description: "The generic trap number of SNMPv1 traps."
+				required:    false
+				type: int: {
+					examples: [6]
+				}
+			}
+			specific_trap: {
+				description: "The specific trap number of SNMPv1 traps."
+				required:    false
+				type: int: {
+					examples: [17]
+				}
+			}
+			user: {
+				description: "The USM user of SNMPv3 notifications."
+				required:    false
+				type: string: {
+					examples: ["monitoring"]
+				}
+			}
+			security_level: {
+				description: "The security level of SNMPv3 notifications."
+				required:    false
+				type: string: {
+					enum: {
+						no_auth_no_priv: "The message was neither authenticated nor encrypted."
+						auth_no_priv:    "The message was authenticated, but not encrypted."
+						auth_priv:       "The message was authenticated and encrypted."
+					}
+				}
+			}
+			context_engine_id: {
+				description: "The context engine ID of SNMPv3 notifications, encoded as hex."
+				required:    false
+				type: string: {
+					examples: ["80001f8880e9630000d61ff449"]
+				}
+			}
+			context_name: {
+				description: "The context name of SNMPv3 notifications."
+				required:    false
+				type: string: {
+					examples: [""]
+				}
+			}
+			varbinds: {
+				description: """
+					The variable bindings of the notification, excluding `sysUpTime.0` and
+					`snmpTrapOID.0`. Each binding has an `oid`, a `type` and a `value`, and a `name`
+					when its OID is defined in a loaded MIB. Octet strings that are not printable
+					are encoded as hex.
+					"""
+				required:    true
+				type: array: items: type: object: options: {}
+			}
+			host: {
+				description: "The IP address of the agent."
+				required:    true
+				type: string: {
+					examples: ["192.0.2.1"]
+				}
+			}
+			port: {
+				description: "The source port of the agent."
+				required:    true
+				type: uint: {
+					unit: null
+					examples: [50000]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["snmp_trap"]
+				}
+			}
+			timestamp: {
+				description: "The time the notification was received."
+				required:    true
+				type: timestamp: {}
+			}
+		}
+	}
+
+	how_it_works: {
+		informs: {
+			title: "Informs"
+			body: """
+				SNMPv2c inform requests are acknowledged with a response as soon as they are
+				decoded. SNMPv3 informs require the source to act as an authoritative engine and
+				are not acknowledged.
+				"""
+		}
+		mibs: {
+			title: "MIB translation"
+			body: """
+				OIDs are translated to names using the `OBJECT IDENTIFIER`, `OBJECT-TYPE`,
+				`NOTIFICATION-TYPE` and similar definitions of the files listed in `mib_paths`.
+				Names are prefixed with the module defining them, and OIDs below a known node keep
+				their remaining arcs, as in `IF-MIB::ifIndex.3`.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_errors_total:               components.sources.internal_metrics.output.metrics.component_errors_total
+		component_received_bytes_total:       components.sources.internal_metrics.output.metrics.component_received_bytes_total
+		component_received_event_bytes_total: components.sources.internal_metrics.output.metrics.component_received_event_bytes_total
+		component_received_events_total:      components.sources.internal_metrics.output.metrics.component_received_events_total
+	}
+}
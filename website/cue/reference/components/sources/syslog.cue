@@ -57,6 +57,20 @@ components: sources: syslog: {
 				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3"]
 			}
 		}
+		framing: {
+			common:        false
+			description:   "How messages are framed on TCP and Unix socket connections."
+			relevant_when: "mode = `tcp` or `unix`"
+			required:      false
+			type: string: {
+				default: "auto"
+				enum: {
+					auto:            "Detect the framing from the first message of each connection. Connections starting with a non-zero digit use octet counting, others are newline delimited."
+					octet_counting:  "Each message is prefixed with its length in bytes, as described in [RFC 6587](\(urls.syslog_6587)). This is the framing required by RFC 5425 for syslog over TLS."
+					non_transparent: "Each message is terminated by a newline."
+				}
+			}
+		}
 		host_key: {
 			category:    "Context"
 			common:      false
@@ -192,7 +206,7 @@ components: sources: syslog: {
 			}
 			client_metadata: fields._client_metadata
 			"*": {
-				description: "In addition to the defined fields, any [Syslog 5424 structured fields](https://datatracker.ietf.org/doc/html/rfc5424#section-6.3) are parsed and inserted, namespaced under the name of each structured data section."
+				description: "In addition to the defined fields, any [Syslog 5424 structured fields](https://datatracker.ietf.org/doc/html/rfc5424#section-6.3) are parsed and inserted, namespaced under the name of each structured data section. Sections sharing a name are merged, and parameters repeated within a section are collected into an array."
 				required:    true
 				type: string: {
 					examples: ["hello world"]
@@ -242,7 +256,15 @@ components: sources: syslog: {
 		line_delimiters: {
 			title: "Line Delimiters"
 			body: """
-				Each line is read until a new line delimiter, the `0xA` byte, is found.
+				When using TCP or Unix sockets, messages are either prefixed with their length
+				(octet counting) or terminated by a new line delimiter, the `0xA` byte
+				(non-transparent framing), as described in [RFC 6587](\(urls.syslog_6587)). By
+				default the framing is detected from the first message of each connection, so
+				senders using either framing can share the same port. Newlines sent after octet
+				counted messages are ignored.
+
+				A byte order mark at the start of the message, as allowed by
+				[RFC 5424](\(urls.syslog_5424)), is removed.
 				"""
 		}
 
@@ -39,6 +39,64 @@ components: transforms: aggregate: {
 				unit:    "milliseconds"
 			}
 		}
+		mode: {
+			common:      true
+			description: "How the metrics of a series are aggregated over an interval."
+			required:    false
+			type: string: {
+				default: "auto"
+				enum: {
+					auto:         "Sum incremental metrics, and keep the latest value of absolute metrics."
+					sum:          "Sum the values."
+					latest:       "Keep the latest value."
+					count:        "Count the values, as a gauge."
+					min:          "Keep the minimum value, as a gauge."
+					max:          "Keep the maximum value, as a gauge."
+					mean:         "Compute the mean of the values, as a gauge."
+					stdev:        "Compute the population standard deviation of the values, as a gauge."
+					distribution: "Collect the values into a distribution."
+					summary:      "Compute the configured `quantiles` of the values, as an aggregated summary."
+				}
+			}
+		}
+		metric_modes: {
+			common: false
+			description: """
+				The aggregation mode of specific metrics, by metric name. Metrics not listed here are
+				aggregated according to `mode`.
+				"""
+			required: false
+			type: object: {
+				examples: [{"http_request_duration_seconds": "summary"}]
+				options: {}
+			}
+		}
+		quantiles: {
+			common:      false
+			description: "The quantiles computed by the `summary` mode, between `0` and `1`."
+			required:    false
+			type: array: {
+				default: [0.5, 0.9, 0.99]
+				items: type: float: {}
+			}
+		}
+		group_by: {
+			common: false
+			description: """
+				The tags to group series by. The other tags are dropped before aggregating, so that
+				the series only differing by them are rolled up into a single series. When unset, all
+				tags are kept.
+
+				With the `auto` mode, the absolute counters and gauges rolled up into a series are
+				summed: the latest value of each of them is kept, and the series is flushed with their
+				sum.
+				"""
+			required: false
+			type: array: {
+				default: null
+				items: type: string: examples: ["service", "region"]
+			}
+		}
 	}
 
 	input: {
@@ -186,6 +244,22 @@ components: transforms: aggregate: {
 				"""
 		}
 
+		aggregation_modes: {
+			title: "Aggregation Modes"
+			body: """
+				The behavior above is the one of the default `auto` mode. The other modes apply to
+				`counter` and `gauge` metrics, while metrics of other types are still aggregated as by
+				`auto`. The `sum` and `latest` modes keep the type and kind of the metrics, while the
+				`count`, `min`, `max`, `mean`, and `stdev` modes flush an `absolute` `gauge`. The
+				`distribution` mode collects the values of the interval into an `incremental`
+				`distribution`, and the `summary` mode computes the configured `quantiles` of these
+				values into an `absolute` `summary`.
+
+				Combined with `group_by`, the modes allow rolling up high-cardinality series before
+				exporting them, for example summing the requests of all the hosts of a region.
+				"""
+		}
+
 		advantages: {
 			title: "Advantages of Use"
 			body: """
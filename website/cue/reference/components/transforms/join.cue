@@ -0,0 +1,194 @@
+package metadata
+
+components: transforms: join: {
+	title: "Join"
+
+	description: """
+		Joins the log events of two sets of inputs by key, within a window of time, such as
+		request and response logs.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		left: {
+			description: "The left side of the join."
+			required:    true
+			type: object: options: {
+				inputs: {
+					description: """
+						The inputs of the left side of the join. The `inputs` option of the
+						transform itself must not be set.
+						"""
+					required: true
+					type: array: items: type: string: examples: ["requests"]
+				}
+				key: {
+					description: "The key events of the left side are joined by."
+					required:    true
+					type: string: {
+						examples: ["{{ request_id }}"]
+						syntax: "template"
+					}
+				}
+			}
+		}
+		right: {
+			description: "The right side of the join."
+			required:    true
+			type: object: options: {
+				inputs: {
+					description: "The inputs of the right side of the join."
+					required:    true
+					type: array: items: type: string: examples: ["responses"]
+				}
+				key: {
+					description: "The key events of the right side are joined by."
+					required:    true
+					type: string: {
+						examples: ["{{ request_id }}"]
+						syntax: "template"
+					}
+				}
+			}
+		}
+		join_type: {
+			common:      true
+			description: "The type of join."
+			required:    false
+			type: string: {
+				default: "inner"
+				enum: {
+					inner: "Only joined events are sent to the default output. Events of both sides left unmatched are sent to the `unmatched` output."
+					left:  "Joined events, and events of the left side left unmatched, are sent to the default output. Events of the right side left unmatched are sent to the `unmatched` output."
+				}
+			}
+		}
+		window_ms: {
+			common: true
+			description: """
+				The maximum period of time to wait for the matching event of the other side, in
+				milliseconds. The window of each event starts when it was received. Events left
+				unmatched at the end of their window are sent to the `unmatched` output, unless kept
+				by a left join.
+				"""
+			required: false
+			type: uint: {
+				default: 30000
+				unit:    "milliseconds"
+			}
+		}
+		max_pending: {
+			common: false
+			description: """
+				The maximum number of events waiting for a match, across all keys. When exceeded, the
+				oldest event waiting for a match is left unmatched without waiting for the end of its
+				window. When unset, the number of waiting events is only bounded by the window.
+				"""
+			required: false
+			type: uint: {
+				default:  null
+				examples: [100000]
+				unit:     "events"
+			}
+		}
+		merge_strategies: {
+			common: false
+			description: """
+				A map of field names to custom merge strategies. For each field specified, the
+				strategy is used to combine the values of the left and right events. Fields present
+				in both events, and without a merge strategy, keep the value of the left event.
+				"""
+			required: false
+			type: object: {
+				examples: [
+					{
+						duration_ms: "sum"
+						message:     "array"
+					},
+				]
+				options: {
+					"*": {
+						description: "The custom merge strategy to use for a field."
+						required:    true
+						type: string: {
+							enum: components.transforms.reduce.configuration.merge_strategies.type.object.options."*".type.string.enum
+						}
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "unmatched"
+			description: """
+				Events left unmatched when their window expires, or without a key, are sent to the
+				`unmatched` output, except for the events of the left side of a `left` join. For a
+				transform component named `foo`, this output can be accessed by specifying
+				`foo.unmatched` as the input to another component.
+				"""
+		},
+	]
+
+	examples: [
+		{
+			title: "Join requests and responses"
+			configuration: {
+				left: {
+					inputs: ["requests"]
+					key: "{{ request_id }}"
+				}
+				right: {
+					inputs: ["responses"]
+					key: "{{ request_id }}"
+				}
+			}
+			input: [
+				{log: {request_id: "a1", path: "/index.html"}},
+				{log: {request_id: "a1", status: 200}},
+			]
+			output: [
+				{log: {request_id: "a1", path: "/index.html", status: 200}},
+			]
+		},
+	]
+
+	how_it_works: {
+		matching: {
+			title: "Matching events"
+			body: """
+				Events are buffered by key until an event with the same key is received from the
+				other side, or their window expires. Events waiting with the same key are joined in
+				the order they were received, so that each event is joined at most once. The joined
+				event holds the fields of both events, combined according to `merge_strategies`.
+
+				When Vector shuts down, the events still waiting for a match are flushed as if their
+				window expired.
+				"""
+		}
+	}
+}
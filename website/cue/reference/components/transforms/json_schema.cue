@@ -0,0 +1,104 @@
+package metadata
+
+components: transforms: json_schema: {
+	title: "JSON Schema"
+
+	description: """
+		Validates log events against a [JSON Schema](\(urls.json_schema)), routing events that
+		don't match it to a separate output.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		schema_file: {
+			description: """
+				The path of the file holding the JSON Schema events are validated against.
+				"""
+			required: true
+			type: string: {
+				examples: ["/etc/vector/schema.json"]
+			}
+		}
+		draft: {
+			common:   false
+			required: false
+			description: """
+				The draft of the JSON Schema specification the schema follows. When unset, the
+				draft is detected from the `$schema` keyword of the schema, and defaults to
+				draft 7.
+				"""
+			type: string: {
+				default: null
+				enum: {
+					"draft-07": "Draft 7."
+					"2020-12":  "Draft 2020-12."
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "_invalid"
+			description: """
+				Events that don't match the schema are sent to the `_invalid` output instead of the
+				default one. For a transform component named `foo`, this output can be accessed by
+				specifying `foo._invalid` as the input to another component. Each event is annotated
+				with the `path` and `message` of its validation errors, in the `validation_errors`
+				field of its metadata.
+				"""
+		},
+	]
+
+	examples: [
+		{
+			title: "Validate events"
+			configuration: {
+				schema_file: "/etc/vector/schema.json"
+			}
+			input: log: {
+				message: "GET /index.html 200"
+				status:  200
+			}
+			output: log: {
+				message: "GET /index.html 200"
+				status:  200
+			}
+		},
+	]
+
+	how_it_works: {
+		schema_definition: {
+			title: "Schema definition"
+			body: """
+				The types described by the schema are used as the schema of the events sent to the
+				default output, so that the components consuming them know which fields the events
+				have. Keywords that don't constrain the type of values, such as `pattern` or
+				`minimum`, and references to other schemas are ignored for this purpose.
+				"""
+		}
+	}
+}
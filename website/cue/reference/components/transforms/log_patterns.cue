@@ -0,0 +1,174 @@
+package metadata
+
+components: transforms: log_patterns: {
+	title: "Log Patterns"
+
+	description: """
+		Clusters log messages into patterns, attaching the ID and template of its pattern to each
+		event, to help find the noisiest sources of logs.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		field: {
+			common:      true
+			required:    false
+			description: "The field holding the message to cluster. Defaults to the global `log_schema.message_key` option."
+			type: string: {
+				default: null
+				examples: ["message", "parsed.msg"]
+			}
+		}
+		pattern_id_field: {
+			common:   false
+			required: false
+			description: """
+				The field the ID of the pattern of the event is written to. Pattern IDs are derived
+				from the first message of each pattern, with the tokens holding digits masked, so
+				that messages only differing by numbers get the same pattern ID across restarts, and
+				across Vector instances.
+				"""
+			type: string: default: "pattern_id"
+		}
+		pattern_field: {
+			common:   false
+			required: false
+			description: """
+				The field the template of the pattern of the event is written to. The variable
+				parts of the template are replaced by `<*>`.
+				"""
+			type: string: default: "pattern"
+		}
+		similarity_threshold: {
+			common:   false
+			required: false
+			description: """
+				The share of tokens a message must have in common with the template of a pattern to
+				be added to it, between `0` and `1`.
+				"""
+			type: float: default: 0.5
+		}
+		prefix_tokens: {
+			common:   false
+			required: false
+			description: """
+				The number of leading tokens messages must have in common to be added to the same
+				pattern. Tokens holding digits match any token.
+				"""
+			type: uint: {
+				default: 2
+				unit:    null
+			}
+		}
+		expire_after_ms: {
+			common:      false
+			required:    false
+			description: "The period of time after which a pattern without new events is forgotten."
+			type: uint: {
+				default: 3600000
+				unit:    "milliseconds"
+			}
+		}
+		max_patterns: {
+			common:   false
+			required: false
+			description: """
+				The maximum number of patterns kept in memory. Once reached, the pattern that has gone
+				without new events the longest is forgotten to make room for new ones.
+				"""
+			type: uint: {
+				default: 10000
+				unit:    null
+			}
+		}
+		metrics_interval_secs: {
+			common:   true
+			required: false
+			description: """
+				The interval at which metrics are emitted for each pattern. When unset, no metrics
+				are emitted.
+				"""
+			type: uint: {
+				default: null
+				examples: [60]
+				unit: "seconds"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Cluster messages"
+			configuration: {}
+			input: [
+				{log: message: "worker 1 started job 42"},
+				{log: message: "worker 2 started job 43"},
+			]
+			output: [
+				{log: {
+					message:    "worker 1 started job 42"
+					pattern:    "worker 1 started job 42"
+					pattern_id: "0f4cce8fa38e39cb"
+				}},
+				{log: {
+					message:    "worker 2 started job 43"
+					pattern:    "worker <*> started job <*>"
+					pattern_id: "0f4cce8fa38e39cb"
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		clustering: {
+			title: "Clustering"
+			body: """
+				Messages are clustered with an online algorithm based on
+				[Drain](\(urls.drain_paper)). Messages are split into whitespace-separated tokens, and
+				grouped by their number of tokens and their leading tokens. Within a group, a message
+				is added to the pattern whose template it is the most similar to, as long as the
+				share of equal tokens reaches `similarity_threshold`. Tokens of the template that
+				differ from the message are then replaced by `<*>`, so the template of a pattern
+				becomes more general as events are added to it, while its ID stays the same.
+				"""
+		}
+		pattern_metrics: {
+			title: "Pattern metrics"
+			body: """
+				When `metrics_interval_secs` is set, the transform also emits, for each pattern, the
+				`log_pattern_events_total` counter, and the `log_pattern_first_seen_timestamp_seconds`
+				and `log_pattern_last_seen_timestamp_seconds` gauges, tagged with the `pattern_id` of
+				the pattern. Templates aren't used as tags, as they change while patterns become more
+				general. The metrics are sent to the same output as the logs.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		log_patterns_created_total: components.sources.internal_metrics.output.metrics.log_patterns_created_total
+		log_patterns_evicted_total: components.sources.internal_metrics.output.metrics.log_patterns_evicted_total
+		log_patterns_expired_total: components.sources.internal_metrics.output.metrics.log_patterns_expired_total
+	}
+}
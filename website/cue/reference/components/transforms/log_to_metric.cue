@@ -125,6 +125,58 @@ components: transforms: log_to_metric: {
 				}
 			}
 		}
+		window: {
+			common: false
+			description: """
+				Aggregates the generated metrics inside the transform, over a window. When unset, a
+				metric is emitted for each metric configuration matching each event.
+				"""
+			required: false
+			type: object: options: {
+				mode: {
+					common:      true
+					description: "The type of window metrics are aggregated over."
+					required:    false
+					type: string: {
+						default: "tumbling"
+						enum: {
+							tumbling: "Consecutive windows, which don't overlap. The metrics of each series are emitted once per window, with their original kind."
+							sliding:  "Windows covering the last `length_secs`, emitted every `step_secs`. As consecutive windows overlap, metrics are emitted as absolute values, and counters as gauges."
+						}
+					}
+				}
+				length_secs: {
+					common:      true
+					description: "The length of the window, in seconds."
+					required:    false
+					type: uint: {
+						default: 60
+						unit:    "seconds"
+					}
+				}
+				step_secs: {
+					common:        false
+					description:   "The interval at which sliding windows are emitted, in seconds. Must divide `length_secs`."
+					relevant_when: "mode = \"sliding\""
+					required:      false
+					type: uint: {
+						default: null
+						examples: [10]
+						unit: "seconds"
+					}
+				}
+				sketches: {
+					common: false
+					description: """
+						Whether to emit histograms and summaries as sketches. Values are inserted into
+						the sketch of their series as they are received, which bounds the memory used
+						by the window, and the size of the emitted metrics.
+						"""
+					required: false
+					type: bool: default: false
+				}
+			}
+		}
 	}
 
 	input: {
@@ -410,7 +462,7 @@ components: transforms: log_to_metric: {
 		reducing: {
 			title: "Reducing"
 			body:  """
-				It's important to understand that this transform does not reduce multiple logs
+				Unless `window` is set, this transform does not reduce multiple logs
 				to a single metric. Instead, this transform converts logs into granular
 				individual metrics that can then be reduced at the edge. Where the reduction
 				happens depends on your metrics storage. For example, the
@@ -419,6 +471,17 @@ components: transforms: log_to_metric: {
 				individual metrics for reduction in the metrics storage itself.
 				"""
 		}
+		windows: {
+			title: "Windows"
+			body: """
+				When `window` is set, the metrics are aggregated by series, that is by name,
+				namespace, and tags, within the transform. Incremental values are summed, and the
+				latest absolute values are kept. The aggregated metrics are emitted when the window
+				ends, and when Vector shuts down, which can cut the number of emitted metrics by
+				orders of magnitude at high volume. Since tags are templated, they define the groups
+				events are counted by.
+				"""
+		}
 		null_fields: {
 			title: "Null Fields"
 			body: """
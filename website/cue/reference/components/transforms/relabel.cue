@@ -0,0 +1,185 @@
+package metadata
+
+components: transforms: relabel: {
+	title: "Relabel"
+
+	description: """
+		Rewrites the names and tags of metric events, or drops them, following
+		[Prometheus relabeling rules](\(urls.prometheus_relabel_config)).
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		relabel_configs: {
+			description: """
+				The relabeling rules, applied in order. The rules follow the format of the
+				Prometheus `metric_relabel_configs` option, which is also accepted as an alias of
+				this option, so that they can be copied from a Prometheus configuration as is.
+				"""
+			required: true
+			type: array: items: type: object: options: {
+				source_labels: {
+					common: true
+					description: """
+						The labels whose values are concatenated and matched against `regex`. The
+						name of the metric is available as the `__name__` label. Missing labels are
+						treated as empty strings.
+						"""
+					required: false
+					type: array: {
+						default: []
+						items: type: string: examples: ["__name__", "job"]
+					}
+				}
+				separator: {
+					common:      false
+					description: "The separator placed between the values of `source_labels`."
+					required:    false
+					type: string: default: ";"
+				}
+				target_label: {
+					common: true
+					description: """
+						The label the result of the `replace` and `hashmod` actions is written to.
+						References to the capture groups of `regex` are expanded for the `replace`
+						action. Required by these actions.
+						"""
+					required: false
+					type: string: {
+						default: null
+						examples: ["instance", "__name__"]
+					}
+				}
+				regex: {
+					common: true
+					description: """
+						The regular expression the concatenated values, or the label names for the
+						`labelmap`, `labeldrop`, and `labelkeep` actions, are matched against. As
+						in Prometheus, the expression is anchored on both ends.
+						"""
+					required: false
+					type: string: {
+						default: "(.*)"
+						examples: ["go_.*", "([^:]+):\\d+"]
+					}
+				}
+				modulus: {
+					common:      false
+					description: "The modulus applied to the hash of the concatenated values by the `hashmod` action."
+					required:    false
+					type: uint: {
+						default: null
+						examples: [8]
+						unit: null
+					}
+				}
+				replacement: {
+					common: false
+					description: """
+						The value written by the `replace` and `labelmap` actions. References to
+						the capture groups of `regex`, such as `$1` or `${name}`, are expanded.
+						"""
+					required: false
+					type: string: default: "$1"
+				}
+				action: {
+					common:      true
+					description: "The action of the rule."
+					required:    false
+					type: string: {
+						default: "replace"
+						enum: {
+							replace:   "Write `replacement` to `target_label` when `regex` matches the concatenated values. When `replacement` expands to an empty string, `target_label` is removed instead."
+							keep:      "Drop metrics for which `regex` doesn't match the concatenated values."
+							drop:      "Drop metrics for which `regex` matches the concatenated values."
+							hashmod:   "Write the MD5 hash of the concatenated values modulo `modulus` to `target_label`."
+							labelmap:  "Copy the tags whose name matches `regex` to the tags named by `replacement`."
+							labeldrop: "Remove the tags whose name matches `regex`."
+							labelkeep: "Remove the tags whose name doesn't match `regex`."
+						}
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Drop and rename metrics"
+			configuration: {
+				relabel_configs: [
+					{
+						source_labels: ["__name__"]
+						regex:  "go_.*"
+						action: "drop"
+					},
+					{
+						source_labels: ["instance"]
+						regex:        "([^:]+):\\d+"
+						target_label: "host"
+					},
+				]
+			}
+			input: metric: {
+				kind: "absolute"
+				name: "node_load1"
+				gauge: value: 0.5
+				tags: instance: "web-1:9100"
+			}
+			output: metric: {
+				kind: "absolute"
+				name: "node_load1"
+				gauge: value: 0.5
+				tags: {
+					host:     "web-1"
+					instance: "web-1:9100"
+				}
+			}
+		},
+	]
+
+	how_it_works: {
+		labels: {
+			title: "Labels"
+			body: """
+				The tags of a metric are its labels, and its name is available as the `__name__`
+				label, so that it can be matched and rewritten like any other label. The namespace of
+				the metric isn't part of its name. Metrics whose `__name__` label is removed, or set to
+				an empty string, are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
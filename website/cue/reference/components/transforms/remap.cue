@@ -41,6 +41,9 @@ components: transforms: "remap": {
 				The [Vector Remap Language](\(urls.vrl_reference)) (VRL) program to execute for each event.
 
 				Required if `file` is missing.
+
+				Functions are shared between `remap` transforms by defining them in a VRL file, imported by each
+				program with an [`import`](\(urls.vrl_expressions)#import) statement.
 				"""
 			common:      true
 			required:    false
@@ -75,6 +78,18 @@ components: transforms: "remap": {
 				]
 			}
 		}
+		import_root: {
+			common:   false
+			required: false
+			description: """
+				The directory relative paths in VRL `import` statements are resolved against. By default, it's the
+				directory of the configuration file defining the transform.
+				"""
+			type: string: {
+				default: null
+				examples: ["/etc/vector/vrl"]
+			}
+		}
 		drop_on_error: {
 			common:   false
 			required: false
@@ -104,6 +119,72 @@ components: transforms: "remap": {
 				"""
 			type: bool: default: false
 		}
+		state: {
+			common:      false
+			required:    false
+			description: """
+				Configuration of the state kept across events by the `state_*` VRL functions. The state is scoped to
+				the transform, and shared by all events it processes.
+				"""
+			type: object: options: {
+				max_keys: {
+					common:      false
+					required:    false
+					description: """
+						The maximum number of keys in the state. When the limit is reached, expired keys are dropped
+						first, followed by the least recently written ones.
+						"""
+					type: uint: {
+						default: 10000
+						unit:    null
+					}
+				}
+				max_bytes: {
+					common:      false
+					required:    false
+					description: """
+						The maximum estimated size of the state. When the limit is reached, expired keys are dropped
+						first, followed by the least recently written ones.
+						"""
+					type: uint: {
+						default: 10485760
+						unit:    "bytes"
+					}
+				}
+				persist: {
+					common:      false
+					required:    false
+					description: """
+						Whether the state is persisted while the transform runs and when it shuts down, and
+						restored when it starts. The state is stored in a subdirectory of the data directory
+						named after the component ID. If Vector is killed or crashes, the changes made since
+						the state was last written are lost.
+						"""
+					type: bool: default: false
+				}
+				persist_interval_secs: {
+					common:      false
+					required:    false
+					description: "How often the state is persisted while the transform runs."
+					type: uint: {
+						default: 60
+						unit:    "seconds"
+					}
+				}
+				data_dir: {
+					common:      false
+					required:    false
+					description: """
+						The directory used to persist the state. By default, the
+						[global `data_dir` option](\(urls.vector_configuration)/global-options#data_dir) is used.
+						"""
+					type: string: {
+						default: null
+						examples: ["/var/lib/vector"]
+					}
+				}
+			}
+		}
 	}
 
 	input: {
@@ -0,0 +1,150 @@
+package metadata
+
+components: transforms: trace_sample: {
+	title: "Trace Sample"
+
+	description: """
+		Samples whole traces, deciding whether to keep them once their spans have been buffered
+		for a while.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		decision_wait_ms: {
+			common:   true
+			required: false
+			description: """
+				The period of time to buffer the spans of a trace, in milliseconds, before deciding
+				whether the trace is kept. The wait starts when the first span of a trace is
+				received. Spans received after the decision follow it, as long as they are received
+				within another `decision_wait_ms`.
+				"""
+			type: uint: {
+				default: 10000
+				unit:    "milliseconds"
+			}
+		}
+		max_traces: {
+			common:   false
+			required: false
+			description: """
+				The maximum number of traces buffered while waiting for a decision. When reached,
+				the decision for the oldest buffered trace is made early.
+				"""
+			type: uint: {
+				default: 50000
+				unit:    null
+			}
+		}
+		max_bytes: {
+			common:   false
+			required: false
+			description: """
+				The maximum size of the events buffered while waiting for a decision. When reached,
+				the decision for the oldest buffered trace is made early.
+				"""
+			type: uint: {
+				default: 104857600
+				unit:    "bytes"
+			}
+		}
+		policies: {
+			common:      true
+			required:    false
+			description: "The policies deciding whether a trace is kept."
+			type: object: options: {
+				keep_errors: {
+					common:      true
+					required:    false
+					description: "Whether to keep all traces with at least one span in error."
+					type: bool: default: true
+				}
+				latency_threshold_ms: {
+					common:      true
+					required:    false
+					description: "Keep all traces whose longest span lasted longer than this."
+					type: uint: {
+						default: null
+						examples: [1000]
+						unit: "milliseconds"
+					}
+				}
+				rate: {
+					common:   true
+					required: false
+					description: """
+						The rate at which the remaining traces are kept, expressed as `1/N`. The
+						decision is based on a hash of the trace ID, so that all Vector instances
+						make the same decision for a given trace.
+						"""
+					type: uint: {
+						default: 10
+						unit:    null
+					}
+				}
+				max_traces_per_second: {
+					common:   false
+					required: false
+					description: """
+						The maximum number of traces kept by `rate` per second, for each service.
+						The service of a trace is the service of its root span.
+						"""
+					type: uint: {
+						default: null
+						examples: [100]
+						unit: null
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		policies: {
+			title: "Sampling policies"
+			body: """
+				Spans are grouped by the ID of their trace, and buffered until `decision_wait_ms`
+				has elapsed since the first span of the trace was received. The whole trace is then
+				kept or dropped:
+
+				1. Traces with a span in error are kept, unless `policies.keep_errors` is `false`.
+				2. Traces whose longest span lasted longer than `policies.latency_threshold_ms` are
+				   kept.
+				3. One out of `policies.rate` of the remaining traces is kept, up to
+				   `policies.max_traces_per_second` for each service.
+
+				Spans without a trace ID are forwarded as is.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		buffered_traces:                  components.sources.internal_metrics.output.metrics.buffered_traces
+		buffered_trace_bytes:             components.sources.internal_metrics.output.metrics.buffered_trace_bytes
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		sampled_traces_total:             components.sources.internal_metrics.output.metrics.sampled_traces_total
+		traces_decided_early_total:       components.sources.internal_metrics.output.metrics.traces_decided_early_total
+	}
+}
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "112": {
+	title:       "Duplicate function definition"
+	description: """
+		A [function definition](\(urls.vrl_expressions)#function-definition) uses the name of a built-in function, or
+		of a function that is already defined in the program or in one of its imports.
+		"""
+	resolution: """
+		Rename one of the functions. Function names must be unique, and can't shadow built-in functions.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (built-in function)"
+			source: #"""
+				fn upcase(value) { value + "!" }
+				"""#
+			diff: #"""
+				-fn upcase(value) { value + "!" }
+				+fn exclaim(value) { value + "!" }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,25 @@
+package metadata
+
+remap: errors: "113": {
+	title:       "Duplicate function parameter"
+	description: """
+		A [function definition](\(urls.vrl_expressions)#function-definition) declares the same parameter more than
+		once.
+		"""
+	resolution: """
+		Give each parameter of the function a unique name.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				fn add(value, value) { value + value }
+				"""#
+			diff: #"""
+				-fn add(value, value) { value + value }
+				+fn add(a, b) { a + b }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,29 @@
+package metadata
+
+remap: errors: "114": {
+	title:       "Recursive function call"
+	description: """
+		A function defined in the program calls itself, either directly or through another user-defined function.
+		"""
+	rationale: """
+		VRL programs are guaranteed to terminate. Recursive functions can run indefinitely, and are therefore rejected
+		at compile time.
+		"""
+	resolution: """
+		Rewrite the function without recursion, for example by using one of the iteration functions, such as
+		`map_values`, or by unrolling a recursion with a known, limited depth.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				fn flatten_twice(value) { flatten_twice(flatten(value)) }
+				"""#
+			diff: #"""
+				-fn flatten_twice(value) { flatten_twice(flatten(value)) }
+				+fn flatten_twice(value) { flatten(flatten(value)) }
+				"""#
+		},
+	]
+}
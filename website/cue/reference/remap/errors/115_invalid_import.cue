@@ -0,0 +1,28 @@
+package metadata
+
+remap: errors: "115": {
+	title:       "Invalid import"
+	description: """
+		An [import](\(urls.vrl_expressions)#import) refers to a file that can't be read, that fails to parse, or that
+		contains anything other than function definitions and imports.
+		"""
+	resolution: """
+		Make sure the file exists, and that it only contains function definitions and imports. Relative paths are
+		resolved against the directory of the configuration file defining the `remap` transform.
+		"""
+
+	examples: [
+		{
+			"title": "\(title) (expressions in imported file)"
+			source: #"""
+				# common.vrl
+				.processed = true
+				fn normalize(value) { downcase(strip_whitespace(value)) }
+				"""#
+			diff: #"""
+				-.processed = true
+				 fn normalize(value) { downcase(strip_whitespace(value)) }
+				"""#
+		},
+	]
+}
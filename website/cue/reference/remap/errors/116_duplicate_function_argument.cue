@@ -0,0 +1,27 @@
+package metadata
+
+remap: errors: "116": {
+	title:       "Duplicate function argument"
+	description: """
+		A call to a [user-defined function](\(urls.vrl_expressions)#function-definition) passes more than one
+		argument for the same parameter, either by repeating a keyword, or by passing a keyword argument for a
+		parameter that a positional argument is already assigned to.
+		"""
+	resolution: """
+		Pass a single argument for each parameter of the function.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				fn add(a, b) { a + b }
+				add(1, a: 2)
+				"""#
+			diff: #"""
+				-add(1, a: 2)
+				+add(1, b: 2)
+				"""#
+		},
+	]
+}
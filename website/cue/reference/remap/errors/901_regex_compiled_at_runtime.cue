@@ -0,0 +1,32 @@
+package metadata
+
+remap: errors: "901": {
+	title: "Regex compiled at runtime"
+	description: """
+		A regular expression is built with `to_regex` from a string that never changes.
+		"""
+
+	rationale: """
+		`to_regex` compiles its argument each time the program runs, which is much more expensive than matching
+		against a regular expression. Regex literals are compiled once, when the program is compiled.
+
+		This warning is only reported when performance lints are enabled, as they are by `vector validate`.
+		"""
+
+	resolution: """
+		Replace the call with a regex literal.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				.matched = match(.message, to_regex!("^[a-z]+$"))
+				"""#
+			diff: #"""
+				-.matched = match(.message, to_regex!("^[a-z]+$"))
+				+.matched = match(.message, r'^[a-z]+$')
+				"""#
+		},
+	]
+}
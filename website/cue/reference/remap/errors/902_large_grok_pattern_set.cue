@@ -0,0 +1,20 @@
+package metadata
+
+remap: errors: "902": {
+	title: "Large grok pattern set"
+	description: """
+		A `parse_groks` call tries more than 16 patterns against each event.
+		"""
+
+	rationale: """
+		`parse_groks` tries its patterns in order until one of them matches, so events matching none of them, or only
+		the last ones, are matched against each pattern. The cost of the call grows with the number of patterns.
+
+		This warning is only reported when performance lints are enabled, as they are by `vector validate`.
+		"""
+
+	resolution: """
+		Split the patterns between several `parse_groks` calls, and pick the call to make with a cheaper check of the
+		event, such as its source or a prefix of its message.
+		"""
+}
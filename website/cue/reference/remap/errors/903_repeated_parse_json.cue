@@ -0,0 +1,35 @@
+package metadata
+
+remap: errors: "903": {
+	title: "Value parsed as JSON more than once"
+	description: """
+		`parse_json` is called several times on the same value, which wasn't changed in between.
+		"""
+
+	rationale: """
+		Each call parses the whole value again, even when only a single field of the result is used.
+
+		This warning is only reported when performance lints are enabled, as they are by `vector validate`.
+		"""
+
+	resolution: """
+		Assign the parsed value to a variable once, and reuse it.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				.user = parse_json!(.message).user
+				.action = parse_json!(.message).action
+				"""#
+			diff: #"""
+				-.user = parse_json!(.message).user
+				-.action = parse_json!(.message).action
+				+parsed = parse_json!(.message)
+				+.user = parsed.user
+				+.action = parsed.action
+				"""#
+		},
+	]
+}
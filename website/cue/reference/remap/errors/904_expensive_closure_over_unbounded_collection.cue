@@ -0,0 +1,33 @@
+package metadata
+
+remap: errors: "904": {
+	title: "Expensive closure over a collection of unknown size"
+	description: """
+		An iteration function, such as `for_each` or `map_values`, runs an expensive closure over a collection whose
+		size isn't bounded.
+		"""
+
+	rationale: """
+		The closure runs once per element, so the cost of the program grows with the size of the collection found in
+		each event, which a single large event can make arbitrarily high.
+
+		This warning is only reported when performance lints are enabled, as they are by `vector validate`.
+		"""
+
+	resolution: """
+		Bound the size of the collection with `slice`, or move the expensive calls out of the closure.
+		"""
+
+	examples: [
+		{
+			"title": "\(title)"
+			source: #"""
+				.items = map_values(array!(.items)) -> |item| { parse_json!(item) }
+				"""#
+			diff: #"""
+				-.items = map_values(array!(.items)) -> |item| { parse_json!(item) }
+				+.items = map_values(slice!(array!(.items), 0, 100)) -> |item| { parse_json!(item) }
+				"""#
+		},
+	]
+}
@@ -0,0 +1,68 @@
+package metadata
+
+remap: expressions: function_definition: {
+	title:       "Function definition"
+	description: """
+		A _function definition_ declares a named function that can be called from anywhere after the definition
+		in the program, using the [function call](\(urls.vrl_expressions)#function-call) syntax.
+
+		Function definitions must appear at the top level of a program. The name of a function can't be the name of a
+		built-in function, or of another function defined in the program. Functions can't call themselves, directly or
+		through other functions, which guarantees that every VRL program terminates.
+		"""
+	return: """
+		A function definition doesn't return a value. Calling the function returns the value of the last expression in
+		its body. If an expression in the body fails, the call fails, and the error must be
+		[handled](\(urls.vrl_errors_reference)) by the caller.
+		"""
+
+	grammar: {
+		source: """
+			"fn" ~ name ~ "(" ~ parameters? ~ ")" ~ block
+			"""
+		definitions: {
+			name: {
+				description: """
+					`name` is the name the function is called by.
+					"""
+			}
+			parameters: {
+				description: """
+					`parameters` is a comma-delimited list of names. Arguments can be passed to the function by position,
+					or by using these names as keywords.
+					"""
+			}
+			block: {
+				description: """
+					`block` is the body of the function. The body is type checked at each call site, using the types of
+					the arguments passed to it. It can read and modify the event, but it runs in a scope of its own, so
+					the variables of the caller aren't visible to it, and its own variables aren't visible to the
+					caller.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Function definition"
+			input: log: {
+				first_name: "Jane"
+				last_name:  "Doe"
+			}
+			source: #"""
+				fn full_name(first, last) {
+					first + " " + last
+				}
+
+				.name = full_name(string!(.first_name), last: string!(.last_name))
+				"""#
+			return: "Jane Doe"
+			output: log: {
+				first_name: "Jane"
+				last_name:  "Doe"
+				name:       "Jane Doe"
+			}
+		},
+	]
+}
@@ -0,0 +1,44 @@
+package metadata
+
+remap: expressions: import: {
+	title:       "Import"
+	description: """
+		An _import_ expression makes the [functions defined](\(urls.vrl_expressions)#function-definition) in another
+		file available to the program.
+
+		Imported files can only contain function definitions and other imports. A file is imported only once, even if
+		it's imported multiple times. Imports are how `remap` transforms share function definitions: the functions
+		are defined once in a file, imported by each program.
+
+		Relative paths are resolved against the directory of the configuration file defining the `remap` transform,
+		or its `import_root` option when set.
+		"""
+	return: """
+		An import doesn't return a value.
+		"""
+
+	grammar: {
+		source: """
+			"import" ~ path
+			"""
+		definitions: {
+			path: {
+				description: """
+					`path` is a string literal containing the path of the file to import.
+					"""
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Import shared functions"
+			source: #"""
+				import "vrl/common.vrl"
+
+				.message = normalize(.message)
+				"""#
+			skip_test: true
+		},
+	]
+}
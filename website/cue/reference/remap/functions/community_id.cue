@@ -0,0 +1,84 @@
+package metadata
+
+remap: functions: community_id: {
+	category: "IP"
+	description: """
+		Generates the [Community ID](\(urls.community_id)) of a network flow, a hash that is the
+		same for both directions of the flow. Zeek, Suricata and other network monitoring tools
+		compute it too, so it can be used to correlate their logs.
+
+		Ports are only used for the TCP, UDP, SCTP, ICMP and ICMPv6 protocols. For ICMP and ICMPv6,
+		the message type and code are given as `source_port` and `destination_port`.
+		"""
+
+	arguments: [
+		{
+			name:        "source_ip"
+			description: "The source IP address."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "destination_ip"
+			description: "The destination IP address."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "source_port"
+			description: "The source port, or the ICMP message type."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "destination_port"
+			description: "The destination port, or the ICMP message code."
+			required:    false
+			type: ["integer"]
+		},
+		{
+			name:        "protocol"
+			description: "The [IANA number](\(urls.iana_protocol_numbers)) of the transport protocol."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "seed"
+			description: "The seed of the hash, to keep IDs from different networks apart."
+			required:    false
+			default:     0
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`source_ip` or `destination_ip` isn't a valid IP address.",
+		"`source_ip` and `destination_ip` have different IP versions.",
+		"`protocol` isn't between 0 and 255.",
+		"`source_port`, `destination_port` or `seed` isn't between 0 and 65535.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Generate the Community ID of a TCP flow"
+			source: #"""
+				community_id!(source_ip: "128.232.110.120", destination_ip: "66.35.250.204", source_port: 34855, destination_port: 80, protocol: 6)
+				"""#
+			return: "1:LQU9qZlK+B5F3KDmev6m5PMibrg="
+		},
+		{
+			title: "Generate the Community ID of the reverse direction"
+			source: #"""
+				community_id!(source_ip: "66.35.250.204", destination_ip: "128.232.110.120", source_port: 80, destination_port: 34855, protocol: 6)
+				"""#
+			return: "1:LQU9qZlK+B5F3KDmev6m5PMibrg="
+		},
+		{
+			title: "Generate the Community ID with positional arguments"
+			source: #"""
+				community_id!("128.232.110.120", "66.35.250.204", 34855, 80, 6)
+				"""#
+			return: "1:LQU9qZlK+B5F3KDmev6m5PMibrg="
+		},
+	]
+}
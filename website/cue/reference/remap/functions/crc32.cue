@@ -0,0 +1,29 @@
+package metadata
+
+remap: functions: crc32: {
+	category:    "Checksum"
+	description: """
+		Calculates the [CRC-32](\(urls.crc)) checksum of the `value`, as used by gzip and zlib.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the checksum for."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Calculate a CRC-32 checksum"
+			source: #"""
+				crc32("foo")
+				"""#
+			return: 2356372769
+		},
+	]
+}
@@ -0,0 +1,46 @@
+package metadata
+
+remap: functions: decode_gzip: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Gzip](\(urls.gzip)) compressed string) into its original string.
+
+		Concatenated gzip members, as produced by appending to a gzip file, are decoded as a single
+		value.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Gzip](\(urls.gzip)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "max_size"
+			description: """
+				The maximum size of the decoded value, in bytes. Values decompressing to more than
+				`max_size` bytes return an error, so that small malicious payloads can't exhaust memory.
+				"""
+			required: false
+			default:  10485760
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Gzip string.",
+		"The decoded `value` is larger than `max_size`.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Gzip data"
+			source: #"""
+				encoded_text = decode_base64!("H4sIAAAAAAACAw3LyQ3AIAwEwFZcAT05ZjkUgiUfkeg+mf8cTRr8gjxF4N5yrUMVohWVHhTR3Y0jF8fU7eX8gQ10GThG8D13Lx+GGe6BRgAAAA==")
+				decode_gzip!(encoded_text)
+				"""#
+			return: "you have successfully decoded me.congratulations.you are breathtaking."
+		},
+	]
+}
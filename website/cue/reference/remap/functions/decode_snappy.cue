@@ -0,0 +1,46 @@
+package metadata
+
+remap: functions: decode_snappy: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Snappy](\(urls.snappy)) compressed string) into its original string.
+
+		The value must use the raw Snappy format, as used by the Prometheus remote write protocol,
+		not the framed format.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Snappy](\(urls.snappy)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "max_size"
+			description: """
+				The maximum size of the decoded value, in bytes. Values decompressing to more than
+				`max_size` bytes return an error, so that small malicious payloads can't exhaust memory.
+				"""
+			required: false
+			default:  10485760
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Snappy string.",
+		"The decoded `value` is larger than `max_size`.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Snappy data"
+			source: #"""
+				encoded_text = decode_base64!("RvBFeW91IGhhdmUgc3VjY2Vzc2Z1bGx5IGRlY29kZWQgbWUuY29uZ3JhdHVsYXRpb25zLnlvdSBhcmUgYnJlYXRodGFraW5nLg==")
+				decode_snappy!(encoded_text)
+				"""#
+			return: "you have successfully decoded me.congratulations.you are breathtaking."
+		},
+	]
+}
@@ -0,0 +1,43 @@
+package metadata
+
+remap: functions: decode_zlib: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Zlib](\(urls.zlib)) compressed string) into its original string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Zlib](\(urls.zlib)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "max_size"
+			description: """
+				The maximum size of the decoded value, in bytes. Values decompressing to more than
+				`max_size` bytes return an error, so that small malicious payloads can't exhaust memory.
+				"""
+			required: false
+			default:  10485760
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Zlib string.",
+		"The decoded `value` is larger than `max_size`.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Zlib data"
+			source: #"""
+				encoded_text = decode_base64!("eJwNy8kNwCAMBMBWXAE9OWY5FIIlH5HoPpn/HE0a/II8ReDecq1DFaIVlR4U0d2NIxfH1O3l/IENdBk4RvA9dy8fv7wa8Q==")
+				decode_zlib!(encoded_text)
+				"""#
+			return: "you have successfully decoded me.congratulations.you are breathtaking."
+		},
+	]
+}
@@ -0,0 +1,43 @@
+package metadata
+
+remap: functions: decode_zstd: {
+	category:    "Codec"
+	description: """
+		Decodes the `value` (a [Zstandard](\(urls.zstd)) compressed string) into its original string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The [Zstandard](\(urls.zstd)) data to decode."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "max_size"
+			description: """
+				The maximum size of the decoded value, in bytes. Values decompressing to more than
+				`max_size` bytes return an error, so that small malicious payloads can't exhaust memory.
+				"""
+			required: false
+			default:  10485760
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid encoded Zstandard string.",
+		"The decoded `value` is larger than `max_size`.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Decode Zstandard data"
+			source: #"""
+				encoded_text = decode_base64!("KLUv/SBGMQIAeW91IGhhdmUgc3VjY2Vzc2Z1bGx5IGRlY29kZWQgbWUuY29uZ3JhdHVsYXRpb25zLnlvdSBhcmUgYnJlYXRodGFraW5nLg==")
+				decode_zstd!(encoded_text)
+				"""#
+			return: "you have successfully decoded me.congratulations.you are breathtaking."
+		},
+	]
+}
@@ -0,0 +1,47 @@
+package metadata
+
+remap: functions: encode_cef: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` object to [Common Event Format](\(urls.cef)) (CEF), the inverse of
+		`parse_cef`.
+
+		The header is built from the `deviceVendor`, `deviceProduct`, `deviceVersion`,
+		`deviceEventClassId`, `name` and `severity` fields, and from `cefVersion`, which defaults to
+		`0`. The remaining fields form the extension, in alphabetical order. Null fields are skipped,
+		and timestamps are encoded as milliseconds since the Unix epoch.
+		"""
+	arguments: [
+		{
+			name:        "value"
+			description: "The object to encode."
+			required:    true
+			type: ["object"]
+		},
+	]
+	internal_failure_reasons: [
+		"A header field is missing from `value`.",
+		"A field of `value` is an array or an object.",
+		"A key of `value` contains characters other than letters, digits, `_`, `.`, `-`, `[` and `]`.",
+	]
+	return: types: ["string"]
+	examples: [
+		{
+			title: "Encode to CEF"
+			source: #"""
+				encode_cef!({
+					"deviceVendor": "Security",
+					"deviceProduct": "threatmanager",
+					"deviceVersion": "1.0",
+					"deviceEventClassId": 100,
+					"name": "worm successfully stopped",
+					"severity": 10,
+					"src": "10.0.0.1",
+					"cs1": "guest",
+					"cs1Label": "Role"
+				})
+				"""#
+			return: "CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|cs1=guest cs1Label=Role src=10.0.0.1"
+		},
+	]
+}
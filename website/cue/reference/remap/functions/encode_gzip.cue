@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: encode_gzip: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` to [Gzip](\(urls.gzip)).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to encode."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "compression_level"
+			description: "The compression level, from 0 (fastest) to 9 (smallest)."
+			required:    false
+			default:     6
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`compression_level` isn't between 0 and 9.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode to Gzip"
+			source: #"""
+				encoded_text = encode_gzip("please encode me", compression_level: 9)
+				decode_gzip!(encoded_text)
+				"""#
+			return: "please encode me"
+		},
+	]
+}
@@ -0,0 +1,39 @@
+package metadata
+
+remap: functions: encode_zlib: {
+	category:    "Codec"
+	description: """
+		Encodes the `value` to [Zlib](\(urls.zlib)).
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to encode."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "compression_level"
+			description: "The compression level, from 0 (fastest) to 9 (smallest)."
+			required:    false
+			default:     6
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`compression_level` isn't between 0 and 9.",
+	]
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Encode to Zlib"
+			source: #"""
+				encoded_text = encode_zlib("please encode me", compression_level: 9)
+				decode_zlib!(encoded_text)
+				"""#
+			return: "please encode me"
+		},
+	]
+}
@@ -0,0 +1,57 @@
+package metadata
+
+remap: functions: hmac: {
+	category:    "Cryptography"
+	description: """
+		Calculates an [HMAC](\(urls.hmac)) of the `value` using the given `key`, returned as a
+		hex encoded string.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the HMAC for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "key"
+			description: "The secret key."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "algorithm"
+			description: "The hashing algorithm to use."
+			enum: {
+				"SHA1":    "SHA1 algorithm"
+				"SHA-224": "SHA-224 algorithm"
+				"SHA-256": "SHA-256 algorithm"
+				"SHA-384": "SHA-384 algorithm"
+				"SHA-512": "SHA-512 algorithm"
+			}
+			required: false
+			default:  "SHA-256"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string"]
+
+	examples: [
+		{
+			title: "Verify a webhook signature"
+			source: #"""
+				hmac("foo", "secret") == "773ba44693c7553d6ee20f61ea5d2757a9a4f4a44d2841ae4e95b52e4cd62db4"
+				"""#
+			return: true
+		},
+		{
+			title: "Calculate a SHA1 HMAC"
+			source: #"""
+				hmac("foo", "secret", algorithm: "SHA1")
+				"""#
+			return: "9baed91be7f58b57c824b60da7cb262b2ecafbd2"
+		},
+	]
+}
@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: ip_is_link_local: {
+	category: "IP"
+	description: """
+		Determines whether the IP address `value` is a link-local address: in `169.254.0.0/16` for IPv4,
+		or `fe80::/10` for IPv6.
+
+		IPv4-mapped IPv6 addresses, such as `::ffff:10.0.0.1`, are classified like the IPv4
+		address they map.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address (v4 or v6)."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address",
+	]
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "IPv4 link-local address"
+			source: #"""
+				ip_is_link_local!("169.254.10.1")
+				"""#
+			return: true
+		},
+		{
+			title: "IPv6 address"
+			source: #"""
+				ip_is_link_local!("2001:db8::1")
+				"""#
+			return: false
+		},
+	]
+}
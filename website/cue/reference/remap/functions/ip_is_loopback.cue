@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: ip_is_loopback: {
+	category: "IP"
+	description: """
+		Determines whether the IP address `value` is a loopback address: in `127.0.0.0/8` for IPv4, or
+		`::1` for IPv6.
+
+		IPv4-mapped IPv6 addresses, such as `::ffff:10.0.0.1`, are classified like the IPv4
+		address they map.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address (v4 or v6)."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address",
+	]
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "IPv4 loopback address"
+			source: #"""
+				ip_is_loopback!("127.0.0.1")
+				"""#
+			return: true
+		},
+		{
+			title: "IPv6 loopback address"
+			source: #"""
+				ip_is_loopback!("::1")
+				"""#
+			return: true
+		},
+	]
+}
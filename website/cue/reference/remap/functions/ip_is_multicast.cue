@@ -0,0 +1,42 @@
+package metadata
+
+remap: functions: ip_is_multicast: {
+	category: "IP"
+	description: """
+		Determines whether the IP address `value` is a multicast address: in `224.0.0.0/4` for IPv4, or
+		`ff00::/8` for IPv6.
+
+		IPv4-mapped IPv6 addresses, such as `::ffff:10.0.0.1`, are classified like the IPv4
+		address they map.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address (v4 or v6)."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address",
+	]
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "IPv4 multicast address"
+			source: #"""
+				ip_is_multicast!("224.0.0.251")
+				"""#
+			return: true
+		},
+		{
+			title: "IPv6 multicast address"
+			source: #"""
+				ip_is_multicast!("ff02::fb")
+				"""#
+			return: true
+		},
+	]
+}
@@ -0,0 +1,43 @@
+package metadata
+
+remap: functions: ip_is_private: {
+	category: "IP"
+	description: """
+		Determines whether the IP address `value` is a private address: in the `10.0.0.0/8`,
+		`172.16.0.0/12` or `192.168.0.0/16` ranges for IPv4, or a unique local address in `fc00::/7` for
+		IPv6.
+
+		IPv4-mapped IPv6 addresses, such as `::ffff:10.0.0.1`, are classified like the IPv4
+		address they map.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address (v4 or v6)."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address",
+	]
+	return: types: ["boolean"]
+
+	examples: [
+		{
+			title: "Private IPv4 address"
+			source: #"""
+				ip_is_private!("192.168.10.32")
+				"""#
+			return: true
+		},
+		{
+			title: "Public IPv4 address"
+			source: #"""
+				ip_is_private!("8.8.8.8")
+				"""#
+			return: false
+		},
+	]
+}
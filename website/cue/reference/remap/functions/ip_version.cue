@@ -0,0 +1,38 @@
+package metadata
+
+remap: functions: ip_version: {
+	category: "IP"
+	description: """
+		Returns the version of the IP address `value`, either `4` or `6`.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The IP address (v4 or v6)."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` isn't a valid IP address",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "IPv4 address"
+			source: #"""
+				ip_version!("192.168.10.32")
+				"""#
+			return: 4
+		},
+		{
+			title: "IPv6 address"
+			source: #"""
+				ip_version!("2001:db8::1")
+				"""#
+			return: 6
+		},
+	]
+}
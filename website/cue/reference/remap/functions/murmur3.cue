@@ -0,0 +1,48 @@
+package metadata
+
+remap: functions: murmur3: {
+	category:    "Checksum"
+	description: """
+		Calculates the 32-bit x86 variant of [MurmurHash3](\(urls.murmur3)) of the `value`.
+
+		MurmurHash3 is a fast, non-cryptographic hash, suitable for bucketing events but not for
+		anything security related.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the hash for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "seed"
+			description: "The seed of the hash, between `0` and `4294967295`."
+			required:    false
+			default:     0
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: [
+		"`seed` isn't between `0` and `4294967295`.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Calculate a MurmurHash3"
+			source: #"""
+				murmur3("foobar")
+				"""#
+			return: 2764362941
+		},
+		{
+			title: "Assign an event to one of 16 shards"
+			source: #"""
+				mod(murmur3("user-1234"), 16)
+				"""#
+			return: 11
+		},
+	]
+}
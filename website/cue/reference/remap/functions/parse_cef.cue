@@ -0,0 +1,75 @@
+package metadata
+
+remap: functions: parse_cef: {
+	category:    "Parse"
+	description: """
+		Parses the `value` in [Common Event Format](\(urls.cef)) (CEF), as emitted by ArcSight and many
+		security appliances.
+
+		The header fields are returned as `cefVersion`, `deviceVendor`, `deviceProduct`,
+		`deviceVersion`, `deviceEventClassId`, `name` and `severity`, and the extension's key/value
+		pairs are added alongside them. All values are returned as strings. Text before the `CEF:`
+		prefix, such as a syslog header, is ignored.
+		"""
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name: "translate_custom_fields"
+			description: """
+				Replaces custom fields, such as `cs1`, and their labels, such as `cs1Label`, with a
+				single field named after the label.
+				"""
+			required: false
+			default:  false
+			type: ["boolean"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't contain a `CEF:` prefix.",
+		"`value` doesn't contain the seven header fields.",
+		"The extension of `value` isn't made of `key=value` pairs.",
+	]
+	return: types: ["object"]
+	examples: [
+		{
+			title: "Parse a CEF message"
+			source: #"""
+				parse_cef!("CEF:0|Security|threatmanager|1.0|100|worm successfully stopped|10|src=10.0.0.1 dst=2.1.2.2 msg=Detected a threat. No action needed")
+				"""#
+			return: {
+				cefVersion:         "0"
+				deviceVendor:       "Security"
+				deviceProduct:      "threatmanager"
+				deviceVersion:      "1.0"
+				deviceEventClassId: "100"
+				name:               "worm successfully stopped"
+				severity:           "10"
+				src:                "10.0.0.1"
+				dst:                "2.1.2.2"
+				msg:                "Detected a threat. No action needed"
+			}
+		},
+		{
+			title: "Parse a CEF message with custom fields"
+			source: #"""
+				parse_cef!("CEF:0|Dev|firewall|2.1|9|Blocked|3|cs1=guest cs1Label=Role act=blocked", translate_custom_fields: true)
+				"""#
+			return: {
+				cefVersion:         "0"
+				deviceVendor:       "Dev"
+				deviceProduct:      "firewall"
+				deviceVersion:      "2.1"
+				deviceEventClassId: "9"
+				name:               "Blocked"
+				severity:           "3"
+				Role:               "guest"
+				act:                "blocked"
+			}
+		},
+	]
+}
@@ -0,0 +1,49 @@
+package metadata
+
+remap: functions: parse_leef: {
+	category:    "Parse"
+	description: """
+		Parses the `value` in IBM QRadar's [Log Event Extended Format](\(urls.leef)) (LEEF), versions
+		1.0 and 2.0.
+
+		The header fields are returned as `leefVersion`, `deviceVendor`, `deviceProduct`,
+		`deviceVersion` and `eventId`, and the attributes are added alongside them. All values are
+		returned as strings. Attributes named like a header field, and the repetitions of an
+		attribute, are ignored, so that the first value is kept. Attributes are delimited by tabs,
+		unless a LEEF 2.0 header declares another delimiter, either as a character or as a hex code
+		such as `x5E`. An empty delimiter field keeps tabs. Text before the `LEEF:` prefix, such as
+		a syslog header, is ignored.
+		"""
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to parse."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: [
+		"`value` doesn't contain a `LEEF:` prefix.",
+		"`value` doesn't contain the five header fields.",
+		"The attributes of `value` aren't `key=value` pairs.",
+	]
+	return: types: ["object"]
+	examples: [
+		{
+			title: "Parse a LEEF 2.0 message"
+			source: #"""
+				parse_leef!("LEEF:2.0|Lancope|StealthWatch|1.0|41|^|src=10.0.1.8^dst=10.0.0.5^sev=5")
+				"""#
+			return: {
+				leefVersion:   "2.0"
+				deviceVendor:  "Lancope"
+				deviceProduct: "StealthWatch"
+				deviceVersion: "1.0"
+				eventId:       "41"
+				src:           "10.0.1.8"
+				dst:           "10.0.0.5"
+				sev:           "5"
+			}
+		},
+	]
+}
@@ -0,0 +1,50 @@
+package metadata
+
+remap: functions: port_name: {
+	category: "IP"
+	description: """
+		Returns the name of the service registered in the
+		[IANA service name and port number registry](\(urls.iana_service_names)) for the port
+		`value` of the `protocol`, such as `ssh` for 22. Only the most common services are known,
+		and `null` is returned for the other ports.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The port number."
+			required:    true
+			type: ["integer"]
+		},
+		{
+			name:        "protocol"
+			description: "The transport protocol of the port."
+			enum: {
+				tcp: "TCP"
+				udp: "UDP"
+			}
+			required: false
+			default:  "tcp"
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string", "null"]
+
+	examples: [
+		{
+			title: "Get the name of a TCP service"
+			source: #"""
+				port_name(443)
+				"""#
+			return: "https"
+		},
+		{
+			title: "Get the name of a UDP service"
+			source: #"""
+				port_name(53, protocol: "udp")
+				"""#
+			return: "domain"
+		},
+	]
+}
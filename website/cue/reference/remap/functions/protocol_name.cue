@@ -0,0 +1,31 @@
+package metadata
+
+remap: functions: protocol_name: {
+	category: "IP"
+	description: """
+		Returns the keyword of the [IANA protocol number](\(urls.iana_protocol_numbers)) `value`,
+		such as `TCP` for 6. Returns `null` for numbers that are unassigned, or assigned without a
+		keyword.
+		"""
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The protocol number."
+			required:    true
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["string", "null"]
+
+	examples: [
+		{
+			title: "Get the name of a protocol"
+			source: #"""
+				protocol_name(17)
+				"""#
+			return: "UDP"
+		},
+	]
+}
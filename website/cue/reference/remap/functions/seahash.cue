@@ -0,0 +1,37 @@
+package metadata
+
+remap: functions: seahash: {
+	category:    "Checksum"
+	description: """
+		Calculates a [SeaHash](\(urls.seahash)) of the `value`.
+
+		SeaHash is a fast, non-cryptographic hash, suitable for bucketing events but not for
+		anything security related.
+		"""
+	notices: [
+		"""
+			The 64-bit hash is returned as a signed integer, so it can be negative.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the hash for."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Calculate a SeaHash"
+			source: #"""
+				seahash("to be or not to be")
+				"""#
+			return: 1988685042348123509
+		},
+	]
+}
@@ -0,0 +1,30 @@
+package metadata
+
+remap: functions: state_delete: {
+	category: "State"
+	description: """
+		Removes the given `key` from the state, and returns its value, or `null` if the key didn't exist or had
+		expired.
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key to remove."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Reset a counter"
+			source: #"""
+				state_delete("events")
+				"""#
+			return: null
+		},
+	]
+}
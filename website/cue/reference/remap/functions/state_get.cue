@@ -0,0 +1,35 @@
+package metadata
+
+remap: functions: state_get: {
+	category: "State"
+	description: """
+		Returns the value stored for the given `key` by `state_set` or `state_incr`, or `null` if the key doesn't
+		exist or has expired.
+
+		The state is shared by all events processed by the same transform, which allows values to be carried from one
+		event to the next.
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key to look up."
+			required:    true
+			type: ["string"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["any"]
+
+	examples: [
+		{
+			title: "Get the last seen value"
+			source: #"""
+				previous = state_get("last_status")
+				state_set!("last_status", "ok")
+				previous
+				"""#
+			return: null
+		},
+	]
+}
@@ -0,0 +1,55 @@
+package metadata
+
+remap: functions: state_incr: {
+	category: "State"
+	description: """
+		Adds `by` to the integer stored for the given `key`, and returns the result. A missing or expired key counts as
+		zero.
+
+		The expiration of an existing key is only updated if `ttl` is set, so a counter created with a `ttl` counts
+		the events within a fixed window.
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key of the counter."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "by"
+			description: "The amount to add to the counter."
+			required:    false
+			default:     1
+			type: ["integer"]
+		},
+		{
+			name:        "ttl"
+			description: "The number of seconds after which the key expires."
+			required:    false
+			type: ["integer", "float"]
+		},
+	]
+	internal_failure_reasons: [
+		"The value stored for `key` isn't an integer.",
+		"The counter overflows.",
+		"`ttl` is negative.",
+	]
+	return: types: ["integer"]
+
+	examples: [
+		{
+			title: "Count the events of each host"
+			input: log: host: "web-1"
+			source: #"""
+				.sequence = state_incr!("events:" + string!(.host))
+				"""#
+			return: 1
+			output: log: {
+				host:     "web-1"
+				sequence: 1
+			}
+		},
+	]
+}
@@ -0,0 +1,52 @@
+package metadata
+
+remap: functions: state_set: {
+	category: "State"
+	description: """
+		Stores `value` for the given `key`, so it can be read back by `state_get` while processing later events.
+
+		The state is scoped to the transform, and is bounded by its `state.max_keys` and `state.max_bytes` options.
+		When one of the limits is reached, expired keys are dropped first, followed by the least recently written
+		ones.
+		"""
+
+	arguments: [
+		{
+			name:        "key"
+			description: "The key to store the value for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "value"
+			description: "The value to store."
+			required:    true
+			type: ["any"]
+		},
+		{
+			name:        "ttl"
+			description: "The number of seconds after which the key expires. If not set, the key doesn't expire."
+			required:    false
+			type: ["integer", "float"]
+		},
+	]
+	internal_failure_reasons: [
+		"`ttl` is negative.",
+		"The value is larger than the maximum size of the state.",
+	]
+	return: types: ["null"]
+
+	examples: [
+		{
+			title: "Remember the last host of a user for an hour"
+			input: log: {
+				user: "alice"
+				host: "web-1"
+			}
+			source: #"""
+				state_set!("host:" + string!(.user), .host, ttl: 3600)
+				"""#
+			return: null
+		},
+	]
+}
@@ -0,0 +1,65 @@
+package metadata
+
+remap: functions: xxhash: {
+	category:    "Checksum"
+	description: """
+		Calculates an [xxHash](\(urls.xxhash)) of the `value`.
+
+		xxHash is a fast, non-cryptographic hash, suitable for bucketing events but not for
+		anything security related.
+		"""
+	notices: [
+		"""
+			64-bit hashes are returned as signed integers, so they can be negative. The 128-bit
+			`XXH3-128` hash doesn't fit in an integer and is returned as a hex encoded string.
+			""",
+	]
+
+	arguments: [
+		{
+			name:        "value"
+			description: "The string to calculate the hash for."
+			required:    true
+			type: ["string"]
+		},
+		{
+			name:        "variant"
+			description: "The variant of the algorithm to use."
+			enum: {
+				"XXH32":    "32-bit XXH32 algorithm"
+				"XXH64":    "64-bit XXH64 algorithm"
+				"XXH3-64":  "64-bit XXH3 algorithm"
+				"XXH3-128": "128-bit XXH3 algorithm"
+			}
+			required: false
+			default:  "XXH64"
+			type: ["string"]
+		},
+		{
+			name:        "seed"
+			description: "The seed of the hash."
+			required:    false
+			default:     0
+			type: ["integer"]
+		},
+	]
+	internal_failure_reasons: []
+	return: types: ["integer", "string"]
+
+	examples: [
+		{
+			title: "Calculate an XXH64 hash"
+			source: #"""
+				xxhash("The quick brown fox jumps over the lazy dog")
+				"""#
+			return: 802816344064684476
+		},
+		{
+			title: "Calculate an XXH32 hash"
+			source: #"""
+				xxhash("The quick brown fox jumps over the lazy dog", variant: "XXH32")
+				"""#
+			return: 3898516702
+		},
+	]
+}
@@ -0,0 +1,8 @@
+package metadata
+
+services: netflow: {
+	name:     "flow exporter"
+	thing:    "a \(name)"
+	url:      urls.ipfix
+	versions: null
+}
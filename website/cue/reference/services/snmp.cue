@@ -0,0 +1,8 @@
+package metadata
+
+services: snmp: {
+	name:     "SNMP agent"
+	thing:    "an \(name)"
+	url:      urls.snmp
+	versions: null
+}
@@ -6,6 +6,8 @@ urls: {
 	affine_type_system:                         "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
 	adaptive_request_concurrency_post:          "/blog/adaptive-request-concurrency/"
 	amazon_linux:                               "https://aws.amazon.com/amazon-linux-ami/"
+	amqp:                                       "https://www.amqp.org/"
+	amqp_091_spec:                              "https://www.rabbitmq.com/resources/specs/amqp0-9-1.pdf"
 	ansi_escape_codes:                          "\(wikipedia)/wiki/ANSI_escape_code"
 	apache:                                     "https://httpd.apache.org"
 	apache_common:                              "\(apache)/docs/current/logs.html#common"
@@ -98,6 +100,7 @@ urls: {
 	bind_dnstap:                                "https://kb.isc.org/docs/aa-01342"
 	b_tree_map:                                 "https://doc.rust-lang.org/std/collections/struct.BTreeMap.html"
 	cargo_audit:                                "\(github)/RustSec/cargo-audit"
+	cef:                                        "https://www.microfocus.com/documentation/arcsight/arcsight-smartconnectors-8.3/cef-implementation-standard/"
 	centos:                                     "https://www.centos.org/"
 	chrono_time_formats:                        "https://docs.rs/chrono/latest/chrono/format/strftime/index.html#specifiers"
 	cgroups_limit_resources:                    "https://the.binbashtheory.com/control-resources-cgroups/"
@@ -106,6 +109,7 @@ urls: {
 	cloudsmith:                                 "https://cloudsmith.io/~timber/repos/vector/packages/"
 	cloudsmith_apt:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-deb"
 	cloudsmith_yum:                             "https://cloudsmith.io/~timber/repos/vector/setup/#formats-rpm"
+	community_id:                               "\(github)/corelight/community-id-spec"
 	console:                                    "\(wikipedia)/wiki/System_console"
 	conventional_commits:                       "https://www.conventionalcommits.org"
 	contributing:                               "\(vector_repo)/blob/master/CONTRIBUTING.md#setup"
@@ -163,6 +167,7 @@ urls: {
 	dogstatsd:                                  "\(datadog_docs)/developers/dogstatsd/?tab=hostagent"
 	dot_format:                                 "https://graphviz.org/doc/info/lang.html"
 	dpkg:                                       "https://wiki.debian.org/dpkg"
+	drain_paper:                                "https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf"
 	dry_code:                                   "\(wikipedia)/wiki/Don%27t_repeat_yourself"
 	cidr:                                       "\(wikipedia)/wiki/Classless_Inter-Domain_Routing"
 	elastic_beats:                              "https://www.elastic.co/beats/"
@@ -234,6 +239,7 @@ urls: {
 	heroku:                                     "https://www.heroku.com"
 	heroku_http_log_drain:                      "https://devcenter.heroku.com/articles/log-drains#https-drains"
 	heroku_start:                               "https://devcenter.heroku.com/start"
+	hmac:                                       "\(wikipedia)/wiki/HMAC"
 	homebrew:                                   "https://brew.sh/"
 	homebrew_services:                          "\(github)/Homebrew/homebrew-services"
 	honeycomb:                                  "https://honeycomb.io"
@@ -248,6 +254,8 @@ urls: {
 	humio_hec:                                  "https://docs.humio.com/integrations/data-shippers/hec/"
 	humio_hec_format_of_data:                   "https://docs.humio.com/integrations/data-shippers/hec/#format-of-data"
 	iam_instance_profile:                       "\(aws_docs)/IAM/latest/UserGuide/id_roles_use_switch-role-ec2_instance-profiles.html"
+	iana_protocol_numbers:                      "https://www.iana.org/assignments/protocol-numbers/protocol-numbers.xhtml"
+	iana_service_names:                         "https://www.iana.org/assignments/service-names-port-numbers/service-names-port-numbers.xhtml"
 	iana_time_zone_format:                      "\(wikipedia)/wiki/Tz_database#Names_of_time_zones"
 	iana_time_zones:                            "\(wikipedia)/wiki/List_of_tz_database_time_zones"
 	ieee_754:                                   "\(wikipedia)/wiki/IEEE_754"
@@ -263,12 +271,14 @@ urls: {
 	ip_ntoa:                                    "https://linux.die.net/man/3/inet_ntoa"
 	ip_ntop:                                    "https://linux.die.net/man/3/inet_ntop"
 	ip_pton:                                    "https://linux.die.net/man/3/inet_pton"
+	ipfix:                                      "https://www.rfc-editor.org/rfc/rfc7011"
 	iso_8601:                                   "\(wikipedia)/wiki/ISO_8601"
 	iso3166_2:                                  "\(wikipedia)/wiki/ISO_3166-2"
 	issue_1694:                                 "\(vector_repo)/issues/1694"
 	journalctl:                                 "https://www.freedesktop.org/software/systemd/man/journalctl.html"
 	journald:                                   "https://www.freedesktop.org/software/systemd/man/systemd-journald.service.html"
 	json:                                       "\(wikipedia)/wiki/JSON"
+	json_schema:                                "https://json-schema.org/"
 	json_types:                                 "\(wikipedia)/wiki/JSON#Data_types_and_syntax"
 	jsonnet:                                    "https://jsonnet.org/"
 	kafka:                                      "https://kafka.apache.org/"
@@ -289,6 +299,8 @@ urls: {
 	kubernetes_rbac:                            "\(kubernetes)/docs/reference/access-authn-authz/rbac/"
 	kubernetes_request_verbs:                   "\(kubernetes)/docs/reference/access-authn-authz/authorization/#determine-the-request-verb"
 	kubernetes_watch_api:                       "\(kubernetes)/docs/reference/generated/kubernetes-api/v1.10/#watch-30"
+	lapin:                                      "\(github)/amqp-rs/lapin"
+	leef:                                       "https://www.ibm.com/docs/en/dsm?topic=overview-leef-event-components"
 	leveldb:                                    "\(github)/google/leveldb"
 	leveldb_sys_2:                              "https://crates.io/crates/leveldb-sys"
 	leveldb_sys_3:                              "\(github)/vectordotdev/leveldb-sys/tree/v3.0.0"
@@ -342,10 +354,12 @@ urls: {
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
+	murmur3:                                    "\(wikipedia)/wiki/MurmurHash"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
 	nats:                                       "https://nats.io/"
+	nats_jetstream:                             "https://docs.nats.io/nats-concepts/jetstream"
 	nats_rs:                                    "\(github)/nats-io/nats.rs"
 	new_bug_report:                             "\(vector_repo)/issues/new?labels=type%3A+bug"
 	new_feature_request:                        "\(vector_repo)/issues/new?labels=type%3A+new+feature"
@@ -357,6 +371,8 @@ urls: {
 	new_source:                                 "\(vector_repo)/issues/new?labels=type%3A+new+feature"
 	new_target:                                 "\(vector_repo)/issues/new?labels=type%3A+task&labels=domain%3A+operations"
 	new_transform:                              "\(vector_repo)/issues/new?labels=type%3A+new+feature"
+	netflow_v5:                                 "https://www.cisco.com/c/en/us/td/docs/net_mgmt/netflow_collection_engine/3-6/user/guide/format.html#wp1006108"
+	netflow_v9:                                 "https://www.rfc-editor.org/rfc/rfc3954"
 	nginx:                                      "https://www.nginx.com/"
 	nginx_combined:                             "https://nginx.org/en/docs/http/ngx_http_log_module.html"
 	nginx_error:                                "https://github.com/nginx/nginx/blob/branches/stable-1.18/src/core/ngx_log.c#L102"
@@ -397,11 +413,13 @@ urls: {
 	prometheus_text_based_exposition_format:    "\(github)/prometheus/docs/blob/master/content/docs/instrumenting/exposition_formats.md#text-based-format"
 	prometheus_metric_naming:                   "https://prometheus.io/docs/practices/naming/#metric-names"
 	prometheus_remote_integrations:             "https://prometheus.io/docs/operating/integrations/#remote-endpoints-and-storage"
+	prometheus_relabel_config:                  "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#relabel_config"
 	prometheus_remote_write:                    "https://prometheus.io/docs/prometheus/latest/configuration/configuration/#remote_write"
 	prometheus_remote_write_protocol:           "https://docs.google.com/document/d/1LPhVRSFkGNSuU1fBd81ulhsCPR4hkSZyyBj1SZ8fWOM/edit#heading=h.n0d0vphea3fe"
 	protobuf:                                   "https://developers.google.com/protocol-buffers"
 	pulsar:                                     "https://pulsar.apache.org/"
 	pulsar_protocol:                            "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	rabbitmq:                                   "https://www.rabbitmq.com/"
 	raspbian:                                   "https://www.raspbian.org/"
 	rdkafka:                                    "\(github)/edenhill/librdkafka"
 	regex:                                      "\(wikipedia)/wiki/Regular_expression"
@@ -436,6 +454,7 @@ urls: {
 	rustup:                                     "https://rustup.rs"
 	redis:                                      "https://redis.io"
 	redis_rs:                                   "https://github.com/mitsuhiko/redis-rs"
+	seahash:                                    "https://docs.rs/seahash"
 	sematext:                                   "https://sematext.com"
 	sematext_create_logs_app:                   "https://apps.sematext.com/ui/integrations"
 	sematext_es:                                "https://sematext.com/docs/logs/index-events-via-elasticsearch-api/"
@@ -446,9 +465,13 @@ urls: {
 	sha1:                                       "\(wikipedia)/wiki/SHA-1"
 	sha2:                                       "\(wikipedia)/wiki/SHA-2"
 	sha3:                                       "\(wikipedia)/wiki/SHA-3"
+	sflow_v5:                                   "https://sflow.org/sflow_version_5.txt"
 	signal:                                     "\(wikipedia)/wiki/Signal_(IPC)"
 	snake_case:                                 "\(wikipedia)/wiki/Snake_case"
 	snappy:                                     "https://google.github.io/snappy/"
+	snmp:                                       "https://www.rfc-editor.org/rfc/rfc3416"
+	snmp_mib:                                   "https://www.rfc-editor.org/rfc/rfc2578"
+	snmp_usm:                                   "https://www.rfc-editor.org/rfc/rfc3414"
 	socket:                                     "\(wikipedia)/wiki/Network_socket"
 	splunk:                                     "https://www.splunk.com"
 	splunk_hec:                                 "https://dev.splunk.com/enterprise/docs/dataapps/httpeventcollector/"
@@ -590,7 +613,9 @@ urls: {
 	vector_fluent_source:                       "/docs/reference/configuration/sources/fluent"
 	vector_logstash_source:                     "/docs/reference/configuration/sources/logstash"
 	vector_nats_source:                         "/docs/reference/configuration/sources/nats"
+	vector_netflow_source:                      "/docs/reference/configuration/sources/netflow"
 	vector_rfc_8381:                            "\(vector_repo)/blob/master/rfcs/2021-08-29-8381-vrl-iteration-support.md"
+	vector_snmp_trap_source:                    "/docs/reference/configuration/sources/snmp_trap"
 	vector_socket_source:                       "/docs/reference/configuration/sources/socket"
 	vector_source:                              "/docs/reference/configuration/sources/vector"
 	vector_sources:                             "/docs/reference/configuration/sources"
@@ -632,6 +657,7 @@ urls: {
 	windows_installer:                          "\(wikipedia)/wiki/Windows_Installer"
 	windows_service:                            "https://docs.microsoft.com/en-us/powershell/module/microsoft.powershell.management/new-service"
 	woothee:                                    "https://github.com/woothee/woothee"
+	xxhash:                                     "https://xxhash.com"
 	yaml:                                       "https://yaml.org/"
 	ytt:                                        "https://carvel.dev/ytt/"
 	yum:                                        "\(wikipedia)/wiki/Yum_(software)"